@@ -245,6 +245,7 @@ fn calamari_dev_genesis(
             min_withdraw: 5_000 * KMA,
             gas_reserve: 10_000 * KMA,
             farming_pool_params: Default::default(),
+            _config: PhantomData,
         },
     }
 }
@@ -17,7 +17,9 @@
 //! Fake Runtime API to use in place of native runtime
 
 use frame_support::weights::Weight;
-use manta_primitives::types::{AccountId, Balance, Block, CalamariAssetId, Nonce, PoolId};
+use manta_primitives::types::{
+    AccountId, Balance, Block, BlockNumber, CalamariAssetId, Hash, Nonce, PoolId,
+};
 use manta_support::manta_pay::{InitialSyncResponse, PullResponse, RawCheckpoint};
 use session_key_primitives::NimbusId;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -181,15 +183,29 @@ sp_api::impl_runtime_apis! {
         }
     }
 
-    impl pallet_lottery::runtime::LotteryApi<Block> for Runtime {
+    impl pallet_lottery::runtime::LotteryApi<Block, AccountId, BlockNumber, Hash> for Runtime {
         fn not_in_drawing_freezeout(
-        ) -> bool {
+            _instance: pallet_lottery::runtime::LotteryInstance,
+        ) -> Result<bool, pallet_lottery::runtime::UnknownInstance> {
             unimplemented!();
         }
-        fn current_prize_pool() -> u128 {
+        fn current_prize_pool(_instance: pallet_lottery::runtime::LotteryInstance) -> Result<u128, pallet_lottery::runtime::UnknownInstance> {
+            unimplemented!()
+        }
+        fn next_drawing_at(_instance: pallet_lottery::runtime::LotteryInstance) -> Result<Option<u128>, pallet_lottery::runtime::UnknownInstance> {
             unimplemented!()
         }
-        fn next_drawing_at() -> Option<u128> {
+        fn drawing_history(
+            _instance: pallet_lottery::runtime::LotteryInstance,
+            _from: u32,
+            _count: u32,
+        ) -> Result<Vec<pallet_lottery::DrawingRecord<AccountId, BlockNumber, u128, Hash>>, pallet_lottery::runtime::UnknownInstance> {
+            unimplemented!()
+        }
+        fn user_position(_instance: pallet_lottery::runtime::LotteryInstance, _account: AccountId) -> Result<pallet_lottery::UserPosition<BlockNumber, u128>, pallet_lottery::runtime::UnknownInstance> {
+            unimplemented!()
+        }
+        fn participant_proof(_instance: pallet_lottery::runtime::LotteryInstance, _account: AccountId) -> Result<Option<pallet_lottery::ParticipantProof<AccountId, u128, Hash>>, pallet_lottery::runtime::UnknownInstance> {
             unimplemented!()
         }
     }
@@ -288,5 +304,69 @@ sp_api::impl_runtime_apis! {
         fn get_gauge_rewards(_who: AccountId, _pid: PoolId) -> Vec<(CalamariAssetId, Balance)> {
             unimplemented!()
         }
+
+        fn get_gauge_weights() -> Vec<(PoolId, u128)> {
+            unimplemented!()
+        }
+
+        fn user_positions(
+            _who: AccountId,
+        ) -> Vec<pallet_farming_rpc_runtime_api::UserPosition<CalamariAssetId, BlockNumber, Balance>> {
+            unimplemented!()
+        }
+
+        fn pool_overview(
+            _pid: PoolId,
+        ) -> Option<pallet_farming_rpc_runtime_api::PoolOverview<CalamariAssetId, BlockNumber, Balance>> {
+            unimplemented!()
+        }
+
+        fn pool_overviews() -> Vec<pallet_farming_rpc_runtime_api::PoolOverview<CalamariAssetId, BlockNumber, Balance>> {
+            unimplemented!()
+        }
+
+        fn user_vestings(
+            _who: AccountId,
+        ) -> Vec<pallet_farming_rpc_runtime_api::VestingPosition<CalamariAssetId, BlockNumber, Balance>> {
+            unimplemented!()
+        }
+    }
+
+    impl pallet_asset_manager_rpc_runtime_api::AssetManagerApi<Block, AccountId, CalamariAssetId> for Runtime {
+        fn list_assets(
+            _start_after: Option<CalamariAssetId>,
+            _limit: u32,
+        ) -> Vec<pallet_asset_manager_rpc_runtime_api::AssetInfo<AccountId, CalamariAssetId, Balance>> {
+            unimplemented!()
+        }
+
+        fn asset_by_id(
+            _asset_id: CalamariAssetId,
+        ) -> Option<pallet_asset_manager_rpc_runtime_api::AssetInfo<AccountId, CalamariAssetId, Balance>> {
+            unimplemented!()
+        }
+
+        fn asset_by_location(
+            _location: xcm::latest::MultiLocation,
+        ) -> Option<pallet_asset_manager_rpc_runtime_api::AssetInfo<AccountId, CalamariAssetId, Balance>> {
+            unimplemented!()
+        }
+
+        fn lp_pair_of(_asset_id: CalamariAssetId) -> Option<(CalamariAssetId, CalamariAssetId)> {
+            unimplemented!()
+        }
+
+        fn xcm_fee_info(
+            _asset_id: CalamariAssetId,
+        ) -> Option<pallet_asset_manager_rpc_runtime_api::XcmFeeInfo> {
+            unimplemented!()
+        }
+
+        fn remaining_outflow(
+            _asset_id: CalamariAssetId,
+            _destination: Option<u32>,
+        ) -> Option<Balance> {
+            unimplemented!()
+        }
     }
 }
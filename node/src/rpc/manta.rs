@@ -18,6 +18,8 @@
 
 use super::*;
 use manta_primitives::types::{MantaAssetId, PoolId};
+use pallet_asset_manager_rpc_api::{AssetManagerRpc, AssetManagerRpcApiServer};
+use pallet_asset_manager_rpc_runtime_api::AssetManagerApi;
 use pallet_farming_rpc_api::{FarmingRpc, FarmingRpcApiServer};
 use pallet_farming_rpc_runtime_api::FarmingRuntimeApi;
 use pallet_lottery::{
@@ -47,7 +49,8 @@ where
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BlockBuilder<Block>,
     C::Api: SBTPullLedgerDiffApi<Block>,
-    C::Api: LotteryApi<Block>,
+    C::Api: LotteryApi<Block, AccountId, BlockNumber, Hash>,
+    C::Api: AssetManagerApi<Block, AccountId, MantaAssetId>,
     C::Api: FarmingRuntimeApi<Block, AccountId, MantaAssetId, PoolId>,
     C::Api: ZenlinkProtocolRuntimeApi<Block, AccountId, ZenlinkAssetId>,
     P: TransactionPool + Sync + Send + 'static,
@@ -84,6 +87,10 @@ where
         .merge(FarmingRpc::new(client.clone()).into_rpc())
         .map_err(|e| sc_service::Error::Other(e.to_string()))?;
 
+    module
+        .merge(AssetManagerRpc::new(client.clone()).into_rpc())
+        .map_err(|e| sc_service::Error::Other(e.to_string()))?;
+
     module
         .merge(Lottery::new(client).into_rpc())
         .map_err(|e| sc_service::Error::Other(e.to_string()))?;
@@ -17,7 +17,7 @@
 //! Parachain-specific RPCs implementation.
 
 use frame_rpc_system::SystemApiServer;
-use manta_primitives::types::{AccountId, Balance, Block, Hash, Nonce};
+use manta_primitives::types::{AccountId, Balance, Block, BlockNumber, Hash, Nonce};
 use polkadot_service::SubstrateServiceError;
 use sc_client_api::AuxStore;
 use sc_consensus_manual_seal::rpc::EngineCommand;
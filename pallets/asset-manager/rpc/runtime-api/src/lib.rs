@@ -0,0 +1,46 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use manta_primitives::types::Balance;
+pub use pallet_asset_manager::{AssetInfo, XcmFeeInfo};
+use sp_api::decl_runtime_apis;
+use sp_std::vec::Vec;
+use xcm::latest::MultiLocation;
+
+decl_runtime_apis! {
+    pub trait AssetManagerApi<AccountId, AssetId> where
+        AccountId: Codec,
+        AssetId: Codec,
+    {
+        fn list_assets(
+            start_after: Option<AssetId>,
+            limit: u32,
+        ) -> Vec<AssetInfo<AccountId, AssetId, Balance>>;
+
+        fn asset_by_id(asset_id: AssetId) -> Option<AssetInfo<AccountId, AssetId, Balance>>;
+
+        fn asset_by_location(location: MultiLocation) -> Option<AssetInfo<AccountId, AssetId, Balance>>;
+
+        fn lp_pair_of(asset_id: AssetId) -> Option<(AssetId, AssetId)>;
+
+        fn xcm_fee_info(asset_id: AssetId) -> Option<XcmFeeInfo>;
+
+        fn remaining_outflow(asset_id: AssetId, destination: Option<u32>) -> Option<Balance>;
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorCode, ErrorObject},
+};
+use manta_primitives::types::Balance;
+pub use pallet_asset_manager_rpc_runtime_api::{
+    self as runtime_api, AssetInfo, AssetManagerApi, XcmFeeInfo,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use xcm::latest::MultiLocation;
+
+#[rpc(client, server)]
+pub trait AssetManagerRpcApi<BlockHash, AccountId, AssetId>
+where
+    AccountId: Send + Sync + 'static,
+    AssetId: Send + Sync + 'static,
+{
+    /// rpc method for listing up to `limit` registered assets after `start_after`
+    #[method(name = "assetManager_listAssets")]
+    async fn list_assets(
+        &self,
+        start_after: Option<AssetId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<AssetInfo<AccountId, AssetId, Balance>>>;
+
+    /// rpc method for getting a registered asset by its id
+    #[method(name = "assetManager_assetById")]
+    async fn asset_by_id(
+        &self,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AssetInfo<AccountId, AssetId, Balance>>>;
+
+    /// rpc method for getting a registered asset by its location
+    #[method(name = "assetManager_assetByLocation")]
+    async fn asset_by_location(
+        &self,
+        location: MultiLocation,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AssetInfo<AccountId, AssetId, Balance>>>;
+
+    /// rpc method for getting the asset pair of a LP asset
+    #[method(name = "assetManager_lpPairOf")]
+    async fn lp_pair_of(
+        &self,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<(AssetId, AssetId)>>;
+
+    /// rpc method for getting the XCM fee parameters of an asset
+    #[method(name = "assetManager_xcmFeeInfo")]
+    async fn xcm_fee_info(
+        &self,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<XcmFeeInfo>>;
+
+    /// rpc method for getting the amount of an asset that can still leave this chain in the
+    /// current window of its outflow cap to a sibling parachain, or to any chain
+    #[method(name = "assetManager_remainingOutflow")]
+    async fn remaining_outflow(
+        &self,
+        asset_id: AssetId,
+        destination: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<Balance>>;
+}
+
+#[derive(Clone, Debug)]
+pub struct AssetManagerRpc<C, Block> {
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> AssetManagerRpc<C, Block> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Converts a runtime API error into an RPC error with `message`.
+fn runtime_error(message: &'static str, e: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+    jsonrpsee::core::Error::Call(CallError::Custom(ErrorObject::owned(
+        ErrorCode::InternalError.code(),
+        message,
+        Some(format!("{:?}", e)),
+    )))
+}
+
+#[async_trait]
+impl<C, Block, AccountId, AssetId>
+    AssetManagerRpcApiServer<<Block as BlockT>::Hash, AccountId, AssetId>
+    for AssetManagerRpc<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: AssetManagerApi<Block, AccountId, AssetId>,
+    AccountId: Codec + Send + Sync + 'static,
+    AssetId: Codec + Send + Sync + 'static,
+{
+    async fn list_assets(
+        &self,
+        start_after: Option<AssetId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<AssetInfo<AccountId, AssetId, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.list_assets(at, start_after, limit)
+            .map_err(|e| runtime_error("Failed to list assets.", e))
+    }
+
+    async fn asset_by_id(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<AssetInfo<AccountId, AssetId, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.asset_by_id(at, asset_id)
+            .map_err(|e| runtime_error("Failed to get asset by id.", e))
+    }
+
+    async fn asset_by_location(
+        &self,
+        location: MultiLocation,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<AssetInfo<AccountId, AssetId, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.asset_by_location(at, location)
+            .map_err(|e| runtime_error("Failed to get asset by location.", e))
+    }
+
+    async fn lp_pair_of(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<(AssetId, AssetId)>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.lp_pair_of(at, asset_id)
+            .map_err(|e| runtime_error("Failed to get LP pair.", e))
+    }
+
+    async fn xcm_fee_info(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<XcmFeeInfo>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.xcm_fee_info(at, asset_id)
+            .map_err(|e| runtime_error("Failed to get XCM fee info.", e))
+    }
+
+    async fn remaining_outflow(
+        &self,
+        asset_id: AssetId,
+        destination: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<Balance>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.remaining_outflow(at, asset_id, destination)
+            .map_err(|e| runtime_error("Failed to get remaining outflow.", e))
+    }
+}
@@ -16,8 +16,8 @@
 
 #![cfg(feature = "runtime-benchmarks")]
 
-use crate::{Call, Config, Pallet};
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use crate::{Call, Config, OutflowCap, Pallet, UnitsPerSecondOracle};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
 use frame_support::traits::Get;
 use frame_system::{EventRecord, RawOrigin};
 use manta_primitives::{
@@ -28,8 +28,8 @@ use manta_primitives::{
     types::Balance,
 };
 use scale_info::prelude::*;
-use sp_runtime::traits::One;
-use sp_std::vec;
+use sp_runtime::{traits::One, Perbill};
+use sp_std::{vec, vec::Vec};
 use xcm::latest::prelude::*;
 
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
@@ -60,6 +60,25 @@ pub fn register_asset_helper<T: Config>(location: MultiLocation, i: u32) {
         .expect("Filed to set ups");
 }
 
+/// Registers a permissionless asset owned by `owner` and returns its asset id.
+pub fn register_permissionless_asset_helper<T: Config>(owner: &T::AccountId) -> T::AssetId {
+    let native_asset_id = <T::AssetConfig as AssetConfig<T>>::NativeAssetId::get();
+    let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+        native_asset_id,
+        owner,
+        1_000_000_000_000_000_000_000u128,
+    );
+    Pallet::<T>::permissionless_register_asset(
+        RawOrigin::Signed(owner.clone()).into(),
+        vec![].try_into().unwrap(),
+        vec![].try_into().unwrap(),
+        12,
+        1_000_000_000_000_000,
+    )
+    .expect("Failed to register permissionless asset");
+    crate::NextPermissionlessAssetId::<T>::get() - One::one()
+}
+
 benchmarks! {
     where_clause { where T::Location: From<MultiLocation>, <T as Config>::AssetId: From<u32> }
 
@@ -208,6 +227,114 @@ benchmarks! {
             1_000_000_000_000_000_000_000u128
         );
     }: _(RawOrigin::Signed(caller), vec![].try_into().unwrap(), vec![].try_into().unwrap(), 12, 1_000_000_000_000_000)
+
+    deregister_asset {
+        let n in 1 .. 1000;
+        let location = T::Location::from(MultiLocation::new(1, X1(Parachain(1000))));
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        Pallet::<T>::register_asset(RawOrigin::Root.into(), location, metadata)?;
+        let asset_id = crate::NextAssetId::<T>::get() - One::one();
+        Pallet::<T>::set_units_per_second(RawOrigin::Root.into(), asset_id, 1)?;
+        // The last item of the budget finds no more accounts, so the asset is destroyed
+        for i in 1..n {
+            let holder: T::AccountId = account("holder", i, 0);
+            let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(asset_id, &holder, 1_000_000);
+        }
+    }: _(RawOrigin::Root, asset_id, n)
+    verify {
+        assert_last_event::<T>(crate::Event::AssetDestroyed { asset_id }.into());
+        assert_eq!(Pallet::<T>::destroying_assets(asset_id), None);
+    }
+
+    set_units_per_second_oracle {
+        let location = T::Location::from(MultiLocation::new(1, X1(Parachain(1000))));
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        Pallet::<T>::register_asset(RawOrigin::Root.into(), location, metadata)?;
+        let asset_id = crate::NextAssetId::<T>::get() - One::one();
+        let oracle = UnitsPerSecondOracle {
+            native_units_per_second: 1_000,
+            min_units_per_second: 10,
+            max_units_per_second: 100_000,
+            smoothing: Some(Perbill::from_percent(10)),
+        };
+    }: _(RawOrigin::Root, asset_id, Some(oracle))
+    verify {
+        assert_eq!(Pallet::<T>::units_per_second_oracle(asset_id), Some(oracle));
+    }
+
+    update_permissionless_asset_metadata {
+        let caller: T::AccountId = whitelisted_caller();
+        let asset_id = register_permissionless_asset_helper::<T>(&caller);
+        let name = vec![b'n'; T::TokenNameMaxLen::get() as usize];
+        let symbol = vec![b's'; T::TokenSymbolMaxLen::get() as usize];
+        let logo = vec![b'l'; T::TokenLogoMaxLen::get() as usize];
+    }: _(RawOrigin::Signed(caller), asset_id, name.try_into().unwrap(), symbol.try_into().unwrap(), Some(logo.clone().try_into().unwrap()))
+    verify {
+        assert_eq!(Pallet::<T>::asset_logo(asset_id).map(Into::<Vec<u8>>::into), Some(logo));
+    }
+
+    transfer_asset_ownership {
+        let caller: T::AccountId = whitelisted_caller();
+        let asset_id = register_permissionless_asset_helper::<T>(&caller);
+        let new_owner: T::AccountId = account("new_owner", 0, 0);
+    }: _(RawOrigin::Signed(caller), asset_id, new_owner.clone())
+    verify {
+        assert_eq!(Pallet::<T>::permissionless_asset_owner(asset_id), Some(new_owner));
+    }
+
+    renounce_asset_ownership {
+        let caller: T::AccountId = whitelisted_caller();
+        let asset_id = register_permissionless_asset_helper::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), asset_id)
+    verify {
+        assert_eq!(Pallet::<T>::permissionless_asset_owner(asset_id), None);
+    }
+
+    burn_asset {
+        let caller: T::AccountId = whitelisted_caller();
+        let asset_id = register_permissionless_asset_helper::<T>(&caller);
+        let amount = 1_000_000_000_000;
+    }: _(RawOrigin::Signed(caller.clone()), asset_id, amount)
+    verify {
+        assert_last_event::<T>(crate::Event::AssetBurned { asset_id, who: caller, amount }.into());
+    }
+
+    register_location_alias {
+        let location = T::Location::from(MultiLocation::new(1, X1(Parachain(1000))));
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        Pallet::<T>::register_asset(RawOrigin::Root.into(), location, metadata)?;
+        let asset_id = crate::NextAssetId::<T>::get() - One::one();
+        let alias = T::Location::from(MultiLocation::new(1, X2(Parachain(1000), GeneralIndex(1))));
+    }: _(RawOrigin::Root, asset_id, alias.clone())
+    verify {
+        assert_eq!(Pallet::<T>::location_asset_id(alias), Some(asset_id));
+    }
+
+    remove_location_alias {
+        let location = T::Location::from(MultiLocation::new(1, X1(Parachain(1000))));
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        Pallet::<T>::register_asset(RawOrigin::Root.into(), location, metadata)?;
+        let asset_id = crate::NextAssetId::<T>::get() - One::one();
+        let alias = T::Location::from(MultiLocation::new(1, X2(Parachain(1000), GeneralIndex(1))));
+        Pallet::<T>::register_location_alias(RawOrigin::Root.into(), asset_id, alias.clone())?;
+    }: _(RawOrigin::Root, asset_id, alias.clone())
+    verify {
+        assert_eq!(Pallet::<T>::location_asset_id(alias), None);
+    }
+
+    set_outflow_cap {
+        let location = T::Location::from(MultiLocation::new(1, X1(Parachain(1000))));
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        Pallet::<T>::register_asset(RawOrigin::Root.into(), location, metadata)?;
+        let asset_id = crate::NextAssetId::<T>::get() - One::one();
+        let cap = OutflowCap {
+            amount: 1_000_000,
+            period: 100u32.into(),
+        };
+    }: _(RawOrigin::Root, asset_id, Some(2000), Some(cap))
+    verify {
+        assert_eq!(Pallet::<T>::outflow_cap(asset_id, Some(2000)), Some(cap));
+    }
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
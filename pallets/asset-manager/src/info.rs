@@ -0,0 +1,154 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Asset Registry Views for the Runtime API
+
+use crate::{
+    AssetIdLocation, AssetIdMetadata, AssetLogo, Config, FilteredOutgoingAssetLocations,
+    LocationAssetId, LpToAssetIdPair, MinXcmFee, Pallet, PermissionlessAssetOwner, UnitsPerSecond,
+};
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+use manta_primitives::types::Balance;
+use orml_traits::location::Parse;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+use xcm::latest::MultiLocation;
+
+/// Registered Asset
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AssetInfo<AccountId, AssetId, Balance> {
+    /// Asset Id
+    pub asset_id: AssetId,
+
+    /// Location of the Asset, `None` for LP and permissionless assets
+    pub location: Option<MultiLocation>,
+
+    /// Asset Name
+    pub name: Vec<u8>,
+
+    /// Asset Symbol
+    pub symbol: Vec<u8>,
+
+    /// Number of Decimals
+    pub decimals: u8,
+
+    /// Frozen Flag
+    pub is_frozen: bool,
+
+    /// Minimum Balance
+    pub min_balance: Balance,
+
+    /// Sufficiency Flag
+    pub is_sufficient: bool,
+
+    /// Whether the asset was registered permissionlessly
+    pub is_permissionless: bool,
+
+    /// Owner of a permissionless Asset, `None` once renounced
+    pub owner: Option<AccountId>,
+
+    /// Logo of a permissionless Asset
+    pub logo: Option<Vec<u8>>,
+}
+
+/// XCM Fee Parameters of an Asset
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct XcmFeeInfo {
+    /// Static units of the asset charged per second of XCM execution
+    pub units_per_second: Option<u128>,
+
+    /// Units of the asset charged per second of XCM execution, priced by its oracle if it has one
+    pub effective_units_per_second: Option<u128>,
+
+    /// Minimum XCM execution fee paid on the reserve chain of the asset
+    pub min_xcm_fee: Option<u128>,
+
+    /// Whether the asset can't be transferred out
+    pub is_outgoing_filtered: bool,
+}
+
+impl<T> Pallet<T>
+where
+    T: Config,
+{
+    /// Returns the registered asset with `asset_id`.
+    pub fn asset_info(
+        asset_id: T::AssetId,
+    ) -> Option<AssetInfo<T::AccountId, T::AssetId, Balance>> {
+        let metadata = AssetIdMetadata::<T>::get(asset_id)?;
+        Some(AssetInfo {
+            asset_id,
+            location: AssetIdLocation::<T>::get(asset_id).and_then(Into::into),
+            name: metadata.metadata.name,
+            symbol: metadata.metadata.symbol,
+            decimals: metadata.metadata.decimals,
+            is_frozen: metadata.metadata.is_frozen,
+            min_balance: metadata.min_balance,
+            is_sufficient: metadata.is_sufficient,
+            is_permissionless: asset_id >= T::PermissionlessStartId::get(),
+            owner: PermissionlessAssetOwner::<T>::get(asset_id),
+            logo: AssetLogo::<T>::get(asset_id).map(Into::into),
+        })
+    }
+
+    /// Returns up to `limit` registered assets in storage order, starting after `start_after`.
+    pub fn list_assets(
+        start_after: Option<T::AssetId>,
+        limit: u32,
+    ) -> Vec<AssetInfo<T::AccountId, T::AssetId, Balance>> {
+        let asset_ids = match start_after {
+            Some(asset_id) => {
+                AssetIdMetadata::<T>::iter_keys_from(AssetIdMetadata::<T>::hashed_key_for(asset_id))
+            }
+            None => AssetIdMetadata::<T>::iter_keys(),
+        };
+        asset_ids
+            .take(limit as usize)
+            .filter_map(Self::asset_info)
+            .collect()
+    }
+
+    /// Returns the registered asset at `location`.
+    pub fn asset_by_location(
+        location: MultiLocation,
+    ) -> Option<AssetInfo<T::AccountId, T::AssetId, Balance>> {
+        LocationAssetId::<T>::get(T::Location::from(location)).and_then(Self::asset_info)
+    }
+
+    /// Returns the asset pair of the LP asset with `asset_id`.
+    pub fn lp_pair_of(asset_id: T::AssetId) -> Option<(T::AssetId, T::AssetId)> {
+        LpToAssetIdPair::<T>::get(asset_id)
+    }
+
+    /// Returns the XCM fee parameters of the asset with `asset_id`.
+    pub fn xcm_fee_info(asset_id: T::AssetId) -> Option<XcmFeeInfo> {
+        let location: Option<MultiLocation> = AssetIdLocation::<T>::get(asset_id)?.into();
+        Some(XcmFeeInfo {
+            units_per_second: UnitsPerSecond::<T>::get(asset_id),
+            effective_units_per_second: Self::effective_units_per_second(asset_id),
+            min_xcm_fee: location
+                .and_then(|location| location.chain_part())
+                .and_then(|reserve_chain| MinXcmFee::<T>::get(T::Location::from(reserve_chain))),
+            is_outgoing_filtered: FilteredOutgoingAssetLocations::<T>::contains_key(location),
+        })
+    }
+}
@@ -28,6 +28,10 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
+pub mod info;
+pub mod migrations;
+pub mod oracle;
+pub mod outflow;
 pub mod weights;
 
 #[cfg(test)]
@@ -36,13 +40,22 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-pub use crate::weights::WeightInfo;
+pub use crate::{
+    info::{AssetInfo, XcmFeeInfo},
+    oracle::{PairReserves, UnitsPerSecondOracle},
+    outflow::{OutflowCap, OutflowWindow},
+    weights::WeightInfo,
+};
 pub use pallet::*;
 
 /// Asset Manager Pallet
 #[frame_support::pallet]
 pub mod pallet {
-    use crate::weights::WeightInfo;
+    use crate::{
+        oracle::{PairReserves, UnitsPerSecondOracle},
+        outflow::{OutflowCap, OutflowWindow},
+        weights::WeightInfo,
+    };
     use frame_support::{
         pallet_prelude::*,
         traits::{
@@ -54,9 +67,9 @@ pub mod pallet {
     use frame_system::pallet_prelude::*;
     use manta_primitives::{
         assets::{
-            self, AssetConfig, AssetIdLocationMap, AssetIdLpMap, AssetIdType, AssetMetadata,
-            AssetRegistry, AssetRegistryMetadata, AssetStorageMetadata, FungibleLedger,
-            LocationType,
+            self, AssetConfig, AssetIdLocationMap, AssetIdLpMap, AssetIdType, AssetInUse,
+            AssetMetadata, AssetRegistry, AssetRegistryMetadata, AssetStorageMetadata,
+            FungibleLedger, LocationType,
         },
         types::Balance,
     };
@@ -71,7 +84,7 @@ pub mod pallet {
     use xcm::latest::prelude::*;
 
     /// Storage Version
-    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
     /// Used to set the minimum balance for permissionless assets.
     pub const POSSIBLE_ACCOUNTS_PER_ASSET: Balance = 10_000_000_000;
@@ -82,6 +95,9 @@ pub mod pallet {
     /// Asset Count Type
     pub(crate) type AssetCount = u32;
 
+    /// Outflow Cap Type
+    pub type OutflowCapOf<T> = OutflowCap<BlockNumberFor<T>>;
+
     /// Pallet Configuration
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -132,8 +148,19 @@ pub mod pallet {
         /// Max length of token symbol
         type TokenSymbolMaxLen: Get<u32>;
 
+        /// Max length of the logo of a permissionless token
+        type TokenLogoMaxLen: Get<u32>;
+
         /// Cost of registering a permissionless asset in native token
         type PermissionlessAssetRegistryCost: Get<Balance>;
+
+        /// Assets which are in use outside of this pallet, e.g. in an open farming pool, and can
+        /// not be deregistered, along with the weight of checking it
+        type AssetInUse: AssetInUse<Self::AssetId>;
+
+        /// Reserves of the pairs of assets with the native asset, used to price the
+        /// units-per-second of assets with a [`UnitsPerSecondOracle`]
+        type PairReserves: PairReserves<Self::AssetId>;
     }
 
     /// Asset Manager Pallet
@@ -193,7 +220,7 @@ pub mod pallet {
     {
         #[inline]
         fn units_per_second(id: &Self::AssetId) -> Option<u128> {
-            UnitsPerSecond::<T>::get(id)
+            Self::effective_units_per_second(*id)
         }
     }
 
@@ -326,9 +353,138 @@ pub mod pallet {
             /// Asset Id of new Asset
             asset_id: T::AssetId,
 
+            /// Owner of the new Asset
+            owner: T::AccountId,
+
             /// Metadata Registered to Asset Manager
             metadata: AssetRegistryMetadata<Balance>,
         },
+
+        /// An asset was deregistered and is being destroyed
+        AssetDeregistered {
+            /// Asset Id of the deregistered Asset
+            asset_id: T::AssetId,
+        },
+
+        /// A deregistered asset was destroyed
+        AssetDestroyed {
+            /// Asset Id of the destroyed Asset
+            asset_id: T::AssetId,
+        },
+
+        /// Updated the units-per-second oracle for an asset
+        UnitsPerSecondOracleUpdated {
+            /// Asset Id of the updated Asset
+            asset_id: T::AssetId,
+
+            /// Updated oracle for the Asset, `None` if removed
+            oracle: Option<UnitsPerSecondOracle>,
+        },
+
+        /// The owner of a permissionless asset updated its metadata
+        PermissionlessAssetMetadataUpdated {
+            /// Asset Id of the updated Asset
+            asset_id: T::AssetId,
+
+            /// Owner of the Asset
+            owner: T::AccountId,
+
+            /// Updated Metadata for the Asset
+            metadata: AssetRegistryMetadata<Balance>,
+
+            /// Updated Logo for the Asset
+            logo: Option<BoundedVec<u8, T::TokenLogoMaxLen>>,
+        },
+
+        /// The ownership of a permissionless asset was transferred
+        AssetOwnershipTransferred {
+            /// Asset Id of the Asset
+            asset_id: T::AssetId,
+
+            /// Previous Owner
+            from: T::AccountId,
+
+            /// New Owner
+            to: T::AccountId,
+        },
+
+        /// The owner of a permissionless asset renounced it, the asset is immutable from now on
+        AssetOwnershipRenounced {
+            /// Asset Id of the Asset
+            asset_id: T::AssetId,
+
+            /// Previous Owner
+            owner: T::AccountId,
+        },
+
+        /// An alias location was registered for an asset
+        LocationAliasRegistered {
+            /// Asset Id of the Asset
+            asset_id: T::AssetId,
+
+            /// Alias Location of the Asset
+            location: T::Location,
+        },
+
+        /// An alias location of an asset was removed
+        LocationAliasRemoved {
+            /// Asset Id of the Asset
+            asset_id: T::AssetId,
+
+            /// Removed Alias Location of the Asset
+            location: T::Location,
+        },
+
+        /// An asset was burned
+        AssetBurned {
+            /// Asset Id of the burned Asset
+            asset_id: T::AssetId,
+
+            /// Account the Asset was burned from
+            who: T::AccountId,
+
+            /// Amount Burned
+            amount: Balance,
+        },
+
+        /// Updated the outflow cap of an asset
+        OutflowCapUpdated {
+            /// Asset Id of the updated Asset
+            asset_id: T::AssetId,
+
+            /// Destination Parachain of the capped outflow, `None` for the outflow to any chain
+            destination: Option<ParaId>,
+
+            /// Updated cap, `None` if removed
+            cap: Option<OutflowCapOf<T>>,
+        },
+
+        /// The outflow of an asset reached or crossed its cap, further outflow fails until the
+        /// window ends.
+        OutflowCapReached {
+            /// Asset Id of the capped Asset
+            asset_id: T::AssetId,
+
+            /// Destination Parachain of the capped outflow, `None` for the outflow to any chain
+            destination: Option<ParaId>,
+
+            /// Reached Cap
+            cap: OutflowCapOf<T>,
+        },
+
+        /// An outflow of an asset was rejected by its cap. The event is kept wherever the failed
+        /// outflow does not revert its caller, e.g. an XCM instruction of a message from another
+        /// chain, a reverted extrinsic only fails with its own error.
+        OutflowRejected {
+            /// Asset Id of the capped Asset
+            asset_id: T::AssetId,
+
+            /// Destination Parachain of the capped outflow, `None` for the outflow to any chain
+            destination: Option<ParaId>,
+
+            /// Rejected Amount
+            amount: Balance,
+        },
     }
 
     /// Asset Manager Error
@@ -372,6 +528,33 @@ pub mod pallet {
 
         /// Decimals cannot be set to zero
         DecimalIsZero,
+
+        /// Cannot Deregister Native Asset
+        CannotDeregisterNativeAsset,
+
+        /// The asset backs a LP asset or is otherwise in use
+        AssetInUse,
+
+        /// An error occurred while destroying an asset at the [`AssetRegistry`].
+        ErrorDestroyingAsset,
+
+        /// The minimum units-per-second of an oracle is above its maximum
+        InvalidUnitsPerSecondBounds,
+
+        /// The caller is not the owner of the permissionless asset
+        NotAssetOwner,
+
+        /// An error occurred while burning an asset.
+        BurnError,
+
+        /// The location is not an alias location of the asset
+        LocationAliasNotFound,
+
+        /// The period of an outflow cap is zero
+        InvalidOutflowCap,
+
+        /// The outflow of the asset exceeds its cap in the current window
+        OutflowCapExceeded,
     }
 
     /// [`AssetId`](AssetConfig::AssetId) to [`MultiLocation`] Map
@@ -436,6 +619,82 @@ pub mod pallet {
     pub(super) type LpToAssetIdPair<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AssetId, (T::AssetId, T::AssetId)>;
 
+    /// Number of LP assets whose asset id pair includes the asset.
+    #[pallet::storage]
+    #[pallet::getter(fn lp_count_of_asset_id)]
+    pub(super) type AssetIdToLpCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AssetId, u32, ValueQuery>;
+
+    /// Deregistered assets which are not fully destroyed yet.
+    #[pallet::storage]
+    #[pallet::getter(fn destroying_assets)]
+    pub type DestroyingAssets<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, ()>;
+
+    /// Assets whose units-per-second are priced through their pair with the native asset
+    #[pallet::storage]
+    #[pallet::getter(fn units_per_second_oracle)]
+    pub type UnitsPerSecondOracles<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AssetId, UnitsPerSecondOracle>;
+
+    /// Time-averaged units-per-second of the assets with a smoothed oracle
+    #[pallet::storage]
+    #[pallet::getter(fn averaged_units_per_second)]
+    pub type AveragedUnitsPerSecond<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, u128>;
+
+    /// Alias locations of each asset, besides its canonical location in [`AssetIdLocation`]
+    ///
+    /// Alias locations map to their asset in [`LocationAssetId`] too, so that the asset is
+    /// recognized when it arrives through any of them, but it is always sent out from its
+    /// canonical location.
+    #[pallet::storage]
+    #[pallet::getter(fn location_alias)]
+    pub type LocationAliases<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AssetId, Blake2_128Concat, T::Location, ()>;
+
+    /// Owner of each permissionless asset which has not been renounced
+    #[pallet::storage]
+    #[pallet::getter(fn permissionless_asset_owner)]
+    pub type PermissionlessAssetOwner<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AssetId, T::AccountId>;
+
+    /// Logo of each permissionless asset, e.g. an URL or IPFS CID
+    #[pallet::storage]
+    #[pallet::getter(fn asset_logo)]
+    pub type AssetLogo<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AssetId, BoundedVec<u8, T::TokenLogoMaxLen>>;
+
+    /// Outflow caps of each asset, per destination parachain or for the outflow to any chain
+    /// with a `None` destination
+    #[pallet::storage]
+    #[pallet::getter(fn outflow_cap)]
+    pub type OutflowCaps<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AssetId,
+        Blake2_128Concat,
+        Option<ParaId>,
+        OutflowCapOf<T>,
+    >;
+
+    /// Outflow of each capped asset within the current window of its cap
+    #[pallet::storage]
+    #[pallet::getter(fn outflow_usage)]
+    pub type OutflowUsage<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AssetId,
+        Blake2_128Concat,
+        Option<ParaId>,
+        OutflowWindow<BlockNumberFor<T>>,
+    >;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            Self::update_averaged_units_per_second()
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Register a new asset in the asset manager.
@@ -510,17 +769,7 @@ pub mod pallet {
             if let Some(old_para_id) =
                 Self::para_id_from_multilocation(old_location.into().as_ref())
             {
-                if AllowedDestParaIds::<T>::get(old_para_id) <= Some(<AssetCount as One>::one()) {
-                    AllowedDestParaIds::<T>::remove(old_para_id);
-                } else {
-                    AllowedDestParaIds::<T>::try_mutate(old_para_id, |cnt| -> DispatchResult {
-                        let new_cnt = cnt
-                            .map(|c| c - <AssetCount as One>::one())
-                            .ok_or(Error::<T>::UpdateParaIdError)?;
-                        *cnt = Some(new_cnt);
-                        Ok(())
-                    })?;
-                }
+                Self::decrease_count_of_associated_assets(*old_para_id)?;
             }
 
             // If it's a new para id, which will be inserted with AssetCount as 1.
@@ -712,6 +961,8 @@ pub mod pallet {
 
             AssetIdPairToLp::<T>::insert((asset_id0, asset_id1), asset_id);
             LpToAssetIdPair::<T>::insert(asset_id, (asset_id0, asset_id1));
+            AssetIdToLpCount::<T>::mutate(asset_id0, |count| *count = count.saturating_add(1));
+            AssetIdToLpCount::<T>::mutate(asset_id1, |count| *count = count.saturating_add(1));
 
             Self::deposit_event(Event::<T>::LPAssetRegistered {
                 asset_id0,
@@ -785,13 +1036,282 @@ pub mod pallet {
                 Provenance::Minted,
             )
             .map_err(|_| Error::<T>::MintError)?;
+            PermissionlessAssetOwner::<T>::insert(asset_id, &who);
 
             Self::deposit_event(Event::<T>::PermissionlessAssetRegistered {
                 asset_id,
+                owner: who,
                 metadata: register_metadata,
             });
             Ok(())
         }
+
+        /// Deregister an asset from the asset manager and destroy it, over as many calls as needed.
+        ///
+        /// The first call removes the asset from every map of the asset manager and freezes it
+        /// for destruction. Each call destroys up to `max_items` of its accounts and approvals,
+        /// the asset is gone once a call destroys fewer than `max_items`.
+        ///
+        /// * `origin`: Caller of this extrinsic, the access control is specified by `ModifierOrigin`.
+        /// * `asset_id`: AssetId to be deregistered.
+        /// * `max_items`: Maximum number of accounts and approvals to destroy in this call.
+        #[pallet::call_index(9)]
+        #[pallet::weight(
+            T::WeightInfo::deregister_asset(*max_items)
+                .saturating_add(T::AssetInUse::contains_weight())
+                .saturating_add(T::DbWeight::get().reads(2))
+        )]
+        #[transactional]
+        pub fn deregister_asset(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: T::AssetId,
+            max_items: u32,
+        ) -> DispatchResult {
+            T::ModifierOrigin::ensure_origin(origin)?;
+            if !DestroyingAssets::<T>::contains_key(asset_id) {
+                Self::do_deregister_asset(asset_id)?;
+                Self::deposit_event(Event::<T>::AssetDeregistered { asset_id });
+            }
+            if Self::do_destroy_asset(asset_id, max_items)? {
+                Self::deposit_event(Event::<T>::AssetDestroyed { asset_id });
+            }
+            Ok(())
+        }
+
+        /// Price the units-per-second of an asset through its pair with the native asset.
+        ///
+        /// The native units-per-second are converted at the price of the pair, bounded by the
+        /// oracle, and the static units-per-second are charged while the pair has no price.
+        ///
+        /// * `origin`: Caller of this extrinsic, the access control is specified by `ModifierOrigin`.
+        /// * `asset_id`: AssetId to be updated.
+        /// * `oracle`: Oracle of the asset, `None` to charge the static units-per-second again.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::set_units_per_second_oracle())]
+        #[transactional]
+        pub fn set_units_per_second_oracle(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: T::AssetId,
+            oracle: Option<UnitsPerSecondOracle>,
+        ) -> DispatchResult {
+            T::ModifierOrigin::ensure_origin(origin)?;
+            ensure!(
+                AssetIdLocation::<T>::contains_key(asset_id),
+                Error::<T>::UpdateNonExistentAsset
+            );
+            if let Some(oracle) = &oracle {
+                ensure!(
+                    oracle.min_units_per_second <= oracle.max_units_per_second,
+                    Error::<T>::InvalidUnitsPerSecondBounds
+                );
+            }
+            UnitsPerSecondOracles::<T>::set(asset_id, oracle);
+            AveragedUnitsPerSecond::<T>::remove(asset_id);
+            Self::deposit_event(Event::<T>::UnitsPerSecondOracleUpdated { asset_id, oracle });
+            Ok(())
+        }
+
+        /// Update the name, symbol and logo of a permissionless asset.
+        ///
+        /// * `origin`: Owner of the asset.
+        /// * `asset_id`: AssetId to be updated.
+        /// * `name`: New name of the asset.
+        /// * `symbol`: New symbol of the asset.
+        /// * `logo`: New logo of the asset, `None` to remove it.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::update_permissionless_asset_metadata())]
+        #[transactional]
+        pub fn update_permissionless_asset_metadata(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: T::AssetId,
+            name: BoundedVec<u8, T::TokenNameMaxLen>,
+            symbol: BoundedVec<u8, T::TokenSymbolMaxLen>,
+            logo: Option<BoundedVec<u8, T::TokenLogoMaxLen>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_asset_owner(&who, asset_id)?;
+            let mut metadata =
+                AssetIdMetadata::<T>::get(asset_id).ok_or(Error::<T>::UpdateNonExistentAsset)?;
+            metadata.metadata.name = name.into();
+            metadata.metadata.symbol = symbol.into();
+            <T::AssetConfig as AssetConfig<T>>::AssetRegistry::update_asset_metadata(
+                &asset_id,
+                metadata.clone().into(),
+            )?;
+
+            AssetIdMetadata::<T>::insert(asset_id, &metadata);
+            AssetLogo::<T>::set(asset_id, logo.clone());
+            Self::deposit_event(Event::<T>::PermissionlessAssetMetadataUpdated {
+                asset_id,
+                owner: who,
+                metadata,
+                logo,
+            });
+            Ok(())
+        }
+
+        /// Transfer the ownership of a permissionless asset.
+        ///
+        /// * `origin`: Owner of the asset.
+        /// * `asset_id`: AssetId to be transferred.
+        /// * `new_owner`: New owner of the asset.
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::transfer_asset_ownership())]
+        #[transactional]
+        pub fn transfer_asset_ownership(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: T::AssetId,
+            new_owner: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_asset_owner(&who, asset_id)?;
+            PermissionlessAssetOwner::<T>::insert(asset_id, &new_owner);
+            Self::deposit_event(Event::<T>::AssetOwnershipTransferred {
+                asset_id,
+                from: who,
+                to: new_owner,
+            });
+            Ok(())
+        }
+
+        /// Renounce the ownership of a permissionless asset, its metadata can't be updated by
+        /// an owner anymore.
+        ///
+        /// * `origin`: Owner of the asset.
+        /// * `asset_id`: AssetId to be renounced.
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::renounce_asset_ownership())]
+        #[transactional]
+        pub fn renounce_asset_ownership(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: T::AssetId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_asset_owner(&who, asset_id)?;
+            PermissionlessAssetOwner::<T>::remove(asset_id);
+            Self::deposit_event(Event::<T>::AssetOwnershipRenounced {
+                asset_id,
+                owner: who,
+            });
+            Ok(())
+        }
+
+        /// Burn a permissionless asset from the balance of its owner.
+        ///
+        /// * `origin`: Owner of the asset.
+        /// * `asset_id`: AssetId to be burned.
+        /// * `amount`: Amount of asset being burned.
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::burn_asset())]
+        #[transactional]
+        pub fn burn_asset(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: T::AssetId,
+            #[pallet::compact] amount: Balance,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_asset_owner(&who, asset_id)?;
+            <T::AssetConfig as AssetConfig<T>>::FungibleLedger::withdraw_burning(
+                asset_id,
+                &who,
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )
+            .map_err(|_| Error::<T>::BurnError)?;
+            Self::deposit_event(Event::<T>::AssetBurned {
+                asset_id,
+                who,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Register an alias location for an asset, the asset is recognized when it arrives
+        /// through the alias but keeps being sent out from its canonical location.
+        ///
+        /// * `origin`: Caller of this extrinsic, the access control is specified by `ModifierOrigin`.
+        /// * `asset_id`: AssetId to be aliased.
+        /// * `location`: Alias location of the asset.
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::register_location_alias())]
+        #[transactional]
+        pub fn register_location_alias(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: T::AssetId,
+            location: T::Location,
+        ) -> DispatchResult {
+            T::ModifierOrigin::ensure_origin(origin)?;
+            ensure!(
+                AssetIdLocation::<T>::contains_key(asset_id),
+                Error::<T>::UpdateNonExistentAsset
+            );
+            ensure!(
+                !LocationAssetId::<T>::contains_key(&location),
+                Error::<T>::LocationAlreadyExists
+            );
+            LocationAssetId::<T>::insert(&location, asset_id);
+            LocationAliases::<T>::insert(asset_id, &location, ());
+            Self::deposit_event(Event::<T>::LocationAliasRegistered { asset_id, location });
+            Ok(())
+        }
+
+        /// Remove an alias location of an asset.
+        ///
+        /// * `origin`: Caller of this extrinsic, the access control is specified by `ModifierOrigin`.
+        /// * `asset_id`: AssetId of the alias.
+        /// * `location`: Alias location to be removed.
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::remove_location_alias())]
+        #[transactional]
+        pub fn remove_location_alias(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: T::AssetId,
+            location: T::Location,
+        ) -> DispatchResult {
+            T::ModifierOrigin::ensure_origin(origin)?;
+            ensure!(
+                LocationAliases::<T>::take(asset_id, &location).is_some(),
+                Error::<T>::LocationAliasNotFound
+            );
+            LocationAssetId::<T>::remove(&location);
+            Self::deposit_event(Event::<T>::LocationAliasRemoved { asset_id, location });
+            Ok(())
+        }
+
+        /// Set or remove the cap on the outflow of an asset over a window of blocks, to a
+        /// sibling parachain or to any chain. The outflow so far is forgotten and the next
+        /// outflow starts a new window.
+        ///
+        /// * `origin`: Caller of this extrinsic, the access control is specified by `ModifierOrigin`.
+        /// * `asset_id`: AssetId to be capped.
+        /// * `destination`: Destination parachain of the capped outflow, `None` for any chain.
+        /// * `cap`: Cap on the outflow, `None` to remove it.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::set_outflow_cap())]
+        #[transactional]
+        pub fn set_outflow_cap(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: T::AssetId,
+            destination: Option<ParaId>,
+            cap: Option<OutflowCapOf<T>>,
+        ) -> DispatchResult {
+            T::ModifierOrigin::ensure_origin(origin)?;
+            ensure!(
+                AssetIdMetadata::<T>::contains_key(asset_id),
+                Error::<T>::UpdateNonExistentAsset
+            );
+            if let Some(cap) = &cap {
+                ensure!(!cap.period.is_zero(), Error::<T>::InvalidOutflowCap);
+            }
+            OutflowCaps::<T>::set(asset_id, destination, cap);
+            OutflowUsage::<T>::remove(asset_id, destination);
+            Self::deposit_event(Event::<T>::OutflowCapUpdated {
+                asset_id,
+                destination,
+                cap,
+            });
+            Ok(())
+        }
     }
 
     impl<T> Pallet<T>
@@ -825,6 +1345,106 @@ pub mod pallet {
             Ok(asset_id)
         }
 
+        /// Removes the asset from all maps and starts its destruction at the [`AssetRegistry`].
+        fn do_deregister_asset(asset_id: T::AssetId) -> DispatchResult {
+            ensure!(
+                asset_id != <T::AssetConfig as AssetConfig<T>>::NativeAssetId::get(),
+                Error::<T>::CannotDeregisterNativeAsset
+            );
+            ensure!(
+                AssetIdMetadata::<T>::contains_key(asset_id),
+                Error::<T>::UpdateNonExistentAsset
+            );
+            // An LP asset can go, the assets of its pair only after it
+            ensure!(
+                AssetIdToLpCount::<T>::get(asset_id).is_zero()
+                    && !T::AssetInUse::contains(&asset_id),
+                Error::<T>::AssetInUse
+            );
+            // but not while it is still minted or its pair still holds liquidity
+            if let Some((asset_id0, asset_id1)) = LpToAssetIdPair::<T>::get(asset_id) {
+                ensure!(
+                    <T::AssetConfig as AssetConfig<T>>::FungibleLedger::supply(asset_id).is_zero()
+                        && T::PairReserves::reserves(asset_id0, asset_id1).is_none(),
+                    Error::<T>::AssetInUse
+                );
+            }
+
+            if let Some(location) = AssetIdLocation::<T>::take(asset_id) {
+                LocationAssetId::<T>::remove(&location);
+                let location: Option<MultiLocation> = location.into();
+                if let Some(para_id) = Self::para_id_from_multilocation(location.as_ref()) {
+                    Self::decrease_count_of_associated_assets(*para_id)?;
+                }
+                FilteredOutgoingAssetLocations::<T>::remove(location);
+            }
+            for (location, _) in LocationAliases::<T>::drain_prefix(asset_id) {
+                LocationAssetId::<T>::remove(&location);
+            }
+            if let Some((asset_id0, asset_id1)) = LpToAssetIdPair::<T>::take(asset_id) {
+                AssetIdPairToLp::<T>::remove((asset_id0, asset_id1));
+                for pair_asset_id in [asset_id0, asset_id1] {
+                    AssetIdToLpCount::<T>::mutate_exists(pair_asset_id, |count| {
+                        *count = count
+                            .map(|count| count.saturating_sub(1))
+                            .filter(|count| !count.is_zero());
+                    });
+                }
+            }
+            AssetIdMetadata::<T>::remove(asset_id);
+            UnitsPerSecond::<T>::remove(asset_id);
+            UnitsPerSecondOracles::<T>::remove(asset_id);
+            AveragedUnitsPerSecond::<T>::remove(asset_id);
+            PermissionlessAssetOwner::<T>::remove(asset_id);
+            AssetLogo::<T>::remove(asset_id);
+            let _ = OutflowCaps::<T>::clear_prefix(asset_id, u32::MAX, None);
+            let _ = OutflowUsage::<T>::clear_prefix(asset_id, u32::MAX, None);
+
+            <T::AssetConfig as AssetConfig<T>>::AssetRegistry::start_destroy_asset(&asset_id)
+                .map_err(|_| Error::<T>::ErrorDestroyingAsset)?;
+            DestroyingAssets::<T>::insert(asset_id, ());
+            Ok(())
+        }
+
+        /// Destroys up to `max_items` accounts and approvals of a deregistered asset, returns
+        /// whether the asset is completely destroyed.
+        fn do_destroy_asset(asset_id: T::AssetId, max_items: u32) -> Result<bool, DispatchError> {
+            if max_items.is_zero() {
+                return Ok(false);
+            }
+            let accounts =
+                <T::AssetConfig as AssetConfig<T>>::AssetRegistry::destroy_asset_accounts(
+                    &asset_id, max_items,
+                )
+                .map_err(|_| Error::<T>::ErrorDestroyingAsset)?;
+            // All accounts are gone only if there was budget left over
+            if accounts >= max_items {
+                return Ok(false);
+            }
+            let max_items = max_items - accounts;
+            let approvals =
+                <T::AssetConfig as AssetConfig<T>>::AssetRegistry::destroy_asset_approvals(
+                    &asset_id, max_items,
+                )
+                .map_err(|_| Error::<T>::ErrorDestroyingAsset)?;
+            if approvals >= max_items {
+                return Ok(false);
+            }
+            <T::AssetConfig as AssetConfig<T>>::AssetRegistry::finish_destroy_asset(&asset_id)
+                .map_err(|_| Error::<T>::ErrorDestroyingAsset)?;
+            DestroyingAssets::<T>::remove(asset_id);
+            Ok(true)
+        }
+
+        /// Ensures `who` owns the permissionless asset with `asset_id`.
+        fn ensure_asset_owner(who: &T::AccountId, asset_id: T::AssetId) -> DispatchResult {
+            ensure!(
+                PermissionlessAssetOwner::<T>::get(asset_id).as_ref() == Some(who),
+                Error::<T>::NotAssetOwner
+            );
+            Ok(())
+        }
+
         /// Returns and increments the [`NextAssetId`] by one. Fails if it hits the upper limit of `PermissionlessStartId`
         #[inline]
         pub(super) fn next_asset_id_and_increment() -> Result<T::AssetId, DispatchError> {
@@ -897,6 +1517,22 @@ pub mod pallet {
             }
         }
 
+        /// Decreases the count of associated assets for the para id, removing it at zero.
+        pub fn decrease_count_of_associated_assets(para_id: ParaId) -> DispatchResult {
+            if AllowedDestParaIds::<T>::get(para_id) <= Some(<AssetCount as One>::one()) {
+                AllowedDestParaIds::<T>::remove(para_id);
+                Ok(())
+            } else {
+                AllowedDestParaIds::<T>::try_mutate(para_id, |cnt| -> DispatchResult {
+                    let new_cnt = cnt
+                        .map(|c| c - <AssetCount as One>::one())
+                        .ok_or(Error::<T>::UpdateParaIdError)?;
+                    *cnt = Some(new_cnt);
+                    Ok(())
+                })
+            }
+        }
+
         pub fn check_outgoing_assets_filter(asset_location: &Option<MultiLocation>) -> bool {
             FilteredOutgoingAssetLocations::<T>::contains_key(asset_location)
         }
@@ -0,0 +1,67 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Migrations
+
+use crate::{AssetIdToLpCount, Config, LpToAssetIdPair, Pallet};
+use core::marker::PhantomData;
+use frame_support::{
+    traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Builds [`AssetIdToLpCount`] from the existing [`LpToAssetIdPair`] entries
+pub struct BuildAssetIdToLpCount<T>(PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for BuildAssetIdToLpCount<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let db_weight = T::DbWeight::get();
+        if Pallet::<T>::on_chain_storage_version() != 2 {
+            log::info!(target: "BuildAssetIdToLpCount", "skipping, storage version is not 2");
+            return db_weight.reads(1);
+        }
+        let mut lp_assets = 0u64;
+        for (asset_id0, asset_id1) in LpToAssetIdPair::<T>::iter_values() {
+            lp_assets = lp_assets.saturating_add(1);
+            AssetIdToLpCount::<T>::mutate(asset_id0, |count| *count = count.saturating_add(1));
+            AssetIdToLpCount::<T>::mutate(asset_id1, |count| *count = count.saturating_add(1));
+        }
+        StorageVersion::new(3).put::<Pallet<T>>();
+        log::info!(target: "BuildAssetIdToLpCount", "indexed {:?} LP assets", lp_assets);
+        // every LP asset costs its own read plus a read and write per asset of its pair
+        db_weight.reads_writes(
+            lp_assets.saturating_mul(3).saturating_add(1),
+            lp_assets.saturating_mul(2).saturating_add(1),
+        )
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+        Ok(Vec::new())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+        let pair_assets = LpToAssetIdPair::<T>::iter_values().count();
+        let indexed: u32 = AssetIdToLpCount::<T>::iter_values().sum();
+        frame_support::ensure!(
+            indexed as usize == pair_assets.saturating_mul(2),
+            "AssetIdToLpCount does not match LpToAssetIdPair"
+        );
+        Ok(())
+    }
+}
@@ -20,11 +20,12 @@
 //! Mock runtime for asset-manager
 
 use crate as pallet_asset_manager;
+use crate::PairReserves;
 use frame_support::{
     construct_runtime, derive_impl,
     pallet_prelude::DispatchResult,
     parameter_types,
-    traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64},
+    traits::{fungibles::Destroy, AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, IsInVec},
     PalletId,
 };
 use frame_system as system;
@@ -182,6 +183,28 @@ impl AssetRegistry for MantaAssetRegistry {
             metadata.is_frozen,
         )
     }
+
+    fn start_destroy_asset(asset_id: &CalamariAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::start_destroy(*asset_id, None)
+    }
+
+    fn destroy_asset_accounts(
+        asset_id: &CalamariAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_accounts(*asset_id, max_items)
+    }
+
+    fn destroy_asset_approvals(
+        asset_id: &CalamariAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_approvals(*asset_id, max_items)
+    }
+
+    fn finish_destroy_asset(asset_id: &CalamariAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::finish_destroy(*asset_id)
+    }
 }
 
 parameter_types! {
@@ -200,6 +223,25 @@ parameter_types! {
         is_sufficient: true,
     };
     pub const AssetManagerPalletId: PalletId = ASSET_MANAGER_PALLET_ID;
+    pub AssetsInUse: Vec<CalamariAssetId> = vec![IN_USE_ASSET_ID];
+    pub storage PairedAssetReserves: (Balance, Balance) = (0, 0);
+}
+
+/// The pair of the native asset with [`PAIRED_ASSET_ID`] at the [`PairedAssetReserves`], the
+/// pair does not exist while both reserves are zero
+pub struct MockPairReserves;
+impl PairReserves<CalamariAssetId> for MockPairReserves {
+    fn reserves(asset_0: CalamariAssetId, asset_1: CalamariAssetId) -> Option<(Balance, Balance)> {
+        let (native_reserve, asset_reserve) = PairedAssetReserves::get();
+        if native_reserve == 0 && asset_reserve == 0 {
+            return None;
+        }
+        match (asset_0, asset_1) {
+            (NATIVE_ASSET_ID, PAIRED_ASSET_ID) => Some((native_reserve, asset_reserve)),
+            (PAIRED_ASSET_ID, NATIVE_ASSET_ID) => Some((asset_reserve, native_reserve)),
+            _ => None,
+        }
+    }
 }
 
 /// AssetConfig implementations for this runtime
@@ -234,7 +276,10 @@ impl pallet_asset_manager::Config for Runtime {
     type PermissionlessStartId = ConstU128<1_000_000>;
     type TokenNameMaxLen = ConstU32<100>;
     type TokenSymbolMaxLen = ConstU32<100>;
+    type TokenLogoMaxLen = ConstU32<100>;
     type PermissionlessAssetRegistryCost = ConstU128<1000>;
+    type AssetInUse = IsInVec<AssetsInUse>;
+    type PairReserves = MockPairReserves;
     type WeightInfo = ();
 }
 
@@ -252,6 +297,14 @@ construct_runtime!(
 
 pub const PALLET_BALANCES_INDEX: u8 = 3;
 
+/// Asset id the mock pretends to be in use outside of the asset manager
+pub const IN_USE_ASSET_ID: CalamariAssetId = 9;
+
+pub const NATIVE_ASSET_ID: CalamariAssetId = 1;
+
+/// Asset id the mock pairs with the native asset
+pub const PAIRED_ASSET_ID: CalamariAssetId = 8;
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
     let mut t = frame_system::GenesisConfig::<Runtime>::default()
         .build_storage()
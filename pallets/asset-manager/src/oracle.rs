@@ -0,0 +1,127 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Units-per-second Priced by Pair Reserves
+
+use crate::{AveragedUnitsPerSecond, Config, Pallet, UnitsPerSecond, UnitsPerSecondOracles};
+use codec::{Decode, Encode};
+use frame_support::{traits::Get, weights::Weight};
+use manta_primitives::{assets::AssetConfig, types::Balance};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational_with_rounding, Perbill, Rounding, RuntimeDebug,
+};
+
+/// Reserves of asset pairs, e.g. of the pools of an AMM like zenlink.
+pub trait PairReserves<AssetId> {
+    /// Returns the reserves of `asset_0` and `asset_1` in their pair, if the pair exists.
+    fn reserves(asset_0: AssetId, asset_1: AssetId) -> Option<(Balance, Balance)>;
+}
+
+impl<AssetId> PairReserves<AssetId> for () {
+    fn reserves(_asset_0: AssetId, _asset_1: AssetId) -> Option<(Balance, Balance)> {
+        None
+    }
+}
+
+/// Units-per-second of an asset priced through its pair with the native asset
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct UnitsPerSecondOracle {
+    /// Units of the native asset charged per second of XCM execution
+    pub native_units_per_second: u128,
+
+    /// Lower bound of the priced units-per-second
+    pub min_units_per_second: u128,
+
+    /// Upper bound of the priced units-per-second
+    pub max_units_per_second: u128,
+
+    /// Weight of the latest price in the time-averaged units-per-second, updated every block,
+    /// the latest price is charged if `None`
+    pub smoothing: Option<Perbill>,
+}
+
+impl<T> Pallet<T>
+where
+    T: Config,
+{
+    /// Returns the units-per-second of `asset_id` at the current price of its pair with the
+    /// native asset, unbounded.
+    pub fn spot_units_per_second(
+        asset_id: T::AssetId,
+        oracle: &UnitsPerSecondOracle,
+    ) -> Option<u128> {
+        let native_asset_id = <T::AssetConfig as AssetConfig<T>>::NativeAssetId::get();
+        let (native_reserve, asset_reserve) = T::PairReserves::reserves(native_asset_id, asset_id)?;
+        if native_reserve == 0 || asset_reserve == 0 {
+            return None;
+        }
+        multiply_by_rational_with_rounding(
+            oracle.native_units_per_second,
+            asset_reserve,
+            native_reserve,
+            Rounding::Up,
+        )
+    }
+
+    /// Returns the units-per-second charged for `asset_id`.
+    ///
+    /// Assets with an oracle are charged at the bounded price of their pair with the native
+    /// asset, falling back to the static [`UnitsPerSecond`] while the pair has no price.
+    pub fn effective_units_per_second(asset_id: T::AssetId) -> Option<u128> {
+        let static_units_per_second = UnitsPerSecond::<T>::get(asset_id);
+        let Some(oracle) = UnitsPerSecondOracles::<T>::get(asset_id) else {
+            return static_units_per_second;
+        };
+        let priced = match oracle.smoothing {
+            Some(_) => AveragedUnitsPerSecond::<T>::get(asset_id)
+                .or_else(|| Self::spot_units_per_second(asset_id, &oracle)),
+            None => Self::spot_units_per_second(asset_id, &oracle),
+        };
+        priced
+            .map(|units| units.clamp(oracle.min_units_per_second, oracle.max_units_per_second))
+            .or(static_units_per_second)
+    }
+
+    /// Moves the time-averaged units-per-second of every smoothed oracle towards its latest
+    /// price.
+    pub(crate) fn update_averaged_units_per_second() -> Weight {
+        let mut weight = Weight::zero();
+        for (asset_id, oracle) in UnitsPerSecondOracles::<T>::iter() {
+            weight = weight.saturating_add(T::DbWeight::get().reads(1));
+            let Some(smoothing) = oracle.smoothing else {
+                continue;
+            };
+            // Pair reserves of zenlink take a few reads, the average one more read and a write
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(4, 1));
+            let Some(spot) = Self::spot_units_per_second(asset_id, &oracle) else {
+                continue;
+            };
+            AveragedUnitsPerSecond::<T>::mutate(asset_id, |averaged| {
+                *averaged = Some(match averaged {
+                    Some(previous) => {
+                        (smoothing * spot).saturating_add(smoothing.left_from_one() * *previous)
+                    }
+                    None => spot,
+                });
+            });
+        }
+        weight
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Caps on the Cross-chain Outflow of Assets
+
+use crate::{Config, Error, Event, OutflowCaps, OutflowUsage, Pallet, ParaId};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_system::pallet_prelude::BlockNumberFor;
+use manta_primitives::{types::Balance, xcm::OutflowLimiter};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{traits::Saturating, DispatchError, RuntimeDebug};
+
+/// Cap on the outflow of an asset over a window of blocks
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OutflowCap<BlockNumber> {
+    /// Maximum amount of the asset leaving this chain within a window
+    pub amount: Balance,
+
+    /// Number of blocks in a window
+    pub period: BlockNumber,
+}
+
+/// Outflow of an asset within its current window
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct OutflowWindow<BlockNumber> {
+    /// Block the window started at
+    pub start: BlockNumber,
+
+    /// Amount of the asset which left this chain within the window
+    pub used: Balance,
+}
+
+impl<T> Pallet<T>
+where
+    T: Config,
+{
+    /// Returns the amount of `asset_id` that can still leave this chain for the parachain
+    /// `destination` in the current window, or for any chain if `destination` is `None`.
+    ///
+    /// Returns `None` if that outflow is not capped.
+    pub fn remaining_outflow(asset_id: T::AssetId, destination: Option<ParaId>) -> Option<Balance> {
+        let cap = OutflowCaps::<T>::get(asset_id, destination)?;
+        let now = frame_system::Pallet::<T>::block_number();
+        let used = OutflowUsage::<T>::get(asset_id, destination)
+            .filter(|window| now < window.start.saturating_add(cap.period))
+            .map_or(0, |window| window.used);
+        Some(cap.amount.saturating_sub(used))
+    }
+}
+
+impl<T> OutflowLimiter<T::AssetId, Balance> for Pallet<T>
+where
+    T: Config,
+{
+    /// Records the outflow in the current window of its cap, starting a new window if the last
+    /// one has passed, and fails with [`Error::OutflowCapExceeded`] if it exceeds the cap.
+    ///
+    /// Deposits [`Event::OutflowCapReached`] once the outflow reaches or crosses the cap, and
+    /// [`Event::OutflowRejected`] for an outflow exceeding it.
+    fn try_record_outflow(
+        asset_id: T::AssetId,
+        destination: Option<ParaId>,
+        amount: Balance,
+    ) -> Result<(), DispatchError> {
+        let Some(cap) = OutflowCaps::<T>::get(asset_id, destination) else {
+            return Ok(());
+        };
+        let now: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        OutflowUsage::<T>::try_mutate(asset_id, destination, |maybe_window| {
+            let mut window = maybe_window
+                .filter(|window| now < window.start.saturating_add(cap.period))
+                .unwrap_or(OutflowWindow {
+                    start: now,
+                    used: 0,
+                });
+            let used_before = window.used;
+            window.used = window
+                .used
+                .checked_add(amount)
+                .filter(|used| *used <= cap.amount)
+                .ok_or(Error::<T>::OutflowCapExceeded)?;
+            if used_before < cap.amount && window.used >= cap.amount {
+                Self::deposit_event(Event::<T>::OutflowCapReached {
+                    asset_id,
+                    destination,
+                    cap,
+                });
+            }
+            *maybe_window = Some(window);
+            Ok(())
+        })
+        .map_err(|error: DispatchError| {
+            Self::deposit_event(Event::<T>::OutflowRejected {
+                asset_id,
+                destination,
+                amount,
+            });
+            error
+        })
+    }
+}
@@ -18,19 +18,25 @@
 
 use crate::{
     mock::*, AssetIdLocation, AssetIdMetadata, AssetIdPairToLp, Error, LocationAssetId,
-    LpToAssetIdPair, NextAssetId, UnitsPerSecond,
+    LpToAssetIdPair, NextAssetId, OutflowCap, UnitsPerSecond, UnitsPerSecondOracle, XcmFeeInfo,
 };
 use frame_support::{
-    assert_noop, assert_ok,
-    traits::{fungibles::metadata::Inspect, Contains},
+    assert_err, assert_noop, assert_ok,
+    traits::{fungibles::metadata::Inspect, Contains, ExistenceRequirement, Hooks},
 };
 use manta_primitives::{
-    assets::{AssetConfig, AssetLocation, AssetRegistryMetadata, FungibleLedger},
+    assets::{
+        AssetConfig, AssetIdLocationConvert, AssetLocation, AssetRegistryMetadata, FungibleLedger,
+    },
     types::Balance,
+    xcm::OutflowLimiter,
 };
 use orml_traits::GetByKey;
 use sp_core::Get;
-use sp_runtime::{traits::BadOrigin, ArithmeticError};
+use sp_runtime::{
+    traits::{BadOrigin, MaybeEquivalence},
+    ArithmeticError, Perbill,
+};
 use xcm::{latest::prelude::*, VersionedMultiLocation};
 
 pub const ALICE: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([0u8; 32]);
@@ -883,3 +889,771 @@ fn counters_test() {
         );
     });
 }
+
+#[test]
+fn deregister_asset_should_work() {
+    new_test_ext().execute_with(|| {
+        let (_, location8) = create_asset_and_location("Asset8");
+        let metadata8 = create_asset_metadata("Asset8", "Asset8", 12, 1u128, false, true);
+        assert_ok!(AssetManager::register_asset(
+            RuntimeOrigin::root(),
+            location8.clone(),
+            metadata8.clone()
+        ));
+        let (metadata9, location9) = create_asset_and_location("Asset9");
+        assert_ok!(AssetManager::register_asset(
+            RuntimeOrigin::root(),
+            location9,
+            metadata9
+        ));
+        let metadata10 = create_asset_metadata("LP10", "LP10", 12, 1u128, false, false);
+        assert_ok!(AssetManager::register_lp_asset(
+            RuntimeOrigin::root(),
+            8,
+            9,
+            metadata10
+        ));
+        assert_ok!(AssetManager::set_units_per_second(
+            RuntimeOrigin::root(),
+            8,
+            100
+        ));
+        assert_ok!(AssetManager::update_outgoing_filtered_assets(
+            RuntimeOrigin::root(),
+            location8.clone(),
+            true
+        ));
+        for who in [ALICE, [1u8; 32].into(), [2u8; 32].into()] {
+            assert_ok!(
+                <MantaAssetConfig as AssetConfig<Runtime>>::FungibleLedger::deposit_minting(
+                    8, &who, 1_000
+                )
+            );
+        }
+        assert_eq!(crate::AllowedDestParaIds::<Runtime>::get(2015), Some(2));
+
+        assert_noop!(
+            AssetManager::deregister_asset(RuntimeOrigin::signed(ALICE), 8, 10),
+            BadOrigin
+        );
+        let native_asset_id = <MantaAssetConfig as AssetConfig<Runtime>>::NativeAssetId::get();
+        assert_noop!(
+            AssetManager::deregister_asset(RuntimeOrigin::root(), native_asset_id, 10),
+            Error::<Runtime>::CannotDeregisterNativeAsset
+        );
+        assert_noop!(
+            AssetManager::deregister_asset(RuntimeOrigin::root(), 100, 10),
+            Error::<Runtime>::UpdateNonExistentAsset
+        );
+        assert_eq!(AssetManager::lp_count_of_asset_id(8), 1);
+        assert_eq!(AssetManager::lp_count_of_asset_id(9), 1);
+        // Both assets back the LP asset, the mock also marks asset 9 as in use
+        assert_noop!(
+            AssetManager::deregister_asset(RuntimeOrigin::root(), 8, 10),
+            Error::<Runtime>::AssetInUse
+        );
+        assert_noop!(
+            AssetManager::deregister_asset(RuntimeOrigin::root(), IN_USE_ASSET_ID, 10),
+            Error::<Runtime>::AssetInUse
+        );
+
+        // The LP asset has no accounts and is destroyed right away
+        assert_ok!(AssetManager::deregister_asset(
+            RuntimeOrigin::root(),
+            10,
+            10
+        ));
+        assert_eq!(AssetIdPairToLp::<Runtime>::get((8, 9)), None);
+        assert_eq!(LpToAssetIdPair::<Runtime>::get(10), None);
+        assert_eq!(AssetManager::lp_count_of_asset_id(8), 0);
+        assert_eq!(AssetManager::lp_count_of_asset_id(9), 0);
+        assert_eq!(AssetIdMetadata::<Runtime>::get(10), None);
+        assert_eq!(AssetManager::destroying_assets(10), None);
+        assert_eq!(Assets::maybe_total_supply(10), None);
+
+        // Asset 8 takes two calls to destroy its three accounts
+        assert_ok!(AssetManager::deregister_asset(RuntimeOrigin::root(), 8, 2));
+        assert_eq!(AssetIdLocation::<Runtime>::get(8), None);
+        assert_eq!(LocationAssetId::<Runtime>::get(&location8), None);
+        assert_eq!(AssetIdMetadata::<Runtime>::get(8), None);
+        assert_eq!(UnitsPerSecond::<Runtime>::get(8), None);
+        assert_eq!(crate::AllowedDestParaIds::<Runtime>::get(2015), Some(1));
+        assert!(!AssetManager::check_outgoing_assets_filter(
+            &location8.clone().into()
+        ));
+        assert_eq!(AssetManager::destroying_assets(8), Some(()));
+        assert!(Assets::maybe_total_supply(8).is_some());
+        assert_noop!(
+            AssetManager::mint_asset(RuntimeOrigin::root(), 8, ALICE, 1_000),
+            Error::<Runtime>::UpdateNonExistentAsset
+        );
+
+        assert_ok!(AssetManager::deregister_asset(RuntimeOrigin::root(), 8, 2));
+        assert_eq!(AssetManager::destroying_assets(8), None);
+        assert_eq!(Assets::maybe_total_supply(8), None);
+
+        // The location is free to register again
+        assert_ok!(AssetManager::register_asset(
+            RuntimeOrigin::root(),
+            location8.clone(),
+            metadata8
+        ));
+        assert_eq!(LocationAssetId::<Runtime>::get(&location8), Some(11));
+        assert_eq!(crate::AllowedDestParaIds::<Runtime>::get(2015), Some(2));
+    });
+}
+
+#[test]
+fn deregister_lp_asset_in_use_should_fail() {
+    new_test_ext().execute_with(|| {
+        let (metadata8, location8) = create_asset_and_location("Asset8");
+        assert_ok!(AssetManager::register_asset(
+            RuntimeOrigin::root(),
+            location8,
+            metadata8
+        ));
+        let (metadata9, location9) = create_asset_and_location("Asset9");
+        assert_ok!(AssetManager::register_asset(
+            RuntimeOrigin::root(),
+            location9,
+            metadata9
+        ));
+        let metadata10 = create_asset_metadata("LP10", "LP10", 12, 1u128, false, true);
+        assert_ok!(AssetManager::register_lp_asset(
+            RuntimeOrigin::root(),
+            NATIVE_ASSET_ID,
+            PAIRED_ASSET_ID,
+            metadata10
+        ));
+        let lp_asset_id = 10;
+        PairedAssetReserves::set(&(1_000, 1_000));
+        assert_ok!(
+            <MantaAssetConfig as AssetConfig<Runtime>>::FungibleLedger::deposit_minting(
+                lp_asset_id,
+                &ALICE,
+                1_000
+            )
+        );
+
+        // The LP asset stays while it is minted or its pair holds liquidity
+        assert_noop!(
+            AssetManager::deregister_asset(RuntimeOrigin::root(), lp_asset_id, 10),
+            Error::<Runtime>::AssetInUse
+        );
+        assert_ok!(
+            <MantaAssetConfig as AssetConfig<Runtime>>::FungibleLedger::withdraw_burning(
+                lp_asset_id,
+                &ALICE,
+                1_000,
+                ExistenceRequirement::AllowDeath
+            )
+        );
+        assert_noop!(
+            AssetManager::deregister_asset(RuntimeOrigin::root(), lp_asset_id, 10),
+            Error::<Runtime>::AssetInUse
+        );
+
+        PairedAssetReserves::set(&(0, 0));
+        assert_ok!(AssetManager::deregister_asset(
+            RuntimeOrigin::root(),
+            lp_asset_id,
+            10
+        ));
+        assert_eq!(LpToAssetIdPair::<Runtime>::get(lp_asset_id), None);
+    });
+}
+
+#[test]
+fn asset_manager_api_should_work() {
+    new_test_ext().execute_with(|| {
+        let (metadata8, location8) = create_asset_and_location("Asset8");
+        assert_ok!(AssetManager::register_asset(
+            RuntimeOrigin::root(),
+            location8.clone(),
+            metadata8.clone()
+        ));
+        let (metadata9, location9) = create_asset_and_location("Asset9");
+        assert_ok!(AssetManager::register_asset(
+            RuntimeOrigin::root(),
+            location9,
+            metadata9
+        ));
+        let metadata10 = create_asset_metadata("LP10", "LP10", 12, 1u128, false, false);
+        assert_ok!(AssetManager::register_lp_asset(
+            RuntimeOrigin::root(),
+            8,
+            9,
+            metadata10
+        ));
+        let native_asset_id = <MantaAssetConfig as AssetConfig<Runtime>>::NativeAssetId::get();
+        assert_ok!(
+            <MantaAssetConfig as AssetConfig<Runtime>>::FungibleLedger::deposit_minting(
+                native_asset_id,
+                &ALICE,
+                1_000_000,
+            )
+        );
+        assert_ok!(AssetManager::permissionless_register_asset(
+            RuntimeOrigin::signed(ALICE),
+            "dog token".as_bytes().to_vec().try_into().unwrap(),
+            "dog".as_bytes().to_vec().try_into().unwrap(),
+            12,
+            1_000_000_000_000_000,
+        ));
+
+        let asset8 = AssetManager::asset_info(8).unwrap();
+        assert_eq!(
+            asset8.location,
+            Into::<Option<MultiLocation>>::into(location8.clone())
+        );
+        assert_eq!(asset8.name, metadata8.metadata.name);
+        assert_eq!(asset8.min_balance, metadata8.min_balance);
+        assert!(!asset8.is_permissionless);
+        let dog = AssetManager::asset_info(1_000_000).unwrap();
+        assert_eq!(dog.location, None);
+        assert_eq!(dog.symbol, b"dog".to_vec());
+        assert!(dog.is_permissionless);
+        assert_eq!(AssetManager::asset_info(100), None);
+
+        // Paging through the assets visits each of them once
+        let mut asset_ids = vec![];
+        let mut start_after = None;
+        loop {
+            let page = AssetManager::list_assets(start_after, 2);
+            asset_ids.extend(page.iter().map(|asset| asset.asset_id));
+            if page.len() < 2 {
+                break;
+            }
+            start_after = page.last().map(|asset| asset.asset_id);
+        }
+        asset_ids.sort();
+        assert_eq!(asset_ids, vec![native_asset_id, 8, 9, 10, 1_000_000]);
+        assert!(AssetManager::list_assets(None, 0).is_empty());
+
+        assert_eq!(
+            AssetManager::asset_by_location(location8.clone().into()),
+            Some(asset8)
+        );
+        assert_eq!(
+            AssetManager::asset_by_location(MultiLocation::new(1, X1(Parachain(2084)))),
+            None
+        );
+        assert_eq!(AssetManager::lp_pair_of(10), Some((8, 9)));
+        assert_eq!(AssetManager::lp_pair_of(8), None);
+
+        assert_eq!(
+            AssetManager::xcm_fee_info(8),
+            Some(XcmFeeInfo {
+                units_per_second: None,
+                effective_units_per_second: None,
+                min_xcm_fee: None,
+                is_outgoing_filtered: false,
+            })
+        );
+        assert_ok!(AssetManager::set_units_per_second(
+            RuntimeOrigin::root(),
+            8,
+            100
+        ));
+        assert_ok!(AssetManager::set_min_xcm_fee(
+            RuntimeOrigin::root(),
+            AssetLocation(VersionedMultiLocation::V3(MultiLocation::new(
+                1,
+                X1(Parachain(2015))
+            ))),
+            10,
+        ));
+        assert_ok!(AssetManager::update_outgoing_filtered_assets(
+            RuntimeOrigin::root(),
+            location8,
+            true
+        ));
+        assert_eq!(
+            AssetManager::xcm_fee_info(8),
+            Some(XcmFeeInfo {
+                units_per_second: Some(100),
+                effective_units_per_second: Some(100),
+                min_xcm_fee: Some(10),
+                is_outgoing_filtered: true,
+            })
+        );
+        assert_eq!(AssetManager::xcm_fee_info(10), None);
+    });
+}
+
+#[test]
+fn units_per_second_oracle_should_work() {
+    new_test_ext().execute_with(|| {
+        let (metadata, location) = create_asset_and_location("Asset8");
+        assert_ok!(AssetManager::register_asset(
+            RuntimeOrigin::root(),
+            location,
+            metadata
+        ));
+        assert_ok!(AssetManager::set_units_per_second(
+            RuntimeOrigin::root(),
+            PAIRED_ASSET_ID,
+            500
+        ));
+        let mut oracle = UnitsPerSecondOracle {
+            native_units_per_second: 1_000,
+            min_units_per_second: 100,
+            max_units_per_second: 10_000,
+            smoothing: None,
+        };
+
+        assert_noop!(
+            AssetManager::set_units_per_second_oracle(
+                RuntimeOrigin::signed(ALICE),
+                PAIRED_ASSET_ID,
+                Some(oracle)
+            ),
+            BadOrigin
+        );
+        assert_noop!(
+            AssetManager::set_units_per_second_oracle(RuntimeOrigin::root(), 100, Some(oracle)),
+            Error::<Runtime>::UpdateNonExistentAsset
+        );
+        assert_noop!(
+            AssetManager::set_units_per_second_oracle(
+                RuntimeOrigin::root(),
+                PAIRED_ASSET_ID,
+                Some(UnitsPerSecondOracle {
+                    min_units_per_second: 10_001,
+                    ..oracle
+                })
+            ),
+            Error::<Runtime>::InvalidUnitsPerSecondBounds
+        );
+        assert_ok!(AssetManager::set_units_per_second_oracle(
+            RuntimeOrigin::root(),
+            PAIRED_ASSET_ID,
+            Some(oracle)
+        ));
+        assert_eq!(
+            AssetManager::units_per_second_oracle(PAIRED_ASSET_ID),
+            Some(oracle)
+        );
+
+        // The static units-per-second are charged while the pair has no price
+        assert_eq!(
+            <AssetManager as manta_primitives::assets::UnitsPerSecond>::units_per_second(
+                &PAIRED_ASSET_ID
+            ),
+            Some(500)
+        );
+        // One native unit is worth three units of the asset
+        PairedAssetReserves::set(&(1_000, 3_000));
+        assert_eq!(
+            <AssetManager as manta_primitives::assets::UnitsPerSecond>::units_per_second(
+                &PAIRED_ASSET_ID
+            ),
+            Some(3_000)
+        );
+        // The price is bounded by the oracle
+        PairedAssetReserves::set(&(1_000, 30_000));
+        assert_eq!(
+            AssetManager::effective_units_per_second(PAIRED_ASSET_ID),
+            Some(10_000)
+        );
+        PairedAssetReserves::set(&(1_000, 50));
+        assert_eq!(
+            AssetManager::effective_units_per_second(PAIRED_ASSET_ID),
+            Some(100)
+        );
+
+        // A smoothed oracle charges the spot price until it has an average
+        oracle.smoothing = Some(Perbill::from_percent(50));
+        assert_ok!(AssetManager::set_units_per_second_oracle(
+            RuntimeOrigin::root(),
+            PAIRED_ASSET_ID,
+            Some(oracle)
+        ));
+        PairedAssetReserves::set(&(1_000, 2_000));
+        assert_eq!(
+            AssetManager::effective_units_per_second(PAIRED_ASSET_ID),
+            Some(2_000)
+        );
+        AssetManager::on_initialize(1);
+        assert_eq!(
+            AssetManager::averaged_units_per_second(PAIRED_ASSET_ID),
+            Some(2_000)
+        );
+        PairedAssetReserves::set(&(1_000, 4_000));
+        assert_eq!(
+            AssetManager::effective_units_per_second(PAIRED_ASSET_ID),
+            Some(2_000)
+        );
+        AssetManager::on_initialize(2);
+        assert_eq!(
+            AssetManager::averaged_units_per_second(PAIRED_ASSET_ID),
+            Some(3_000)
+        );
+        assert_eq!(
+            AssetManager::xcm_fee_info(PAIRED_ASSET_ID)
+                .map(|info| (info.units_per_second, info.effective_units_per_second)),
+            Some((Some(500), Some(3_000)))
+        );
+
+        // Removing the oracle charges the static units-per-second again
+        assert_ok!(AssetManager::set_units_per_second_oracle(
+            RuntimeOrigin::root(),
+            PAIRED_ASSET_ID,
+            None
+        ));
+        assert_eq!(
+            AssetManager::averaged_units_per_second(PAIRED_ASSET_ID),
+            None
+        );
+        assert_eq!(
+            AssetManager::effective_units_per_second(PAIRED_ASSET_ID),
+            Some(500)
+        );
+    });
+}
+
+#[test]
+fn permissionless_asset_ownership_should_work() {
+    new_test_ext().execute_with(|| {
+        let bob: sp_runtime::AccountId32 = [1u8; 32].into();
+        let native_asset_id = <MantaAssetConfig as AssetConfig<Runtime>>::NativeAssetId::get();
+        assert_ok!(
+            <MantaAssetConfig as AssetConfig<Runtime>>::FungibleLedger::deposit_minting(
+                native_asset_id,
+                &ALICE,
+                1_000_000,
+            )
+        );
+        let asset_id = <Runtime as crate::pallet::Config>::PermissionlessStartId::get();
+        assert_ok!(AssetManager::permissionless_register_asset(
+            RuntimeOrigin::signed(ALICE),
+            "dog token".as_bytes().to_vec().try_into().unwrap(),
+            "dog".as_bytes().to_vec().try_into().unwrap(),
+            12,
+            1_000_000_000_000_000,
+        ));
+        assert_eq!(
+            AssetManager::permissionless_asset_owner(asset_id),
+            Some(ALICE)
+        );
+        assert_eq!(
+            AssetManager::asset_info(asset_id).and_then(|info| info.owner),
+            Some(ALICE)
+        );
+
+        // Only the owner manages the asset
+        assert_noop!(
+            AssetManager::update_permissionless_asset_metadata(
+                RuntimeOrigin::signed(bob.clone()),
+                asset_id,
+                "cat token".as_bytes().to_vec().try_into().unwrap(),
+                "cat".as_bytes().to_vec().try_into().unwrap(),
+                None,
+            ),
+            Error::<Runtime>::NotAssetOwner
+        );
+        assert_noop!(
+            AssetManager::burn_asset(RuntimeOrigin::signed(bob.clone()), asset_id, 1),
+            Error::<Runtime>::NotAssetOwner
+        );
+        assert_noop!(
+            AssetManager::transfer_asset_ownership(
+                RuntimeOrigin::signed(bob.clone()),
+                asset_id,
+                bob.clone()
+            ),
+            Error::<Runtime>::NotAssetOwner
+        );
+
+        assert_ok!(AssetManager::update_permissionless_asset_metadata(
+            RuntimeOrigin::signed(ALICE),
+            asset_id,
+            "cat token".as_bytes().to_vec().try_into().unwrap(),
+            "cat".as_bytes().to_vec().try_into().unwrap(),
+            Some("ipfs://cat".as_bytes().to_vec().try_into().unwrap()),
+        ));
+        assert_eq!(Assets::name(asset_id), b"cat token".to_vec());
+        assert_eq!(Assets::symbol(asset_id), b"cat".to_vec());
+        assert_eq!(Assets::decimals(asset_id), 12);
+        let asset = AssetManager::asset_info(asset_id).unwrap();
+        assert_eq!(asset.symbol, b"cat".to_vec());
+        assert_eq!(asset.logo, Some(b"ipfs://cat".to_vec()));
+
+        assert_ok!(AssetManager::burn_asset(
+            RuntimeOrigin::signed(ALICE),
+            asset_id,
+            1_000_000_000_000
+        ));
+        assert_eq!(Assets::balance(asset_id, &ALICE), 999_000_000_000_000);
+        assert_eq!(
+            Assets::maybe_total_supply(asset_id),
+            Some(999_000_000_000_000)
+        );
+
+        assert_ok!(AssetManager::transfer_asset_ownership(
+            RuntimeOrigin::signed(ALICE),
+            asset_id,
+            bob.clone()
+        ));
+        assert_eq!(
+            AssetManager::permissionless_asset_owner(asset_id),
+            Some(bob.clone())
+        );
+        // The new owner can't burn the balance of the previous one
+        assert_noop!(
+            AssetManager::burn_asset(RuntimeOrigin::signed(bob.clone()), asset_id, 1),
+            Error::<Runtime>::BurnError
+        );
+        assert_noop!(
+            AssetManager::renounce_asset_ownership(RuntimeOrigin::signed(ALICE), asset_id),
+            Error::<Runtime>::NotAssetOwner
+        );
+
+        // A renounced asset is immutable
+        assert_ok!(AssetManager::renounce_asset_ownership(
+            RuntimeOrigin::signed(bob.clone()),
+            asset_id
+        ));
+        assert_eq!(AssetManager::permissionless_asset_owner(asset_id), None);
+        assert_eq!(
+            AssetManager::asset_info(asset_id).and_then(|info| info.owner),
+            None
+        );
+        assert_noop!(
+            AssetManager::update_permissionless_asset_metadata(
+                RuntimeOrigin::signed(bob),
+                asset_id,
+                "dog token".as_bytes().to_vec().try_into().unwrap(),
+                "dog".as_bytes().to_vec().try_into().unwrap(),
+                None,
+            ),
+            Error::<Runtime>::NotAssetOwner
+        );
+    });
+}
+
+#[test]
+fn location_alias_should_work() {
+    new_test_ext().execute_with(|| {
+        let (metadata, location) = create_asset_and_location("Asset8");
+        assert_ok!(AssetManager::register_asset(
+            RuntimeOrigin::root(),
+            location.clone(),
+            metadata
+        ));
+        let alias_location =
+            MultiLocation::new(1, X3(Parachain(1000), PalletInstance(50), GeneralIndex(8)));
+        let alias = AssetLocation(VersionedMultiLocation::V3(alias_location));
+
+        assert_noop!(
+            AssetManager::register_location_alias(RuntimeOrigin::signed(ALICE), 8, alias.clone()),
+            BadOrigin
+        );
+        assert_noop!(
+            AssetManager::register_location_alias(RuntimeOrigin::root(), 100, alias.clone()),
+            Error::<Runtime>::UpdateNonExistentAsset
+        );
+        assert_noop!(
+            AssetManager::register_location_alias(RuntimeOrigin::root(), 8, location.clone()),
+            Error::<Runtime>::LocationAlreadyExists
+        );
+        assert_ok!(AssetManager::register_location_alias(
+            RuntimeOrigin::root(),
+            8,
+            alias.clone()
+        ));
+        assert_eq!(AssetManager::location_alias(8, &alias), Some(()));
+        assert_noop!(
+            AssetManager::register_location_alias(RuntimeOrigin::root(), 8, alias.clone()),
+            Error::<Runtime>::LocationAlreadyExists
+        );
+
+        // Both locations resolve to the asset, which is sent out from its canonical location
+        assert_eq!(
+            AssetIdLocationConvert::<AssetManager>::convert(&alias_location),
+            Some(8)
+        );
+        assert_eq!(
+            AssetIdLocationConvert::<AssetManager>::convert(
+                &Into::<Option<MultiLocation>>::into(location.clone()).unwrap()
+            ),
+            Some(8)
+        );
+        assert_eq!(
+            AssetIdLocationConvert::<AssetManager>::convert_back(&8),
+            Into::<Option<MultiLocation>>::into(location.clone())
+        );
+        assert_eq!(crate::AllowedDestParaIds::<Runtime>::get(2015), Some(1));
+        assert_eq!(crate::AllowedDestParaIds::<Runtime>::get(1000), None);
+
+        assert_noop!(
+            AssetManager::remove_location_alias(RuntimeOrigin::root(), 8, location),
+            Error::<Runtime>::LocationAliasNotFound
+        );
+        assert_ok!(AssetManager::remove_location_alias(
+            RuntimeOrigin::root(),
+            8,
+            alias.clone()
+        ));
+        assert_eq!(LocationAssetId::<Runtime>::get(&alias), None);
+        assert_eq!(AssetManager::location_alias(8, &alias), None);
+
+        // Deregistering the asset removes its aliases
+        assert_ok!(AssetManager::register_location_alias(
+            RuntimeOrigin::root(),
+            8,
+            alias.clone()
+        ));
+        assert_ok!(AssetManager::deregister_asset(RuntimeOrigin::root(), 8, 10));
+        assert_eq!(LocationAssetId::<Runtime>::get(&alias), None);
+        assert_eq!(AssetManager::location_alias(8, &alias), None);
+    });
+}
+
+#[test]
+fn outflow_cap_should_work() {
+    new_test_ext().execute_with(|| {
+        let (metadata, location) = create_asset_and_location("Asset8");
+        assert_ok!(AssetManager::register_asset(
+            RuntimeOrigin::root(),
+            location,
+            metadata
+        ));
+        let cap = OutflowCap {
+            amount: 100,
+            period: 10,
+        };
+
+        assert_noop!(
+            AssetManager::set_outflow_cap(RuntimeOrigin::signed(ALICE), 8, None, Some(cap)),
+            BadOrigin
+        );
+        assert_noop!(
+            AssetManager::set_outflow_cap(RuntimeOrigin::root(), 100, None, Some(cap)),
+            Error::<Runtime>::UpdateNonExistentAsset
+        );
+        assert_noop!(
+            AssetManager::set_outflow_cap(
+                RuntimeOrigin::root(),
+                8,
+                None,
+                Some(OutflowCap {
+                    amount: 100,
+                    period: 0
+                })
+            ),
+            Error::<Runtime>::InvalidOutflowCap
+        );
+        assert_eq!(AssetManager::remaining_outflow(8, None), None);
+        assert_ok!(AssetManager::try_record_outflow(8, None, 1_000));
+
+        assert_ok!(AssetManager::set_outflow_cap(
+            RuntimeOrigin::root(),
+            8,
+            None,
+            Some(cap)
+        ));
+        assert_ok!(AssetManager::set_outflow_cap(
+            RuntimeOrigin::root(),
+            8,
+            Some(2000),
+            Some(OutflowCap {
+                amount: 30,
+                period: 10
+            })
+        ));
+        assert_eq!(AssetManager::outflow_cap(8, None), Some(cap));
+        assert_eq!(AssetManager::remaining_outflow(8, None), Some(100));
+        assert_eq!(AssetManager::remaining_outflow(8, Some(2000)), Some(30));
+        // Destinations without a cap of their own are only bound by the global cap
+        assert_eq!(AssetManager::remaining_outflow(8, Some(2001)), None);
+
+        System::set_block_number(1);
+        assert_ok!(AssetManager::try_record_outflow(8, None, 60));
+        assert_ok!(AssetManager::try_record_outflow(8, Some(2000), 30));
+        assert_eq!(AssetManager::remaining_outflow(8, None), Some(40));
+        assert_eq!(AssetManager::remaining_outflow(8, Some(2000)), Some(0));
+        System::assert_last_event(
+            crate::Event::<Runtime>::OutflowCapReached {
+                asset_id: 8,
+                destination: Some(2000),
+                cap: OutflowCap {
+                    amount: 30,
+                    period: 10,
+                },
+            }
+            .into(),
+        );
+        assert_err!(
+            AssetManager::try_record_outflow(8, None, 41),
+            Error::<Runtime>::OutflowCapExceeded
+        );
+        System::assert_last_event(
+            crate::Event::<Runtime>::OutflowRejected {
+                asset_id: 8,
+                destination: None,
+                amount: 41,
+            }
+            .into(),
+        );
+        assert_err!(
+            AssetManager::try_record_outflow(8, Some(2000), 1),
+            Error::<Runtime>::OutflowCapExceeded
+        );
+        System::assert_last_event(
+            crate::Event::<Runtime>::OutflowRejected {
+                asset_id: 8,
+                destination: Some(2000),
+                amount: 1,
+            }
+            .into(),
+        );
+
+        // Rejected outflows are not recorded
+        assert_eq!(AssetManager::remaining_outflow(8, None), Some(40));
+
+        // The cap applies again from the start of the next window
+        System::set_block_number(11);
+        assert_eq!(AssetManager::remaining_outflow(8, None), Some(100));
+        assert_ok!(AssetManager::try_record_outflow(8, None, 100));
+        assert_eq!(AssetManager::remaining_outflow(8, None), Some(0));
+        System::assert_last_event(
+            crate::Event::<Runtime>::OutflowCapReached {
+                asset_id: 8,
+                destination: None,
+                cap,
+            }
+            .into(),
+        );
+        // Empty transfers at the cap do not announce it again
+        let events = System::events().len();
+        assert_ok!(AssetManager::try_record_outflow(8, None, 0));
+        assert_eq!(System::events().len(), events);
+        System::set_block_number(20);
+        assert_err!(
+            AssetManager::try_record_outflow(8, None, 1),
+            Error::<Runtime>::OutflowCapExceeded
+        );
+        System::assert_last_event(
+            crate::Event::<Runtime>::OutflowRejected {
+                asset_id: 8,
+                destination: None,
+                amount: 1,
+            }
+            .into(),
+        );
+
+        // Removing the cap lifts it, deregistering the asset removes all its caps
+        assert_ok!(AssetManager::set_outflow_cap(
+            RuntimeOrigin::root(),
+            8,
+            None,
+            None
+        ));
+        assert_eq!(AssetManager::remaining_outflow(8, None), None);
+        assert_ok!(AssetManager::try_record_outflow(8, None, 1_000));
+        assert_ok!(AssetManager::deregister_asset(RuntimeOrigin::root(), 8, 10));
+        assert_eq!(AssetManager::outflow_cap(8, Some(2000)), None);
+        assert_eq!(AssetManager::outflow_usage(8, Some(2000)), None);
+    });
+}
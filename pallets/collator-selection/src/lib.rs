@@ -192,7 +192,7 @@ pub mod pallet {
     pub type Candidates<T: Config> =
         StorageValue<_, Vec<CandidateInfo<T::AccountId, BalanceOf<T>>>, ValueQuery>;
 
-    pub(super) type BlockCount = u32;
+    pub type BlockCount = u32;
     #[pallet::type_value]
     pub(super) fn StartingBlockCount() -> BlockCount {
         Zero::zero()
@@ -575,39 +575,83 @@ pub mod pallet {
         pub fn evict_bad_collators(
             candidates: Vec<CandidateInfo<T::AccountId, BalanceOf<T>>>,
         ) -> Vec<T::AccountId> {
-            use sp_runtime::PerThing;
-
             // 0. Storage reads and precondition checks
             if candidates.is_empty() {
                 return Vec::new(); // No candidates means we're running invulnerables only
             }
+            let mut collator_perf_this_session =
+                <BlocksPerCollatorThisSession<T>>::iter().collect::<Vec<_>>();
+            let evict_below_blocks = match Self::eviction_threshold(
+                collator_perf_this_session
+                    .iter()
+                    .map(|(_, blocks)| *blocks)
+                    .collect(),
+            ) {
+                Some(threshold) => threshold,
+                None => return Vec::new(), // kicking disabled or no validator performance recorded ( should not happen )
+            };
+
+            // 1. Ascending sort of collator performance list by number of produced blocks
+            collator_perf_this_session.sort_unstable_by_key(|k| k.1);
+
+            // 2. Walk the slice of collators under threshold, call try_remove_candidate on each
+            // NOTE: the collator at the baseline percentile is safe as the threshold never exceeds its block count
+            let kick_candidates =
+                collator_perf_this_session
+                    .iter()
+                    .take_while(|(_, my_blocks_this_session)| {
+                        *my_blocks_this_session < evict_below_blocks
+                    });
+            let mut removed_account_ids: Vec<T::AccountId> = Vec::new();
+            kick_candidates.for_each(|(acc_id, my_blocks_this_session)| {
+                // If our validator is not also a candidate we're invulnerable or already kicked
+                if candidates.iter().any(|x| x.who == *acc_id) {
+                    #[allow(clippy::bind_instead_of_map)] Self::try_remove_candidate(acc_id)
+                        .and_then(|_| {
+                            removed_account_ids.push(acc_id.clone());
+                            log::info!("Removed collator of account {:?} as it only produced {} blocks this session which is below acceptable threshold of {}", &acc_id, my_blocks_this_session,evict_below_blocks);
+                            Ok(())
+                        })
+                        .unwrap_or_else(|why| {
+                            log::warn!("Failed to remove candidate due to underperformance {:?}", why);
+                            debug_assert!(false, "failed to remove candidate {why:?}");
+                        });
+                }
+            });
+            removed_account_ids.shrink_to_fit();
+            removed_account_ids
+        }
+
+        /// Number of blocks a collator must produce to not be evicted, given the blocks produced by each collator
+        /// Returns `None` if eviction is disabled or no performance is given
+        ///
+        /// Collators are evicted if they produced fewer than (EvictionTolerance * EvictionBaseline rounded up) blocks than the percentile
+        pub fn eviction_threshold(mut blocks_per_collator: Vec<BlockCount>) -> Option<BlockCount> {
+            use sp_runtime::PerThing;
+
             let percentile_for_kick = Self::eviction_baseline();
             if percentile_for_kick == Percent::zero() {
-                return Vec::new(); // Selecting 0-th percentile disables kicking. Upper bound check in fn build()
+                return None; // Selecting 0-th percentile disables kicking. Upper bound check in fn build()
             }
             let underperformance_tolerated = Self::eviction_tolerance();
             if underperformance_tolerated == Percent::one() {
-                return Vec::new(); // tolerating 100% underperformance disables kicking
+                return None; // tolerating 100% underperformance disables kicking
             }
-            let mut collator_perf_this_session =
-                <BlocksPerCollatorThisSession<T>>::iter().collect::<Vec<_>>();
-            if collator_perf_this_session.is_empty() {
-                return Vec::new(); // no validator performance recorded ( should not happen )
+            if blocks_per_collator.is_empty() {
+                return None;
             }
 
-            // 1. Ascending sort of collator performance list by number of produced blocks
-            collator_perf_this_session.sort_unstable_by_key(|k| k.1);
-            let collator_count = collator_perf_this_session.len();
+            // 1. Ascending sort of collator performance
+            blocks_per_collator.sort_unstable();
 
             // 2. get percentile by _exclusive_ nearest rank method https://en.wikipedia.org/wiki/Percentile#The_nearest-rank_method (rust percentile API is feature gated and unstable)
-            let ordinal_rank = percentile_for_kick.mul_ceil(collator_count);
+            let ordinal_rank = percentile_for_kick.mul_ceil(blocks_per_collator.len());
             let index_at_ordinal_rank = ordinal_rank.saturating_sub(One::one()); // -1 to accommodate 0-index counting, should not saturate due to precondition check and round up multiplication
 
             // 3. Block number at rank is the percentile and our kick performance benchmark
-            let blocks_created_at_baseline: BlockCount =
-                collator_perf_this_session[index_at_ordinal_rank].1;
+            let blocks_created_at_baseline: BlockCount = blocks_per_collator[index_at_ordinal_rank];
 
-            // 4. We kick if a collator produced fewer than (EvictionTolerance * EvictionBaseline rounded up) blocks than the percentile
+            // 4. Threshold is (EvictionTolerance * EvictionBaseline rounded up) blocks less than the percentile
             let evict_below_blocks = (underperformance_tolerated
                 .left_from_one()
                 .mul_ceil(blocks_created_at_baseline))
@@ -618,30 +662,18 @@ pub mod pallet {
                 blocks_created_at_baseline,
                 evict_below_blocks
             );
+            Some(evict_below_blocks)
+        }
 
-            // 5. Walk the percentile slice, call try_remove_candidate if a collator is under threshold
-            let kick_candidates = &collator_perf_this_session[..index_at_ordinal_rank]; // ordinal-rank exclusive, the collator at percentile is safe
-            let mut removed_account_ids: Vec<T::AccountId> =
-                Vec::with_capacity(kick_candidates.len());
-            kick_candidates.iter().for_each(|(acc_id, my_blocks_this_session)| {
-                if *my_blocks_this_session < evict_below_blocks {
-                    // If our validator is not also a candidate we're invulnerable or already kicked
-                    if candidates.iter().any(|x| x.who == *acc_id) {
-                        #[allow(clippy::bind_instead_of_map)] Self::try_remove_candidate(acc_id)
-                            .and_then(|_| {
-                                removed_account_ids.push(acc_id.clone());
-                                log::info!("Removed collator of account {:?} as it only produced {} blocks this session which is below acceptable threshold of {}", &acc_id, my_blocks_this_session,evict_below_blocks);
-                                Ok(())
-                            })
-                            .unwrap_or_else(|why| {
-                                log::warn!("Failed to remove candidate due to underperformance {:?}", why);
-                                debug_assert!(false, "failed to remove candidate {why:?}");
-                            });
-                    }
-                }
-            });
-            removed_account_ids.shrink_to_fit();
-            removed_account_ids
+        /// Number of blocks a collator must produce in the current session to not be evicted at the end of it,
+        /// based on the blocks produced by all collators so far
+        pub fn session_eviction_threshold() -> Option<BlockCount> {
+            Self::eviction_threshold(<BlocksPerCollatorThisSession<T>>::iter_values().collect())
+        }
+
+        /// Number of blocks `collator` produced in the current session
+        pub fn blocks_this_session(collator: &T::AccountId) -> BlockCount {
+            <BlocksPerCollatorThisSession<T>>::get(collator)
         }
 
         /// Reset the performance map to the currently active validators at 0 blocks
@@ -17,11 +17,18 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use manta_primitives::types::Balance;
+use manta_primitives::types::{Balance, BlockNumber};
+pub use pallet_farming::{
+    PenaltyDestination, PoolOverview, PoolState, RewardVesting, UserPosition, VestingPosition,
+    EMISSION_PER_SHARE_UNIT,
+};
 use sp_api::decl_runtime_apis;
 use sp_std::vec::Vec;
 
 decl_runtime_apis! {
+    /// Version 2 adds the gauge weights, version 3 the user positions and pool overviews,
+    /// version 4 the reward vestings
+    #[api_version(4)]
     pub trait FarmingRuntimeApi<AccountId, CurrencyId, PoolId> where
         AccountId: Codec,
         PoolId: Codec,
@@ -36,5 +43,21 @@ decl_runtime_apis! {
             who: AccountId,
             pid: PoolId,
         ) -> Vec<(CurrencyId, Balance)>;
+
+        fn get_gauge_weights() -> Vec<(PoolId, u128)>;
+
+        fn user_positions(
+            who: AccountId,
+        ) -> Vec<UserPosition<CurrencyId, BlockNumber, Balance>>;
+
+        fn pool_overview(
+            pid: PoolId,
+        ) -> Option<PoolOverview<CurrencyId, BlockNumber, Balance>>;
+
+        fn pool_overviews() -> Vec<PoolOverview<CurrencyId, BlockNumber, Balance>>;
+
+        fn user_vestings(
+            who: AccountId,
+        ) -> Vec<VestingPosition<CurrencyId, BlockNumber, Balance>>;
     }
 }
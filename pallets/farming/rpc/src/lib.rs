@@ -22,8 +22,10 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::error::{CallError, ErrorCode, ErrorObject},
 };
-use manta_primitives::types::Balance;
-pub use pallet_farming_rpc_runtime_api::{self as runtime_api, FarmingRuntimeApi};
+use manta_primitives::types::{Balance, BlockNumber};
+pub use pallet_farming_rpc_runtime_api::{
+    self as runtime_api, FarmingRuntimeApi, PoolOverview, UserPosition, VestingPosition,
+};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_rpc::number::NumberOrHex;
@@ -52,6 +54,44 @@ where
         pid: PoolId,
         at: Option<BlockHash>,
     ) -> RpcResult<Vec<(CurrencyId, NumberOrHex)>>;
+
+    /// rpc method for getting the gauge weights of the current voting epoch
+    #[method(name = "farming_getGaugeWeights")]
+    async fn get_gauge_weights(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(PoolId, NumberOrHex)>>;
+
+    /// rpc method for getting the positions of an account in every farming pool
+    #[method(name = "farming_userPositions")]
+    async fn user_positions(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<UserPosition<CurrencyId, BlockNumber, Balance>>>;
+
+    /// rpc method for getting the emissions and limits of a farming pool
+    #[method(name = "farming_poolOverview")]
+    async fn pool_overview(
+        &self,
+        pid: PoolId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<PoolOverview<CurrencyId, BlockNumber, Balance>>>;
+
+    /// rpc method for getting the emissions and limits of every farming pool
+    #[method(name = "farming_poolOverviews")]
+    async fn pool_overviews(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<PoolOverview<CurrencyId, BlockNumber, Balance>>>;
+
+    /// rpc method for getting the vesting rewards of an account from every farming pool
+    #[method(name = "farming_userVestings")]
+    async fn user_vestings(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<VestingPosition<CurrencyId, BlockNumber, Balance>>>;
 }
 
 #[derive(Clone, Debug)]
@@ -130,4 +170,94 @@ where
         }
         .map_err(jsonrpsee::core::Error::Call)
     }
+
+    async fn get_gauge_weights(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(PoolId, NumberOrHex)>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let rs: Result<Vec<(PoolId, u128)>, _> = api.get_gauge_weights(at);
+
+        match rs {
+            Ok(weights) => Ok(weights
+                .into_iter()
+                .map(|(pid, weight)| (pid, NumberOrHex::Hex(weight.into())))
+                .collect()),
+            Err(e) => Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Failed to get gauge weights.",
+                Some(format!("{:?}", e)),
+            ))),
+        }
+        .map_err(jsonrpsee::core::Error::Call)
+    }
+
+    async fn user_positions(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<UserPosition<CurrencyId, BlockNumber, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.user_positions(at, who).map_err(|e| {
+            jsonrpsee::core::Error::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Failed to get user positions.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    async fn pool_overview(
+        &self,
+        pid: PoolId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<PoolOverview<CurrencyId, BlockNumber, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.pool_overview(at, pid).map_err(|e| {
+            jsonrpsee::core::Error::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Failed to get pool overview.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    async fn pool_overviews(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<PoolOverview<CurrencyId, BlockNumber, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.pool_overviews(at).map_err(|e| {
+            jsonrpsee::core::Error::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Failed to get pool overviews.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    async fn user_vestings(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<VestingPosition<CurrencyId, BlockNumber, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.user_vestings(at, who).map_err(|e| {
+            jsonrpsee::core::Error::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Failed to get user vestings.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
 }
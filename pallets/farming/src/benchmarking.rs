@@ -18,8 +18,11 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use crate::{Pallet as Farming, *};
-use frame_benchmarking::{benchmarks, whitelisted_caller};
-use frame_support::{assert_ok, sp_runtime::traits::UniqueSaturatedFrom};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+    assert_ok,
+    sp_runtime::traits::{UniqueSaturatedFrom, UniqueSaturatedInto},
+};
 use frame_system::{Pallet as System, RawOrigin};
 use manta_primitives::{
     assets::{AssetConfig, AssetRegistryMetadata, FungibleLedger, TestingDefault},
@@ -32,8 +35,6 @@ pub const INITIAL_VALUE: u128 = 1_000_000_000_000_000_000_000u128;
 
 benchmarks! {
     where_clause { where T: pallet_assets::Config + pallet_asset_manager::Config, <T as pallet_asset_manager::Config>::AssetId: From<u32> }
-    on_initialize {}:{Farming::<T>::on_initialize(BlockNumberFor::<T>::from(10u32));}
-
     create_farming_pool {
         let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
         let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
@@ -43,6 +44,7 @@ benchmarks! {
     }: _(RawOrigin::Root,
         tokens_proportion,
         basic_rewards,
+        EmissionCurve::Constant,
         Some((ksm_asset_id, BlockNumberFor::<T>::from(1000u32), gauge_basic_rewards)),
         BalanceOf::<T>::unique_saturated_from(0u128),
         BlockNumberFor::<T>::from(0u32),
@@ -51,6 +53,36 @@ benchmarks! {
         5
     )
 
+    create_permissionless_pool {
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        let asset_0 = AssetManager::<T>::do_register_asset(None, &metadata)?;
+        let asset_1 = AssetManager::<T>::do_register_asset(None, &metadata)?;
+        let lp_asset_id: u128 = AssetManager::<T>::next_asset_id().unique_saturated_into();
+        AssetManager::<T>::register_lp_asset(RawOrigin::Root.into(), asset_0, asset_1, metadata)?;
+
+        let lp_asset_id = CurrencyIdOf::<T>::unique_saturated_from(lp_asset_id);
+        let caller: T::AccountId = whitelisted_caller();
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        let tokens_proportion = vec![(lp_asset_id, Perbill::from_percent(100))];
+        let basic_rewards = vec![(lp_asset_id, token_amount)];
+        T::Currency::make_free_balance_be(
+            &caller,
+            T::PoolCreationDeposit::get().saturating_mul(2u32.into()),
+        );
+    }: _(RawOrigin::Signed(caller.clone()),
+        tokens_proportion,
+        basic_rewards,
+        EmissionCurve::Constant,
+        BalanceOf::<T>::unique_saturated_from(0u128),
+        BlockNumberFor::<T>::from(0u32),
+        BlockNumberFor::<T>::from(7u32),
+        BlockNumberFor::<T>::from(6u32),
+        5
+    )
+    verify {
+        assert_eq!(Farming::<T>::pool_owners(0).map(|(creator, _)| creator), Some(caller));
+    }
+
     charge {
         let location = T::Location::default();
         let metadata = AssetRegistryMetadata::<Balance>::testing_default();
@@ -66,6 +98,7 @@ benchmarks! {
             RawOrigin::Root.into(),
             tokens_proportion,
             basic_rewards,
+            EmissionCurve::Constant,
             Some((ksm_asset_id, BlockNumberFor::<T>::from(1000u32), gauge_basic_rewards)),
             BalanceOf::<T>::unique_saturated_from(0u128),
             BlockNumberFor::<T>::from(0u32),
@@ -80,7 +113,10 @@ benchmarks! {
             &caller,
             INITIAL_VALUE,
         );
-        // assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
+        // rewards accrue on the charge of an ongoing pool
+        assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards.clone()));
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
+        System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
     }: _(RawOrigin::Signed(caller.clone()), 0, charge_rewards)
 
     deposit {
@@ -98,6 +134,7 @@ benchmarks! {
             RawOrigin::Root.into(),
             tokens_proportion,
             basic_rewards,
+            EmissionCurve::Constant,
             Some((ksm_asset_id, BlockNumberFor::<T>::from(1000u32), gauge_basic_rewards)),
             BalanceOf::<T>::unique_saturated_from(0u128),
             BlockNumberFor::<T>::from(0u32),
@@ -114,6 +151,9 @@ benchmarks! {
             INITIAL_VALUE,
         );
         assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
+        // rewards accrue on the deposit into an ongoing pool
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
+        System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
     }: _(RawOrigin::Signed(caller.clone()), 0, token_amount, None)
 
     withdraw {
@@ -131,6 +171,7 @@ benchmarks! {
             RawOrigin::Root.into(),
             tokens_proportion,
             basic_rewards,
+            EmissionCurve::Constant,
             Some((ksm_asset_id, BlockNumberFor::<T>::from(1000u32), gauge_basic_rewards)),
             BalanceOf::<T>::unique_saturated_from(0u128),
             BlockNumberFor::<T>::from(0u32),
@@ -147,6 +188,7 @@ benchmarks! {
         let charge_rewards = vec![(ksm_asset_id,BalanceOf::<T>::unique_saturated_from(300000u128))];
         assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
         assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
+        System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
     }: _(RawOrigin::Signed(caller.clone()), 0, None)
 
     claim {
@@ -164,6 +206,7 @@ benchmarks! {
             RawOrigin::Root.into(),
             tokens_proportion,
             basic_rewards,
+            EmissionCurve::Constant,
             Some((ksm_asset_id, BlockNumberFor::<T>::from(1000u32), gauge_basic_rewards)),
             BalanceOf::<T>::unique_saturated_from(0u128),
             BlockNumberFor::<T>::from(0u32),
@@ -181,7 +224,6 @@ benchmarks! {
         assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
         assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, None));
         System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
-        Farming::<T>::on_initialize(BlockNumberFor::<T>::from(0u32));
     }: _(RawOrigin::Signed(caller.clone()), 0)
 
     gauge_withdraw {
@@ -199,6 +241,7 @@ benchmarks! {
             RawOrigin::Root.into(),
             tokens_proportion,
             basic_rewards,
+            EmissionCurve::Constant,
             Some((ksm_asset_id, BlockNumberFor::<T>::from(1000u32), gauge_basic_rewards)),
             BalanceOf::<T>::unique_saturated_from(0u128),
             BlockNumberFor::<T>::from(0u32),
@@ -216,4 +259,255 @@ benchmarks! {
         assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
         assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, Some((BalanceOf::<T>::unique_saturated_from(100u128), BlockNumberFor::<T>::from(100u32)))));
     }: _(RawOrigin::Signed(caller.clone()), 0)
+
+    vote_gauge {
+        let v in 1 .. T::MaxVotablePools::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let votes = init_gauge_votes::<T>(&caller, v)?;
+    }: _(RawOrigin::Signed(caller.clone()), votes)
+    verify {
+        assert!(Farming::<T>::gauge_votes(&caller).is_some());
+    }
+
+    settle_gauge_votes {
+        let p in 1 .. T::MaxVotablePools::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let votes = init_gauge_votes::<T>(&caller, p)?;
+        assert_ok!(Farming::<T>::vote_gauge(RawOrigin::Signed(caller.clone()).into(), votes));
+
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+            8.into(),
+            &T::TreasuryAccount::get(),
+            INITIAL_VALUE,
+        );
+        assert_ok!(Farming::<T>::set_gauge_vote_emission(
+            RawOrigin::Root.into(),
+            vec![(ksm_asset_id, BalanceOf::<T>::unique_saturated_from(1_000_000u128))],
+        ));
+        System::<T>::set_block_number(System::<T>::block_number() + T::VoteEpochLength::get());
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert!(GaugeWeights::<T>::iter_prefix(0u32).next().is_none());
+    }
+
+    set_reward_vesting {
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            vec![(ksm_asset_id, Perbill::from_percent(100))],
+            vec![(ksm_asset_id, token_amount)],
+            EmissionCurve::Constant,
+            None,
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+        ));
+        let reward_vesting = RewardVesting {
+            duration: BlockNumberFor::<T>::from(100u32),
+            penalty: Perbill::from_percent(50),
+            penalty_destination: PenaltyDestination::Pool,
+        };
+    }: _(RawOrigin::Root, 0, Some(reward_vesting))
+    verify {
+        assert!(Farming::<T>::reward_vestings(0).is_some());
+    }
+
+    claim_vested {
+        let caller: T::AccountId = whitelisted_caller();
+        init_vesting_rewards::<T>(&caller)?;
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+
+    exit_vesting {
+        let caller: T::AccountId = whitelisted_caller();
+        init_vesting_rewards::<T>(&caller)?;
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert!(Farming::<T>::vesting_ledgers(&caller, 0).is_none());
+    }
+
+    transfer_shares {
+        let location = T::Location::default();
+        let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+        AssetManager::<T>::do_register_asset(Some(&location), &metadata)?;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let to: T::AccountId = account("to", 0, 0);
+        let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+        let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+        let tokens_proportion = vec![(ksm_asset_id, Perbill::from_percent(100))];
+        let basic_rewards = vec![(ksm_asset_id, token_amount)];
+        let gauge_basic_rewards = vec![(ksm_asset_id, token_amount)];
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            tokens_proportion,
+            basic_rewards,
+            EmissionCurve::Constant,
+            Some((ksm_asset_id, BlockNumberFor::<T>::from(1000u32), gauge_basic_rewards)),
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+        ));
+
+        for who in [&caller, &to] {
+            let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+                8.into(),
+                who,
+                INITIAL_VALUE,
+            );
+        }
+        let charge_rewards = vec![(ksm_asset_id,BalanceOf::<T>::unique_saturated_from(300000u128))];
+        assert_ok!(Farming::<T>::charge(RawOrigin::Signed(caller.clone()).into(), 0, charge_rewards));
+        let gauge_info = Some((BalanceOf::<T>::unique_saturated_from(100u128), BlockNumberFor::<T>::from(100u32)));
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), 0, token_amount, gauge_info));
+        assert_ok!(Farming::<T>::deposit(RawOrigin::Signed(to.clone()).into(), 0, token_amount, gauge_info));
+        System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
+    }: _(RawOrigin::Signed(caller.clone()), 0, to.clone(), token_amount)
+    verify {
+        assert!(Farming::<T>::shares_and_withdrawn_rewards(0, &caller).is_none());
+    }
+}
+
+/// Locks a gauge of the vote token for `caller` in each of `v` votable pools and returns votes
+/// split across them.
+fn init_gauge_votes<T>(
+    caller: &T::AccountId,
+    v: u32,
+) -> Result<Vec<(PoolId, Perbill)>, frame_benchmarking::BenchmarkError>
+where
+    T: Config + pallet_assets::Config + pallet_asset_manager::Config,
+    <T as pallet_asset_manager::Config>::AssetId: From<u32>,
+{
+    let location = T::Location::default();
+    let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+    AssetManager::<T>::do_register_asset(Some(&location), &metadata)?;
+
+    let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+    let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+    let tokens_proportion = vec![(ksm_asset_id, Perbill::from_percent(100))];
+    let basic_rewards = vec![(ksm_asset_id, token_amount)];
+    let gauge_basic_rewards = vec![(ksm_asset_id, token_amount)];
+    let vote_token = T::VoteToken::get();
+    let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+        8.into(),
+        caller,
+        INITIAL_VALUE,
+    );
+    let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+        UniqueSaturatedInto::<u32>::unique_saturated_into(vote_token).into(),
+        caller,
+        INITIAL_VALUE,
+    );
+    for pool_id in 0..v {
+        assert_ok!(Farming::<T>::create_farming_pool(
+            RawOrigin::Root.into(),
+            tokens_proportion.clone(),
+            basic_rewards.clone(),
+            EmissionCurve::Constant,
+            Some((
+                vote_token,
+                BlockNumberFor::<T>::from(1000u32),
+                gauge_basic_rewards.clone(),
+            )),
+            BalanceOf::<T>::unique_saturated_from(0u128),
+            BlockNumberFor::<T>::from(0u32),
+            BlockNumberFor::<T>::from(7u32),
+            BlockNumberFor::<T>::from(6u32),
+            5,
+        ));
+        assert_ok!(Farming::<T>::set_votable_pool(
+            RawOrigin::Root.into(),
+            pool_id.into(),
+            true
+        ));
+        let charge_rewards = vec![(
+            ksm_asset_id,
+            BalanceOf::<T>::unique_saturated_from(300000u128),
+        )];
+        assert_ok!(Farming::<T>::charge(
+            RawOrigin::Signed(caller.clone()).into(),
+            pool_id.into(),
+            charge_rewards
+        ));
+        assert_ok!(Farming::<T>::deposit(
+            RawOrigin::Signed(caller.clone()).into(),
+            pool_id.into(),
+            token_amount,
+            Some((
+                BalanceOf::<T>::unique_saturated_from(100u128),
+                BlockNumberFor::<T>::from(100u32)
+            ))
+        ));
+    }
+
+    Ok((0..v)
+        .map(|pool_id| (pool_id.into(), Perbill::from_rational(1, v)))
+        .collect())
+}
+
+/// Locks the rewards `caller` claims from a vesting pool and lets half of them release.
+fn init_vesting_rewards<T>(caller: &T::AccountId) -> Result<(), frame_benchmarking::BenchmarkError>
+where
+    T: Config + pallet_assets::Config + pallet_asset_manager::Config,
+    <T as pallet_asset_manager::Config>::AssetId: From<u32>,
+{
+    let location = T::Location::default();
+    let metadata = AssetRegistryMetadata::<Balance>::testing_default();
+    AssetManager::<T>::do_register_asset(Some(&location), &metadata)?;
+
+    let ksm_asset_id = CurrencyIdOf::<T>::unique_saturated_from(8u128);
+    let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+    let _ = <T::AssetConfig as AssetConfig<T>>::FungibleLedger::deposit_minting(
+        8.into(),
+        caller,
+        INITIAL_VALUE,
+    );
+    assert_ok!(Farming::<T>::create_farming_pool(
+        RawOrigin::Root.into(),
+        vec![(ksm_asset_id, Perbill::from_percent(100))],
+        vec![(ksm_asset_id, token_amount)],
+        EmissionCurve::Constant,
+        None,
+        BalanceOf::<T>::unique_saturated_from(0u128),
+        BlockNumberFor::<T>::from(0u32),
+        BlockNumberFor::<T>::from(7u32),
+        BlockNumberFor::<T>::from(6u32),
+        5,
+    ));
+    assert_ok!(Farming::<T>::set_reward_vesting(
+        RawOrigin::Root.into(),
+        0,
+        Some(RewardVesting {
+            duration: BlockNumberFor::<T>::from(100u32),
+            penalty: Perbill::from_percent(50),
+            penalty_destination: PenaltyDestination::Pool,
+        })
+    ));
+    let charge_rewards = vec![(
+        ksm_asset_id,
+        BalanceOf::<T>::unique_saturated_from(300000u128),
+    )];
+    assert_ok!(Farming::<T>::charge(
+        RawOrigin::Signed(caller.clone()).into(),
+        0,
+        charge_rewards
+    ));
+    assert_ok!(Farming::<T>::deposit(
+        RawOrigin::Signed(caller.clone()).into(),
+        0,
+        token_amount,
+        None
+    ));
+    System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(10u32));
+    assert_ok!(Farming::<T>::claim(
+        RawOrigin::Signed(caller.clone()).into(),
+        0
+    ));
+    System::<T>::set_block_number(System::<T>::block_number() + BlockNumberFor::<T>::from(50u32));
+    Ok(())
 }
@@ -18,7 +18,7 @@ use codec::HasCompact;
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
 use sp_runtime::{
-    traits::{AtLeast32BitUnsigned, CheckedSub, One, Zero},
+    traits::{AtLeast32BitUnsigned, CheckedDiv, CheckedSub, One, Saturating, Zero},
     ArithmeticError, Perbill, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::prelude::*;
@@ -72,6 +72,8 @@ pub struct GaugePoolInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf,
     pub total_time_factor: u128,
     pub gauge_state: GaugeState,
     pub gauge_last_block: BlockNumberFor,
+    /// The block up to which `gauge_basic_rewards` are added to `rewards`
+    pub last_accrual_block: BlockNumberFor,
 }
 
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
@@ -80,6 +82,14 @@ pub enum GaugeState {
     Bonded,
 }
 
+/// The gauge vote of an account in a voting epoch.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct GaugeVote {
+    pub epoch: u32,
+    pub power: u128,
+    pub votes: Vec<(PoolId, Perbill)>,
+}
+
 impl<BalanceOf, CurrencyIdOf, AccountIdOf, BlockNumberFor>
     GaugePoolInfo<BalanceOf, CurrencyIdOf, AccountIdOf, BlockNumberFor>
 where
@@ -106,8 +116,9 @@ where
             max_block,
             gauge_amount: Default::default(),
             total_time_factor: Default::default(),
-            gauge_last_block: current_block_number,
+            gauge_last_block: current_block_number.clone(),
             gauge_state: GaugeState::Bonded,
+            last_accrual_block: current_block_number,
         }
     }
 }
@@ -147,6 +158,32 @@ where
         Ok(())
     }
 
+    /// Adds the `gauge_basic_rewards` of every block after `last_accrual_block` up to `n` to the
+    /// gauge pool rewards while it is bonded.
+    pub fn accrue_gauge_rewards(gauge_pool_info: &mut GaugePoolInfoOf<T>, n: BlockNumberFor<T>) {
+        if gauge_pool_info.gauge_state == GaugeState::Bonded {
+            let blocks: BalanceOf<T> = n
+                .saturating_sub(gauge_pool_info.last_accrual_block)
+                .saturated_into::<u128>()
+                .saturated_into();
+            if !blocks.is_zero() {
+                gauge_pool_info.gauge_basic_rewards.iter().for_each(
+                    |(reward_currency_id, reward_amount)| {
+                        let accrued = reward_amount.saturating_mul(blocks);
+                        gauge_pool_info
+                            .rewards
+                            .entry(*reward_currency_id)
+                            .and_modify(|(total_reward, _, _)| {
+                                *total_reward = total_reward.saturating_add(accrued);
+                            })
+                            .or_insert((accrued, Zero::zero(), Zero::zero()));
+                    },
+                );
+            }
+        }
+        gauge_pool_info.last_accrual_block = gauge_pool_info.last_accrual_block.max(n);
+    }
+
     pub fn gauge_add(
         who: &AccountIdOf<T>,
         gid: PoolId,
@@ -189,6 +226,7 @@ where
         gauge_block: BlockNumberFor<T>,
     ) -> DispatchResult {
         let current_block_number = frame_system::Pallet::<T>::block_number();
+        Self::accrue_gauge_rewards(gauge_pool_info, current_block_number);
         gauge_pool_info.gauge_last_block = current_block_number;
         gauge_pool_info.gauge_amount = gauge_pool_info
             .gauge_amount
@@ -235,6 +273,7 @@ where
         let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
         let mut gauge_pool_info =
             GaugePoolInfos::<T>::get(gid).ok_or(Error::<T>::GaugePoolNotExist)?;
+        Self::accrue_gauge_rewards(&mut gauge_pool_info, current_block_number);
         let pool_info =
             PoolInfos::<T>::get(gauge_pool_info.pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
         GaugeInfos::<T>::mutate_exists(gid, who, |maybe_gauge_info| -> DispatchResult {
@@ -318,8 +357,12 @@ where
         match pool_info.gauge {
             None => (),
             Some(gid) => {
-                let gauge_pool_info =
+                let mut gauge_pool_info =
                     GaugePoolInfos::<T>::get(gid).ok_or(Error::<T>::GaugePoolNotExist)?;
+                Self::accrue_gauge_rewards(
+                    &mut gauge_pool_info,
+                    frame_system::Pallet::<T>::block_number(),
+                );
                 let gauge_info =
                     GaugeInfos::<T>::get(gid, who).ok_or(Error::<T>::GaugeInfoNotExist)?;
                 let (gauge_rate, _) = Self::get_gauge_rate(&gauge_pool_info, &gauge_info)?;
@@ -434,4 +477,53 @@ where
         };
         Ok(increase_total_time_factor)
     }
+
+    /// The voting epoch block `n` belongs to.
+    pub fn vote_epoch(n: BlockNumberFor<T>) -> u32 {
+        n.checked_div(&T::VoteEpochLength::get())
+            .unwrap_or_default()
+            .saturated_into()
+    }
+
+    /// The voting power of `who` at block `n`, every amount of `VoteToken` locked in the bonded
+    /// gauge pool of a votable pool weighted by its remaining lock time over the `max_block` of
+    /// the gauge pool.
+    ///
+    /// Only the gauge pools of the at most `MaxVotablePools` votable pools are read.
+    pub fn voting_power(who: &AccountIdOf<T>, n: BlockNumberFor<T>) -> u128 {
+        Self::votable_pools()
+            .into_iter()
+            .filter_map(|pid| PoolInfos::<T>::get(pid).and_then(|pool_info| pool_info.gauge))
+            .filter_map(|gid| GaugePoolInfos::<T>::get(gid).map(|info| (gid, info)))
+            .filter(|(_, gauge_pool_info)| {
+                gauge_pool_info.token == T::VoteToken::get()
+                    && gauge_pool_info.gauge_state == GaugeState::Bonded
+                    && !gauge_pool_info.max_block.is_zero()
+            })
+            .filter_map(|(gid, gauge_pool_info)| {
+                GaugeInfos::<T>::get(gid, who).map(|gauge_info| (gauge_pool_info, gauge_info))
+            })
+            .fold(0u128, |power, (gauge_pool_info, gauge_info)| {
+                let remaining_block = gauge_info
+                    .gauge_stop_block
+                    .saturating_sub(n)
+                    .min(gauge_pool_info.max_block);
+                let gauge_power = U256::from(gauge_info.gauge_amount.saturated_into::<u128>())
+                    .saturating_mul(U256::from(remaining_block.saturated_into::<u128>()))
+                    .checked_div(U256::from(
+                        gauge_pool_info.max_block.saturated_into::<u128>(),
+                    ))
+                    .unwrap_or_default()
+                    .as_u128();
+                power.saturating_add(gauge_power)
+            })
+    }
+
+    /// The voting power directed to every pool in the current voting epoch.
+    pub fn get_gauge_weights() -> Vec<(PoolId, u128)> {
+        let epoch = Self::vote_epoch(frame_system::Pallet::<T>::block_number());
+        GaugeWeights::<T>::iter_prefix(epoch)
+            .filter(|(_, weight)| !weight.is_zero())
+            .collect()
+    }
 }
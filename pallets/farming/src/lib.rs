@@ -16,7 +16,12 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{pallet_prelude::*, PalletId};
+use frame_support::{
+    pallet_prelude::*,
+    storage::with_storage_layer,
+    traits::{BalanceStatus, Contains, Currency, ReservableCurrency},
+    PalletId,
+};
 use frame_system::pallet_prelude::*;
 use manta_primitives::types::PoolId;
 use orml_traits::{arithmetic::CheckedAdd, MultiCurrency};
@@ -25,21 +30,32 @@ use sp_runtime::{
     traits::{AccountIdConversion, AtLeast32BitUnsigned, One, Saturating, Zero},
     ArithmeticError, Perbill, SaturatedConversion,
 };
-use sp_std::{borrow::ToOwned, collections::btree_map::BTreeMap, vec::Vec};
+use sp_std::{
+    borrow::ToOwned,
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    vec::Vec,
+};
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 pub mod gauge;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
+pub mod portfolio;
 pub mod rewards;
 #[cfg(test)]
 mod tests;
+pub mod vesting;
 pub mod weights;
+pub mod zap;
 pub use gauge::*;
 pub use pallet::*;
+pub use portfolio::*;
 pub use rewards::*;
+pub use vesting::*;
 pub use weights::WeightInfo;
+pub use zap::*;
 
 #[allow(type_alias_bounds)]
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
@@ -53,6 +69,9 @@ pub type CurrencyIdOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<
 type BalanceOf<T: Config> =
     <<T as Config>::MultiCurrency as MultiCurrency<AccountIdOf<T>>>::Balance;
 
+#[allow(type_alias_bounds)]
+type DepositBalanceOf<T: Config> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+
 #[allow(type_alias_bounds)]
 type GaugeInitType<T: Config> = (
     CurrencyIdOf<T>,
@@ -77,13 +96,22 @@ type ShareInfoOf<T> = ShareInfo<BalanceOf<T>, CurrencyIdOf<T>, BlockNumberFor<T>
 #[allow(type_alias_bounds)]
 type RewardOf<T> = Vec<(CurrencyIdOf<T>, BalanceOf<T>)>;
 
+#[allow(type_alias_bounds)]
+type RewardVestingOf<T: Config> = RewardVesting<BlockNumberFor<T>>;
+
+#[allow(type_alias_bounds)]
+type VestingLedgerOf<T: Config> = VestingLedger<BalanceOf<T>, BlockNumberFor<T>>;
+
 #[frame_support::pallet]
 #[allow(clippy::too_many_arguments)]
 pub mod pallet {
     use super::*;
 
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
@@ -119,6 +147,35 @@ pub mod pallet {
 
         #[pallet::constant]
         type RewardIssuer: Get<PalletId>;
+
+        /// The currency the deposit of permissionless farming pools is reserved in
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// The deposit reserved from the creator of a permissionless farming pool
+        #[pallet::constant]
+        type PoolCreationDeposit: Get<DepositBalanceOf<Self>>;
+
+        /// The currencies permissionless farming pools can take shares in
+        type PermissionlessPoolTokens: Contains<CurrencyIdOf<Self>>;
+
+        /// The number of blocks of a gauge voting epoch
+        #[pallet::constant]
+        type VoteEpochLength: Get<BlockNumberFor<Self>>;
+
+        /// The max number of farming pools gauge voting can direct emissions to
+        #[pallet::constant]
+        type MaxVotablePools: Get<u32>;
+
+        /// The currency whose gauge locks give voting power
+        #[pallet::constant]
+        type VoteToken: Get<CurrencyIdOf<Self>>;
+
+        /// Adds and removes liquidity of the currency pairs LP farming pools take shares in
+        type LiquidityProvider: LiquidityProvider<
+            AccountIdOf<Self>,
+            CurrencyIdOf<Self>,
+            BalanceOf<Self>,
+        >;
     }
 
     #[pallet::event]
@@ -127,6 +184,11 @@ pub mod pallet {
         FarmingPoolCreated {
             pid: PoolId,
         },
+        PermissionlessPoolCreated {
+            pid: PoolId,
+            creator: AccountIdOf<T>,
+            deposit: DepositBalanceOf<T>,
+        },
         FarmingPoolReset {
             pid: PoolId,
         },
@@ -182,6 +244,66 @@ pub mod pallet {
         RetireLimitSet {
             limit: u32,
         },
+        VotablePoolSet {
+            pid: PoolId,
+            votable: bool,
+        },
+        GaugeVoteEmissionSet {
+            emission: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+        },
+        GaugeVoted {
+            who: AccountIdOf<T>,
+            epoch: u32,
+            power: u128,
+            votes: Vec<(PoolId, Perbill)>,
+        },
+        GaugeVotesSettled {
+            epoch: u32,
+            total_weight: u128,
+        },
+        GaugeVoteChargeFailed {
+            epoch: u32,
+            pid: PoolId,
+            error: DispatchError,
+        },
+        LiquidityZapped {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            liquidity: BalanceOf<T>,
+        },
+        LiquidityRemoved {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            liquidity: BalanceOf<T>,
+            amounts: (BalanceOf<T>, BalanceOf<T>),
+        },
+        RewardVestingSet {
+            pid: PoolId,
+            reward_vesting: Option<RewardVesting<BlockNumberFor<T>>>,
+        },
+        RewardsLocked {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            currency_id: CurrencyIdOf<T>,
+            amount: BalanceOf<T>,
+        },
+        VestedRewardsClaimed {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+        },
+        VestingExited {
+            who: AccountIdOf<T>,
+            pid: PoolId,
+            rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+            penalties: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+        },
+        SharesTransferred {
+            from: AccountIdOf<T>,
+            to: AccountIdOf<T>,
+            pid: PoolId,
+            amount: BalanceOf<T>,
+        },
     }
 
     #[pallet::error]
@@ -210,6 +332,34 @@ pub mod pallet {
         CanNotDeposit,
         /// The retire limit number is not set
         RetireLimitNotSet,
+        /// Token is not allowed in permissionless farming pools
+        TokenNotAllowed,
+        /// The pool creator is not allowed to make this edit
+        EditNotAllowed,
+        /// The pool can not be voted for
+        PoolNotVotable,
+        /// MaxVotablePools exceeded
+        TooManyVotablePools,
+        /// The votes exceed 100% of the voting power
+        InvalidVotes,
+        /// No gauge locked to vote with
+        NoVotingPower,
+        /// The voting epoch has not ended yet
+        EpochNotEnded,
+        /// No votes to settle in the voting epoch
+        NothingToSettle,
+        /// The pool does not take shares in the LP currency of the pair alone
+        NotLiquidityPool,
+        /// Less LP currency minted than the minimum
+        SlippageExceeded,
+        /// No LP currency unstaked to remove liquidity of
+        NothingUnstaked,
+        /// No vesting rewards to claim
+        NoVestingRewards,
+        /// Shares can not be transferred to oneself or in a zero amount
+        InvalidShareTransfer,
+        /// Not enough shares to transfer
+        InsufficientShares,
     }
 
     /// The next farming pool id.
@@ -258,56 +408,61 @@ pub mod pallet {
     pub type SharesAndWithdrawnRewards<T: Config> =
         StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, T::AccountId, ShareInfoOf<T>>;
 
-    #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
-            PoolInfos::<T>::iter().for_each(|(pool_id, mut pool_info)| match pool_info.state {
-                PoolState::Ongoing => {
-                    pool_info.basic_rewards.clone().iter().for_each(
-                        |(reward_currency_id, reward_amount)| {
-                            pool_info
-                                .rewards
-                                .entry(*reward_currency_id)
-                                .and_modify(|(total_reward, _)| {
-                                    *total_reward = total_reward.saturating_add(*reward_amount);
-                                })
-                                .or_insert((*reward_amount, Zero::zero()));
-                        },
-                    );
-                    PoolInfos::<T>::insert(pool_id, &pool_info);
-                }
-                PoolState::Charged => {
-                    if n >= pool_info.after_block_to_start
-                        && pool_info.total_shares >= pool_info.min_deposit_to_start
-                    {
-                        pool_info.block_startup = Some(n);
-                        pool_info.state = PoolState::Ongoing;
-                        PoolInfos::<T>::insert(pool_id, &pool_info);
-                    }
-                }
-                _ => (),
-            });
+    /// Record the creator and reserved deposit of permissionless farming pools.
+    ///
+    /// map PoolId => (AccountId, Deposit)
+    #[pallet::storage]
+    #[pallet::getter(fn pool_owners)]
+    pub type PoolOwners<T: Config> =
+        StorageMap<_, Twox64Concat, PoolId, (AccountIdOf<T>, DepositBalanceOf<T>)>;
 
-            GaugePoolInfos::<T>::iter().for_each(|(pool_id, mut gauge_pool_info)| {
-                if gauge_pool_info.gauge_state == GaugeState::Bonded {
-                    gauge_pool_info.gauge_basic_rewards.clone().iter().for_each(
-                        |(reward_currency_id, reward_amount)| {
-                            gauge_pool_info
-                                .rewards
-                                .entry(*reward_currency_id)
-                                .and_modify(|(total_reward, _, _)| {
-                                    *total_reward = total_reward.saturating_add(*reward_amount);
-                                })
-                                .or_insert((*reward_amount, Zero::zero(), Zero::zero()));
-                        },
-                    );
-                    GaugePoolInfos::<T>::insert(pool_id, &gauge_pool_info);
-                }
-            });
+    /// The farming pools gauge voting can direct emissions to.
+    #[pallet::storage]
+    #[pallet::getter(fn votable_pools)]
+    pub type VotablePools<T: Config> = StorageValue<_, BTreeSet<PoolId>, ValueQuery>;
 
-            T::WeightInfo::on_initialize()
-        }
-    }
+    /// The emission split between the votable pools by the gauge votes of every epoch.
+    #[pallet::storage]
+    #[pallet::getter(fn gauge_vote_emission)]
+    pub type GaugeVoteEmission<T: Config> =
+        StorageValue<_, BTreeMap<CurrencyIdOf<T>, BalanceOf<T>>, ValueQuery>;
+
+    /// Record the latest gauge vote of specific `AccountId`.
+    ///
+    /// map AccountId => GaugeVote
+    #[pallet::storage]
+    #[pallet::getter(fn gauge_votes)]
+    pub type GaugeVotes<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, GaugeVote>;
+
+    /// Record the voting power directed to every pool in a voting epoch.
+    ///
+    /// double_map (Epoch, PoolId) => Weight
+    #[pallet::storage]
+    #[pallet::getter(fn gauge_weights)]
+    pub type GaugeWeights<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, PoolId, u128, ValueQuery>;
+
+    /// Record the lock-up of the rewards claimed from a pool, the rewards of pools without one
+    /// are paid out on claim.
+    ///
+    /// map PoolId => RewardVesting
+    #[pallet::storage]
+    #[pallet::getter(fn reward_vestings)]
+    pub type RewardVestings<T: Config> = StorageMap<_, Twox64Concat, PoolId, RewardVestingOf<T>>;
+
+    /// Record the vesting rewards an account claimed from a pool by reward currency.
+    ///
+    /// double_map (AccountId, PoolId) => BTreeMap<CurrencyId, VestingLedger>
+    #[pallet::storage]
+    #[pallet::getter(fn vesting_ledgers)]
+    pub type VestingLedgers<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        PoolId,
+        BTreeMap<CurrencyIdOf<T>, VestingLedgerOf<T>>,
+    >;
 
     #[pallet::call]
     impl<T: Config> Pallet<T>
@@ -322,6 +477,7 @@ pub mod pallet {
             origin: OriginFor<T>,
             tokens_proportion: Vec<(CurrencyIdOf<T>, Perbill)>,
             basic_rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+            emission_curve: EmissionCurve<BlockNumberFor<T>>,
             gauge_init: Option<GaugeInitType<T>>,
             #[pallet::compact] min_deposit_to_start: BalanceOf<T>,
             #[pallet::compact] after_block_to_start: BlockNumberFor<T>,
@@ -331,58 +487,17 @@ pub mod pallet {
         ) -> DispatchResult {
             T::ControlOrigin::ensure_origin(origin)?;
 
-            let pool_id = Self::pool_next_id();
-            let keeper = T::Keeper::get().into_sub_account_truncating(pool_id);
-            let reward_issuer = T::RewardIssuer::get().into_sub_account_truncating(pool_id);
-            ensure!(
-                !tokens_proportion.is_empty(),
-                Error::<T>::InvalidPoolParameter
-            );
-            let basic_token = tokens_proportion[0];
-            let tokens_proportion_map: BTreeMap<CurrencyIdOf<T>, Perbill> =
-                tokens_proportion.into_iter().map(|(k, v)| (k, v)).collect();
-            let basic_rewards_map: BTreeMap<CurrencyIdOf<T>, BalanceOf<T>> =
-                basic_rewards.into_iter().map(|(k, v)| (k, v)).collect();
-
-            let mut pool_info = PoolInfo::new(
-                keeper,
-                reward_issuer,
-                tokens_proportion_map,
-                basic_token,
-                basic_rewards_map,
-                None,
+            Self::create_farming_pool_inner(
+                tokens_proportion,
+                basic_rewards,
+                emission_curve,
+                gauge_init,
                 min_deposit_to_start,
                 after_block_to_start,
                 withdraw_limit_time,
                 claim_limit_time,
                 withdraw_limit_count,
-            );
-
-            if let Some((gauge_token, max_block, gauge_basic_rewards)) = gauge_init {
-                let gauge_basic_rewards_map: BTreeMap<CurrencyIdOf<T>, BalanceOf<T>> =
-                    gauge_basic_rewards
-                        .into_iter()
-                        .map(|(k, v)| (k, v))
-                        .collect();
-
-                Self::create_gauge_pool(
-                    pool_id,
-                    &mut pool_info,
-                    gauge_token,
-                    gauge_basic_rewards_map,
-                    max_block,
-                )?;
-            };
-
-            PoolInfos::<T>::insert(pool_id, &pool_info);
-            PoolNextId::<T>::mutate(|id| -> DispatchResult {
-                *id = id
-                    .checked_add(One::one())
-                    .ok_or(ArithmeticError::Overflow)?;
-                Ok(())
-            })?;
-
-            Self::deposit_event(Event::FarmingPoolCreated { pid: pool_id });
+            )?;
             Ok(())
         }
 
@@ -396,26 +511,7 @@ pub mod pallet {
         ) -> DispatchResult {
             let exchanger = ensure_signed(origin)?;
 
-            let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
-            rewards
-                .iter()
-                .try_for_each(|(reward_currency, reward)| -> DispatchResult {
-                    T::MultiCurrency::transfer(
-                        *reward_currency,
-                        &exchanger,
-                        &pool_info.reward_issuer,
-                        *reward,
-                    )
-                })?;
-            pool_info.state = PoolState::Charged;
-            PoolInfos::<T>::insert(pool_id, pool_info);
-
-            Self::deposit_event(Event::Charged {
-                who: exchanger,
-                pid: pool_id,
-                rewards,
-            });
-            Ok(())
+            Self::charge_inner(exchanger, pool_id, rewards, false)
         }
 
         /// User can deposit token to farming pool, and get share of pool.
@@ -452,13 +548,14 @@ pub mod pallet {
         pub fn claim(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
             let exchanger = ensure_signed(origin)?;
 
-            let pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+            Self::accrue_pool_rewards(&mut pool_info, current_block_number);
             ensure!(
                 PoolState::state_valid(Action::Claim, pool_info.state),
                 Error::<T>::InvalidPoolState
             );
 
-            let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
             let share_info = Self::shares_and_withdrawn_rewards(pool_id, &exchanger)
                 .ok_or(Error::<T>::ShareInfoNotExists)?;
             ensure!(
@@ -491,9 +588,11 @@ pub mod pallet {
         #[pallet::call_index(6)]
         #[pallet::weight(T::DbWeight::get().write)]
         pub fn close_pool(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
-            T::ControlOrigin::ensure_origin(origin)?;
+            Self::ensure_pool_admin(origin, pool_id)?;
 
             let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+            Self::accrue_pool_rewards(&mut pool_info, n);
             ensure!(
                 PoolState::state_valid(Action::ClosePool, pool_info.state),
                 Error::<T>::InvalidPoolState
@@ -522,7 +621,7 @@ pub mod pallet {
         #[pallet::call_index(8)]
         #[pallet::weight(T::DbWeight::get().write)]
         pub fn retire_pool(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
-            T::ControlOrigin::ensure_origin(origin)?;
+            Self::ensure_pool_admin(origin, pool_id)?;
 
             let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
             ensure!(
@@ -553,6 +652,10 @@ pub mod pallet {
                 if let Some(ref gid) = pool_info.gauge {
                     let mut gauge_pool_info =
                         Self::gauge_pool_infos(gid).ok_or(Error::<T>::GaugePoolNotExist)?;
+                    Self::accrue_gauge_rewards(
+                        &mut gauge_pool_info,
+                        frame_system::Pallet::<T>::block_number(),
+                    );
                     gauge_pool_info.gauge_state = GaugeState::Unbond;
                     GaugePoolInfos::<T>::insert(gid, gauge_pool_info);
                 }
@@ -635,16 +738,30 @@ pub mod pallet {
         #[pallet::call_index(10)]
         #[pallet::weight(T::DbWeight::get().write)]
         pub fn kill_pool(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
-            T::ControlOrigin::ensure_origin(origin)?;
+            let creator = Self::ensure_pool_admin(origin, pool_id)?;
 
             let pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
             ensure!(
                 PoolState::state_valid(Action::KillPool, pool_info.state),
                 Error::<T>::InvalidPoolState
             );
+            if let Some((owner, deposit)) = PoolOwners::<T>::take(pool_id) {
+                if creator.is_some() {
+                    T::Currency::unreserve(&owner, deposit);
+                } else {
+                    T::Currency::repatriate_reserved(
+                        &owner,
+                        &T::TreasuryAccount::get(),
+                        deposit,
+                        BalanceStatus::Free,
+                    )?;
+                }
+            }
             #[allow(deprecated)]
             SharesAndWithdrawnRewards::<T>::remove_prefix(pool_id, None);
             PoolInfos::<T>::remove(pool_id);
+            VotablePools::<T>::mutate(|pools| pools.remove(&pool_id));
+            RewardVestings::<T>::remove(pool_id);
 
             Self::deposit_event(Event::FarmingPoolKilled { pid: pool_id });
             Ok(())
@@ -656,24 +773,48 @@ pub mod pallet {
             origin: OriginFor<T>,
             pool_id: PoolId,
             basic_rewards: Option<Vec<(CurrencyIdOf<T>, BalanceOf<T>)>>,
+            emission_curve: Option<EmissionCurve<BlockNumberFor<T>>>,
             withdraw_limit_time: Option<BlockNumberFor<T>>,
             claim_limit_time: Option<BlockNumberFor<T>>,
             gauge_basic_rewards: Option<Vec<(CurrencyIdOf<T>, BalanceOf<T>)>>,
             withdraw_limit_count: Option<u8>,
         ) -> DispatchResult {
-            T::ControlOrigin::ensure_origin(origin)?;
+            let creator = Self::ensure_pool_admin(origin, pool_id)?;
 
             let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+            Self::accrue_pool_rewards(&mut pool_info, n);
             ensure!(
                 PoolState::state_valid(Action::EditPool, pool_info.state),
                 Error::<T>::InvalidPoolState
             );
+            // The creator can not make the limits of the shares stricter
+            if creator.is_some() {
+                ensure!(
+                    gauge_basic_rewards.is_none()
+                        && withdraw_limit_time.map_or(true, |t| t <= pool_info.withdraw_limit_time)
+                        && claim_limit_time.map_or(true, |t| t <= pool_info.claim_limit_time)
+                        && withdraw_limit_count
+                            .map_or(true, |count| count >= pool_info.withdraw_limit_count),
+                    Error::<T>::EditNotAllowed
+                );
+            }
 
             if let Some(basic_rewards) = basic_rewards {
                 let basic_rewards_map: BTreeMap<CurrencyIdOf<T>, BalanceOf<T>> =
                     basic_rewards.into_iter().map(|(k, v)| (k, v)).collect();
                 pool_info.basic_rewards = basic_rewards_map;
             };
+            if let Some(emission_curve) = emission_curve {
+                // A pool that has not started yet measures its curve from its start
+                let start = match pool_info.block_startup {
+                    Some(_) => n,
+                    None => pool_info.after_block_to_start.max(n),
+                };
+                Self::ensure_valid_emission_curve(&emission_curve, start)?;
+                pool_info.emission_curve = emission_curve;
+                pool_info.emission_start = n;
+            };
             if let Some(withdraw_limit_time) = withdraw_limit_time {
                 pool_info.withdraw_limit_time = withdraw_limit_time;
             };
@@ -693,6 +834,7 @@ pub mod pallet {
                     pool_info.gauge.ok_or(Error::<T>::GaugePoolNotExist)?,
                     |gauge_pool_info_old| {
                         if let Some(mut gauge_pool_info) = gauge_pool_info_old.take() {
+                            Self::accrue_gauge_rewards(&mut gauge_pool_info, n);
                             gauge_pool_info.gauge_basic_rewards = gauge_basic_rewards_map;
                             *gauge_pool_info_old = Some(gauge_pool_info);
                         }
@@ -770,6 +912,405 @@ pub mod pallet {
             }
             Ok(())
         }
+
+        /// Anyone can create a farming pool without gauge for `PermissionlessPoolTokens` by
+        /// reserving `PoolCreationDeposit`, and `charge` it with their own reward tokens.
+        ///
+        /// The creator can close, edit within limits, retire and kill the pool. The deposit is
+        /// returned when the creator kills the pool, and goes to the treasury when `ControlOrigin`
+        /// kills it.
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::create_permissionless_pool())]
+        pub fn create_permissionless_pool(
+            origin: OriginFor<T>,
+            tokens_proportion: Vec<(CurrencyIdOf<T>, Perbill)>,
+            basic_rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+            emission_curve: EmissionCurve<BlockNumberFor<T>>,
+            #[pallet::compact] min_deposit_to_start: BalanceOf<T>,
+            #[pallet::compact] after_block_to_start: BlockNumberFor<T>,
+            #[pallet::compact] withdraw_limit_time: BlockNumberFor<T>,
+            #[pallet::compact] claim_limit_time: BlockNumberFor<T>,
+            #[pallet::compact] withdraw_limit_count: u8,
+        ) -> DispatchResult {
+            let creator = ensure_signed(origin)?;
+            ensure!(
+                tokens_proportion
+                    .iter()
+                    .all(|(token, _)| T::PermissionlessPoolTokens::contains(token)),
+                Error::<T>::TokenNotAllowed
+            );
+
+            let deposit = T::PoolCreationDeposit::get();
+            T::Currency::reserve(&creator, deposit)?;
+            let pool_id = Self::create_farming_pool_inner(
+                tokens_proportion,
+                basic_rewards,
+                emission_curve,
+                None,
+                min_deposit_to_start,
+                after_block_to_start,
+                withdraw_limit_time,
+                claim_limit_time,
+                withdraw_limit_count,
+            )?;
+            PoolOwners::<T>::insert(pool_id, (&creator, deposit));
+
+            Self::deposit_event(Event::PermissionlessPoolCreated {
+                pid: pool_id,
+                creator,
+                deposit,
+            });
+            Ok(())
+        }
+
+        /// `ControlOrigin` sets the emission split between the votable pools every voting epoch.
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::DbWeight::get().write)]
+        pub fn set_gauge_vote_emission(
+            origin: OriginFor<T>,
+            emission: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+        ) -> DispatchResult {
+            T::ControlOrigin::ensure_origin(origin)?;
+
+            GaugeVoteEmission::<T>::put(emission.iter().copied().collect::<BTreeMap<_, _>>());
+
+            Self::deposit_event(Event::GaugeVoteEmissionSet { emission });
+            Ok(())
+        }
+
+        /// `ControlOrigin` adds or removes a pool from the pools gauge voting can direct
+        /// emissions to.
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+        pub fn set_votable_pool(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            votable: bool,
+        ) -> DispatchResult {
+            T::ControlOrigin::ensure_origin(origin)?;
+
+            VotablePools::<T>::try_mutate(|pools| -> DispatchResult {
+                if votable {
+                    ensure!(
+                        PoolInfos::<T>::contains_key(pool_id),
+                        Error::<T>::PoolDoesNotExist
+                    );
+                    pools.insert(pool_id);
+                    ensure!(
+                        pools.len() <= T::MaxVotablePools::get() as usize,
+                        Error::<T>::TooManyVotablePools
+                    );
+                } else {
+                    pools.remove(&pool_id);
+                }
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::VotablePoolSet {
+                pid: pool_id,
+                votable,
+            });
+            Ok(())
+        }
+
+        /// Allocates the voting power of the gauges locked by the caller across votable pools
+        /// for the current epoch, replacing their earlier votes in the epoch.
+        ///
+        /// The voting power decays with the remaining lock time, so votes need to be cast again
+        /// every epoch. Computing it reads the gauge of every votable pool, so the call is charged
+        /// for `MaxVotablePools` regardless of the number of votes.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::vote_gauge(T::MaxVotablePools::get()))]
+        pub fn vote_gauge(origin: OriginFor<T>, votes: Vec<(PoolId, Perbill)>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let votable_pools = Self::votable_pools();
+            ensure!(
+                votes.iter().all(|(pid, _)| votable_pools.contains(pid)),
+                Error::<T>::PoolNotVotable
+            );
+            ensure!(
+                votes.len() <= votable_pools.len()
+                    && votes
+                        .iter()
+                        .map(|(_, proportion)| proportion.deconstruct() as u64)
+                        .sum::<u64>()
+                        <= Perbill::one().deconstruct() as u64,
+                Error::<T>::InvalidVotes
+            );
+
+            let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+            let epoch = Self::vote_epoch(n);
+            let power = Self::voting_power(&who, n);
+            ensure!(!power.is_zero(), Error::<T>::NoVotingPower);
+
+            if let Some(last_vote) = Self::gauge_votes(&who) {
+                if last_vote.epoch == epoch {
+                    last_vote.votes.iter().for_each(|(pid, proportion)| {
+                        GaugeWeights::<T>::mutate(epoch, pid, |weight| {
+                            *weight = weight.saturating_sub(proportion.mul_floor(last_vote.power))
+                        });
+                    });
+                }
+            }
+            votes.iter().for_each(|(pid, proportion)| {
+                GaugeWeights::<T>::mutate(epoch, pid, |weight| {
+                    *weight = weight.saturating_add(proportion.mul_floor(power))
+                });
+            });
+            GaugeVotes::<T>::insert(
+                &who,
+                GaugeVote {
+                    epoch,
+                    power,
+                    votes: votes.clone(),
+                },
+            );
+
+            Self::deposit_event(Event::GaugeVoted {
+                who,
+                epoch,
+                power,
+                votes,
+            });
+            Ok(())
+        }
+
+        /// Anyone can settle an ended voting epoch, which charges `GaugeVoteEmission` from the
+        /// treasury into the voted pools in proportion to their gauge weights.
+        ///
+        /// The share of pools which can no longer be charged stays in the treasury, as does the
+        /// share of pools whose charge fails, which are reported with `GaugeVoteChargeFailed`
+        /// without reverting the settlement of the other pools.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::settle_gauge_votes(T::MaxVotablePools::get()))]
+        pub fn settle_gauge_votes(origin: OriginFor<T>, epoch: u32) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+            ensure!(epoch < Self::vote_epoch(n), Error::<T>::EpochNotEnded);
+            let gauge_weights: Vec<(PoolId, u128)> = GaugeWeights::<T>::drain_prefix(epoch)
+                .filter(|(_, weight)| !weight.is_zero())
+                .collect();
+            let total_weight = gauge_weights
+                .iter()
+                .fold(0u128, |total, (_, weight)| total.saturating_add(*weight));
+            ensure!(!total_weight.is_zero(), Error::<T>::NothingToSettle);
+
+            let emission = Self::gauge_vote_emission();
+            for (pool_id, weight) in gauge_weights {
+                let chargeable = Self::pool_infos(pool_id).map_or(false, |pool_info| {
+                    matches!(
+                        pool_info.state,
+                        PoolState::UnCharged | PoolState::Charged | PoolState::Ongoing
+                    )
+                });
+                if !chargeable {
+                    continue;
+                }
+                let rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)> = emission
+                    .iter()
+                    .map(|(reward_currency, amount)| {
+                        let reward = Self::get_reward_inflation(
+                            weight.saturated_into(),
+                            amount,
+                            total_weight.saturated_into(),
+                        );
+                        (*reward_currency, reward)
+                    })
+                    .filter(|(_, reward)| !reward.is_zero())
+                    .collect();
+                if rewards.is_empty() {
+                    continue;
+                }
+                if let Err(error) = with_storage_layer(|| {
+                    Self::charge_inner(T::TreasuryAccount::get(), pool_id, rewards, true)
+                }) {
+                    Self::deposit_event(Event::GaugeVoteChargeFailed {
+                        epoch,
+                        pid: pool_id,
+                        error,
+                    });
+                }
+            }
+
+            Self::deposit_event(Event::GaugeVotesSettled {
+                epoch,
+                total_weight,
+            });
+            Ok(())
+        }
+
+        /// Adds liquidity of `currencies` and deposits the minted LP currency into the farming
+        /// pool taking shares in it, in one go.
+        ///
+        /// Fails unless at least `min_liquidity` of the LP currency is minted.
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::zap_deposit())]
+        pub fn zap_deposit(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            currencies: (CurrencyIdOf<T>, CurrencyIdOf<T>),
+            amounts_desired: (BalanceOf<T>, BalanceOf<T>),
+            amounts_min: (BalanceOf<T>, BalanceOf<T>),
+            min_liquidity: BalanceOf<T>,
+            gauge_info: Option<(BalanceOf<T>, BlockNumberFor<T>)>,
+        ) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            Self::zap_deposit_inner(
+                exchanger,
+                pool_id,
+                currencies,
+                amounts_desired,
+                amounts_min,
+                min_liquidity,
+                gauge_info,
+            )
+        }
+
+        /// Withdraws from the LP farming pool of `currencies` and removes the unstaked liquidity,
+        /// paying out both currencies of the pair.
+        ///
+        /// With a `withdraw_limit_time`, `withdraw` first and call this with a `remove_value` of
+        /// zero once the withdrawal is unlocked.
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::withdraw_and_remove_liquidity())]
+        pub fn withdraw_and_remove_liquidity(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            currencies: (CurrencyIdOf<T>, CurrencyIdOf<T>),
+            remove_value: Option<BalanceOf<T>>,
+            amounts_min: (BalanceOf<T>, BalanceOf<T>),
+        ) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            Self::withdraw_and_remove_liquidity_inner(
+                exchanger,
+                pool_id,
+                currencies,
+                remove_value,
+                amounts_min,
+            )
+        }
+
+        /// Sets the lock-up of the rewards claimed from the pool, `None` pays them out on claim
+        /// again.
+        ///
+        /// Rewards locked before keep vesting with the penalty they were locked with, an early exit
+        /// pays no penalty once the pool stops vesting its rewards.
+        ///
+        /// The creator of a pool with shares can only shorten the lock-up or lower its penalty.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::set_reward_vesting())]
+        pub fn set_reward_vesting(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            reward_vesting: Option<RewardVesting<BlockNumberFor<T>>>,
+        ) -> DispatchResult {
+            let creator = Self::ensure_pool_admin(origin, pool_id)?;
+            let pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            if creator.is_some() && !pool_info.total_shares.is_zero() {
+                let loosened = match (Self::reward_vestings(pool_id), reward_vesting) {
+                    (_, None) => true,
+                    (Some(current), Some(new)) => {
+                        new.duration <= current.duration && new.penalty <= current.penalty
+                    }
+                    (None, Some(_)) => false,
+                };
+                ensure!(loosened, Error::<T>::EditNotAllowed);
+            }
+
+            match reward_vesting {
+                Some(reward_vesting) => {
+                    ensure!(
+                        !reward_vesting.duration.is_zero(),
+                        Error::<T>::InvalidPoolParameter
+                    );
+                    RewardVestings::<T>::insert(pool_id, reward_vesting);
+                }
+                None => RewardVestings::<T>::remove(pool_id),
+            }
+
+            Self::deposit_event(Event::RewardVestingSet {
+                pid: pool_id,
+                reward_vesting,
+            });
+            Ok(())
+        }
+
+        /// Pays out the vesting rewards from the pool released so far.
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::claim_vested())]
+        pub fn claim_vested(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            Self::claim_vested_inner(exchanger, pool_id)
+        }
+
+        /// Pays out all vesting rewards from the pool at once, the penalty of the pool on the
+        /// locked rewards is added to the pool rewards or burned.
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::exit_vesting())]
+        pub fn exit_vesting(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+
+            Self::exit_vesting_inner(exchanger, pool_id)
+        }
+
+        /// Moves `amount` of the shares in the pool to `to` without unstaking, pending rewards
+        /// stay with their accounts.
+        ///
+        /// Gauge locks are not moved. The gauge rewards of both accounts are claimed first since
+        /// they are weighted by the shares at claim.
+        ///
+        /// Since the rewards are claimed, the transfer is only possible when the accounts it
+        /// claims for could `claim` themselves.
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::transfer_shares())]
+        pub fn transfer_shares(
+            origin: OriginFor<T>,
+            pool_id: PoolId,
+            to: T::AccountId,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let exchanger = ensure_signed(origin)?;
+            ensure!(
+                exchanger != to && !amount.is_zero(),
+                Error::<T>::InvalidShareTransfer
+            );
+
+            let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+            let share_info = Self::shares_and_withdrawn_rewards(pool_id, &exchanger)
+                .ok_or(Error::<T>::ShareInfoNotExists)?;
+            ensure!(amount <= share_info.share, Error::<T>::InsufficientShares);
+            let current_block_number: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+            Self::accrue_pool_rewards(&mut pool_info, current_block_number);
+            ensure!(
+                PoolState::state_valid(Action::Claim, pool_info.state),
+                Error::<T>::InvalidPoolState
+            );
+            let can_claim = |share_info: &ShareInfoOf<T>| {
+                share_info.claim_last_block + pool_info.claim_limit_time <= current_block_number
+            };
+            ensure!(can_claim(&share_info), Error::<T>::CanNotClaim);
+            if let Some(gid) = pool_info.gauge {
+                Self::gauge_claim_inner(&exchanger, gid)?;
+                if let Some(to_share_info) = Self::shares_and_withdrawn_rewards(pool_id, &to) {
+                    ensure!(can_claim(&to_share_info), Error::<T>::CanNotClaim);
+                    Self::gauge_claim_inner(&to, gid)?;
+                }
+            }
+            Self::transfer_share(&exchanger, &to, pool_id, amount)?;
+
+            Self::deposit_event(Event::SharesTransferred {
+                from: exchanger,
+                to,
+                pid: pool_id,
+                amount,
+            });
+            Ok(())
+        }
     }
 }
 
@@ -797,13 +1338,143 @@ impl<T: Config> Pallet<T> {
         T::MultiCurrency::transfer(*reward_currency, from, &account_to_send, reward_to_withdraw)
     }
 
+    /// Transfers `rewards` from `exchanger` to the pool and adds them to its budget, then the
+    /// pool waits to start again unless it is `Ongoing` and `keep_ongoing` is set.
+    pub fn charge_inner(
+        exchanger: AccountIdOf<T>,
+        pool_id: PoolId,
+        rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+        keep_ongoing: bool,
+    ) -> DispatchResult {
+        let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        Self::accrue_pool_rewards(&mut pool_info, n);
+        rewards
+            .iter()
+            .try_for_each(|(reward_currency, reward)| -> DispatchResult {
+                T::MultiCurrency::transfer(
+                    *reward_currency,
+                    &exchanger,
+                    &pool_info.reward_issuer,
+                    *reward,
+                )?;
+                pool_info
+                    .remaining_rewards
+                    .entry(*reward_currency)
+                    .and_modify(|remaining| *remaining = remaining.saturating_add(*reward))
+                    .or_insert(*reward);
+                Ok(())
+            })?;
+        if !(keep_ongoing && pool_info.state == PoolState::Ongoing) {
+            pool_info.state = PoolState::Charged;
+        }
+        PoolInfos::<T>::insert(pool_id, pool_info);
+
+        Self::deposit_event(Event::Charged {
+            who: exchanger,
+            pid: pool_id,
+            rewards,
+        });
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_farming_pool_inner(
+        tokens_proportion: Vec<(CurrencyIdOf<T>, Perbill)>,
+        basic_rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+        emission_curve: EmissionCurve<BlockNumberFor<T>>,
+        gauge_init: Option<GaugeInitType<T>>,
+        min_deposit_to_start: BalanceOf<T>,
+        after_block_to_start: BlockNumberFor<T>,
+        withdraw_limit_time: BlockNumberFor<T>,
+        claim_limit_time: BlockNumberFor<T>,
+        withdraw_limit_count: u8,
+    ) -> Result<PoolId, DispatchError> {
+        let pool_id = Self::pool_next_id();
+        let keeper = T::Keeper::get().into_sub_account_truncating(pool_id);
+        let reward_issuer = T::RewardIssuer::get().into_sub_account_truncating(pool_id);
+        ensure!(
+            !tokens_proportion.is_empty(),
+            Error::<T>::InvalidPoolParameter
+        );
+        Self::ensure_valid_emission_curve(
+            &emission_curve,
+            after_block_to_start.max(frame_system::Pallet::<T>::block_number()),
+        )?;
+        let basic_token = tokens_proportion[0];
+        let tokens_proportion_map: BTreeMap<CurrencyIdOf<T>, Perbill> =
+            tokens_proportion.into_iter().map(|(k, v)| (k, v)).collect();
+        let basic_rewards_map: BTreeMap<CurrencyIdOf<T>, BalanceOf<T>> =
+            basic_rewards.into_iter().map(|(k, v)| (k, v)).collect();
+
+        let mut pool_info = PoolInfo::new(
+            keeper,
+            reward_issuer,
+            tokens_proportion_map,
+            basic_token,
+            basic_rewards_map,
+            emission_curve,
+            None,
+            min_deposit_to_start,
+            after_block_to_start,
+            withdraw_limit_time,
+            claim_limit_time,
+            withdraw_limit_count,
+        );
+
+        if let Some((gauge_token, max_block, gauge_basic_rewards)) = gauge_init {
+            let gauge_basic_rewards_map: BTreeMap<CurrencyIdOf<T>, BalanceOf<T>> =
+                gauge_basic_rewards
+                    .into_iter()
+                    .map(|(k, v)| (k, v))
+                    .collect();
+
+            Self::create_gauge_pool(
+                pool_id,
+                &mut pool_info,
+                gauge_token,
+                gauge_basic_rewards_map,
+                max_block,
+            )?;
+        };
+
+        PoolInfos::<T>::insert(pool_id, &pool_info);
+        PoolNextId::<T>::mutate(|id| -> DispatchResult {
+            *id = id
+                .checked_add(One::one())
+                .ok_or(ArithmeticError::Overflow)?;
+            Ok(())
+        })?;
+
+        Self::deposit_event(Event::FarmingPoolCreated { pid: pool_id });
+        Ok(pool_id)
+    }
+
+    /// Returns `None` for `ControlOrigin` and the creator for the creator of a permissionless
+    /// pool.
+    fn ensure_pool_admin(
+        origin: OriginFor<T>,
+        pool_id: PoolId,
+    ) -> Result<Option<AccountIdOf<T>>, DispatchError> {
+        match T::ControlOrigin::try_origin(origin) {
+            Ok(_) => Ok(None),
+            Err(origin) => {
+                let who = ensure_signed(origin)?;
+                let (creator, _) = Self::pool_owners(pool_id).ok_or(DispatchError::BadOrigin)?;
+                ensure!(who == creator, DispatchError::BadOrigin);
+                Ok(Some(who))
+            }
+        }
+    }
+
     pub fn get_farming_rewards(
         who: &T::AccountId,
         pool_id: PoolId,
     ) -> Result<RewardOf<T>, DispatchError> {
         let share_info = SharesAndWithdrawnRewards::<T>::get(pool_id, who)
             .ok_or(Error::<T>::ShareInfoNotExists)?;
-        let pool_info = PoolInfos::<T>::get(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        let mut pool_info = PoolInfos::<T>::get(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        Self::accrue_pool_rewards(&mut pool_info, frame_system::Pallet::<T>::block_number());
         let total_shares = pool_info.total_shares;
         let mut result_vec = Vec::<(CurrencyIdOf<T>, BalanceOf<T>)>::new();
 
@@ -828,6 +1499,29 @@ impl<T: Config> Pallet<T> {
         Ok(result_vec)
     }
 
+    /// The weight of [`Self::is_farming_currency`], one read of every farming and gauge pool id
+    /// handed out so far.
+    pub fn is_farming_currency_weight() -> Weight {
+        let pools = u64::from(PoolNextId::<T>::get())
+            .saturating_add(GaugePoolNextId::<T>::get().into())
+            .saturating_add(2);
+        T::DbWeight::get().reads(pools)
+    }
+
+    /// Whether any farming or gauge pool takes shares, pays rewards or locks gauges in
+    /// `currency_id`.
+    pub fn is_farming_currency(currency_id: &CurrencyIdOf<T>) -> bool {
+        PoolInfos::<T>::iter_values().any(|pool_info| {
+            pool_info.tokens_proportion.contains_key(currency_id)
+                || pool_info.basic_rewards.contains_key(currency_id)
+                || pool_info.remaining_rewards.contains_key(currency_id)
+                || pool_info.rewards.contains_key(currency_id)
+        }) || GaugePoolInfos::<T>::iter_values().any(|gauge_pool_info| {
+            gauge_pool_info.token == *currency_id
+                || gauge_pool_info.rewards.contains_key(currency_id)
+        })
+    }
+
     fn get_reward_amount(
         share_info: &ShareInfoOf<T>,
         total_reward: &BalanceOf<T>,
@@ -851,6 +1545,20 @@ impl<T: Config> Pallet<T> {
         Ok((withdrawn_reward, reward_to_withdraw))
     }
 
+    /// A `LinearTaper` curve must end after `start`, the earliest block it can be measured from.
+    fn ensure_valid_emission_curve(
+        emission_curve: &EmissionCurve<BlockNumberFor<T>>,
+        start: BlockNumberFor<T>,
+    ) -> DispatchResult {
+        let valid = match emission_curve {
+            EmissionCurve::Constant => true,
+            EmissionCurve::StepHalving { period } => !period.is_zero(),
+            EmissionCurve::LinearTaper { end_block } => *end_block > start,
+        };
+        ensure!(valid, Error::<T>::InvalidPoolParameter);
+        Ok(())
+    }
+
     fn get_reward_inflation(
         amount: BalanceOf<T>,
         total_reward: &BalanceOf<T>,
@@ -873,13 +1581,14 @@ impl<T: Config> Pallet<T> {
         gauge_info: Option<(BalanceOf<T>, BlockNumberFor<T>)>,
     ) -> DispatchResult {
         let mut pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        Self::accrue_pool_rewards(&mut pool_info, n);
         ensure!(
             PoolState::state_valid(Action::Deposit, pool_info.state),
             Error::<T>::InvalidPoolState
         );
 
         if let PoolState::Charged = pool_info.state {
-            let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
             ensure!(
                 n >= pool_info.after_block_to_start,
                 Error::<T>::CanNotDeposit
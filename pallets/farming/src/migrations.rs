@@ -0,0 +1,178 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Migrations
+
+use crate::*;
+use codec::HasCompact;
+use core::marker::PhantomData;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+
+/// [`PoolInfo`] before lazy reward accrual and emission curves
+#[derive(Decode)]
+struct OldPoolInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf, BlockNumberFor> {
+    tokens_proportion: BTreeMap<CurrencyIdOf, Perbill>,
+    basic_token: (CurrencyIdOf, Perbill),
+    total_shares: BalanceOf,
+    basic_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+    rewards: BTreeMap<CurrencyIdOf, (BalanceOf, BalanceOf)>,
+    state: PoolState,
+    keeper: AccountIdOf,
+    reward_issuer: AccountIdOf,
+    gauge: Option<PoolId>,
+    block_startup: Option<BlockNumberFor>,
+    min_deposit_to_start: BalanceOf,
+    after_block_to_start: BlockNumberFor,
+    withdraw_limit_time: BlockNumberFor,
+    claim_limit_time: BlockNumberFor,
+    withdraw_limit_count: u8,
+}
+
+/// [`GaugePoolInfo`] before lazy reward accrual
+#[derive(Decode)]
+struct OldGaugePoolInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf, BlockNumberFor> {
+    pool_id: PoolId,
+    token: CurrencyIdOf,
+    keeper: AccountIdOf,
+    reward_issuer: AccountIdOf,
+    rewards: BTreeMap<CurrencyIdOf, (BalanceOf, BalanceOf, BalanceOf)>,
+    gauge_basic_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+    max_block: BlockNumberFor,
+    gauge_amount: BalanceOf,
+    total_time_factor: u128,
+    gauge_state: GaugeState,
+    gauge_last_block: BlockNumberFor,
+}
+
+/// Adds `last_accrual_block` to every [`PoolInfos`] and [`GaugePoolInfos`] entry, and a
+/// constant emission curve with the not yet accrued charged rewards to every pool.
+///
+/// The old `on_initialize` hook accrued the rewards of every block before the upgrade,
+/// so the pools continue accruing from the upgrade block on. The remaining rewards of a pool
+/// are the balances of its reward issuer that are not owed to its shares.
+pub struct MigrateToV1<T>(PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let db_weight = T::DbWeight::get();
+        if Pallet::<T>::on_chain_storage_version() != 0 {
+            log::info!(target: "MigrateToV1", "skipping, storage version is not 0");
+            return db_weight.reads(1);
+        }
+        let last_accrual_block =
+            frame_system::Pallet::<T>::block_number().saturating_sub(One::one());
+
+        let mut pools = 0u64;
+        let mut reads = 0u64;
+        PoolInfos::<T>::translate::<
+            OldPoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+            _,
+        >(|_, old| {
+            pools = pools.saturating_add(1);
+            let remaining_rewards = old
+                .basic_rewards
+                .keys()
+                .map(|reward_currency| {
+                    reads = reads.saturating_add(1);
+                    let unclaimed = old
+                        .rewards
+                        .get(reward_currency)
+                        .map(|(total_reward, total_withdrawn_reward)| {
+                            total_reward.saturating_sub(*total_withdrawn_reward)
+                        })
+                        .unwrap_or_default();
+                    let remaining =
+                        T::MultiCurrency::free_balance(*reward_currency, &old.reward_issuer)
+                            .saturating_sub(unclaimed);
+                    (*reward_currency, remaining)
+                })
+                .collect();
+            Some(PoolInfo {
+                tokens_proportion: old.tokens_proportion,
+                basic_token: old.basic_token,
+                total_shares: old.total_shares,
+                basic_rewards: old.basic_rewards,
+                emission_curve: EmissionCurve::Constant,
+                emission_start: old.block_startup.unwrap_or(last_accrual_block),
+                remaining_rewards,
+                rewards: old.rewards,
+                state: old.state,
+                keeper: old.keeper,
+                reward_issuer: old.reward_issuer,
+                gauge: old.gauge,
+                block_startup: old.block_startup,
+                last_accrual_block,
+                min_deposit_to_start: old.min_deposit_to_start,
+                after_block_to_start: old.after_block_to_start,
+                withdraw_limit_time: old.withdraw_limit_time,
+                claim_limit_time: old.claim_limit_time,
+                withdraw_limit_count: old.withdraw_limit_count,
+            })
+        });
+        GaugePoolInfos::<T>::translate::<
+            OldGaugePoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+            _,
+        >(|_, old| {
+            pools = pools.saturating_add(1);
+            Some(GaugePoolInfo {
+                pool_id: old.pool_id,
+                token: old.token,
+                keeper: old.keeper,
+                reward_issuer: old.reward_issuer,
+                rewards: old.rewards,
+                gauge_basic_rewards: old.gauge_basic_rewards,
+                max_block: old.max_block,
+                gauge_amount: old.gauge_amount,
+                total_time_factor: old.total_time_factor,
+                gauge_state: old.gauge_state,
+                gauge_last_block: old.gauge_last_block,
+                last_accrual_block,
+            })
+        });
+
+        StorageVersion::new(1).put::<Pallet<T>>();
+        log::info!(target: "MigrateToV1", "migrated {:?} pools", pools);
+        db_weight.reads_writes(
+            pools.saturating_add(reads).saturating_add(1),
+            pools.saturating_add(1),
+        )
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+        let pools = PoolInfos::<T>::iter_keys().count() as u32;
+        let gauge_pools = GaugePoolInfos::<T>::iter_keys().count() as u32;
+        Ok((pools, gauge_pools).encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+        let (pools, gauge_pools): (u32, u32) = Decode::decode(&mut &state[..])
+            .map_err(|_| "MigrateToV1: invalid pre_upgrade state")?;
+        frame_support::ensure!(
+            PoolInfos::<T>::iter_values().count() as u32 == pools,
+            "MigrateToV1: pool infos were lost"
+        );
+        frame_support::ensure!(
+            GaugePoolInfos::<T>::iter_values().count() as u32 == gauge_pools,
+            "MigrateToV1: gauge pool infos were lost"
+        );
+        frame_support::ensure!(
+            Pallet::<T>::on_chain_storage_version() == 1,
+            "MigrateToV1: storage version was not updated"
+        );
+        Ok(())
+    }
+}
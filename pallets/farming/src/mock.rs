@@ -22,8 +22,8 @@
 use frame_support::{
     derive_impl,
     dispatch::DispatchResult,
-    ord_parameter_types, parameter_types,
-    traits::{AsEnsureOriginWithArg, EitherOfDiverse},
+    ensure, ord_parameter_types, parameter_types,
+    traits::{fungibles::Destroy, AsEnsureOriginWithArg, EitherOfDiverse, IsInVec, Nothing},
     PalletId,
 };
 use frame_system::{EnsureNever, EnsureRoot, EnsureSignedBy};
@@ -39,7 +39,7 @@ use manta_primitives::{
 use sp_core::{ConstU128, ConstU32, H256};
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
-    AccountId32, BuildStorage,
+    AccountId32, BuildStorage, DispatchError,
 };
 use xcm::{
     prelude::{Parachain, X1},
@@ -48,17 +48,21 @@ use xcm::{
 };
 
 use crate as pallet_farming;
+use crate::LiquidityProvider;
+use orml_traits::MultiCurrency;
 
 pub type AccountId = AccountId32;
 pub type Balance = u128;
 
 pub const KSM: CalamariAssetId = 8;
 pub const KMA: CalamariAssetId = 1;
+pub const KMA_KSM_LP: CalamariAssetId = 10;
 
 pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
 pub const CHARLIE: AccountId = AccountId32::new([3u8; 32]);
 pub const TREASURY_ACCOUNT: AccountId = AccountId32::new([9u8; 32]);
+pub const PAIR_ACCOUNT: AccountId = AccountId32::new([7u8; 32]);
 
 frame_support::construct_runtime!(
     pub enum Runtime
@@ -217,6 +221,28 @@ impl AssetRegistry for MantaAssetRegistry {
             metadata.is_frozen,
         )
     }
+
+    fn start_destroy_asset(asset_id: &CalamariAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::start_destroy(*asset_id, None)
+    }
+
+    fn destroy_asset_accounts(
+        asset_id: &CalamariAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_accounts(*asset_id, max_items)
+    }
+
+    fn destroy_asset_approvals(
+        asset_id: &CalamariAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_approvals(*asset_id, max_items)
+    }
+
+    fn finish_destroy_asset(asset_id: &CalamariAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::finish_destroy(*asset_id)
+    }
 }
 
 parameter_types! {
@@ -271,13 +297,18 @@ impl pallet_asset_manager::Config for Runtime {
     type PermissionlessStartId = ConstU128<100>;
     type TokenNameMaxLen = ConstU32<100>;
     type TokenSymbolMaxLen = ConstU32<100>;
+    type TokenLogoMaxLen = ConstU32<100>;
     type PermissionlessAssetRegistryCost = ConstU128<1000>;
+    type AssetInUse = Nothing;
+    type PairReserves = ();
 }
 
 parameter_types! {
     pub const FarmingKeeperPalletId: PalletId = PalletId(*b"bf/fmkpr");
     pub const FarmingRewardIssuerPalletId: PalletId = PalletId(*b"bf/fmrir");
     pub const TreasuryAccount: AccountId32 = TREASURY_ACCOUNT;
+    pub PermissionlessPoolTokens: Vec<CalamariAssetId> = vec![KSM];
+    pub const VoteEpochLength: u64 = 10;
 }
 
 ord_parameter_types! {
@@ -286,6 +317,51 @@ ord_parameter_types! {
 
 type MantaCurrencies = Currencies<Runtime, MantaAssetConfig, Balances, Assets>;
 
+/// A KMA/KSM pair which takes both currencies 1:1 for its LP currency.
+pub struct MockLiquidityProvider;
+impl LiquidityProvider<AccountId, CalamariAssetId, Balance> for MockLiquidityProvider {
+    fn lp_currency(
+        currency_0: CalamariAssetId,
+        currency_1: CalamariAssetId,
+    ) -> Option<CalamariAssetId> {
+        match (currency_0, currency_1) {
+            (KMA, KSM) | (KSM, KMA) => Some(KMA_KSM_LP),
+            _ => None,
+        }
+    }
+
+    fn add_liquidity(
+        who: &AccountId,
+        currencies: (CalamariAssetId, CalamariAssetId),
+        amounts_desired: (Balance, Balance),
+        amounts_min: (Balance, Balance),
+    ) -> DispatchResult {
+        let liquidity = amounts_desired.0.min(amounts_desired.1);
+        ensure!(
+            liquidity >= amounts_min.0 && liquidity >= amounts_min.1,
+            DispatchError::Other("insufficient amount")
+        );
+        MantaCurrencies::transfer(currencies.0, who, &PAIR_ACCOUNT, liquidity)?;
+        MantaCurrencies::transfer(currencies.1, who, &PAIR_ACCOUNT, liquidity)?;
+        MantaCurrencies::deposit(KMA_KSM_LP, who, liquidity)
+    }
+
+    fn remove_liquidity(
+        who: &AccountId,
+        currencies: (CalamariAssetId, CalamariAssetId),
+        liquidity: Balance,
+        amounts_min: (Balance, Balance),
+    ) -> DispatchResult {
+        ensure!(
+            liquidity >= amounts_min.0 && liquidity >= amounts_min.1,
+            DispatchError::Other("insufficient amount")
+        );
+        MantaCurrencies::withdraw(KMA_KSM_LP, who, liquidity)?;
+        MantaCurrencies::transfer(currencies.0, &PAIR_ACCOUNT, who, liquidity)?;
+        MantaCurrencies::transfer(currencies.1, &PAIR_ACCOUNT, who, liquidity)
+    }
+}
+
 impl pallet_farming::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type CurrencyId = CalamariAssetId;
@@ -295,6 +371,13 @@ impl pallet_farming::Config for Runtime {
     type Keeper = FarmingKeeperPalletId;
     type RewardIssuer = FarmingRewardIssuerPalletId;
     type WeightInfo = ();
+    type Currency = Balances;
+    type PoolCreationDeposit = ConstU128<100>;
+    type PermissionlessPoolTokens = IsInVec<PermissionlessPoolTokens>;
+    type VoteEpochLength = VoteEpochLength;
+    type MaxVotablePools = ConstU32<3>;
+    type VoteToken = ConstU128<KSM>;
+    type LiquidityProvider = MockLiquidityProvider;
 }
 
 #[derive(Default)]
@@ -338,10 +421,12 @@ impl ExtBuilder {
             assets: vec![
                 // id, owner, is_sufficient, min_balance
                 (KSM, ALICE, true, 1),
+                (KMA_KSM_LP, ALICE, true, 1),
             ],
             metadata: vec![
                 // id, name, symbol, decimals
                 (KSM, "KSM".into(), "Kusama".into(), 12),
+                (KMA_KSM_LP, "KMA-KSM LP".into(), "KMA-KSM LP".into(), 12),
             ],
             accounts: initial_asset_accounts,
         };
@@ -0,0 +1,230 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::pallet_prelude::*;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, One, Saturating, Zero},
+    RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+
+use crate::*;
+
+/// The number of shares `emission_per_share` of a [`PoolOverview`] is measured for.
+pub const EMISSION_PER_SHARE_UNIT: u128 = 1_000_000_000_000_000_000;
+
+/// The position of an account in a farming pool.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct UserPosition<CurrencyId, BlockNumber, Balance> {
+    pub pid: PoolId,
+    /// The shares of the account in the pool
+    pub share: Balance,
+    /// The farming rewards the account can claim
+    pub pending_rewards: Vec<(CurrencyId, Balance)>,
+    /// The gauge rewards the account can claim
+    pub pending_gauge_rewards: Vec<(CurrencyId, Balance)>,
+    /// The amount the account locked in the gauge of the pool and the block it unlocks at
+    pub gauge_lock: Option<(Balance, BlockNumber)>,
+    /// The block the account can claim from after `claim_limit_time`
+    pub claimable_at: BlockNumber,
+    /// The withdrawn shares and the blocks they are returned at
+    pub withdraw_list: Vec<(BlockNumber, Balance)>,
+    /// Whether `withdraw_limit_count` allows the account to withdraw again
+    pub can_withdraw: bool,
+}
+
+/// The emissions and limits of a farming pool.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PoolOverview<CurrencyId, BlockNumber, Balance> {
+    pub pid: PoolId,
+    pub state: PoolState,
+    /// Total shares amount
+    pub total_shares: Balance,
+    /// The rewards the next block emits
+    pub emission_per_block: Vec<(CurrencyId, Balance)>,
+    /// The rewards the next block emits to [`EMISSION_PER_SHARE_UNIT`] shares
+    pub emission_per_share: Vec<(CurrencyId, Balance)>,
+    /// The charged rewards that are not emitted yet
+    pub remaining_rewards: Vec<(CurrencyId, Balance)>,
+    /// The limit block number to withdraw
+    pub withdraw_limit_time: BlockNumber,
+    /// Gauge pool id
+    pub gauge: Option<PoolId>,
+    /// The lock-up of the rewards claimed from the pool
+    pub reward_vesting: Option<RewardVesting<BlockNumber>>,
+}
+
+/// The vesting rewards an account claimed from a farming pool in one currency.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VestingPosition<CurrencyId, BlockNumber, Balance> {
+    pub pid: PoolId,
+    pub currency_id: CurrencyId,
+    /// The released rewards `claim_vested` pays out
+    pub claimable: Balance,
+    /// The rewards that are not released yet
+    pub locked: Balance,
+    /// The block all rewards are released at
+    pub unlocked_at: BlockNumber,
+    /// The part of `locked` an early exit forfeits
+    pub exit_penalty: Balance,
+}
+
+#[allow(type_alias_bounds)]
+type UserPositionOf<T: Config> = UserPosition<CurrencyIdOf<T>, BlockNumberFor<T>, BalanceOf<T>>;
+
+#[allow(type_alias_bounds)]
+type PoolOverviewOf<T: Config> = PoolOverview<CurrencyIdOf<T>, BlockNumberFor<T>, BalanceOf<T>>;
+
+#[allow(type_alias_bounds)]
+type VestingPositionOf<T: Config> =
+    VestingPosition<CurrencyIdOf<T>, BlockNumberFor<T>, BalanceOf<T>>;
+
+impl<T: Config> Pallet<T>
+where
+    BlockNumberFor<T>: AtLeast32BitUnsigned + Copy,
+    BalanceOf<T>: AtLeast32BitUnsigned + Copy,
+{
+    /// The positions of `who` in every farming pool it has shares or withdrawn shares in.
+    pub fn user_positions(who: &AccountIdOf<T>) -> Vec<UserPositionOf<T>> {
+        PoolInfos::<T>::iter()
+            .filter_map(|(pool_id, pool_info)| {
+                let share_info = SharesAndWithdrawnRewards::<T>::get(pool_id, who)?;
+                let gauge_lock = pool_info
+                    .gauge
+                    .and_then(|gid| GaugeInfos::<T>::get(gid, who))
+                    .map(|gauge_info| (gauge_info.gauge_amount, gauge_info.gauge_stop_block));
+                let pending_gauge_rewards = match gauge_lock {
+                    Some(_) => Self::get_gauge_rewards(who, pool_id).unwrap_or_default(),
+                    None => Vec::new(),
+                };
+                Some(UserPosition {
+                    pid: pool_id,
+                    share: share_info.share,
+                    pending_rewards: Self::get_farming_rewards(who, pool_id).unwrap_or_default(),
+                    pending_gauge_rewards,
+                    gauge_lock,
+                    claimable_at: share_info
+                        .claim_last_block
+                        .saturating_add(pool_info.claim_limit_time),
+                    can_withdraw: share_info.withdraw_list.len()
+                        < pool_info.withdraw_limit_count.into(),
+                    withdraw_list: share_info.withdraw_list,
+                })
+            })
+            .collect()
+    }
+
+    /// The overview of the pool with its rewards accrued up to the current block.
+    pub fn pool_overview(pool_id: PoolId) -> Option<PoolOverviewOf<T>> {
+        let mut pool_info = Self::pool_infos(pool_id)?;
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        Self::accrue_pool_rewards(&mut pool_info, n);
+
+        // A pool that never started would start its emission curve now
+        let emission_start = match pool_info.block_startup {
+            Some(_) => pool_info.emission_start,
+            None => n,
+        };
+        let emitting = matches!(
+            pool_info.state,
+            PoolState::UnCharged | PoolState::Charged | PoolState::Ongoing
+        );
+        let emission_per_block: Vec<(CurrencyIdOf<T>, BalanceOf<T>)> = pool_info
+            .basic_rewards
+            .iter()
+            .map(|(reward_currency, basic_reward)| {
+                let remaining = pool_info
+                    .remaining_rewards
+                    .get(reward_currency)
+                    .copied()
+                    .unwrap_or_default();
+                let emission = if emitting {
+                    Self::emitted_rewards(
+                        &pool_info.emission_curve,
+                        *basic_reward,
+                        emission_start,
+                        n,
+                        n.saturating_add(One::one()),
+                    )
+                    .min(remaining)
+                } else {
+                    Zero::zero()
+                };
+                (*reward_currency, emission)
+            })
+            .collect();
+        let emission_per_share = emission_per_block
+            .iter()
+            .map(|(reward_currency, emission)| {
+                let emission_per_share = Self::get_reward_inflation(
+                    EMISSION_PER_SHARE_UNIT.saturated_into(),
+                    emission,
+                    pool_info.total_shares,
+                );
+                (*reward_currency, emission_per_share)
+            })
+            .collect();
+
+        Some(PoolOverview {
+            pid: pool_id,
+            state: pool_info.state,
+            total_shares: pool_info.total_shares,
+            emission_per_block,
+            emission_per_share,
+            remaining_rewards: pool_info.remaining_rewards.into_iter().collect(),
+            withdraw_limit_time: pool_info.withdraw_limit_time,
+            gauge: pool_info.gauge,
+            reward_vesting: Self::reward_vestings(pool_id),
+        })
+    }
+
+    /// The vesting rewards of `who` from every farming pool, released up to the current block.
+    pub fn user_vestings(who: &AccountIdOf<T>) -> Vec<VestingPositionOf<T>> {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        VestingLedgers::<T>::iter_prefix(who)
+            .flat_map(|(pool_id, ledgers)| {
+                let vesting = Self::reward_vestings(pool_id).is_some();
+                ledgers.into_iter().map(move |(currency_id, mut ledger)| {
+                    ledger.release(n);
+                    VestingPosition {
+                        pid: pool_id,
+                        currency_id,
+                        claimable: ledger.unlocked,
+                        locked: ledger.locked,
+                        unlocked_at: ledger.end(),
+                        exit_penalty: if vesting {
+                            ledger.penalty * ledger.locked
+                        } else {
+                            Zero::zero()
+                        },
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// The overviews of every farming pool.
+    pub fn pool_overviews() -> Vec<PoolOverviewOf<T>> {
+        PoolInfos::<T>::iter_keys()
+            .filter_map(Self::pool_overview)
+            .collect()
+    }
+}
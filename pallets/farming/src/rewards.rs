@@ -17,9 +17,11 @@
 use crate::*;
 use codec::HasCompact;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
-    traits::{Saturating, Zero},
-    RuntimeDebug,
+    traits::{One, Saturating, Zero},
+    RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
@@ -57,6 +59,12 @@ pub struct PoolInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf, Block
     /// Total shares amount
     pub total_shares: BalanceOf,
     pub basic_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+    /// How `basic_rewards` change over time
+    pub emission_curve: EmissionCurve<BlockNumberFor>,
+    /// The block `emission_curve` is measured from
+    pub emission_start: BlockNumberFor,
+    /// The charged rewards that are not emitted yet
+    pub remaining_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
     /// Reward infos <reward_currency, (total_reward, total_withdrawn_reward)>
     pub rewards: BTreeMap<CurrencyIdOf, (BalanceOf, BalanceOf)>,
     pub state: PoolState,
@@ -65,6 +73,8 @@ pub struct PoolInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, AccountIdOf, Block
     /// Gauge pool id
     pub gauge: Option<PoolId>,
     pub block_startup: Option<BlockNumberFor>,
+    /// The block up to which `basic_rewards` are added to `rewards`
+    pub last_accrual_block: BlockNumberFor,
     /// The minimum share to starting farming
     pub min_deposit_to_start: BalanceOf,
     /// The minimum block number to starting farming
@@ -82,6 +92,7 @@ impl<BalanceOf, CurrencyIdOf, AccountIdOf, BlockNumberFor>
 where
     BalanceOf: Default + HasCompact,
     CurrencyIdOf: Ord,
+    BlockNumberFor: Default,
 {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -90,6 +101,7 @@ where
         tokens_proportion: BTreeMap<CurrencyIdOf, Perbill>,
         basic_token: (CurrencyIdOf, Perbill),
         basic_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+        emission_curve: EmissionCurve<BlockNumberFor>,
         gauge: Option<PoolId>,
         min_deposit_to_start: BalanceOf,
         after_block_to_start: BlockNumberFor,
@@ -102,12 +114,16 @@ where
             basic_token,
             total_shares: Default::default(),
             basic_rewards,
+            emission_curve,
+            emission_start: Default::default(),
+            remaining_rewards: BTreeMap::new(),
             rewards: BTreeMap::new(),
             state: PoolState::UnCharged,
             keeper,
             reward_issuer,
             gauge,
             block_startup: None,
+            last_accrual_block: Default::default(),
             min_deposit_to_start,
             after_block_to_start,
             withdraw_limit_time,
@@ -117,7 +133,20 @@ where
     }
 }
 
+/// The emission schedule of the `basic_rewards` of a pool.
+#[derive(Encode, Decode, Copy, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum EmissionCurve<BlockNumberFor> {
+    /// Every block emits `basic_rewards`
+    #[default]
+    Constant,
+    /// The emission halves every `period` blocks
+    StepHalving { period: BlockNumberFor },
+    /// The emission tapers linearly down to zero at `end_block`
+    LinearTaper { end_block: BlockNumberFor },
+}
+
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum PoolState {
     UnCharged,
     Charged,
@@ -163,6 +192,141 @@ impl PoolState {
 }
 
 impl<T: Config> Pallet<T> {
+    /// Adds the rewards emitted by every block after `last_accrual_block` up to `n` to the pool
+    /// rewards.
+    ///
+    /// A `Charged` pool starts at the first block it met both start conditions, its shares
+    /// can only have changed at `last_accrual_block` since every share change accrues first.
+    /// Its `emission_curve` is measured from its first start, charging it again does not restart
+    /// the curve. An `Ongoing` pool is closed once all of its charged `basic_rewards` are emitted
+    /// or its `LinearTaper` curve has ended.
+    pub fn accrue_pool_rewards(pool_info: &mut PoolInfoOf<T>, n: BlockNumberFor<T>) {
+        if pool_info.state == PoolState::Charged
+            && pool_info.total_shares >= pool_info.min_deposit_to_start
+        {
+            let block_startup = pool_info
+                .after_block_to_start
+                .max(pool_info.last_accrual_block.saturating_add(One::one()));
+            if block_startup <= n {
+                pool_info.state = PoolState::Ongoing;
+                if pool_info.block_startup.is_none() {
+                    pool_info.block_startup = Some(block_startup);
+                    pool_info.emission_start = block_startup;
+                }
+                pool_info.last_accrual_block = block_startup;
+            }
+        }
+
+        if pool_info.state == PoolState::Ongoing && pool_info.last_accrual_block < n {
+            let emission_curve = pool_info.emission_curve;
+            let emission_start = pool_info.emission_start;
+            let last_accrual_block = pool_info.last_accrual_block;
+            pool_info
+                .basic_rewards
+                .iter()
+                .for_each(|(reward_currency_id, reward_amount)| {
+                    let remaining = pool_info
+                        .remaining_rewards
+                        .entry(*reward_currency_id)
+                        .or_default();
+                    let accrued = Self::emitted_rewards(
+                        &emission_curve,
+                        *reward_amount,
+                        emission_start,
+                        last_accrual_block,
+                        n,
+                    )
+                    .min(*remaining);
+                    *remaining = remaining.saturating_sub(accrued);
+                    pool_info
+                        .rewards
+                        .entry(*reward_currency_id)
+                        .and_modify(|(total_reward, _)| {
+                            *total_reward = total_reward.saturating_add(accrued);
+                        })
+                        .or_insert((accrued, Zero::zero()));
+                });
+
+            let mut emitting = pool_info
+                .basic_rewards
+                .iter()
+                .filter(|(_, reward_amount)| !reward_amount.is_zero())
+                .peekable();
+            let exhausted = emitting.peek().is_some()
+                && emitting.all(|(reward_currency_id, _)| {
+                    pool_info
+                        .remaining_rewards
+                        .get(reward_currency_id)
+                        .map_or(true, Zero::is_zero)
+                });
+            let ended = match emission_curve {
+                EmissionCurve::LinearTaper { end_block } => n >= end_block,
+                _ => false,
+            };
+            if exhausted || ended {
+                pool_info.state = PoolState::Dead;
+            }
+        }
+        pool_info.last_accrual_block = pool_info.last_accrual_block.max(n);
+    }
+
+    /// The rewards emitted at the `basic_reward` rate by the blocks after `from` up to `to`,
+    /// for an `emission_curve` measured from `emission_start`.
+    pub fn emitted_rewards(
+        emission_curve: &EmissionCurve<BlockNumberFor<T>>,
+        basic_reward: BalanceOf<T>,
+        emission_start: BlockNumberFor<T>,
+        from: BlockNumberFor<T>,
+        to: BlockNumberFor<T>,
+    ) -> BalanceOf<T> {
+        let basic_reward = basic_reward.saturated_into::<u128>();
+        let start = emission_start.saturated_into::<u128>();
+        let from = from.saturated_into::<u128>();
+        let to = to.saturated_into::<u128>();
+        if to <= from {
+            return Zero::zero();
+        }
+
+        let emitted = match *emission_curve {
+            EmissionCurve::Constant => basic_reward.saturating_mul(to - from),
+            EmissionCurve::StepHalving { period } => {
+                let period = period.saturated_into::<u128>().max(1);
+                let mut emitted = 0u128;
+                let mut block = from;
+                // The blocks in `(start + k * period, start + (k + 1) * period]` emit
+                // `basic_reward >> k`
+                while block < to {
+                    let halvings = block.saturating_sub(start) / period;
+                    if halvings >= u128::BITS.into() {
+                        break;
+                    }
+                    let epoch_end = start
+                        .saturating_add(halvings.saturating_add(1).saturating_mul(period))
+                        .min(to);
+                    emitted = emitted.saturating_add(
+                        (basic_reward >> halvings).saturating_mul(epoch_end - block),
+                    );
+                    block = epoch_end;
+                }
+                emitted
+            }
+            EmissionCurve::LinearTaper { end_block } => {
+                // Block `b` emits `basic_reward * (end - b + 1) / (end - start)` until `end`
+                let end = end_block.saturated_into::<u128>();
+                let length = end.saturating_sub(start);
+                let triangle = |k: u128| k.saturating_mul(k.saturating_add(1)) / 2;
+                let steps = triangle(end.saturating_sub(from))
+                    .saturating_sub(triangle(end.saturating_sub(to)));
+                U256::from(basic_reward)
+                    .saturating_mul(U256::from(steps))
+                    .checked_div(U256::from(length))
+                    .map(|x| u128::try_from(x).unwrap_or(u128::MAX))
+                    .unwrap_or_default()
+            }
+        };
+        emitted.saturated_into()
+    }
+
     pub fn add_share(
         who: &T::AccountId,
         pool_id: PoolId,
@@ -173,12 +337,14 @@ impl<T: Config> Pallet<T> {
             return;
         }
 
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        Self::accrue_pool_rewards(pool_info, n);
+
         // update pool total share
         let initial_total_shares = pool_info.total_shares;
         pool_info.total_shares = pool_info.total_shares.saturating_add(add_amount);
 
         // update user share
-        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
         let mut share_info = SharesAndWithdrawnRewards::<T>::get(pool_id, who)
             .unwrap_or_else(|| ShareInfo::new(who.clone(), n));
         share_info.share = share_info.share.saturating_add(add_amount);
@@ -217,6 +383,54 @@ impl<T: Config> Pallet<T> {
         PoolInfos::<T>::insert(pool_id, pool_info);
     }
 
+    /// Moves `amount` of the shares of `from` in the pool to `to` together with the part of the
+    /// withdrawn rewards of `from` they carry, so the pending rewards of neither account change.
+    ///
+    /// The rewards of `from` are claimed first, its `withdraw_list` stays with it.
+    pub fn transfer_share(
+        from: &T::AccountId,
+        to: &T::AccountId,
+        pool_id: PoolId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::claim_rewards(from, pool_id)?;
+
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let mut from_share_info = SharesAndWithdrawnRewards::<T>::get(pool_id, from)
+            .ok_or(Error::<T>::ShareInfoNotExists)?;
+        ensure!(
+            amount <= from_share_info.share,
+            Error::<T>::InsufficientShares
+        );
+        let mut to_share_info = SharesAndWithdrawnRewards::<T>::get(pool_id, to)
+            .unwrap_or_else(|| ShareInfo::new(to.clone(), n));
+
+        let from_share = from_share_info.share;
+        from_share_info.withdrawn_rewards.iter_mut().for_each(
+            |(reward_currency, withdrawn_reward)| {
+                let moved_reward = Self::get_reward_inflation(amount, withdrawn_reward, from_share);
+                *withdrawn_reward = withdrawn_reward.saturating_sub(moved_reward);
+                to_share_info
+                    .withdrawn_rewards
+                    .entry(*reward_currency)
+                    .and_modify(|to_withdrawn_reward| {
+                        *to_withdrawn_reward = to_withdrawn_reward.saturating_add(moved_reward);
+                    })
+                    .or_insert(moved_reward);
+            },
+        );
+        from_share_info.share = from_share.saturating_sub(amount);
+        to_share_info.share = to_share_info.share.saturating_add(amount);
+
+        if from_share_info.share.is_zero() && from_share_info.withdraw_list.is_empty() {
+            SharesAndWithdrawnRewards::<T>::remove(pool_id, from);
+        } else {
+            SharesAndWithdrawnRewards::<T>::insert(pool_id, from, from_share_info);
+        }
+        SharesAndWithdrawnRewards::<T>::insert(pool_id, to, to_share_info);
+        Ok(())
+    }
+
     pub fn remove_share(
         who: &T::AccountId,
         pool: PoolId,
@@ -249,6 +463,7 @@ impl<T: Config> Pallet<T> {
                     let pool_info = maybe_pool_info
                         .as_mut()
                         .ok_or(Error::<T>::PoolDoesNotExist)?;
+                    Self::accrue_pool_rewards(pool_info, n);
                     pool_info.total_shares = pool_info.total_shares.saturating_sub(remove_amount);
 
                     // update withdrawn rewards for each reward currency
@@ -294,6 +509,7 @@ impl<T: Config> Pallet<T> {
     }
 
     pub fn claim_rewards(who: &T::AccountId, pool: PoolId) -> DispatchResult {
+        let reward_vesting = Self::reward_vestings(pool);
         SharesAndWithdrawnRewards::<T>::mutate_exists(
             pool,
             who,
@@ -308,6 +524,7 @@ impl<T: Config> Pallet<T> {
                         let pool_info = maybe_pool_info
                             .as_mut()
                             .ok_or(Error::<T>::PoolDoesNotExist)?;
+                        Self::accrue_pool_rewards(pool_info, n);
 
                         let total_shares = pool_info.total_shares;
                         pool_info.rewards.iter_mut().try_for_each(
@@ -326,7 +543,12 @@ impl<T: Config> Pallet<T> {
 									withdrawn_reward.saturating_add(reward_to_withdraw),
 								);
 
-                                Self::farming_token_transfer(reward_currency, reward_to_withdraw, who, &pool_info.reward_issuer)
+                                match reward_vesting {
+                                    Some(ref reward_vesting) => Self::lock_reward(
+                                        who, pool, reward_vesting, reward_currency, reward_to_withdraw, &pool_info.reward_issuer),
+                                    None => Self::farming_token_transfer(
+                                        reward_currency, reward_to_withdraw, who, &pool_info.reward_issuer),
+                                }
 							},
 						)?;
                         Ok(())
@@ -22,6 +22,13 @@ use sp_runtime::traits::AccountIdConversion;
 
 use crate::{mock::*, *};
 
+/// Pool info with its rewards accrued up to the current block
+fn accrued_pool_info(pool_id: PoolId) -> PoolInfoOf<Runtime> {
+    let mut pool_info = Farming::pool_infos(pool_id).unwrap();
+    Farming::accrue_pool_rewards(&mut pool_info, System::block_number());
+    pool_info
+}
+
 fn init_gauge_900() -> (PoolId, BalanceOf<Runtime>) {
     let tokens_proportion = vec![(KSM, Perbill::from_percent(100))];
     let tokens = 1000;
@@ -32,6 +39,7 @@ fn init_gauge_900() -> (PoolId, BalanceOf<Runtime>) {
         RuntimeOrigin::signed(ALICE),
         tokens_proportion,
         basic_rewards,
+        EmissionCurve::Constant,
         Some((KSM, 1000, gauge_basic_rewards)),
         0, // min_deposit_to_start
         0, // after_block_to_start
@@ -66,6 +74,7 @@ fn init_gauge_1000() -> (PoolId, BalanceOf<Runtime>) {
         RuntimeOrigin::signed(ALICE),
         tokens_proportion,
         basic_rewards,
+        EmissionCurve::Constant,
         Some(gauge_basic_rewards),
         0,  // min_deposit_to_start
         0,  // after_block_to_start
@@ -103,6 +112,7 @@ fn init_no_gauge() -> (PoolId, BalanceOf<Runtime>) {
         RuntimeOrigin::signed(ALICE),
         tokens_proportion,
         basic_rewards,
+        EmissionCurve::Constant,
         None,
         0,  // min_deposit_to_start
         0,  // after_block_to_start
@@ -146,6 +156,7 @@ fn precondition_check_should_work() {
                     RuntimeOrigin::signed(ALICE),
                     vec![],
                     vec![(KSM, 1000)],
+                    EmissionCurve::Constant,
                     None,
                     0,  // min_deposit_to_start
                     0,  // after_block_to_start
@@ -219,6 +230,7 @@ fn precondition_check_should_work() {
                     None,
                     None,
                     None,
+                    None,
                     None
                 ),
                 Error::<Runtime>::PoolDoesNotExist
@@ -267,9 +279,9 @@ fn precondition_check_should_work() {
                 Error::<Runtime>::InvalidPoolState
             );
 
-            // Pool state is Ongoing
-            Farming::on_initialize(System::block_number() + 10);
-            let pool1: PoolInfoOf<Runtime> = Farming::pool_infos(pool_id0).unwrap();
+            // Pool state is Ongoing from the next block
+            System::set_block_number(System::block_number() + 1);
+            let pool1: PoolInfoOf<Runtime> = accrued_pool_info(pool_id0);
             assert_eq!(pool1.state, PoolState::Ongoing);
 
             assert_noop!(
@@ -327,6 +339,7 @@ fn no_gauge_farming_pool_should_work() {
                 RuntimeOrigin::signed(ALICE),
                 tokens_proportion.clone(),
                 basic_rewards.clone(),
+                EmissionCurve::Constant,
                 None,
                 2, // min_deposit_to_start
                 1, // after_block_to_start
@@ -339,6 +352,7 @@ fn no_gauge_farming_pool_should_work() {
                 RuntimeOrigin::signed(ALICE),
                 tokens_proportion,
                 basic_rewards,
+                EmissionCurve::Constant,
                 None,
                 2, // min_deposit_to_start
                 1, // after_block_to_start
@@ -424,12 +438,12 @@ fn no_gauge_farming_pool_should_work() {
                 Error::<Runtime>::InvalidPoolState
             );
 
-            // OnInitialize hook change the pool state and also pool rewards
-            Farming::on_initialize(System::block_number() + 3);
-            Farming::on_initialize(0);
-            pool1 = Farming::pool_infos(pool_id).unwrap();
+            // The pool starts on the next block and accrues rewards from then on
+            System::set_block_number(System::block_number() + 2);
+            pool1 = accrued_pool_info(pool_id);
             assert_eq!(pool1.total_shares, deposit_amount);
             assert_eq!(pool1.state, PoolState::Ongoing);
+            assert_eq!(pool1.block_startup, Some(4));
             assert_eq!(pool1.claim_limit_time, 6);
             assert_eq!(pool1.rewards.get(&KSM).unwrap(), &(reward_amount, 0));
 
@@ -452,108 +466,72 @@ fn no_gauge_farming_pool_should_work() {
                 Error::<Runtime>::CanNotClaim
             );
 
-            // Claim success, user get reward.
-            System::set_block_number(System::block_number() + 6);
+            // Claim success, user get the rewards of the blocks 5 to 9.
+            System::set_block_number(System::block_number() + 4);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
             assert_eq!(
                 Assets::balance(KSM, &ALICE),
-                alice_init_balance - deposit_amount + reward_amount
+                alice_init_balance - deposit_amount + reward_amount * 5
             );
             assert_eq!(
                 Assets::balance(KSM, &pool1.reward_issuer),
-                total_rewards - reward_amount
+                total_rewards - reward_amount * 5
             );
 
             // Claim operation update pool info's rewards and also share info's withdrawn_rewards
             pool1 = Farming::pool_infos(pool_id).unwrap();
             assert_eq!(
                 pool1.rewards.get(&KSM).unwrap(),
-                &(reward_amount, reward_amount)
+                &(reward_amount * 5, reward_amount * 5)
             );
+            assert_eq!(pool1.last_accrual_block, 9);
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
-            assert_eq!(reward.withdrawn_rewards.get(&KSM).unwrap(), &reward_amount);
+            assert_eq!(
+                reward.withdrawn_rewards.get(&KSM).unwrap(),
+                &(reward_amount * 5)
+            );
             // The withdraw list of user share info is still empty.
             assert!(reward.withdraw_list.is_empty());
 
-            // Claim without new block.
-            for i in 0..5 {
-                System::set_block_number(System::block_number() + 100);
-                assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-
-                reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
-                assert_eq!(reward.withdrawn_rewards.get(&KSM).unwrap(), &reward_amount);
-                assert_eq!(reward.claim_last_block, 109 + i * 100);
-                assert_eq!(reward.share, deposit_amount);
-                assert!(reward.withdraw_list.is_empty());
-
-                pool1 = Farming::pool_infos(pool_id).unwrap();
-                assert_eq!(
-                    pool1.rewards.get(&KSM).unwrap(),
-                    &(reward_amount, reward_amount)
-                );
-                assert_eq!(pool1.total_shares, deposit_amount);
-
-                assert_eq!(
-                    Assets::balance(KSM, &ALICE),
-                    alice_init_balance - deposit_amount + reward_amount
-                );
-                assert_eq!(
-                    Assets::balance(KSM, &pool1.reward_issuer),
-                    total_rewards - reward_amount
-                );
-                assert_eq!(Assets::balance(KSM, &pool1.keeper), deposit_amount);
-            }
-            assert_eq!(
-                Assets::balance(KSM, &pool1.reward_issuer),
-                total_rewards - reward_amount
-            );
-            assert_eq!(Assets::balance(KSM, &pool1.keeper), deposit_amount);
             // Claim with new block
             for i in 1..5 {
                 System::set_block_number(System::block_number() + 6);
-                Farming::on_initialize(System::block_number() + 3);
                 assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
 
+                let claimed = reward_amount * (5 + 6 * i);
                 reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
-                assert_eq!(
-                    reward.withdrawn_rewards.get(&KSM).unwrap(),
-                    &(reward_amount * (i + 1))
-                );
-                assert_eq!(reward.claim_last_block as u128, 509 + i * 6);
+                assert_eq!(reward.withdrawn_rewards.get(&KSM).unwrap(), &claimed);
+                assert_eq!(reward.claim_last_block as u128, 9 + i * 6);
                 assert_eq!(reward.share, deposit_amount);
                 assert!(reward.withdraw_list.is_empty());
 
                 pool1 = Farming::pool_infos(pool_id).unwrap();
-                assert_eq!(
-                    pool1.rewards.get(&KSM).unwrap(),
-                    &(reward_amount * (i + 1), reward_amount * (i + 1))
-                );
+                assert_eq!(pool1.rewards.get(&KSM).unwrap(), &(claimed, claimed));
                 assert_eq!(pool1.total_shares, deposit_amount);
 
                 assert_eq!(
                     Assets::balance(KSM, &ALICE),
-                    alice_init_balance - deposit_amount + reward_amount * (i + 1)
+                    alice_init_balance - deposit_amount + claimed
                 );
                 assert_eq!(
                     Assets::balance(KSM, &pool1.reward_issuer),
-                    total_rewards - reward_amount * (i + 1)
+                    total_rewards - claimed
                 );
                 // Because withdraw_list of user share is empty, keeper not return token to user.
                 assert_eq!(Assets::balance(KSM, &pool1.keeper), deposit_amount);
             }
+            // Rewards of the blocks 5 to 33
+            let claimed = reward_amount * 29;
             assert_eq!(
                 Assets::balance(KSM, &ALICE),
-                alice_init_balance - deposit_amount + reward_amount * 5
+                alice_init_balance - deposit_amount + claimed
             );
             assert_eq!(
                 Assets::balance(KSM, &pool1.reward_issuer),
-                total_rewards - reward_amount * 5
+                total_rewards - claimed
             );
             assert_eq!(Assets::balance(KSM, &pool1.keeper), deposit_amount);
-            assert_eq!(
-                pool1.rewards.get(&KSM).unwrap(),
-                &(reward_amount * 5, reward_amount * 5)
-            );
+            assert_eq!(pool1.rewards.get(&KSM).unwrap(), &(claimed, claimed));
 
             // Withdraw failed because of share info not exist.
             assert_err!(
@@ -567,12 +545,9 @@ fn no_gauge_farming_pool_should_work() {
             assert!(reward.withdraw_list.is_empty());
 
             let share_reward = reward.withdrawn_rewards.get(&KSM).unwrap();
-            assert_eq!(share_reward, &(reward_amount * 5));
+            assert_eq!(share_reward, &claimed);
             let (total_reward, total_withdrawn_reward) = pool1.rewards.get(&KSM).unwrap();
-            assert_eq!(
-                pool1.rewards.get(&KSM).unwrap(),
-                &(reward_amount * 5, reward_amount * 5)
-            );
+            assert_eq!(pool1.rewards.get(&KSM).unwrap(), &(claimed, claimed));
 
             let reward_amount1 = Farming::get_reward_amount(
                 &reward,
@@ -582,10 +557,10 @@ fn no_gauge_farming_pool_should_work() {
                 &KSM,
             )
             .unwrap();
-            assert_eq!(reward_amount1, (reward_amount * 5, 0));
+            assert_eq!(reward_amount1, (claimed, 0));
             let reward_inflation =
                 Farming::get_reward_inflation(reward.share, total_reward, pool1.total_shares);
-            assert_eq!(reward_inflation, reward_amount * 5);
+            assert_eq!(reward_inflation, claimed);
 
             let reward_inflation = Farming::get_reward_inflation(800, share_reward, reward.share);
             assert_eq!(reward_inflation, share_reward * 8 / 10);
@@ -595,11 +570,7 @@ fn no_gauge_farming_pool_should_work() {
             assert_eq!(reward_inflation, share_reward / 10);
 
             // Withdraw partial tokens
-            assert_eq!(System::block_number(), 533);
-            assert_eq!(
-                Assets::balance(KSM, &ALICE),
-                alice_init_balance - deposit_amount + reward_amount * 5
-            );
+            assert_eq!(System::block_number(), 33);
             assert_ok!(Farming::withdraw(
                 RuntimeOrigin::signed(ALICE),
                 pool_id,
@@ -610,11 +581,11 @@ fn no_gauge_farming_pool_should_work() {
             // So both user and reward issuer account balance not change.
             assert_eq!(
                 Assets::balance(KSM, &ALICE),
-                alice_init_balance - deposit_amount + reward_amount * 5
+                alice_init_balance - deposit_amount + claimed
             );
             assert_eq!(
                 Assets::balance(KSM, &pool1.reward_issuer),
-                total_rewards - reward_amount * 5
+                total_rewards - claimed
             );
             assert_eq!(Assets::balance(KSM, &pool1.keeper), deposit_amount);
 
@@ -625,16 +596,20 @@ fn no_gauge_farming_pool_should_work() {
             pool1 = Farming::pool_infos(pool_id).unwrap();
 
             assert_eq!(pool1.total_shares, 200);
-            assert_eq!(reward.withdraw_list, vec![(533 + withdraw_limit_time, 800)]);
+            assert_eq!(reward.withdraw_list, vec![(33 + withdraw_limit_time, 800)]);
             assert_eq!(reward.share, 200);
-            assert_eq!(reward.withdrawn_rewards.get(&KSM).unwrap(), &reward_amount);
+            assert_eq!(reward.withdrawn_rewards.get(&KSM).unwrap(), &(claimed / 5));
             assert_eq!(
                 pool1.rewards.get(&KSM).unwrap(),
-                &(reward_amount, reward_amount)
+                &(claimed / 5, claimed / 5)
             );
 
+            // The remaining share gets all rewards of the new blocks
             System::set_block_number(System::block_number() + 6);
-            Farming::on_initialize(System::block_number() + 3);
+            assert_eq!(
+                Farming::get_farming_rewards(&ALICE, pool_id).unwrap(),
+                vec![(KSM, reward_amount * 6)]
+            );
 
             // Withdraw rest all of share
             assert_ok!(Farming::withdraw(
@@ -642,12 +617,16 @@ fn no_gauge_farming_pool_should_work() {
                 pool_id,
                 Some(300)
             ));
+            assert_eq!(
+                Assets::balance(KSM, &ALICE),
+                alice_init_balance - deposit_amount + claimed + reward_amount * 6
+            );
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
             pool1 = Farming::pool_infos(pool_id).unwrap();
             assert_eq!(pool1.total_shares, 0);
             assert!(pool1.rewards.is_empty());
             assert_eq!(reward.share, 0);
-            assert_eq!(reward.withdraw_list, vec![(540, 800), (546, 200)]);
+            assert_eq!(reward.withdraw_list, vec![(40, 800), (46, 200)]);
             assert_eq!(reward.withdrawn_rewards.get(&KSM).unwrap(), &0);
         })
 }
@@ -676,6 +655,7 @@ fn staking_minimum_kma(stake_token: CalamariAssetId, reward_token: CalamariAsset
                 RuntimeOrigin::signed(ALICE),
                 tokens_proportion,
                 basic_rewards,
+                EmissionCurve::Constant,
                 None,
                 1, // min_deposit_to_start
                 1, // after_block_to_start
@@ -706,7 +686,6 @@ fn staking_minimum_kma(stake_token: CalamariAssetId, reward_token: CalamariAsset
             assert_eq!(Balances::free_balance(&CHARLIE), 0);
             assert_eq!(Balances::free_balance(&pool1.keeper), charlie_init_kma);
 
-            Farming::on_initialize(System::block_number() + 10);
             System::set_block_number(System::block_number() + 10);
             assert_eq!(Balances::free_balance(&CHARLIE), 0);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(CHARLIE), pool_id));
@@ -755,6 +734,7 @@ fn staking_all_kma(stake_token: CalamariAssetId, reward_token: CalamariAssetId)
                 RuntimeOrigin::signed(ALICE),
                 tokens_proportion,
                 basic_rewards,
+                EmissionCurve::Constant,
                 None,
                 2, // min_deposit_to_start
                 1, // after_block_to_start
@@ -785,7 +765,6 @@ fn staking_all_kma(stake_token: CalamariAssetId, reward_token: CalamariAssetId)
             assert_eq!(Balances::free_balance(&ALICE), 0);
             assert_eq!(Balances::free_balance(&pool1.keeper), alice_init_kma);
 
-            Farming::on_initialize(System::block_number() + 10);
             System::set_block_number(System::block_number() + 10);
             assert_eq!(Balances::free_balance(&ALICE), 0);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
@@ -838,6 +817,7 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
                 RuntimeOrigin::signed(ALICE),
                 tokens_proportion,
                 basic_rewards,
+                EmissionCurve::Constant,
                 None,
                 2, // min_deposit_to_start
                 1, // after_block_to_start
@@ -911,10 +891,9 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             assert_eq!(pool1.state, PoolState::Charged);
             assert!(pool1.rewards.is_empty());
 
-            // OnInitialize hook change the pool state and also pool rewards
-            Farming::on_initialize(System::block_number() + 3);
-            Farming::on_initialize(0);
-            pool1 = Farming::pool_infos(pool_id).unwrap();
+            // The pool starts on the next block and accrues rewards from then on
+            System::set_block_number(System::block_number() + 2);
+            pool1 = accrued_pool_info(pool_id);
             assert_eq!(pool1.total_shares, deposit_amount);
             assert_eq!(pool1.state, PoolState::Ongoing);
             assert_eq!(pool1.claim_limit_time, 6);
@@ -930,28 +909,28 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             assert!(reward.withdraw_list.is_empty());
             assert_eq!(reward.claim_last_block, 3);
 
-            // Claim success, user get reward.
-            System::set_block_number(System::block_number() + 6);
+            // Claim success, user get the rewards of the blocks 5 to 9.
+            System::set_block_number(System::block_number() + 4);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
             if reward_token == KSM {
                 // Stake KMA, reward KSM
                 assert_eq!(
                     Assets::balance(reward_token, &ALICE),
-                    alice_init_ksm + reward_amount
+                    alice_init_ksm + reward_amount * 5
                 );
                 assert_eq!(
                     Assets::balance(reward_token, &pool1.reward_issuer),
-                    total_rewards - reward_amount
+                    total_rewards - reward_amount * 5
                 );
             } else {
                 // Stake KSM, reward KMA
                 assert_eq!(
                     Balances::free_balance(&ALICE),
-                    alice_init_kma + reward_amount
+                    alice_init_kma + reward_amount * 5
                 );
                 assert_eq!(
                     Balances::free_balance(&pool1.reward_issuer),
-                    total_rewards - reward_amount
+                    total_rewards - reward_amount * 5
                 );
             }
 
@@ -959,50 +938,15 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             pool1 = Farming::pool_infos(pool_id).unwrap();
             assert_eq!(
                 pool1.rewards.get(&reward_token).unwrap(),
-                &(reward_amount, reward_amount)
+                &(reward_amount * 5, reward_amount * 5)
             );
             reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
             assert_eq!(
                 reward.withdrawn_rewards.get(&reward_token).unwrap(),
-                &reward_amount
+                &(reward_amount * 5)
             );
             // The withdraw list of user share info is still empty.
             assert!(reward.withdraw_list.is_empty());
-
-            // Claim without new block.
-            for i in 0..5 {
-                System::set_block_number(System::block_number() + 100);
-                assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-
-                reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
-                assert_eq!(
-                    reward.withdrawn_rewards.get(&reward_token).unwrap(),
-                    &reward_amount
-                );
-                assert_eq!(reward.claim_last_block, 109 + i * 100);
-                assert_eq!(reward.share, deposit_amount);
-                assert!(reward.withdraw_list.is_empty());
-
-                pool1 = Farming::pool_infos(pool_id).unwrap();
-                assert_eq!(
-                    pool1.rewards.get(&reward_token).unwrap(),
-                    &(reward_amount, reward_amount)
-                );
-                assert_eq!(pool1.total_shares, deposit_amount);
-            }
-            if reward_token == KSM {
-                // Stake KMA, reward KSM
-                assert_eq!(
-                    Assets::balance(reward_token, &pool1.reward_issuer),
-                    total_rewards - reward_amount
-                );
-            } else {
-                // Stake KSM, reward KMA
-                assert_eq!(
-                    Balances::free_balance(&pool1.reward_issuer),
-                    total_rewards - reward_amount
-                );
-            }
             if stake_token == KSM {
                 assert_eq!(Assets::balance(stake_token, &pool1.keeper), deposit_amount);
             } else {
@@ -1012,44 +956,43 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             // Claim with new block
             for i in 1..5 {
                 System::set_block_number(System::block_number() + 6);
-                Farming::on_initialize(System::block_number() + 3);
                 assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
 
+                let claimed = reward_amount * (5 + 6 * i);
                 reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
                 assert_eq!(
                     reward.withdrawn_rewards.get(&reward_token).unwrap(),
-                    &(reward_amount * (i + 1))
+                    &claimed
                 );
-                assert_eq!(reward.claim_last_block as u128, 509 + i * 6);
+                assert_eq!(reward.claim_last_block as u128, 9 + i * 6);
                 assert_eq!(reward.share, deposit_amount);
                 assert!(reward.withdraw_list.is_empty());
 
                 pool1 = Farming::pool_infos(pool_id).unwrap();
                 assert_eq!(
                     pool1.rewards.get(&reward_token).unwrap(),
-                    &(reward_amount * (i + 1), reward_amount * (i + 1))
+                    &(claimed, claimed)
                 );
                 assert_eq!(pool1.total_shares, deposit_amount);
             }
+            // Rewards of the blocks 5 to 33
+            let claimed = reward_amount * 29;
             if reward_token == KSM {
-                // Stake KMA, reward another 4 times KSM
+                // Stake KMA, reward KSM
                 assert_eq!(
                     Assets::balance(reward_token, &ALICE),
-                    alice_init_ksm + reward_amount * 5
+                    alice_init_ksm + claimed
                 );
                 assert_eq!(
                     Assets::balance(reward_token, &pool1.reward_issuer),
-                    total_rewards - reward_amount * 5
+                    total_rewards - claimed
                 );
             } else {
-                // Stake KSM, reward another 4 times KMA
-                assert_eq!(
-                    Balances::free_balance(&ALICE),
-                    alice_init_kma + reward_amount * 5
-                );
+                // Stake KSM, reward KMA
+                assert_eq!(Balances::free_balance(&ALICE), alice_init_kma + claimed);
                 assert_eq!(
                     Balances::free_balance(&pool1.reward_issuer),
-                    total_rewards - reward_amount * 5
+                    total_rewards - claimed
                 );
             }
             if stake_token == KSM {
@@ -1059,7 +1002,7 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             }
             assert_eq!(
                 pool1.rewards.get(&reward_token).unwrap(),
-                &(reward_amount * 5, reward_amount * 5)
+                &(claimed, claimed)
             );
 
             // Withdraw failed because of share info not exist.
@@ -1074,11 +1017,11 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             assert!(reward.withdraw_list.is_empty());
 
             let share_reward = reward.withdrawn_rewards.get(&reward_token).unwrap();
-            assert_eq!(share_reward, &(reward_amount * 5));
+            assert_eq!(share_reward, &claimed);
             let (total_reward, total_withdrawn_reward) = pool1.rewards.get(&reward_token).unwrap();
             assert_eq!(
                 pool1.rewards.get(&reward_token).unwrap(),
-                &(reward_amount * 5, reward_amount * 5)
+                &(claimed, claimed)
             );
 
             let reward_amount1 = Farming::get_reward_amount(
@@ -1089,10 +1032,10 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
                 &reward_token,
             )
             .unwrap();
-            assert_eq!(reward_amount1, (reward_amount * 5, 0));
+            assert_eq!(reward_amount1, (claimed, 0));
             let reward_inflation =
                 Farming::get_reward_inflation(reward.share, total_reward, pool1.total_shares);
-            assert_eq!(reward_inflation, reward_amount * 5);
+            assert_eq!(reward_inflation, claimed);
 
             let reward_inflation = Farming::get_reward_inflation(800, share_reward, reward.share);
             assert_eq!(reward_inflation, share_reward * 8 / 10);
@@ -1102,7 +1045,7 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             assert_eq!(reward_inflation, share_reward / 10);
 
             // Withdraw partial tokens
-            assert_eq!(System::block_number(), 533);
+            assert_eq!(System::block_number(), 33);
             assert_ok!(Farming::withdraw(
                 RuntimeOrigin::signed(ALICE),
                 pool_id,
@@ -1114,21 +1057,18 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
                 // Stake KMA, reward KSM
                 assert_eq!(
                     Assets::balance(reward_token, &ALICE),
-                    alice_init_ksm + reward_amount * 5
+                    alice_init_ksm + claimed
                 );
                 assert_eq!(
                     Assets::balance(reward_token, &pool1.reward_issuer),
-                    total_rewards - reward_amount * 5
+                    total_rewards - claimed
                 );
             } else {
                 // Stake KSM, reward KMA
-                assert_eq!(
-                    Balances::free_balance(&ALICE),
-                    alice_init_kma + reward_amount * 5
-                );
+                assert_eq!(Balances::free_balance(&ALICE), alice_init_kma + claimed);
                 assert_eq!(
                     Balances::free_balance(&pool1.reward_issuer),
-                    total_rewards - reward_amount * 5
+                    total_rewards - claimed
                 );
             }
             if stake_token == KSM {
@@ -1144,21 +1084,20 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             pool1 = Farming::pool_infos(pool_id).unwrap();
 
             assert_eq!(pool1.total_shares, 200);
-            assert_eq!(reward.withdraw_list, vec![(533 + withdraw_limit_time, 800)]);
+            assert_eq!(reward.withdraw_list, vec![(33 + withdraw_limit_time, 800)]);
             assert_eq!(reward.share, 200);
             assert_eq!(
                 reward.withdrawn_rewards.get(&reward_token).unwrap(),
-                &reward_amount
+                &(claimed / 5)
             );
             assert_eq!(
                 pool1.rewards.get(&reward_token).unwrap(),
-                &(reward_amount, reward_amount)
+                &(claimed / 5, claimed / 5)
             );
 
             System::set_block_number(System::block_number() + 6);
-            Farming::on_initialize(System::block_number() + 3);
 
-            // Withdraw rest all of share
+            // Withdraw rest all of share, which claims the rewards of the blocks 34 to 39
             assert_ok!(Farming::withdraw(
                 RuntimeOrigin::signed(ALICE),
                 pool_id,
@@ -1169,27 +1108,27 @@ fn staking_case(stake_token: CalamariAssetId, reward_token: CalamariAssetId) {
             assert_eq!(pool1.total_shares, 0);
             assert!(pool1.rewards.is_empty());
             assert_eq!(reward.share, 0);
-            assert_eq!(reward.withdraw_list, vec![(540, 800), (546, 200)]);
+            assert_eq!(reward.withdraw_list, vec![(40, 800), (46, 200)]);
             assert_eq!(reward.withdrawn_rewards.get(&reward_token).unwrap(), &0);
             if reward_token == KSM {
                 // Stake KMA, reward KSM
                 assert_eq!(
                     Assets::balance(reward_token, &ALICE),
-                    alice_init_ksm + reward_amount * 6
+                    alice_init_ksm + reward_amount * 35
                 );
                 assert_eq!(
                     Assets::balance(reward_token, &pool1.reward_issuer),
-                    total_rewards - reward_amount * 6
+                    total_rewards - reward_amount * 35
                 );
             } else {
                 // Stake KSM, reward KMA
                 assert_eq!(
                     Balances::free_balance(&ALICE),
-                    alice_init_kma + reward_amount * 6
+                    alice_init_kma + reward_amount * 35
                 );
                 assert_eq!(
                     Balances::free_balance(&pool1.reward_issuer),
-                    total_rewards - reward_amount * 6
+                    total_rewards - reward_amount * 35
                 );
             }
             if stake_token == KSM {
@@ -1244,6 +1183,7 @@ fn gauge_farming_pool_should_work() {
                 RuntimeOrigin::signed(ALICE),
                 tokens_proportion.clone(),
                 basic_rewards.clone(),
+                EmissionCurve::Constant,
                 Some((KSM, 1000, gauge_basic_rewards.clone())),
                 2,
                 1,
@@ -1256,6 +1196,7 @@ fn gauge_farming_pool_should_work() {
                 RuntimeOrigin::signed(ALICE),
                 tokens_proportion,
                 basic_rewards,
+                EmissionCurve::Constant,
                 Some((KSM, 1000, gauge_basic_rewards)),
                 2,
                 1,
@@ -1307,10 +1248,9 @@ fn gauge_farming_pool_should_work() {
             assert_eq!(pool1.min_deposit_to_start, 2);
             assert_eq!(pool1.state, PoolState::Charged);
 
-            // OnInitialize hook change the pool state
-            Farming::on_initialize(System::block_number() + 3);
-            Farming::on_initialize(0);
-            pool1 = Farming::pool_infos(pool_id).unwrap();
+            // The pool starts on the next block
+            System::set_block_number(System::block_number() + 2);
+            pool1 = accrued_pool_info(pool_id);
             assert_eq!(pool1.total_shares, 1000);
             assert_eq!(pool1.min_deposit_to_start, 2);
             assert_eq!(pool1.state, PoolState::Ongoing);
@@ -1320,13 +1260,15 @@ fn gauge_farming_pool_should_work() {
                 Farming::claim(RuntimeOrigin::signed(ALICE), pool_id),
                 Error::<Runtime>::CanNotClaim
             );
-            System::set_block_number(System::block_number() + 6);
+            // Basic rewards of the blocks 5 to 9 and 6% of the gauge rewards of the blocks 1 to 9
+            System::set_block_number(System::block_number() + 4);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 3008);
+            assert_eq!(Assets::balance(KSM, &ALICE), 1900 + 5000 + 486);
 
+            // The gauge ends at block 103 and returns the gauge tokens
             System::set_block_number(System::block_number() + 100);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 4698);
+            assert_eq!(Assets::balance(KSM, &ALICE), 7386 + 100_000 + 91_757 + 100);
 
             // Withdraw part tokens
             assert_ok!(Farming::withdraw(
@@ -1334,19 +1276,19 @@ fn gauge_farming_pool_should_work() {
                 pool_id,
                 Some(800)
             ));
-            assert_eq!(Assets::balance(KSM, &ALICE), 4698);
+            assert_eq!(Assets::balance(KSM, &ALICE), 199_243);
 
-            // Claim again
+            // Claim again, the remaining share gets all basic rewards
             System::set_block_number(System::block_number() + 6);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
             assert_err!(
                 Farming::claim(RuntimeOrigin::signed(ALICE), pool_id),
                 Error::<Runtime>::CanNotClaim
             );
-            assert_eq!(Assets::balance(KSM, &ALICE), 4698);
+            assert_eq!(Assets::balance(KSM, &ALICE), 199_243 + 6000);
             System::set_block_number(System::block_number() + 6);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 5498);
+            assert_eq!(Assets::balance(KSM, &ALICE), 205_243 + 6000 + 800);
         })
 }
 
@@ -1428,60 +1370,55 @@ fn deposit_should_work(use_gauge: bool) {
     if use_gauge {
         let mut gauge_basic_rewards = BTreeMap::<CurrencyIdOf<Runtime>, BalanceOf<Runtime>>::new();
         gauge_basic_rewards.entry(KSM).or_insert(tokens);
+        // the gauge rewards of the blocks 1 and 2 accrued on the gauge deposits
+        let mut rewards = BTreeMap::<
+            CurrencyIdOf<Runtime>,
+            (BalanceOf<Runtime>, BalanceOf<Runtime>, BalanceOf<Runtime>),
+        >::new();
+        rewards.insert(KSM, (2000, 0, 0));
         let gauge_pool_info = GaugePoolInfo {
             pool_id,
             token: KSM,
             keeper,
             reward_issuer,
-            rewards: BTreeMap::<
-                CurrencyIdOf<Runtime>,
-                (BalanceOf<Runtime>, BalanceOf<Runtime>, BalanceOf<Runtime>),
-            >::new(),
+            rewards,
             gauge_basic_rewards,
             max_block: 1000,
             gauge_amount: 200,
             total_time_factor: 39900,
             gauge_last_block: System::block_number(),
             gauge_state: GaugeState::Bonded,
+            last_accrual_block: System::block_number(),
         };
         assert_eq!(Farming::gauge_pool_infos(0), Some(gauge_pool_info));
     } else {
         assert_eq!(Farming::gauge_pool_infos(0), None);
     }
 
-    Farming::on_initialize(0);
-    assert_eq!(Farming::pool_infos(0).unwrap().state, PoolState::Ongoing);
-    assert!(Farming::pool_infos(0).unwrap().rewards.is_empty());
-    if use_gauge {
-        assert_eq!(
-            Farming::gauge_pool_infos(0).unwrap().rewards.get(&KSM),
-            Some(&(1000, 0, 0))
-        );
-    }
-
-    Farming::on_initialize(0);
-    assert_eq!(
-        Farming::pool_infos(0).unwrap().rewards.get(&KSM),
-        Some(&(1000, 0))
-    );
+    // The second deposit started the pool at block 1
+    let pool_info = Farming::pool_infos(0).unwrap();
+    assert_eq!(pool_info.state, PoolState::Ongoing);
+    assert_eq!(pool_info.block_startup, Some(1));
     if use_gauge {
-        assert_eq!(
-            Farming::gauge_pool_infos(0).unwrap().rewards.get(&KSM),
-            Some(&(2000, 0, 0))
-        );
+        // The deposit without new shares didn't touch the pool
+        assert!(pool_info.rewards.is_empty());
+        assert_eq!(pool_info.last_accrual_block, 1);
+    } else {
+        // The rewards of block 2 accrued before the new shares, which withdraw their inflation
+        assert_eq!(pool_info.rewards.get(&KSM), Some(&(1050, 50)));
+        assert_eq!(pool_info.last_accrual_block, 2);
     }
 
-    System::set_block_number(System::block_number() + 1000);
-    Farming::on_initialize(0);
-    assert_eq!(
-        Farming::pool_infos(0).unwrap().rewards.get(&KSM),
-        Some(&(2000, 0))
-    );
+    // Rewards accrue for every block without touching the pool
+    System::set_block_number(System::block_number() + 10);
+    assert_eq!(Farming::pool_infos(0).unwrap(), pool_info);
     if use_gauge {
-        assert_eq!(
-            Farming::gauge_pool_infos(0).unwrap().rewards.get(&KSM),
-            Some(&(3000, 0, 0))
-        );
+        assert_eq!(accrued_pool_info(0).rewards.get(&KSM), Some(&(11_000, 0)));
+        let mut gauge_pool_info = Farming::gauge_pool_infos(0).unwrap();
+        Farming::accrue_gauge_rewards(&mut gauge_pool_info, System::block_number());
+        assert_eq!(gauge_pool_info.rewards.get(&KSM), Some(&(12_000, 0, 0)));
+    } else {
+        assert_eq!(accrued_pool_info(0).rewards.get(&KSM), Some(&(11_050, 50)));
     }
 }
 
@@ -1517,9 +1454,8 @@ fn withdraw_should_work(use_gauge: bool) {
     assert_eq!(Assets::balance(KSM, &reward_issuer), 100_000);
     assert_eq!(Assets::balance(KSM, &token_keeper), 1_000);
 
-    Farming::on_initialize(0);
-    Farming::on_initialize(0);
-    System::set_block_number(System::block_number() + 1);
+    // The pool starts at block 1
+    System::set_block_number(System::block_number() + 2);
 
     let reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
     assert!(reward.withdraw_list.is_empty());
@@ -1552,7 +1488,7 @@ fn withdraw_should_work(use_gauge: bool) {
     assert_eq!(Assets::balance(KSM, &token_keeper), 1_000);
 
     // Bob deposit
-    System::set_block_number(System::block_number() + 100);
+    System::set_block_number(System::block_number() + 10);
     if use_gauge {
         assert_ok!(Farming::deposit(
             RuntimeOrigin::signed(BOB),
@@ -1576,11 +1512,12 @@ fn withdraw_should_work(use_gauge: bool) {
         assert_eq!(Assets::balance(KSM, &token_keeper), 2_000);
     }
 
-    // Alice claim again, because new block produces, so has reward now
-    Farming::on_initialize(0);
+    // Alice claim again, because new blocks were produced, so has reward now
+    // and her first withdrawal is unlocked.
+    System::set_block_number(System::block_number() + 1);
     assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-    assert_eq!(Assets::balance(KSM, &ALICE), 3_966);
-    assert_eq!(Assets::balance(KSM, &reward_issuer), 98_834);
+    assert_eq!(Assets::balance(KSM, &ALICE), 13_966);
+    assert_eq!(Assets::balance(KSM, &reward_issuer), 88_834);
 
     // withdraw
     assert_ok!(Farming::withdraw(
@@ -1588,8 +1525,8 @@ fn withdraw_should_work(use_gauge: bool) {
         pool_id,
         Some(200)
     ));
-    assert_eq!(Assets::balance(KSM, &ALICE), 3_966);
-    assert_eq!(Assets::balance(KSM, &reward_issuer), 98_834);
+    assert_eq!(Assets::balance(KSM, &ALICE), 13_966);
+    assert_eq!(Assets::balance(KSM, &reward_issuer), 88_834);
     if use_gauge {
         assert_eq!(Assets::balance(KSM, &token_keeper), 1_300);
     } else {
@@ -1603,8 +1540,8 @@ fn withdraw_should_work(use_gauge: bool) {
         RuntimeOrigin::signed(ALICE),
         pool_id
     ));
-    assert_eq!(Assets::balance(KSM, &ALICE), 4_166);
-    assert_eq!(Assets::balance(KSM, &reward_issuer), 98_834);
+    assert_eq!(Assets::balance(KSM, &ALICE), 14_166);
+    assert_eq!(Assets::balance(KSM, &reward_issuer), 88_834);
     if use_gauge {
         assert_eq!(Assets::balance(KSM, &token_keeper), 1_100);
     } else {
@@ -1615,8 +1552,8 @@ fn withdraw_should_work(use_gauge: bool) {
     let reward = SharesAndWithdrawnRewards::<Runtime>::get(pool_id, &ALICE).unwrap();
     assert!(reward.withdraw_list.is_empty());
     assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-    assert_eq!(Assets::balance(KSM, &ALICE), 4_166);
-    assert_eq!(Assets::balance(KSM, &reward_issuer), 98_834);
+    assert_eq!(Assets::balance(KSM, &ALICE), 14_166);
+    assert_eq!(Assets::balance(KSM, &reward_issuer), 88_834);
     if use_gauge {
         assert_eq!(Assets::balance(KSM, &token_keeper), 1_100);
     } else {
@@ -1647,14 +1584,14 @@ fn claim() {
                 Error::<Runtime>::InvalidPoolState
             );
 
-            System::set_block_number(System::block_number() + 100);
-            Farming::on_initialize(0);
+            // The pool starts at block 1 without rewards for that block
+            System::set_block_number(System::block_number() + 1);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
             assert_eq!(Assets::balance(KSM, &ALICE), 2000);
             assert_eq!(Assets::balance(KSM, &keeper), 1000);
             assert_eq!(Assets::balance(KSM, &reward_issuer), 100_000);
 
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 1);
             assert_ok!(Farming::withdraw_claim(
                 RuntimeOrigin::signed(ALICE),
                 pool_id
@@ -1666,7 +1603,7 @@ fn claim() {
             assert_eq!(Assets::balance(KSM, &keeper), 1000);
             assert_eq!(Assets::balance(KSM, &reward_issuer), 99_000);
 
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 1);
             assert_ok!(Farming::close_pool(RuntimeOrigin::signed(ALICE), pool_id));
 
             assert_ok!(Farming::retire_pool(RuntimeOrigin::signed(ALICE), pool_id));
@@ -1687,13 +1624,10 @@ fn gauge() {
             if let Some(gauge_pool_infos) = Farming::gauge_pool_infos(0) {
                 assert!(gauge_pool_infos.rewards.is_empty());
             };
-            Farming::on_initialize(0);
-            System::set_block_number(System::block_number() + 1);
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 2);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 2918);
+            assert_eq!(Assets::balance(KSM, &ALICE), 2936);
 
-            Farming::on_initialize(0);
             System::set_block_number(System::block_number() + 10);
             assert_noop!(
                 Farming::deposit(
@@ -1710,11 +1644,11 @@ fn gauge() {
                 tokens,
                 Some((100, 100))
             ));
-            assert_eq!(Assets::balance(KSM, &ALICE), 1818);
+            assert_eq!(Assets::balance(KSM, &ALICE), 1836);
 
             System::set_block_number(System::block_number() + 20);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 3163);
+            assert_eq!(Assets::balance(KSM, &ALICE), 35543);
 
             assert_ok!(Farming::deposit(
                 RuntimeOrigin::signed(BOB),
@@ -1724,9 +1658,9 @@ fn gauge() {
             ));
             assert_eq!(Assets::balance(KSM, &BOB), 9699990);
 
-            System::set_block_number(System::block_number() + 200);
+            System::set_block_number(System::block_number() + 40);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 5383);
+            assert_eq!(Assets::balance(KSM, &ALICE), 87869);
             assert_eq!(Assets::balance(KSM, &BOB), 9699990);
             assert_ok!(Farming::deposit(
                 RuntimeOrigin::signed(BOB),
@@ -1745,7 +1679,8 @@ fn gauge() {
                 RuntimeOrigin::signed(ALICE),
                 pool_id
             ));
-            assert_eq!(Assets::balance(KSM, &BOB), 9699991);
+            assert_eq!(Assets::balance(KSM, &BOB), 9700530);
+            assert_eq!(Assets::balance(KSM, &ALICE), 207324);
 
             assert_ok!(Farming::deposit(
                 RuntimeOrigin::signed(ALICE),
@@ -1753,7 +1688,7 @@ fn gauge() {
                 tokens,
                 Some((100, 1))
             ));
-            System::set_block_number(System::block_number() + 200);
+            System::set_block_number(System::block_number() + 10);
             assert_noop!(
                 Farming::deposit(
                     RuntimeOrigin::signed(ALICE),
@@ -1777,20 +1712,18 @@ fn gauge_withdraw() {
             if let Some(gauge_pool_infos) = Farming::gauge_pool_infos(0) {
                 assert_eq!(gauge_pool_infos.gauge_amount, 100)
             };
-            Farming::on_initialize(0);
-            System::set_block_number(System::block_number() + 1);
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 2);
             assert_ok!(Farming::gauge_withdraw(
                 RuntimeOrigin::signed(ALICE),
                 pool_id
             ));
-            assert_eq!(Assets::balance(KSM, &ALICE), 1918);
-            System::set_block_number(System::block_number() + 1000);
+            assert_eq!(Assets::balance(KSM, &ALICE), 1936);
+            System::set_block_number(System::block_number() + 200);
             assert_ok!(Farming::gauge_withdraw(
                 RuntimeOrigin::signed(ALICE),
                 pool_id
             ));
-            assert_eq!(Assets::balance(KSM, &ALICE), 3782);
+            assert_eq!(Assets::balance(KSM, &ALICE), 180165);
             if let Some(gauge_pool_infos) = Farming::gauge_pool_infos(0) {
                 assert_eq!(gauge_pool_infos.gauge_amount, 0)
             };
@@ -1804,7 +1737,6 @@ fn pool_admin_operation_should_work() {
         .build()
         .execute_with(|| {
             let (pool_id, tokens) = init_gauge_1000();
-            Farming::on_initialize(0);
             System::set_block_number(System::block_number() + 1);
             assert_ok!(Farming::deposit(
                 RuntimeOrigin::signed(ALICE),
@@ -1883,6 +1815,7 @@ fn pool_admin_operation_should_work() {
                     None,
                     None,
                     None,
+                    None,
                     None
                 ),
                 Error::<Runtime>::InvalidPoolState
@@ -1905,7 +1838,7 @@ fn pool_admin_operation_should_work() {
             assert_eq!(pool.state, PoolState::Retired);
 
             // claim all rewards automatically to user
-            assert_eq!(Assets::balance(KSM, &ALICE), 3000);
+            assert_eq!(Assets::balance(KSM, &ALICE), 5000);
             assert_eq!(Farming::shares_and_withdrawn_rewards(pool_id, &ALICE), None);
 
             // Pool is retired, not allow to retire again, deposit, withdraw, claim, close
@@ -1944,11 +1877,9 @@ fn reset() {
         .execute_with(|| {
             let (pool_id, _tokens) = init_gauge_900();
             assert_eq!(Assets::balance(KSM, &ALICE), 1900);
-            Farming::on_initialize(0);
-            System::set_block_number(System::block_number() + 1);
-            Farming::on_initialize(0);
+            System::set_block_number(System::block_number() + 2);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 2918);
+            assert_eq!(Assets::balance(KSM, &ALICE), 2936);
             assert_ok!(Farming::close_pool(RuntimeOrigin::signed(ALICE), pool_id));
             assert_ok!(Farming::set_retire_limit(RuntimeOrigin::signed(ALICE), 10));
             assert_ok!(Farming::retire_pool(RuntimeOrigin::signed(ALICE), pool_id));
@@ -1980,12 +1911,16 @@ fn reset() {
                 total_shares: Default::default(),
                 basic_token: (KSM, Perbill::from_percent(100)),
                 basic_rewards: basic_rewards_map.clone(),
+                emission_curve: EmissionCurve::Constant,
+                emission_start: 1,
+                remaining_rewards: BTreeMap::from([(KSM, 299_000)]),
                 rewards: BTreeMap::new(),
                 state: PoolState::UnCharged,
                 keeper: keeper.clone(),
                 reward_issuer: reward_issuer.clone(),
                 gauge: Some(1),
                 block_startup: None,
+                last_accrual_block: System::block_number(),
                 min_deposit_to_start: Default::default(),
                 after_block_to_start: Default::default(),
                 withdraw_limit_time: Default::default(),
@@ -2008,9 +1943,10 @@ fn reset() {
                 total_time_factor: 0,
                 gauge_last_block: System::block_number(),
                 gauge_state: GaugeState::Bonded,
+                last_accrual_block: System::block_number(),
             };
             assert_eq!(Farming::gauge_pool_infos(1), Some(gauge_pool_info));
-            assert_eq!(Assets::balance(KSM, &ALICE), 3918);
+            assert_eq!(Assets::balance(KSM, &ALICE), 3936);
             let charge_rewards = vec![(KSM, 300000)];
             assert_ok!(Farming::charge(
                 RuntimeOrigin::signed(BOB),
@@ -2023,10 +1959,1427 @@ fn reset() {
                 1,
                 Some((100, 100))
             ));
-            assert_eq!(Assets::balance(KSM, &ALICE), 3817);
-            Farming::on_initialize(0);
+            assert_eq!(Assets::balance(KSM, &ALICE), 3835);
             System::set_block_number(System::block_number() + 20);
             assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
-            assert_eq!(Assets::balance(KSM, &ALICE), 4017);
+            assert_eq!(Assets::balance(KSM, &ALICE), 26835);
+        })
+}
+
+#[test]
+fn rewards_accrue_lazily() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                EmissionCurve::Constant,
+                None,
+                1000, // min_deposit_to_start
+                3,    // after_block_to_start
+                0,    // withdraw_limit_time
+                0,    // claim_limit_time
+                5     // withdraw_limit_count
+            ));
+            let pool_id = 0;
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 100000)]
+            ));
+            System::set_block_number(3);
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+            assert_eq!(Assets::balance(KSM, &ALICE), 2000);
+
+            // Nothing is written to storage until the pool is touched again
+            System::set_block_number(10);
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_info.state, PoolState::Charged);
+            assert_eq!(pool_info.last_accrual_block, 3);
+            assert!(pool_info.rewards.is_empty());
+
+            // The pool starts in the block after the minimum deposit was reached
+            let pool_info = accrued_pool_info(pool_id);
+            assert_eq!(pool_info.state, PoolState::Ongoing);
+            assert_eq!(pool_info.block_startup, Some(4));
+            assert_eq!(pool_info.rewards.get(&KSM), Some(&(6000, 0)));
+            assert_eq!(
+                Farming::get_farming_rewards(&ALICE, pool_id).unwrap(),
+                vec![(KSM, 6000)]
+            );
+
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 8000);
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_info.state, PoolState::Ongoing);
+            assert_eq!(pool_info.last_accrual_block, 10);
+            assert_eq!(pool_info.rewards.get(&KSM), Some(&(6000, 6000)));
+        })
+}
+
+#[test]
+fn emitted_rewards_should_follow_emission_curve() {
+    ExtBuilder::default().build().execute_with(|| {
+        let constant = EmissionCurve::Constant;
+        assert_eq!(Farming::emitted_rewards(&constant, 1000, 0, 0, 10), 10_000);
+        assert_eq!(Farming::emitted_rewards(&constant, 1000, 0, 10, 10), 0);
+
+        // 1000 per block up to block 10, 500 up to block 20, 250 up to block 30
+        let halving = EmissionCurve::StepHalving { period: 10 };
+        assert_eq!(Farming::emitted_rewards(&halving, 1000, 0, 0, 25), 16_250);
+        assert_eq!(Farming::emitted_rewards(&halving, 1000, 0, 5, 15), 7_500);
+        assert_eq!(Farming::emitted_rewards(&halving, 1000, 10, 15, 25), 7_500);
+        let halving = EmissionCurve::StepHalving { period: 1 };
+        assert_eq!(
+            Farming::emitted_rewards(&halving, 1000, 0, 0, 10_000),
+            1_994
+        );
+
+        // Block `b` emits `1000 * (11 - b) / 10` up to block 10
+        let taper = EmissionCurve::LinearTaper { end_block: 10 };
+        assert_eq!(Farming::emitted_rewards(&taper, 1000, 0, 0, 10), 5_500);
+        assert_eq!(Farming::emitted_rewards(&taper, 1000, 0, 0, 20), 5_500);
+        assert_eq!(Farming::emitted_rewards(&taper, 1000, 0, 5, 10), 1_500);
+        assert_eq!(Farming::emitted_rewards(&taper, 1000, 0, 10, 20), 0);
+    })
+}
+
+#[test]
+fn charging_should_not_restart_emission_curve() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                EmissionCurve::StepHalving { period: 2 },
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5  // withdraw_limit_count
+            ));
+            let pool_id = 0;
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 100000)]
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+
+            // Blocks 2 and 3 emit 1000 each, blocks 4 and 5 emit 500 each
+            System::set_block_number(5);
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 1)]
+            ));
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_info.state, PoolState::Charged);
+            assert_eq!(pool_info.rewards.get(&KSM), Some(&(3000, 0)));
+
+            // The pool starts again at block 6 but its curve stays halved twice at block 7
+            System::set_block_number(7);
+            let pool_info = accrued_pool_info(pool_id);
+            assert_eq!(pool_info.state, PoolState::Ongoing);
+            assert_eq!(pool_info.block_startup, Some(1));
+            assert_eq!(pool_info.emission_start, 1);
+            assert_eq!(pool_info.rewards.get(&KSM), Some(&(3250, 0)));
+        })
+}
+
+#[test]
+fn linear_taper_pool_should_close_when_curve_ends() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            // The curve would end before the pool can start
+            assert_noop!(
+                Farming::create_farming_pool(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![(KSM, Perbill::from_percent(100))],
+                    vec![(KSM, 1000)],
+                    EmissionCurve::LinearTaper { end_block: 4 },
+                    None,
+                    0, // min_deposit_to_start
+                    4, // after_block_to_start
+                    0, // withdraw_limit_time
+                    0, // claim_limit_time
+                    5  // withdraw_limit_count
+                ),
+                Error::<Runtime>::InvalidPoolParameter
+            );
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                EmissionCurve::LinearTaper { end_block: 4 },
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5  // withdraw_limit_count
+            ));
+            let pool_id = 0;
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 100000)]
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+
+            // Blocks 2 to 4 emit 2000 of the budget, the rest is never emitted
+            System::set_block_number(6);
+            let pool_info = accrued_pool_info(pool_id);
+            assert_eq!(pool_info.state, PoolState::Dead);
+            assert_eq!(pool_info.rewards.get(&KSM), Some(&(2000, 0)));
         })
 }
+
+#[test]
+fn pool_should_close_when_budget_is_exhausted() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Farming::create_farming_pool(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![(KSM, Perbill::from_percent(100))],
+                    vec![(KSM, 1000)],
+                    EmissionCurve::StepHalving { period: 0 },
+                    None,
+                    0, // min_deposit_to_start
+                    0, // after_block_to_start
+                    0, // withdraw_limit_time
+                    0, // claim_limit_time
+                    5  // withdraw_limit_count
+                ),
+                Error::<Runtime>::InvalidPoolParameter
+            );
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                EmissionCurve::Constant,
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5  // withdraw_limit_count
+            ));
+            let pool_id = 0;
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 5000)]
+            ));
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 500)]
+            ));
+            assert_eq!(
+                Farming::pool_infos(pool_id).unwrap().remaining_rewards,
+                BTreeMap::from([(KSM, 5500)])
+            );
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+
+            // The pool starts at block 1 and halves its emission every 2 blocks from block 3
+            System::set_block_number(3);
+            assert_noop!(
+                Farming::edit_pool(
+                    RuntimeOrigin::signed(ALICE),
+                    pool_id,
+                    None,
+                    Some(EmissionCurve::LinearTaper { end_block: 3 }),
+                    None,
+                    None,
+                    None,
+                    None
+                ),
+                Error::<Runtime>::InvalidPoolParameter
+            );
+            assert_ok!(Farming::edit_pool(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                None,
+                Some(EmissionCurve::StepHalving { period: 2 }),
+                None,
+                None,
+                None,
+                None
+            ));
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_info.emission_start, 3);
+            assert_eq!(pool_info.rewards.get(&KSM), Some(&(2000, 0)));
+            assert_eq!(pool_info.remaining_rewards.get(&KSM), Some(&3500));
+
+            System::set_block_number(7);
+            let pool_info = accrued_pool_info(pool_id);
+            assert_eq!(pool_info.state, PoolState::Ongoing);
+            assert_eq!(pool_info.rewards.get(&KSM), Some(&(5000, 0)));
+            assert_eq!(pool_info.remaining_rewards.get(&KSM), Some(&500));
+
+            // Blocks 8 to 11 would emit 250 more than the remaining budget
+            System::set_block_number(11);
+            let pool_info = accrued_pool_info(pool_id);
+            assert_eq!(pool_info.state, PoolState::Dead);
+            assert_eq!(pool_info.rewards.get(&KSM), Some(&(5500, 0)));
+            assert_eq!(pool_info.remaining_rewards.get(&KSM), Some(&0));
+
+            assert_noop!(
+                Farming::deposit(RuntimeOrigin::signed(BOB), pool_id, 1000, None),
+                Error::<Runtime>::InvalidPoolState
+            );
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 7500);
+            assert_eq!(Farming::pool_infos(pool_id).unwrap().state, PoolState::Dead);
+        })
+}
+
+fn create_permissionless_pool(who: AccountId, token: CalamariAssetId) -> DispatchResult {
+    Farming::create_permissionless_pool(
+        RuntimeOrigin::signed(who),
+        vec![(token, Perbill::from_percent(100))],
+        vec![(KSM, 1000)],
+        EmissionCurve::Constant,
+        0, // min_deposit_to_start
+        0, // after_block_to_start
+        5, // withdraw_limit_time
+        0, // claim_limit_time
+        2, // withdraw_limit_count
+    )
+}
+
+#[test]
+fn permissionless_pool_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                create_permissionless_pool(BOB, KMA),
+                Error::<Runtime>::TokenNotAllowed
+            );
+            assert_noop!(
+                create_permissionless_pool(CHARLIE, KSM),
+                pallet_balances::Error::<Runtime>::InsufficientBalance
+            );
+            assert_ok!(create_permissionless_pool(BOB, KSM));
+            let pool_id = 0;
+            assert_eq!(Balances::reserved_balance(BOB), 100);
+            assert_eq!(Farming::pool_owners(pool_id), Some((BOB, 100)));
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 100_000)]
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+
+            // The creator can only relax the limits of the shares
+            assert_noop!(
+                Farming::edit_pool(
+                    RuntimeOrigin::signed(CHARLIE),
+                    pool_id,
+                    Some(vec![(KSM, 2000)]),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Farming::edit_pool(
+                    RuntimeOrigin::signed(BOB),
+                    pool_id,
+                    None,
+                    None,
+                    Some(6),
+                    None,
+                    None,
+                    None
+                ),
+                Error::<Runtime>::EditNotAllowed
+            );
+            assert_noop!(
+                Farming::edit_pool(
+                    RuntimeOrigin::signed(BOB),
+                    pool_id,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some(1)
+                ),
+                Error::<Runtime>::EditNotAllowed
+            );
+            assert_ok!(Farming::edit_pool(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                Some(vec![(KSM, 2000)]),
+                Some(EmissionCurve::StepHalving { period: 100 }),
+                Some(0),
+                None,
+                None,
+                Some(5)
+            ));
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_info.basic_rewards.get(&KSM), Some(&2000));
+            assert_eq!(pool_info.withdraw_limit_time, 0);
+            assert_eq!(pool_info.withdraw_limit_count, 5);
+
+            // The creator winds the pool down and gets the deposit back
+            assert_noop!(
+                Farming::close_pool(RuntimeOrigin::signed(CHARLIE), pool_id),
+                DispatchError::BadOrigin
+            );
+            System::set_block_number(System::block_number() + 2);
+            assert_ok!(Farming::close_pool(RuntimeOrigin::signed(BOB), pool_id));
+            assert_ok!(Farming::set_retire_limit(RuntimeOrigin::signed(ALICE), 10));
+            assert_ok!(Farming::retire_pool(RuntimeOrigin::signed(BOB), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 5000);
+            assert_ok!(Farming::kill_pool(RuntimeOrigin::signed(BOB), pool_id));
+            assert_eq!(Balances::reserved_balance(BOB), 0);
+            assert_eq!(Balances::free_balance(BOB), 400_000);
+            assert_eq!(Farming::pool_owners(pool_id), None);
+        })
+}
+
+#[test]
+fn governance_should_kill_permissionless_pool() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(create_permissionless_pool(BOB, KSM));
+            let pool_id = 0;
+            assert_noop!(
+                Farming::reset_pool(
+                    RuntimeOrigin::signed(BOB),
+                    pool_id,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None
+                ),
+                DispatchError::BadOrigin
+            );
+
+            assert_ok!(Farming::kill_pool(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Farming::pool_infos(pool_id), None);
+            assert_eq!(Farming::pool_owners(pool_id), None);
+            assert_eq!(Balances::reserved_balance(BOB), 0);
+            assert_eq!(Balances::free_balance(BOB), 399_900);
+            assert_eq!(Balances::free_balance(TREASURY_ACCOUNT), 100);
+        })
+}
+
+#[test]
+fn gauge_voting_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, _) = init_gauge_900();
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                EmissionCurve::Constant,
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5  // withdraw_limit_count
+            ));
+            let pool_id1 = 1;
+            // BOB locks 1000 for 500 blocks of the 1000 max_block
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                1000,
+                Some((1000, 500))
+            ));
+
+            assert_noop!(
+                Farming::set_votable_pool(RuntimeOrigin::signed(BOB), pool_id, true),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Farming::set_votable_pool(RuntimeOrigin::signed(ALICE), 5, true),
+                Error::<Runtime>::PoolDoesNotExist
+            );
+            // Only the gauges of votable pools give voting power
+            assert_eq!(Farming::voting_power(&BOB, 0), 0);
+            assert_ok!(Farming::set_votable_pool(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                true
+            ));
+            assert_eq!(Farming::voting_power(&BOB, 0), 500);
+            assert_eq!(Farming::voting_power(&ALICE, 0), 10);
+            assert_ok!(Farming::set_votable_pool(
+                RuntimeOrigin::signed(ALICE),
+                pool_id1,
+                true
+            ));
+            assert_ok!(Farming::set_gauge_vote_emission(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, 10_000)]
+            ));
+
+            assert_noop!(
+                Farming::vote_gauge(
+                    RuntimeOrigin::signed(CHARLIE),
+                    vec![(pool_id, Perbill::from_percent(100))]
+                ),
+                Error::<Runtime>::NoVotingPower
+            );
+            assert_noop!(
+                Farming::vote_gauge(
+                    RuntimeOrigin::signed(BOB),
+                    vec![(2, Perbill::from_percent(100))]
+                ),
+                Error::<Runtime>::PoolNotVotable
+            );
+            assert_noop!(
+                Farming::vote_gauge(
+                    RuntimeOrigin::signed(BOB),
+                    vec![
+                        (pool_id, Perbill::from_percent(60)),
+                        (pool_id1, Perbill::from_percent(50))
+                    ]
+                ),
+                Error::<Runtime>::InvalidVotes
+            );
+
+            // Voting again in the same epoch replaces the earlier votes
+            assert_ok!(Farming::vote_gauge(
+                RuntimeOrigin::signed(BOB),
+                vec![(pool_id, Perbill::from_percent(100))]
+            ));
+            assert_ok!(Farming::vote_gauge(
+                RuntimeOrigin::signed(BOB),
+                vec![
+                    (pool_id, Perbill::from_percent(20)),
+                    (pool_id1, Perbill::from_percent(80))
+                ]
+            ));
+            assert_ok!(Farming::vote_gauge(
+                RuntimeOrigin::signed(ALICE),
+                vec![(pool_id1, Perbill::from_percent(100))]
+            ));
+            assert_eq!(Farming::gauge_weights(0u32, pool_id), 100);
+            assert_eq!(Farming::gauge_weights(0u32, pool_id1), 410);
+            let gauge_weights: BTreeMap<PoolId, u128> =
+                Farming::get_gauge_weights().into_iter().collect();
+            assert_eq!(
+                gauge_weights,
+                BTreeMap::from([(pool_id, 100), (pool_id1, 410)])
+            );
+
+            assert_noop!(
+                Farming::settle_gauge_votes(RuntimeOrigin::signed(CHARLIE), 0),
+                Error::<Runtime>::EpochNotEnded
+            );
+
+            // The power decays with the remaining lock time
+            System::set_block_number(10);
+            assert_eq!(Farming::voting_power(&BOB, 10), 490);
+            assert_eq!(Farming::get_gauge_weights(), vec![]);
+            assert_ok!(Assets::transfer(
+                RuntimeOrigin::signed(BOB),
+                KSM,
+                TREASURY_ACCOUNT,
+                20_000
+            ));
+            assert_ok!(Farming::settle_gauge_votes(
+                RuntimeOrigin::signed(CHARLIE),
+                0
+            ));
+            assert_eq!(Assets::balance(KSM, &TREASURY_ACCOUNT), 10_001);
+
+            // The Ongoing pool keeps emitting with the settled rewards added to its budget
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_info.state, PoolState::Ongoing);
+            assert_eq!(pool_info.remaining_rewards.get(&KSM), Some(&292_960));
+            let pool_info1 = Farming::pool_infos(pool_id1).unwrap();
+            assert_eq!(pool_info1.state, PoolState::Charged);
+            assert_eq!(pool_info1.remaining_rewards.get(&KSM), Some(&8_039));
+
+            assert_noop!(
+                Farming::settle_gauge_votes(RuntimeOrigin::signed(CHARLIE), 0),
+                Error::<Runtime>::NothingToSettle
+            );
+
+            assert_ok!(Farming::set_votable_pool(
+                RuntimeOrigin::signed(ALICE),
+                pool_id1,
+                false
+            ));
+            assert_eq!(Farming::votable_pools(), BTreeSet::from([pool_id]));
+        })
+}
+
+#[test]
+fn settle_gauge_votes_skips_pools_failing_to_charge() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, _) = init_gauge_900();
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                EmissionCurve::Constant,
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5  // withdraw_limit_count
+            ));
+            let pool_id1 = 1;
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                1000,
+                Some((1000, 500))
+            ));
+            assert_ok!(Farming::set_votable_pool(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                true
+            ));
+            assert_ok!(Farming::set_votable_pool(
+                RuntimeOrigin::signed(ALICE),
+                pool_id1,
+                true
+            ));
+            assert_ok!(Farming::set_gauge_vote_emission(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, 10_000)]
+            ));
+            assert_ok!(Farming::vote_gauge(
+                RuntimeOrigin::signed(BOB),
+                vec![
+                    (pool_id, Perbill::from_percent(20)),
+                    (pool_id1, Perbill::from_percent(80))
+                ]
+            ));
+            assert_ok!(Farming::vote_gauge(
+                RuntimeOrigin::signed(ALICE),
+                vec![(pool_id1, Perbill::from_percent(100))]
+            ));
+
+            // The treasury only covers the share of the first pool
+            System::set_block_number(10);
+            assert_ok!(Assets::transfer(
+                RuntimeOrigin::signed(BOB),
+                KSM,
+                TREASURY_ACCOUNT,
+                2_000
+            ));
+            assert_ok!(Farming::settle_gauge_votes(
+                RuntimeOrigin::signed(CHARLIE),
+                0
+            ));
+            assert_eq!(Assets::balance(KSM, &TREASURY_ACCOUNT), 40);
+
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_info.remaining_rewards.get(&KSM), Some(&292_960));
+            let pool_info1 = Farming::pool_infos(pool_id1).unwrap();
+            assert_eq!(pool_info1.state, PoolState::UnCharged);
+            assert_eq!(pool_info1.remaining_rewards.get(&KSM), None);
+            assert!(System::events().iter().any(|record| matches!(
+                record.event,
+                RuntimeEvent::Farming(Event::GaugeVoteChargeFailed { epoch: 0, pid, .. })
+                    if pid == pool_id1
+            )));
+            assert_noop!(
+                Farming::settle_gauge_votes(RuntimeOrigin::signed(CHARLIE), 0),
+                Error::<Runtime>::NothingToSettle
+            );
+        })
+}
+
+#[test]
+fn gauges_of_other_tokens_give_no_voting_power() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            // The gauge of this pool locks KMA, the vote token of the mock is KSM
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                EmissionCurve::Constant,
+                Some((KMA, 1000, vec![(KSM, 900)])),
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5  // withdraw_limit_count
+            ));
+            let pool_id = 0;
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 300000)]
+            ));
+            assert_ok!(Farming::set_votable_pool(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                true
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                1000,
+                Some((1000, 500))
+            ));
+            assert_eq!(Farming::gauge_infos(0, BOB).unwrap().gauge_amount, 1000);
+
+            assert_eq!(Farming::voting_power(&BOB, 0), 0);
+            assert_noop!(
+                Farming::vote_gauge(
+                    RuntimeOrigin::signed(BOB),
+                    vec![(pool_id, Perbill::from_percent(100))]
+                ),
+                Error::<Runtime>::NoVotingPower
+            );
+        })
+}
+
+#[test]
+fn user_positions_and_pool_overview_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, tokens) = init_gauge_1000();
+            assert_eq!(Farming::pool_overview(1), None);
+            assert_eq!(
+                Farming::pool_overview(pool_id),
+                Some(PoolOverview {
+                    pid: pool_id,
+                    state: PoolState::Charged,
+                    total_shares: tokens,
+                    emission_per_block: vec![(KSM, 1000)],
+                    emission_per_share: vec![(KSM, EMISSION_PER_SHARE_UNIT)],
+                    remaining_rewards: vec![(KSM, 100_000)],
+                    withdraw_limit_time: 10,
+                    gauge: Some(0),
+                    reward_vesting: None,
+                })
+            );
+            assert_eq!(
+                Farming::user_positions(&ALICE),
+                vec![UserPosition {
+                    pid: pool_id,
+                    share: tokens,
+                    pending_rewards: vec![],
+                    pending_gauge_rewards: vec![],
+                    gauge_lock: None,
+                    claimable_at: 0,
+                    withdraw_list: vec![],
+                    can_withdraw: true,
+                }]
+            );
+            assert_eq!(Farming::user_positions(&BOB), vec![]);
+
+            // The pool starts at block 1
+            System::set_block_number(10);
+            let pool_overview = Farming::pool_overview(pool_id).unwrap();
+            assert_eq!(pool_overview.state, PoolState::Ongoing);
+            assert_eq!(pool_overview.remaining_rewards, vec![(KSM, 91_000)]);
+            assert_eq!(Farming::pool_overviews(), vec![pool_overview]);
+            assert_eq!(
+                Farming::user_positions(&ALICE)[0].pending_rewards,
+                vec![(KSM, 9000)]
+            );
+
+            assert_ok!(Farming::withdraw(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(400)
+            ));
+            let position = &Farming::user_positions(&ALICE)[0];
+            assert_eq!(position.share, 600);
+            assert_eq!(position.withdraw_list, vec![(20, 400)]);
+            assert!(!position.can_withdraw);
+            assert_eq!(
+                Farming::pool_overview(pool_id).unwrap().emission_per_share,
+                vec![(KSM, 1_666_666_666_666_666_666)]
+            );
+        })
+}
+
+fn init_lp_pool(withdraw_limit_time: u64) -> PoolId {
+    let pool_id = Farming::pool_next_id();
+    assert_ok!(Farming::create_farming_pool(
+        RuntimeOrigin::signed(ALICE),
+        vec![(KMA_KSM_LP, Perbill::from_percent(100))],
+        vec![(KSM, 1000)],
+        EmissionCurve::Constant,
+        None,
+        0, // min_deposit_to_start
+        0, // after_block_to_start
+        withdraw_limit_time,
+        0, // claim_limit_time
+        5  // withdraw_limit_count
+    ));
+    assert_ok!(Farming::charge(
+        RuntimeOrigin::signed(BOB),
+        pool_id,
+        vec![(KSM, 100_000)]
+    ));
+    pool_id
+}
+
+#[test]
+fn zap_deposit_and_remove_liquidity_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let pool_id = init_lp_pool(0);
+            assert_noop!(
+                Farming::zap_deposit(
+                    RuntimeOrigin::signed(ALICE),
+                    pool_id,
+                    (KSM, KSM),
+                    (1000, 1000),
+                    (0, 0),
+                    0,
+                    None
+                ),
+                Error::<Runtime>::NotLiquidityPool
+            );
+            assert_noop!(
+                Farming::zap_deposit(
+                    RuntimeOrigin::signed(ALICE),
+                    pool_id,
+                    (KMA, KSM),
+                    (1000, 1500),
+                    (0, 0),
+                    1001,
+                    None
+                ),
+                Error::<Runtime>::SlippageExceeded
+            );
+            assert_ok!(Farming::zap_deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                (KMA, KSM),
+                (1000, 1500),
+                (0, 0),
+                1000,
+                None
+            ));
+            assert_eq!(Balances::free_balance(&ALICE), 2000);
+            assert_eq!(Assets::balance(KSM, &ALICE), 2000);
+            assert_eq!(Assets::balance(KMA_KSM_LP, &ALICE), 0);
+            let keeper = Farming::pool_infos(pool_id).unwrap().keeper;
+            assert_eq!(Assets::balance(KMA_KSM_LP, &keeper), 1000);
+            assert_eq!(
+                Farming::shares_and_withdrawn_rewards(pool_id, &ALICE)
+                    .unwrap()
+                    .share,
+                1000
+            );
+
+            System::set_block_number(System::block_number() + 10);
+            let ksm_before = Assets::balance(KSM, &ALICE);
+            assert_ok!(Farming::withdraw_and_remove_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                (KMA, KSM),
+                Some(400),
+                (400, 400)
+            ));
+            assert_eq!(Balances::free_balance(&ALICE), 2400);
+            // The claimed rewards are paid in KSM as well
+            assert!(Assets::balance(KSM, &ALICE) > ksm_before + 400);
+            assert_eq!(Assets::balance(KMA_KSM_LP, &ALICE), 0);
+            assert_eq!(
+                Farming::shares_and_withdrawn_rewards(pool_id, &ALICE)
+                    .unwrap()
+                    .share,
+                600
+            );
+            assert_noop!(
+                Farming::withdraw_and_remove_liquidity(
+                    RuntimeOrigin::signed(ALICE),
+                    pool_id,
+                    (KMA, KSM),
+                    None,
+                    (601, 0)
+                ),
+                DispatchError::Other("insufficient amount")
+            );
+            assert_ok!(Farming::withdraw_and_remove_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                (KMA, KSM),
+                None,
+                (600, 600)
+            ));
+            assert_eq!(Balances::free_balance(&ALICE), 3000);
+            assert_eq!(Assets::balance(KMA_KSM_LP, &keeper), 0);
+        })
+}
+
+#[test]
+fn remove_liquidity_should_wait_for_withdraw_limit_time() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let pool_id = init_lp_pool(5);
+            assert_ok!(Farming::zap_deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                (KSM, KMA),
+                (1000, 1000),
+                (1000, 1000),
+                1000,
+                None
+            ));
+            assert_noop!(
+                Farming::withdraw_and_remove_liquidity(
+                    RuntimeOrigin::signed(ALICE),
+                    pool_id,
+                    (KSM, KMA),
+                    Some(1000),
+                    (0, 0)
+                ),
+                Error::<Runtime>::NothingUnstaked
+            );
+
+            assert_ok!(Farming::withdraw(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(1000)
+            ));
+            System::set_block_number(System::block_number() + 5);
+            assert_ok!(Farming::withdraw_and_remove_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                (KSM, KMA),
+                Some(0),
+                (1000, 1000)
+            ));
+            assert_eq!(Balances::free_balance(&ALICE), 3000);
+        })
+}
+
+#[test]
+fn reward_vesting_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                EmissionCurve::Constant,
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5  // withdraw_limit_count
+            ));
+            let pool_id = 0;
+            let reward_vesting = RewardVesting {
+                duration: 100,
+                penalty: Perbill::from_percent(50),
+                penalty_destination: PenaltyDestination::Pool,
+            };
+            assert_noop!(
+                Farming::set_reward_vesting(
+                    RuntimeOrigin::signed(BOB),
+                    pool_id,
+                    Some(reward_vesting)
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Farming::set_reward_vesting(
+                    RuntimeOrigin::signed(ALICE),
+                    pool_id,
+                    Some(RewardVesting {
+                        duration: 0,
+                        ..reward_vesting
+                    })
+                ),
+                Error::<Runtime>::InvalidPoolParameter
+            );
+            assert_ok!(Farming::set_reward_vesting(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(reward_vesting)
+            ));
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 100_000)]
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                1000,
+                None
+            ));
+            assert_eq!(Assets::balance(KSM, &ALICE), 2000);
+
+            // The pool starts at block 1, ALICE's half of 10 blocks of rewards is locked
+            System::set_block_number(11);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 2000);
+            assert_eq!(Assets::balance(KSM, &Farming::vesting_account()), 5000);
+            assert_noop!(
+                Farming::claim_vested(RuntimeOrigin::signed(ALICE), pool_id),
+                Error::<Runtime>::NoVestingRewards
+            );
+            assert_noop!(
+                Farming::claim_vested(RuntimeOrigin::signed(BOB), pool_id),
+                Error::<Runtime>::NoVestingRewards
+            );
+
+            System::set_block_number(61);
+            assert_eq!(
+                Farming::user_vestings(&ALICE),
+                vec![VestingPosition {
+                    pid: pool_id,
+                    currency_id: KSM,
+                    claimable: 2500,
+                    locked: 2500,
+                    unlocked_at: 111,
+                    exit_penalty: 1250,
+                }]
+            );
+            assert_ok!(Farming::claim_vested(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 4500);
+
+            // 500 more are released, half of the other 2000 go to the pool shares
+            System::set_block_number(71);
+            assert_ok!(Farming::exit_vesting(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 6000);
+            assert_eq!(Assets::balance(KSM, &Farming::vesting_account()), 0);
+            assert_eq!(Farming::vesting_ledgers(&ALICE, pool_id), None);
+            assert_eq!(
+                Farming::get_farming_rewards(&BOB, pool_id).unwrap(),
+                vec![(KSM, 35_500)]
+            );
+
+            assert_ok!(Farming::set_reward_vesting(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(RewardVesting {
+                    penalty_destination: PenaltyDestination::Burn,
+                    ..reward_vesting
+                })
+            ));
+            System::set_block_number(81);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            let total_supply = Assets::total_supply(KSM);
+            assert_ok!(Farming::exit_vesting(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 23_750);
+            assert_eq!(Assets::total_supply(KSM), total_supply - 17_750);
+        })
+}
+
+#[test]
+fn reward_vesting_should_keep_earlier_locks() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                EmissionCurve::Constant,
+                None,
+                0, // min_deposit_to_start
+                0, // after_block_to_start
+                0, // withdraw_limit_time
+                0, // claim_limit_time
+                5  // withdraw_limit_count
+            ));
+            let pool_id = 0;
+            let reward_vesting = RewardVesting {
+                duration: 100,
+                penalty: Perbill::from_percent(50),
+                penalty_destination: PenaltyDestination::Pool,
+            };
+            assert_ok!(Farming::set_reward_vesting(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(reward_vesting)
+            ));
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 100_000)]
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                1000,
+                None
+            ));
+
+            System::set_block_number(11);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+
+            // The 2500 still locked until 111 and the 25_000 claimed now for 100 blocks end
+            // together at their weighted end instead of restarting
+            System::set_block_number(61);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(ALICE), pool_id));
+            let position = VestingPosition {
+                pid: pool_id,
+                currency_id: KSM,
+                claimable: 2500,
+                locked: 27_500,
+                unlocked_at: 156,
+                exit_penalty: 13_750,
+            };
+            assert_eq!(Farming::user_vestings(&ALICE), vec![position.clone()]);
+
+            // Raising the penalty does not apply to the rewards locked before
+            assert_ok!(Farming::set_reward_vesting(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(RewardVesting {
+                    penalty: Perbill::from_percent(100),
+                    ..reward_vesting
+                })
+            ));
+            assert_eq!(Farming::user_vestings(&ALICE), vec![position]);
+            assert_ok!(Farming::exit_vesting(RuntimeOrigin::signed(ALICE), pool_id));
+            assert_eq!(Assets::balance(KSM, &ALICE), 2000 + 16_250);
+        })
+}
+
+#[test]
+fn creator_should_only_loosen_reward_vesting() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(create_permissionless_pool(BOB, KSM));
+            let pool_id = 0;
+            let reward_vesting = RewardVesting {
+                duration: 100,
+                penalty: Perbill::from_percent(50),
+                penalty_destination: PenaltyDestination::Pool,
+            };
+            // The creator is free to set the lock-up until the pool has shares
+            assert_ok!(Farming::set_reward_vesting(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                Some(reward_vesting)
+            ));
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 100_000)]
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+
+            assert_noop!(
+                Farming::set_reward_vesting(
+                    RuntimeOrigin::signed(BOB),
+                    pool_id,
+                    Some(RewardVesting {
+                        duration: 200,
+                        ..reward_vesting
+                    })
+                ),
+                Error::<Runtime>::EditNotAllowed
+            );
+            assert_noop!(
+                Farming::set_reward_vesting(
+                    RuntimeOrigin::signed(BOB),
+                    pool_id,
+                    Some(RewardVesting {
+                        penalty: Perbill::from_percent(60),
+                        ..reward_vesting
+                    })
+                ),
+                Error::<Runtime>::EditNotAllowed
+            );
+            let shorter = RewardVesting {
+                duration: 50,
+                ..reward_vesting
+            };
+            assert_ok!(Farming::set_reward_vesting(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                Some(shorter)
+            ));
+            assert_eq!(Farming::reward_vestings(pool_id), Some(shorter));
+            assert_ok!(Farming::set_reward_vesting(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                None
+            ));
+            assert_noop!(
+                Farming::set_reward_vesting(
+                    RuntimeOrigin::signed(BOB),
+                    pool_id,
+                    Some(reward_vesting)
+                ),
+                Error::<Runtime>::EditNotAllowed
+            );
+
+            // Governance is not bound to loosen the lock-up
+            assert_ok!(Farming::set_reward_vesting(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                Some(reward_vesting)
+            ));
+            assert_eq!(Farming::reward_vestings(pool_id), Some(reward_vesting));
+        })
+}
+
+#[test]
+fn vesting_account_should_differ_from_reward_issuers() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, _) = init_gauge_900();
+            let pool_info = Farming::pool_infos(pool_id).unwrap();
+            assert_eq!(pool_id, 0);
+            assert_ne!(Farming::vesting_account(), pool_info.reward_issuer);
+            assert_ne!(Farming::vesting_account(), pool_info.keeper);
+        })
+}
+
+#[test]
+fn transfer_shares_should_work() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            let (pool_id, tokens) = init_no_gauge();
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                tokens,
+                None
+            ));
+            let bob_balance = Assets::balance(KSM, &BOB);
+
+            assert_noop!(
+                Farming::transfer_shares(RuntimeOrigin::signed(ALICE), pool_id, ALICE, 500),
+                Error::<Runtime>::InvalidShareTransfer
+            );
+            assert_noop!(
+                Farming::transfer_shares(RuntimeOrigin::signed(ALICE), pool_id, BOB, 0),
+                Error::<Runtime>::InvalidShareTransfer
+            );
+            assert_noop!(
+                Farming::transfer_shares(RuntimeOrigin::signed(ALICE), pool_id, BOB, 1001),
+                Error::<Runtime>::InsufficientShares
+            );
+            assert_noop!(
+                Farming::transfer_shares(RuntimeOrigin::signed(CHARLIE), pool_id, BOB, 1),
+                Error::<Runtime>::ShareInfoNotExists
+            );
+
+            // The pool starts at block 1, the rewards of 10 blocks are split in half
+            System::set_block_number(11);
+            assert_ok!(Farming::transfer_shares(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                BOB,
+                500
+            ));
+            assert_eq!(Assets::balance(KSM, &ALICE), 7000);
+            assert_eq!(
+                Farming::shares_and_withdrawn_rewards(pool_id, &ALICE)
+                    .unwrap()
+                    .share,
+                500
+            );
+            assert_eq!(
+                Farming::shares_and_withdrawn_rewards(pool_id, &BOB)
+                    .unwrap()
+                    .share,
+                1500
+            );
+            assert_eq!(Farming::pool_infos(pool_id).unwrap().total_shares, 2000);
+            assert_eq!(
+                Farming::get_farming_rewards(&BOB, pool_id).unwrap(),
+                vec![(KSM, 5000)]
+            );
+            assert_eq!(
+                Farming::get_farming_rewards(&ALICE, pool_id).unwrap(),
+                vec![]
+            );
+
+            System::set_block_number(21);
+            assert_eq!(
+                Farming::get_farming_rewards(&ALICE, pool_id).unwrap(),
+                vec![(KSM, 2500)]
+            );
+            assert_eq!(
+                Farming::get_farming_rewards(&BOB, pool_id).unwrap(),
+                vec![(KSM, 12_500)]
+            );
+
+            // All shares of ALICE go to a new account, leaving nothing behind
+            assert_ok!(Farming::transfer_shares(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                CHARLIE,
+                500
+            ));
+            assert_eq!(Assets::balance(KSM, &ALICE), 9500);
+            assert_eq!(Farming::shares_and_withdrawn_rewards(pool_id, &ALICE), None);
+            assert_eq!(
+                Farming::shares_and_withdrawn_rewards(pool_id, &CHARLIE)
+                    .unwrap()
+                    .share,
+                500
+            );
+            assert_eq!(
+                Farming::get_farming_rewards(&CHARLIE, pool_id).unwrap(),
+                vec![]
+            );
+
+            System::set_block_number(31);
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(BOB), pool_id));
+            assert_ok!(Farming::claim(RuntimeOrigin::signed(CHARLIE), pool_id));
+            assert_eq!(Assets::balance(KSM, &BOB), bob_balance + 20_000);
+            assert_eq!(Assets::balance(KSM, &CHARLIE), 2500);
+        });
+}
+
+#[test]
+fn transfer_shares_should_respect_claim_limits() {
+    ExtBuilder::default()
+        .one_hundred_for_alice_n_bob()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Farming::create_farming_pool(
+                RuntimeOrigin::signed(ALICE),
+                vec![(KSM, Perbill::from_percent(100))],
+                vec![(KSM, 1000)],
+                EmissionCurve::Constant,
+                None,
+                0,  // min_deposit_to_start
+                10, // after_block_to_start
+                0,  // withdraw_limit_time
+                15, // claim_limit_time
+                5   // withdraw_limit_count
+            ));
+            let pool_id = 0;
+            assert_ok!(Farming::charge(
+                RuntimeOrigin::signed(BOB),
+                pool_id,
+                vec![(KSM, 100_000)]
+            ));
+            assert_ok!(Farming::deposit(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                1000,
+                None
+            ));
+            let alice_balance = Assets::balance(KSM, &ALICE);
+
+            // The rewards claimed on transfer can not be claimed before the pool starts
+            System::set_block_number(5);
+            assert_noop!(
+                Farming::transfer_shares(RuntimeOrigin::signed(ALICE), pool_id, BOB, 500),
+                Error::<Runtime>::InvalidPoolState
+            );
+            System::set_block_number(12);
+            assert_noop!(
+                Farming::transfer_shares(RuntimeOrigin::signed(ALICE), pool_id, BOB, 500),
+                Error::<Runtime>::CanNotClaim
+            );
+
+            System::set_block_number(15);
+            assert_ok!(Farming::transfer_shares(
+                RuntimeOrigin::signed(ALICE),
+                pool_id,
+                BOB,
+                500
+            ));
+            assert_eq!(Assets::balance(KSM, &ALICE), alice_balance + 5000);
+            assert_eq!(
+                Farming::shares_and_withdrawn_rewards(pool_id, &ALICE)
+                    .unwrap()
+                    .claim_last_block,
+                15
+            );
+        });
+}
@@ -0,0 +1,279 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::pallet_prelude::*;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+    traits::{AccountIdConversion, AtLeast32BitUnsigned, Saturating, Zero},
+    Perbill, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+
+use crate::*;
+
+/// Where the penalty of an early exit from reward vesting goes.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PenaltyDestination {
+    /// Added to the rewards of the shares of the pool, burned if the pool has no shares
+    Pool,
+    /// Burned
+    Burn,
+}
+
+/// The lock-up of the rewards claimed from a farming pool.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RewardVesting<BlockNumber> {
+    /// The number of blocks claimed rewards are released over linearly
+    pub duration: BlockNumber,
+    /// The part of the locked rewards an early exit forfeits
+    pub penalty: Perbill,
+    pub penalty_destination: PenaltyDestination,
+}
+
+/// The claimed rewards of an account from a farming pool in one currency.
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VestingLedger<BalanceOf, BlockNumberFor> {
+    /// The released rewards which are not claimed yet
+    pub unlocked: BalanceOf,
+    /// The rewards released linearly over the `duration` blocks after `start`
+    pub locked: BalanceOf,
+    pub start: BlockNumberFor,
+    pub duration: BlockNumberFor,
+    /// The part of `locked` an early exit forfeits, the penalty of the pool when they were locked
+    pub penalty: Perbill,
+}
+
+impl<BalanceOf, BlockNumberFor> VestingLedger<BalanceOf, BlockNumberFor>
+where
+    BalanceOf: AtLeast32BitUnsigned + Copy,
+    BlockNumberFor: AtLeast32BitUnsigned + Copy,
+{
+    /// Moves the rewards released up to block `n` from `locked` to `unlocked`.
+    pub fn release(&mut self, n: BlockNumberFor) {
+        let elapsed = n.saturating_sub(self.start).min(self.duration);
+        if elapsed.is_zero() {
+            return;
+        }
+        let released: BalanceOf = U256::from(self.locked.saturated_into::<u128>())
+            .saturating_mul(U256::from(elapsed.saturated_into::<u128>()))
+            .checked_div(U256::from(self.duration.saturated_into::<u128>()))
+            .unwrap_or_default()
+            .as_u128()
+            .saturated_into();
+        self.unlocked = self.unlocked.saturating_add(released);
+        self.locked = self.locked.saturating_sub(released);
+        self.start = self.start.saturating_add(elapsed);
+        self.duration = self.duration.saturating_sub(elapsed);
+    }
+
+    /// The block all locked rewards are released at.
+    pub fn end(&self) -> BlockNumberFor {
+        self.start.saturating_add(self.duration)
+    }
+}
+
+impl<T: Config> Pallet<T>
+where
+    BlockNumberFor<T>: AtLeast32BitUnsigned + Copy,
+    BalanceOf<T>: AtLeast32BitUnsigned + Copy,
+{
+    /// The account claimed rewards are held in while they vest.
+    ///
+    /// A sub account of `RewardIssuer` apart from the reward issuers of the pools, which are its
+    /// sub accounts by pool id.
+    pub fn vesting_account() -> T::AccountId {
+        T::RewardIssuer::get().into_sub_account_truncating(b"vesting")
+    }
+
+    /// Locks the `reward` `who` claimed from the pool into its vesting ledger.
+    ///
+    /// Rewards locked before keep what they released so far. The rest vests together with
+    /// `reward` until the average of their ends weighted by amount, with the likewise weighted
+    /// penalty, so claiming again does not restart the lock-up of earlier rewards.
+    pub(crate) fn lock_reward(
+        who: &T::AccountId,
+        pool_id: PoolId,
+        reward_vesting: &RewardVestingOf<T>,
+        reward_currency: &CurrencyIdOf<T>,
+        reward: BalanceOf<T>,
+        from: &T::AccountId,
+    ) -> DispatchResult {
+        T::MultiCurrency::transfer(*reward_currency, from, &Self::vesting_account(), reward)?;
+
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        VestingLedgers::<T>::mutate(who, pool_id, |maybe_ledgers| {
+            let ledger = maybe_ledgers
+                .get_or_insert_with(BTreeMap::new)
+                .entry(*reward_currency)
+                .or_default();
+            ledger.release(n);
+            let locked = ledger.locked.saturating_add(reward);
+            let weighted = |earlier: u128, new: u128| -> u128 {
+                U256::from(ledger.locked.saturated_into::<u128>())
+                    .saturating_mul(U256::from(earlier))
+                    .saturating_add(
+                        U256::from(reward.saturated_into::<u128>()).saturating_mul(U256::from(new)),
+                    )
+                    .checked_div(U256::from(locked.saturated_into::<u128>()))
+                    .unwrap_or_default()
+                    .as_u128()
+            };
+            let duration = weighted(
+                ledger.end().saturating_sub(n).saturated_into(),
+                reward_vesting.duration.saturated_into(),
+            );
+            let penalty = weighted(
+                ledger.penalty.deconstruct().into(),
+                reward_vesting.penalty.deconstruct().into(),
+            );
+            ledger.locked = locked;
+            ledger.start = n;
+            ledger.duration = duration.saturated_into();
+            ledger.penalty = Perbill::from_parts(penalty.saturated_into());
+        });
+
+        Self::deposit_event(Event::RewardsLocked {
+            who: who.clone(),
+            pid: pool_id,
+            currency_id: *reward_currency,
+            amount: reward,
+        });
+        Ok(())
+    }
+
+    pub fn claim_vested_inner(exchanger: T::AccountId, pool_id: PoolId) -> DispatchResult {
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let vesting_account = Self::vesting_account();
+        let rewards = VestingLedgers::<T>::try_mutate_exists(
+            &exchanger,
+            pool_id,
+            |maybe_ledgers| -> Result<RewardOf<T>, DispatchError> {
+                let ledgers = maybe_ledgers.as_mut().ok_or(Error::<T>::NoVestingRewards)?;
+                let mut rewards: RewardOf<T> = Vec::new();
+                for (reward_currency, ledger) in ledgers.iter_mut() {
+                    ledger.release(n);
+                    if ledger.unlocked.is_zero() {
+                        continue;
+                    }
+                    Self::farming_token_transfer(
+                        reward_currency,
+                        ledger.unlocked,
+                        &exchanger,
+                        &vesting_account,
+                    )?;
+                    rewards.push((*reward_currency, ledger.unlocked));
+                    ledger.unlocked = Zero::zero();
+                }
+                ensure!(!rewards.is_empty(), Error::<T>::NoVestingRewards);
+
+                ledgers.retain(|_, ledger| !ledger.locked.is_zero());
+                if ledgers.is_empty() {
+                    *maybe_ledgers = None;
+                }
+                Ok(rewards)
+            },
+        )?;
+
+        Self::deposit_event(Event::VestedRewardsClaimed {
+            who: exchanger,
+            pid: pool_id,
+            rewards,
+        });
+        Ok(())
+    }
+
+    /// Pays out all vesting rewards of `exchanger` from the pool, less the early exit penalty
+    /// the locked rewards were locked with if the pool still vests its rewards.
+    pub fn exit_vesting_inner(exchanger: T::AccountId, pool_id: PoolId) -> DispatchResult {
+        let ledgers =
+            VestingLedgers::<T>::take(&exchanger, pool_id).ok_or(Error::<T>::NoVestingRewards)?;
+        let n: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        let vesting_account = Self::vesting_account();
+        let maybe_penalty_destination =
+            Self::reward_vestings(pool_id).map(|reward_vesting| reward_vesting.penalty_destination);
+        let penalty_destination = maybe_penalty_destination.unwrap_or(PenaltyDestination::Burn);
+
+        // Penalties only go to a pool which has shares to add them to
+        let mut maybe_pool_info = match penalty_destination {
+            PenaltyDestination::Pool => Self::pool_infos(pool_id)
+                .map(|mut pool_info| {
+                    Self::accrue_pool_rewards(&mut pool_info, n);
+                    pool_info
+                })
+                .filter(|pool_info| !pool_info.total_shares.is_zero()),
+            PenaltyDestination::Burn => None,
+        };
+
+        let mut rewards: RewardOf<T> = Vec::new();
+        let mut penalties: RewardOf<T> = Vec::new();
+        for (reward_currency, mut ledger) in ledgers {
+            ledger.release(n);
+            let penalty = match maybe_penalty_destination {
+                Some(_) => ledger.penalty * ledger.locked,
+                None => Zero::zero(),
+            };
+            let reward = ledger
+                .unlocked
+                .saturating_add(ledger.locked)
+                .saturating_sub(penalty);
+            if !reward.is_zero() {
+                Self::farming_token_transfer(
+                    &reward_currency,
+                    reward,
+                    &exchanger,
+                    &vesting_account,
+                )?;
+                rewards.push((reward_currency, reward));
+            }
+            if penalty.is_zero() {
+                continue;
+            }
+            match maybe_pool_info {
+                Some(ref mut pool_info) => {
+                    T::MultiCurrency::transfer(
+                        reward_currency,
+                        &vesting_account,
+                        &pool_info.reward_issuer,
+                        penalty,
+                    )?;
+                    pool_info
+                        .rewards
+                        .entry(reward_currency)
+                        .and_modify(|(total_reward, _)| {
+                            *total_reward = total_reward.saturating_add(penalty);
+                        })
+                        .or_insert((penalty, Zero::zero()));
+                }
+                None => T::MultiCurrency::withdraw(reward_currency, &vesting_account, penalty)?,
+            }
+            penalties.push((reward_currency, penalty));
+        }
+        if let Some(pool_info) = maybe_pool_info {
+            PoolInfos::<T>::insert(pool_id, pool_info);
+        }
+
+        Self::deposit_event(Event::VestingExited {
+            who: exchanger,
+            pid: pool_id,
+            rewards,
+            penalties,
+        });
+        Ok(())
+    }
+}
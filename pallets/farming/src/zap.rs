@@ -0,0 +1,155 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::pallet_prelude::*;
+use sp_runtime::{traits::Saturating, Perbill};
+
+use crate::*;
+
+/// Adds and removes liquidity of currency pairs, e.g. on an AMM like zenlink.
+pub trait LiquidityProvider<AccountId, CurrencyId, Balance> {
+    /// Returns the LP currency of the pair of `currency_0` and `currency_1`, if the pair exists.
+    fn lp_currency(currency_0: CurrencyId, currency_1: CurrencyId) -> Option<CurrencyId>;
+
+    /// Adds liquidity of `who` to the pair of `currencies`, minting the LP currency to `who`.
+    fn add_liquidity(
+        who: &AccountId,
+        currencies: (CurrencyId, CurrencyId),
+        amounts_desired: (Balance, Balance),
+        amounts_min: (Balance, Balance),
+    ) -> DispatchResult;
+
+    /// Burns `liquidity` of the LP currency of `who`, paying both underlying currencies to `who`.
+    fn remove_liquidity(
+        who: &AccountId,
+        currencies: (CurrencyId, CurrencyId),
+        liquidity: Balance,
+        amounts_min: (Balance, Balance),
+    ) -> DispatchResult;
+}
+
+impl<AccountId, CurrencyId, Balance> LiquidityProvider<AccountId, CurrencyId, Balance> for () {
+    fn lp_currency(_currency_0: CurrencyId, _currency_1: CurrencyId) -> Option<CurrencyId> {
+        None
+    }
+
+    fn add_liquidity(
+        _who: &AccountId,
+        _currencies: (CurrencyId, CurrencyId),
+        _amounts_desired: (Balance, Balance),
+        _amounts_min: (Balance, Balance),
+    ) -> DispatchResult {
+        Err(DispatchError::Other("no liquidity provider"))
+    }
+
+    fn remove_liquidity(
+        _who: &AccountId,
+        _currencies: (CurrencyId, CurrencyId),
+        _liquidity: Balance,
+        _amounts_min: (Balance, Balance),
+    ) -> DispatchResult {
+        Err(DispatchError::Other("no liquidity provider"))
+    }
+}
+
+impl<T: Config> Pallet<T>
+where
+    BlockNumberFor<T>: AtLeast32BitUnsigned + Copy,
+    BalanceOf<T>: AtLeast32BitUnsigned + Copy,
+{
+    /// Returns the LP currency of `currencies` if the pool takes shares in it alone.
+    fn ensure_liquidity_pool(
+        pool_info: &PoolInfoOf<T>,
+        currencies: (CurrencyIdOf<T>, CurrencyIdOf<T>),
+    ) -> Result<CurrencyIdOf<T>, DispatchError> {
+        let lp_currency = T::LiquidityProvider::lp_currency(currencies.0, currencies.1)
+            .ok_or(Error::<T>::NotLiquidityPool)?;
+        ensure!(
+            pool_info.tokens_proportion.len() == 1
+                && pool_info.basic_token == (lp_currency, Perbill::one()),
+            Error::<T>::NotLiquidityPool
+        );
+        Ok(lp_currency)
+    }
+
+    pub fn zap_deposit_inner(
+        exchanger: T::AccountId,
+        pool_id: PoolId,
+        currencies: (CurrencyIdOf<T>, CurrencyIdOf<T>),
+        amounts_desired: (BalanceOf<T>, BalanceOf<T>),
+        amounts_min: (BalanceOf<T>, BalanceOf<T>),
+        min_liquidity: BalanceOf<T>,
+        gauge_info: Option<(BalanceOf<T>, BlockNumberFor<T>)>,
+    ) -> DispatchResult {
+        let pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        let lp_currency = Self::ensure_liquidity_pool(&pool_info, currencies)?;
+
+        // The minted LP amount is measured, the provider may not take the desired amounts
+        let lp_before = T::MultiCurrency::free_balance(lp_currency, &exchanger);
+        T::LiquidityProvider::add_liquidity(&exchanger, currencies, amounts_desired, amounts_min)?;
+        let liquidity =
+            T::MultiCurrency::free_balance(lp_currency, &exchanger).saturating_sub(lp_before);
+        ensure!(
+            !liquidity.is_zero() && liquidity >= min_liquidity,
+            Error::<T>::SlippageExceeded
+        );
+
+        Self::deposit_farming(exchanger.clone(), pool_id, liquidity, gauge_info)?;
+
+        Self::deposit_event(Event::LiquidityZapped {
+            who: exchanger,
+            pid: pool_id,
+            liquidity,
+        });
+        Ok(())
+    }
+
+    pub fn withdraw_and_remove_liquidity_inner(
+        exchanger: T::AccountId,
+        pool_id: PoolId,
+        currencies: (CurrencyIdOf<T>, CurrencyIdOf<T>),
+        remove_value: Option<BalanceOf<T>>,
+        amounts_min: (BalanceOf<T>, BalanceOf<T>),
+    ) -> DispatchResult {
+        let pool_info = Self::pool_infos(pool_id).ok_or(Error::<T>::PoolDoesNotExist)?;
+        let lp_currency = Self::ensure_liquidity_pool(&pool_info, currencies)?;
+
+        // Only the LP currency actually unstaked is removed, `withdraw_limit_time` may hold it back
+        let lp_before = T::MultiCurrency::free_balance(lp_currency, &exchanger);
+        Self::withdraw_and_unstake(exchanger.clone(), pool_id, remove_value)?;
+        let liquidity =
+            T::MultiCurrency::free_balance(lp_currency, &exchanger).saturating_sub(lp_before);
+        ensure!(!liquidity.is_zero(), Error::<T>::NothingUnstaked);
+
+        let before = (
+            T::MultiCurrency::free_balance(currencies.0, &exchanger),
+            T::MultiCurrency::free_balance(currencies.1, &exchanger),
+        );
+        T::LiquidityProvider::remove_liquidity(&exchanger, currencies, liquidity, amounts_min)?;
+        let amounts = (
+            T::MultiCurrency::free_balance(currencies.0, &exchanger).saturating_sub(before.0),
+            T::MultiCurrency::free_balance(currencies.1, &exchanger).saturating_sub(before.1),
+        );
+
+        Self::deposit_event(Event::LiquidityRemoved {
+            who: exchanger,
+            pid: pool_id,
+            liquidity,
+            amounts,
+        });
+        Ok(())
+    }
+}
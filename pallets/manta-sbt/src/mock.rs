@@ -18,7 +18,10 @@
 
 use frame_support::{
     derive_impl, parameter_types,
-    traits::{AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU32, ConstU64, Everything, IsInVec},
+    traits::{
+        fungibles::Destroy, AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU32, ConstU64,
+        Everything, IsInVec, Nothing,
+    },
     weights::RuntimeDbWeight,
     PalletId,
 };
@@ -230,6 +233,28 @@ impl AssetRegistry for MantaAssetRegistry {
             metadata.is_frozen,
         )
     }
+
+    fn start_destroy_asset(asset_id: &StandardAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId32>>::start_destroy(*asset_id, None)
+    }
+
+    fn destroy_asset_accounts(
+        asset_id: &StandardAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId32>>::destroy_accounts(*asset_id, max_items)
+    }
+
+    fn destroy_asset_approvals(
+        asset_id: &StandardAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId32>>::destroy_approvals(*asset_id, max_items)
+    }
+
+    fn finish_destroy_asset(asset_id: &StandardAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId32>>::finish_destroy(*asset_id)
+    }
 }
 
 parameter_types! {
@@ -283,7 +308,10 @@ impl pallet_asset_manager::Config for Test {
     type PermissionlessStartId = ConstU128<100>;
     type TokenNameMaxLen = ConstU32<100>;
     type TokenSymbolMaxLen = ConstU32<100>;
+    type TokenLogoMaxLen = ConstU32<100>;
     type PermissionlessAssetRegistryCost = ConstU128<1000>;
+    type AssetInUse = Nothing;
+    type PairReserves = ();
     type WeightInfo = ();
 }
 
@@ -0,0 +1,243 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Audit trail of lottery drawings
+//!
+//! When the [`Config::DrawingFreezeout`] before a drawing starts, the participants can no longer change.
+//! From that block on the pallet hashes up to [`Config::MaxCommittedSlotsPerBlock`] participant slots per block
+//! into a Merkle root over the participants in slot order ( see [`crate::participants`] ), keeping its progress in
+//! [`PendingCommitment`]. The complete root is kept in [`DrawingCommitment`], a drawing commits any slots left
+//! itself and records this root, its randomness and the sampled winning ticket in [`DrawingHistory`].
+//!
+//! Anyone can then recompute the winner off-chain: With `ticket_blocks` of the drawing, a participant's tickets
+//! are `balance * ticket_blocks - ticket_penalty` and the winner is the first leaf whose cumulative tickets exceed
+//! the winning ticket. [`Pallet::participant_proof`] proves that a leaf is part of the committed snapshot.
+//!
+//! The tree hashes leaves as `hash(0u8 ++ SCALE(leaf))` and inner nodes as `hash(1u8 ++ left ++ right)` with
+//! [`frame_system::Config::Hashing`], a node without a sibling is promoted to the next level unchanged.
+//! The prefixes keep an inner node from passing as a leaf whose encoding happens to be two hashes.
+//! This tree is the same as folding the perfect subtrees over the leaves from the right, which lets the
+//! root be built a leaf at a time from the roots of these subtrees.
+
+use super::*;
+use codec::Encode;
+use sp_runtime::traits::Hash;
+use sp_std::{vec, vec::Vec};
+
+/// Hash of a leaf of the participants tree
+pub(crate) fn leaf_hash<T: Config<I>, I: 'static>(leaf: &impl Encode) -> T::Hash {
+    T::Hashing::hash_of(&(0u8, leaf))
+}
+
+/// Hash of an inner node of the participants tree
+pub(crate) fn node_hash<T: Config<I>, I: 'static>(left: &T::Hash, right: &T::Hash) -> T::Hash {
+    T::Hashing::hash_of(&(1u8, left, right))
+}
+
+/// Hashes of the level above `nodes`
+fn next_level<T: Config<I>, I: 'static>(nodes: &[T::Hash]) -> Vec<T::Hash> {
+    nodes
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash::<T, I>(left, right),
+            _ => pair[0],
+        })
+        .collect()
+}
+
+/// Merkle root over `leaves`, the default hash if there are none
+fn merkle_root<T: Config<I>, I: 'static>(leaves: &[ParticipantLeafOf<T>]) -> T::Hash {
+    let mut nodes: Vec<_> = leaves.iter().map(leaf_hash::<T, I>).collect();
+    while nodes.len() > 1 {
+        nodes = next_level::<T, I>(&nodes);
+    }
+    nodes.pop().unwrap_or_default()
+}
+
+/// Adds the leaf hashed to `hash` to the perfect subtrees `peaks`, merging subtrees of equal height
+fn push_leaf<T: Config<I>, I: 'static>(peaks: &mut Vec<(u8, T::Hash)>, mut hash: T::Hash) {
+    let mut height = 0u8;
+    while let Some(&(peak_height, peak)) = peaks.last() {
+        if peak_height != height {
+            break;
+        }
+        peaks.pop();
+        hash = node_hash::<T, I>(&peak, &hash);
+        height = height.saturating_add(1);
+    }
+    peaks.push((height, hash));
+}
+
+/// Merkle root over the leaves of the perfect subtrees `peaks`, the default hash if there are none
+fn fold_peaks<T: Config<I>, I: 'static>(peaks: &[(u8, T::Hash)]) -> T::Hash {
+    peaks
+        .iter()
+        .rev()
+        .map(|(_, peak)| *peak)
+        .reduce(|right, left| node_hash::<T, I>(&left, &right))
+        .unwrap_or_default()
+}
+
+/// Sibling hashes from the leaf at `index` up to the root, skipping levels where the node has no sibling
+fn merkle_proof<T: Config<I>, I: 'static>(
+    leaves: &[ParticipantLeafOf<T>],
+    mut index: usize,
+) -> Vec<T::Hash> {
+    let mut nodes: Vec<_> = leaves.iter().map(leaf_hash::<T, I>).collect();
+    let mut proof = vec![];
+    while nodes.len() > 1 {
+        if let Some(sibling) = nodes.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        nodes = next_level::<T, I>(&nodes);
+        index /= 2;
+    }
+    proof
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+    /// Leaf of the participant in `slot`, `None` if the slot is free
+    fn participant_leaf(slot: u32) -> Option<ParticipantLeafOf<T>> {
+        SlotOwner::<T, I>::get(slot).map(|account| ParticipantLeaf {
+            balance: Self::active_balance_per_user(&account),
+            ticket_penalty: Self::ticket_penalty_of(&account),
+            account,
+        })
+    }
+
+    /// Active participants in slot order, the leaves of the participants tree
+    pub(crate) fn participant_leaves() -> Vec<ParticipantLeafOf<T>> {
+        (1..=ParticipantSlots::<T, I>::get())
+            .filter_map(Self::participant_leaf)
+            .collect()
+    }
+
+    /// Starts committing to the participants of the drawing scheduled at `drawing_at`, replacing any earlier commitment
+    pub(crate) fn start_participants_commitment(drawing_at: BlockNumberFor<T>) {
+        DrawingCommitment::<T, I>::kill();
+        PendingCommitment::<T, I>::put(PendingCommitment {
+            block: <frame_system::Pallet<T>>::block_number(),
+            drawing_at,
+            next_slot: 1,
+            participants: 0,
+            peaks: Vec::new(),
+        });
+    }
+
+    /// Hashes up to `max_slots` participant slots into the pending commitment, completing it in
+    /// [`DrawingCommitment`] after the last slot. Returns the number of slots hashed
+    pub(crate) fn commit_participants(max_slots: u32) -> u32 {
+        let mut pending = match Self::pending_commitment() {
+            Some(pending) => pending,
+            None => return 0,
+        };
+        let slots = ParticipantSlots::<T, I>::get();
+        let end = pending
+            .next_slot
+            .saturating_add(max_slots)
+            .min(slots.saturating_add(1));
+        let committed = end.saturating_sub(pending.next_slot);
+        for slot in pending.next_slot..end {
+            if let Some(leaf) = Self::participant_leaf(slot) {
+                push_leaf::<T, I>(&mut pending.peaks, leaf_hash::<T, I>(&leaf));
+                pending.participants = pending.participants.saturating_add(1);
+            }
+        }
+        pending.next_slot = pending.next_slot.max(end);
+        if pending.next_slot <= slots {
+            PendingCommitment::<T, I>::put(pending);
+            return committed;
+        }
+        let commitment = SnapshotCommitment {
+            block: pending.block,
+            drawing_at: pending.drawing_at,
+            participants_root: fold_peaks::<T, I>(&pending.peaks),
+            participants: pending.participants,
+            randomness_subject: Self::randomness_subject(0).to_vec(),
+        };
+        log::debug!("committing to drawing participants {:?}", commitment);
+        DrawingCommitment::<T, I>::put(commitment);
+        PendingCommitment::<T, I>::kill();
+        committed
+    }
+
+    /// Drops the commitment to the participants of the next drawing and any commitment in progress
+    pub(crate) fn clear_participants_commitment() {
+        DrawingCommitment::<T, I>::kill();
+        PendingCommitment::<T, I>::kill();
+    }
+
+    /// Proof that `account` is part of the committed participants of the upcoming drawing
+    /// Returns `None` if there is no commitment, the account is not participating or the participants changed since
+    pub fn participant_proof(account: T::AccountId) -> Option<ParticipantProofOf<T>> {
+        let commitment = Self::drawing_commitment()?;
+        let leaves = Self::participant_leaves();
+        if merkle_root::<T, I>(&leaves) != commitment.participants_root {
+            return None;
+        }
+        let leaf_index = leaves.iter().position(|leaf| leaf.account == account)?;
+        Some(ParticipantProof {
+            proof: merkle_proof::<T, I>(&leaves, leaf_index),
+            leaf: leaves[leaf_index].clone(),
+            leaf_index: leaf_index as u32,
+            number_of_leaves: leaves.len() as u32,
+        })
+    }
+
+    /// Returns true if `proof` shows its leaf is part of the participants tree with root `root`
+    pub fn verify_participant_proof(root: T::Hash, proof: &ParticipantProofOf<T>) -> bool {
+        Self::verify_leaf_hash(
+            root,
+            leaf_hash::<T, I>(&proof.leaf),
+            proof.leaf_index,
+            proof.number_of_leaves,
+            &proof.proof,
+        )
+    }
+
+    /// Returns true if the sibling hashes of `proof` lead from the leaf hash `hash` at `leaf_index` to `root`
+    pub(crate) fn verify_leaf_hash(
+        root: T::Hash,
+        mut hash: T::Hash,
+        leaf_index: u32,
+        number_of_leaves: u32,
+        proof: &[T::Hash],
+    ) -> bool {
+        if leaf_index >= number_of_leaves {
+            return false;
+        }
+        let mut index = leaf_index;
+        let mut width = number_of_leaves;
+        let mut siblings = proof.iter();
+        while width > 1 {
+            // the last node of an odd level has no sibling and is promoted unchanged
+            if index ^ 1 < width {
+                let sibling = match siblings.next() {
+                    Some(sibling) => sibling,
+                    None => return false,
+                };
+                hash = if index % 2 == 0 {
+                    node_hash::<T, I>(&hash, sibling)
+                } else {
+                    node_hash::<T, I>(sibling, &hash)
+                };
+            }
+            index /= 2;
+            width = (width + 1) / 2;
+        }
+        siblings.next().is_none() && hash == root
+    }
+}
@@ -18,7 +18,7 @@
 
 //! Benchmarking
 use crate::{Call, Config, Pallet, Request};
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_benchmarking::{benchmarks_instance_pallet, impl_benchmark_test_suite};
 use frame_support::{
     assert_ok,
     traits::{Currency, EstimateCallFee, Get, OnFinalize, OnInitialize},
@@ -28,13 +28,13 @@ use pallet_parachain_staking::{
     benchmarks::{create_funded_collator, create_funded_user, parachain_staking_on_finalize},
     BalanceOf, Pallet as Staking,
 };
-use sp_runtime::{traits::Zero, Saturating};
+use sp_runtime::{traits::Zero, Perbill, Saturating};
 
 const MAX_COLLATOR_COUNT: u32 = 63;
 const USER_SEED: u32 = 696969;
 
 /// Run to end block and author
-fn roll_rounds_and_author<T: Config>(rounds: u32) {
+fn roll_rounds_and_author<T: Config<I>, I: 'static>(rounds: u32) {
     let total_rounds = rounds + 1u32;
     let round_length: BlockNumberFor<T> = Staking::<T>::round().length.into();
     let mut now = <frame_system::Pallet<T>>::block_number() + 1u32.into();
@@ -53,14 +53,14 @@ fn roll_rounds_and_author<T: Config>(rounds: u32) {
     }
 }
 
-fn fund_lottery_account<T: Config>(bal: BalanceOf<T>) {
+fn fund_lottery_account<T: Config<I>, I: 'static>(bal: BalanceOf<T>) {
     <T as pallet_parachain_staking::Config>::Currency::deposit_creating(
-        &Pallet::<T>::account_id(),
+        &Pallet::<T, I>::account_id(),
         bal,
     );
 }
 
-fn register_collators<T: Config>(number: u32) {
+fn register_collators<T: Config<I>, I: 'static>(number: u32) {
     let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
     let mut collator_seed: u32 = 444;
     for _ in 0..number {
@@ -75,41 +75,41 @@ fn register_collators<T: Config>(number: u32) {
     }
 }
 
-fn deposit_prior_users<T: Config>(number: u32, amount: BalanceOf<T>) {
+fn deposit_prior_users<T: Config<I>, I: 'static>(number: u32, amount: BalanceOf<T>) {
     for user in 0..number {
         <frame_system::Pallet<T>>::set_block_number(user.into());
         let (depositor, _) = create_funded_user::<T>("depositor", USER_SEED - 1 - user, amount);
-        assert_ok!(Pallet::<T>::deposit(
+        assert_ok!(Pallet::<T, I>::deposit(
             RawOrigin::Signed(depositor).into(),
             amount
         ));
     }
 }
 
-benchmarks! {
+benchmarks_instance_pallet! {
     // USER DISPATCHABLES
 
     deposit {
         let x in 0u32..1_000u32; // other users that have already deposited to the lottery previously
         let y in 0..MAX_COLLATOR_COUNT; // registered collators
 
-        fund_lottery_account::<T>(Pallet::<T>::gas_reserve());
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
         let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
         let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
         let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
         // fill collators
-        register_collators::<T>(y);
+        register_collators::<T, I>(y);
         assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
-        assert_eq!(Pallet::<T>::total_pot(), Zero::zero());
+        assert_eq!(Pallet::<T, I>::total_pot(), Zero::zero());
 
         let original_staked_amount = Staking::<T>::total();
-        deposit_prior_users::<T>(x,deposit_amount);
+        deposit_prior_users::<T, I>(x,deposit_amount);
 
         let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
     }: _(RawOrigin::Signed(caller.clone()), deposit_amount)
     verify {
-        assert_eq!(Pallet::<T>::active_balance_per_user(caller), deposit_amount);
-        assert_eq!(Pallet::<T>::total_pot(), deposit_amount.saturating_mul((x+1).into()));
+        assert_eq!(Pallet::<T, I>::active_balance_per_user(caller), deposit_amount);
+        assert_eq!(Pallet::<T, I>::total_pot(), deposit_amount.saturating_mul((x+1).into()));
         assert_eq!(Staking::<T>::total(), original_staked_amount + deposit_amount.saturating_mul((x+1).into()));
     }
 
@@ -117,81 +117,165 @@ benchmarks! {
         let x in 0..1_000; // other users that have already deposited to the lottery previously
         let y in 0..MAX_COLLATOR_COUNT; // registered collators
 
-        fund_lottery_account::<T>(Pallet::<T>::gas_reserve());
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
         let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
         let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
         let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
         // fill collators
-        register_collators::<T>(y);
+        register_collators::<T, I>(y);
         assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
 
         let original_staked_amount = Staking::<T>::total();
-        deposit_prior_users::<T>(x,deposit_amount);
+        deposit_prior_users::<T, I>(x,deposit_amount);
 
         let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
-        assert_ok!(Pallet::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
-        assert_eq!(Pallet::<T>::active_balance_per_user(caller.clone()), deposit_amount);
+        assert_ok!(Pallet::<T, I>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        assert_eq!(Pallet::<T, I>::active_balance_per_user(caller.clone()), deposit_amount);
     }: _(RawOrigin::Signed(caller.clone()), deposit_amount)
     verify {
-        assert!(Pallet::<T>::active_balance_per_user(caller.clone()).is_zero());
+        assert!(Pallet::<T, I>::active_balance_per_user(caller.clone()).is_zero());
         let now = <frame_system::Pallet<T>>::block_number();
         let should_be_request = Request {
             user: caller.clone(),
             block: now,
             balance: deposit_amount,
         };
-        let mut request_queue = Pallet::<T>::withdrawal_request_queue();
+        let mut request_queue = Pallet::<T, I>::withdrawal_request_queue();
         assert_eq!(request_queue.len(),1usize);
         assert_eq!(request_queue.pop().unwrap(), should_be_request);
     }
 
+    cancel_withdraw_request {
+        let x in 0..1_000; // other users that have already deposited to the lottery previously
+        let y in 0..MAX_COLLATOR_COUNT; // registered collators
+
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
+        let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+        let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
+        let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
+        // fill collators
+        register_collators::<T, I>(y);
+        assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
+
+        deposit_prior_users::<T, I>(x,deposit_amount);
+
+        let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
+        assert_ok!(Pallet::<T, I>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        assert_ok!(Pallet::<T, I>::request_withdraw(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        assert_eq!(Pallet::<T, I>::withdrawal_request_queue().len(), 1usize);
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert_eq!(Pallet::<T, I>::active_balance_per_user(caller.clone()), deposit_amount);
+        assert!(Pallet::<T, I>::withdrawal_request_queue().is_empty());
+    }
+
+    instant_withdraw {
+        let x in 0..1_000; // other users that have already deposited to the lottery previously
+
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
+        let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+        let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
+        deposit_prior_users::<T, I>(x,deposit_amount);
+
+        // keep the caller's deposit unstaked
+        assert_ok!(Pallet::<T, I>::set_liquidity_buffer_target(RawOrigin::Root.into(), deposit_amount));
+        assert_ok!(Pallet::<T, I>::set_instant_withdraw_fee(RawOrigin::Root.into(), Perbill::from_percent(1)));
+        let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
+        assert_ok!(Pallet::<T, I>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        assert_eq!(Pallet::<T, I>::liquidity_buffer(), deposit_amount);
+    }: _(RawOrigin::Signed(caller.clone()), deposit_amount)
+    verify {
+        assert!(Pallet::<T, I>::active_balance_per_user(caller.clone()).is_zero());
+        assert!(Pallet::<T, I>::liquidity_buffer().is_zero());
+    }
+
+    sponsor_deposit {
+        let y in 0..MAX_COLLATOR_COUNT; // registered collators
+
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
+        let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+        let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
+        register_collators::<T, I>(y);
+
+        let original_staked_amount = Staking::<T>::total();
+        let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
+    }: _(RawOrigin::Signed(caller.clone()), deposit_amount)
+    verify {
+        assert_eq!(Pallet::<T, I>::sponsor_balance(caller), deposit_amount);
+        assert!(Pallet::<T, I>::total_pot().is_zero());
+        assert_eq!(Staking::<T>::total(), original_staked_amount + deposit_amount);
+    }
+
+    sponsor_withdraw {
+        let y in 0..MAX_COLLATOR_COUNT; // registered collators
+
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
+        let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+        let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
+        register_collators::<T, I>(y);
+
+        let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
+        assert_ok!(Pallet::<T, I>::sponsor_deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+    }: _(RawOrigin::Signed(caller.clone()), deposit_amount)
+    verify {
+        assert!(Pallet::<T, I>::sponsor_balance(caller.clone()).is_zero());
+        assert_eq!(Pallet::<T, I>::sponsor_withdrawal_request_queue().len(), 1usize);
+    }
+
     claim_my_winnings {
         let y in 0..MAX_COLLATOR_COUNT; // registered collators
 
         // NOTE: We fund 2x gas reserve to have 1x gas reserve to pay out as winnings
-        fund_lottery_account::<T>(Pallet::<T>::gas_reserve().saturating_add(Pallet::<T>::gas_reserve()));
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve().saturating_add(Pallet::<T, I>::gas_reserve()));
 
         let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
         let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
         let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
         // fill collators
-        register_collators::<T>(y);
+        register_collators::<T, I>(y);
         assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
 
         let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
-        assert_ok!(Pallet::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
-        assert_eq!(Pallet::<T>::active_balance_per_user(caller.clone()), deposit_amount);
-        roll_rounds_and_author::<T>(2);
-        assert_ok!(Pallet::<T>::draw_lottery(RawOrigin::Root.into()));
+        assert_ok!(Pallet::<T, I>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        assert_eq!(Pallet::<T, I>::active_balance_per_user(caller.clone()), deposit_amount);
+        roll_rounds_and_author::<T, I>(2);
+        assert_ok!(Pallet::<T, I>::draw_lottery(RawOrigin::Root.into()));
         // should have won now
-        let unclaimed_winnings = Pallet::<T>::total_unclaimed_winnings();
+        let unclaimed_winnings = Pallet::<T, I>::total_unclaimed_winnings();
         let account_balance_before = <T as pallet_parachain_staking::Config>::Currency::free_balance(&caller);
-        let fee_estimate  = T::EstimateCallFee::estimate_call_fee(&Call::<T>::claim_my_winnings {  }, None.into());
+        let fee_estimate  = T::EstimateCallFee::estimate_call_fee(&Call::<T, I>::claim_my_winnings {  }, None.into());
         assert!(!unclaimed_winnings.is_zero());
-        assert_eq!(unclaimed_winnings,Pallet::<T>::unclaimed_winnings_by_account(caller.clone()).unwrap());
+        assert_eq!(unclaimed_winnings,Pallet::<T, I>::unclaimed_winnings_by_account(caller.clone()).unwrap());
     }: _(RawOrigin::Signed(caller.clone()))
     verify {
-        assert!(Pallet::<T>::total_unclaimed_winnings().is_zero());
+        assert!(Pallet::<T, I>::total_unclaimed_winnings().is_zero());
         let account_balance_after = <T as pallet_parachain_staking::Config>::Currency::free_balance(&caller);
-        assert!(Pallet::<T>::unclaimed_winnings_by_account(caller.clone()).is_none());
+        assert!(Pallet::<T, I>::unclaimed_winnings_by_account(caller.clone()).is_none());
         assert!(account_balance_after >= account_balance_before + unclaimed_winnings - fee_estimate);
         assert!(account_balance_after <= account_balance_before + unclaimed_winnings);
     }
 
+    set_auto_compound {
+        let (caller, _) = create_funded_user::<T>("caller", USER_SEED, 0u32.into());
+    }: _(RawOrigin::Signed(caller.clone()), true)
+    verify {
+        assert!(Pallet::<T, I>::auto_compound_winnings(caller));
+    }
+
     // ROOT DISPATCHABLES
     start_lottery {
-                fund_lottery_account::<T>(Pallet::<T>::gas_reserve());
+                fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
     }: _(RawOrigin::Root)
     verify {
-        assert!(Pallet::<T>::next_drawing_at().is_some());
+        assert!(Pallet::<T, I>::next_drawing_at().is_some());
     }
 
     stop_lottery {
-                fund_lottery_account::<T>(Pallet::<T>::gas_reserve());
-        assert_ok!(Pallet::<T>::start_lottery(RawOrigin::Root.into()));
+                fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
+        assert_ok!(Pallet::<T, I>::start_lottery(RawOrigin::Root.into()));
     }: _(RawOrigin::Root)
     verify {
-        assert!(Pallet::<T>::next_drawing_at().is_none());
+        assert!(Pallet::<T, I>::next_drawing_at().is_none());
     }
 
     draw_lottery {
@@ -199,34 +283,64 @@ benchmarks! {
         let y in 0..MAX_COLLATOR_COUNT; // registered collators
 
         // NOTE: We fund 2x gas reserve to have 1x gas reserve to pay out as winnings
-        fund_lottery_account::<T>(Pallet::<T>::gas_reserve().saturating_add(Pallet::<T>::gas_reserve()));
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve().saturating_add(Pallet::<T, I>::gas_reserve()));
 
         let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
         let original_collator_count = Staking::<T>::candidate_pool().len() as u32;
         let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
         // fill collators
-        register_collators::<T>(y);
+        register_collators::<T, I>(y);
         assert_eq!(Staking::<T>::candidate_pool().len() as u32, original_collator_count + y);
 
-        deposit_prior_users::<T>(x,deposit_amount);
+        deposit_prior_users::<T, I>(x,deposit_amount);
 
         let (caller, _) = create_funded_user::<T>("caller", USER_SEED, deposit_amount);
-        assert_ok!(Pallet::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
-        assert_eq!(Pallet::<T>::active_balance_per_user(&caller), deposit_amount);
-        // roll_rounds_and_author::<T>(2);
+        assert_ok!(Pallet::<T, I>::deposit(RawOrigin::Signed(caller.clone()).into(), deposit_amount));
+        assert_eq!(Pallet::<T, I>::active_balance_per_user(&caller), deposit_amount);
+        // roll_rounds_and_author::<T, I>(2);
     }: _(RawOrigin::Root)
     verify {
         // someone should have won now
-        let unclaimed_winnings = Pallet::<T>::total_unclaimed_winnings();
+        let unclaimed_winnings = Pallet::<T, I>::total_unclaimed_winnings();
         assert!(!unclaimed_winnings.is_zero());
     }
 
+    select_winner {
+        let x in 1..1_000; // users that have deposited to the lottery
+
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
+        let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+        let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
+        deposit_prior_users::<T, I>(x, deposit_amount);
+        let prize: BalanceOf<T> = 1u32.into();
+    }: {
+        assert_ok!(Pallet::<T, I>::select_winner(prize));
+    }
+    verify {
+        assert_eq!(Pallet::<T, I>::total_unclaimed_winnings(), prize);
+    }
+
+    commit_participants {
+        let x in 0..<T as Config<I>>::MaxCommittedSlotsPerBlock::get(); // participant slots hashed in one block
+
+        let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+        let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
+        deposit_prior_users::<T, I>(x, deposit_amount);
+        let drawing_at = <frame_system::Pallet<T>>::block_number() + <T as Config<I>>::DrawingFreezeout::get();
+        Pallet::<T, I>::start_participants_commitment(drawing_at);
+    }: {
+        Pallet::<T, I>::commit_participants(x);
+    }
+    verify {
+        assert_eq!(Pallet::<T, I>::drawing_commitment().unwrap().participants, x);
+    }
+
     process_matured_withdrawals {
     }: _(RawOrigin::Root)
     verify {
     }
     set_min_deposit {
-        assert_ok!(Pallet::<T>::set_min_withdraw(RawOrigin::Root.into(),u32::MAX.into()));
+        assert_ok!(Pallet::<T, I>::set_min_withdraw(RawOrigin::Root.into(),u32::MAX.into()));
     }: _(RawOrigin::Root,u32::MAX.into())
     verify {
     }
@@ -238,10 +352,21 @@ benchmarks! {
     }: _(RawOrigin::Root,u32::MAX.into())
     verify {
     }
-    // rebalance_stake {
-    // }: _()
-    // verify {
-    // }
+    rebalance_stake {
+        let y in 1..MAX_COLLATOR_COUNT; // registered collators
+
+        fund_lottery_account::<T, I>(Pallet::<T, I>::gas_reserve());
+        let min_delegator_bond = <<T as pallet_parachain_staking::Config>::MinDelegatorStk as Get<BalanceOf<T>>>::get();
+        let deposit_amount: BalanceOf<T> = min_delegator_bond * 10_000u32.into();
+        // fill collators and spread deposits over them
+        register_collators::<T, I>(y);
+        deposit_prior_users::<T, I>(y, deposit_amount);
+        // only the first collator authors blocks, so all others stop earning rewards
+        roll_rounds_and_author::<T, I>(1);
+    }: _(RawOrigin::Root)
+    verify {
+        assert!(Pallet::<T, I>::collators_to_rebalance().is_empty());
+    }
 
     // liquidate_lottery {
     // }: _(RawOrigin::Root)
@@ -295,17 +420,47 @@ mod tests {
         });
     }
     #[test]
+    fn bench_cancel_withdraw_request() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_cancel_withdraw_request());
+        });
+    }
+    #[test]
+    fn bench_instant_withdraw() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_instant_withdraw());
+        });
+    }
+    #[test]
+    fn bench_sponsor_deposit() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_sponsor_deposit());
+        });
+    }
+    #[test]
+    fn bench_sponsor_withdraw() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_sponsor_withdraw());
+        });
+    }
+    #[test]
     fn bench_claim_my_winnings() {
         new_test_ext().execute_with(|| {
             assert_ok!(Pallet::<Test>::test_benchmark_claim_my_winnings());
         });
     }
-    // #[test]
-    // fn bench_rebalance_stake() {
-    //     new_test_ext().execute_with(|| {
-    //         assert_ok!(Pallet::<Test>::test_benchmark_rebalance_stake());
-    //     });
-    // }
+    #[test]
+    fn bench_set_auto_compound() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_set_auto_compound());
+        });
+    }
+    #[test]
+    fn bench_rebalance_stake() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_rebalance_stake());
+        });
+    }
     #[test]
     fn bench_start_lottery() {
         new_test_ext().execute_with(|| {
@@ -324,6 +479,18 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_draw_lottery());
         });
     }
+    #[test]
+    fn bench_select_winner() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_select_winner());
+        });
+    }
+    #[test]
+    fn bench_commit_participants() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_commit_participants());
+        });
+    }
     // #[test]
     // fn bench_process_matured_withdrawals() {
     //     new_test_ext().execute_with(|| {
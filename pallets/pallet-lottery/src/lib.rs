@@ -28,9 +28,11 @@
 //! 2. A designated manager can start & stop the drawings as well as rebalance the stake to improve the yield generated through staking
 //! 3. In order to prevent gaming of the lottery drawing mechanism, no modifications to this pallet are allowed [`Config::DrawingFreezeout`] blocks before a drawing
 //!     This is needed e.g. using BABE Randomness, where the randomness will be known a day before the scheduled drawing
+//!     During the freezeout, the pallet commits to a Merkle root of all participants in [`DrawingCommitment`] so every drawing recorded in [`DrawingHistory`] can be recomputed off-chain
 //! 4. Winnings must be claimed manually by the winner but there is no time limit for claiming winnings
-//! 5. Deposits are instantly staked by the pallet
+//! 5. Deposits are instantly staked by the pallet, except for funds kept unstaked to refill the liquidity buffer up to [`LiquidityBufferTarget`]
 //! 6. Withdrawals must wait for a timelock imposed by [`pallet_parachain_staking`] and are paid out automatically (via scheduler) in the first lottery drawing after it expires
+//!     Withdrawals of up to [`LiquidityBuffer`] can be paid out instantly for a fee of [`InstantWithdrawFee`] that is added to the prize pool
 //! 7. The [`Config::ManageOrigin`] must at the same time be allowed to use [`frame_support::traits::schedule::Named`] e.g. `ScheduleOrigin` in `pallet_scheduler`
 //!
 //! ## Dependencies
@@ -46,7 +48,10 @@
 //! ### User Dispatchable Functions
 //! * [`Call::deposit`]: Allows any user to deposit tokens into the lottery
 //! * [`Call::request_withdraw`]: Allows any user to request return of their deposited tokens to own wallet
+//! * [`Call::instant_withdraw`]: Allows any user to withdraw deposited tokens from the liquidity buffer for a fee
 //! * [`Call::claim_my_winnings`]: Allows any user to transfer any accrued winnings into their wallet
+//! * [`Call::sponsor_deposit`]: Allows any account to stake tokens that grow the prize pool without being eligible to win
+//! * [`Call::sponsor_withdraw`]: Allows sponsors to request return of their sponsored tokens to own wallet
 //!
 //! ### Manager Dispatchable Functions
 //! * [`Call::start_lottery`]: Schedules periodic lottery drawings to occur each [`Config::DrawingInterval`]
@@ -55,11 +60,15 @@
 //! * [`Call::process_matured_withdrawals`]: Immediately transfer funds of all matured withdrawals to their respective owner's wallets
 //! * [`Call::liquidate_lottery`]: Unstakes all lottery funds and schedules [`Call::process_matured_withdrawals`] after the timelock period
 //! * [`Call::rebalance_stake`]: Immediately unstakes overweight collators (with low APY) for later restaking into underweight collators (with high APY)
+//! * [`Call::set_staking_strategy`]: Selects the [`StakingStrategy`] used to choose collators for staking and unstaking
+//! * [`Call::set_liquidity_buffer_target`]: Sets the amount of user deposits kept unstaked for instant withdrawals
+//! * [`Call::set_instant_withdraw_fee`]: Sets the fee charged on instant withdrawals
 //!
 //! ### Important state queries callable via RPC
 //! * [`Pallet::next_drawing_at`]: Block number where the next drawing will happen
 //! * [`Pallet::not_in_drawing_freezeout`]: False if deposits/withdrawals are currently frozen
 //! * [`Pallet::current_prize_pool`]: Token amount currently in the pallet the winner would get if the drawing was now
+//! * [`Pallet::participant_proof`]: Merkle proof that an account is part of the participants committed to for the next drawing
 //! Call these from a frontend as e.g.
 //! ```bash
 //!    curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0","id":1,"method":"lottery_next_drawing_at","params": []}'
@@ -68,12 +77,25 @@
 //! ```
 //!
 //! Please refer to [`Pallet`] for more documentation on each function.
+//!
+//! ### Instances
+//! The pallet is instantiable to run several independent lotteries, e.g. a daily small-prize lottery next to a monthly jackpot.
+//! Every instance has its own pot account derived from [`Config::LotteryPot`], its own storage and drawing schedule and
+//! delegates from its own pot account, so multiple instances can stake with the same collator.
+//! [`pallet_parachain_staking::Config::OnNewRound`] must notify all instances, e.g. `(Lottery, JackpotLottery)`.
+//! The runtime API and RPC take the [`runtime::LotteryInstance`] to query, the runtime maps each index to one of its instances.
 //! Furthermore, the storage items containing all relevant information about lottery state can be queried via e.g. the [polkadot.js API](https://polkadot.js.org/docs/api)
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod audit;
+mod participants;
 mod staking;
+pub use staking::{
+    BalancedStrategy, RewardHistoryStrategy, StakingStrategy, REWARD_HISTORY_ROUNDS,
+};
 
+pub mod migrations;
 #[cfg(feature = "rpc")]
 pub mod rpc;
 pub mod runtime;
@@ -96,6 +118,7 @@ pub mod pallet {
     use frame_support::{
         ensure,
         pallet_prelude::*,
+        storage::with_storage_layer,
         traits::{
             schedule::{v2::Named as ScheduleNamed, DispatchTime, MaybeHashed, LOWEST_PRIORITY},
             ExistenceRequirement::KeepAlive,
@@ -106,33 +129,37 @@ pub mod pallet {
     use frame_system::{pallet_prelude::*, RawOrigin};
     use manta_primitives::types::PoolId;
     use orml_traits::MultiCurrency;
-    use pallet_parachain_staking::BalanceOf;
+    use pallet_parachain_staking::{BalanceOf, RoundIndex};
     use serde::{Deserialize, Serialize};
     use sp_arithmetic::traits::SaturatedConversion;
     use sp_core::U256;
     use sp_runtime::{
         traits::{AccountIdConversion, CheckedAdd, CheckedSub, Dispatchable, Saturating, Zero},
-        ArithmeticError, DispatchResult,
+        ArithmeticError, DispatchResult, Perbill,
     };
     use sp_std::prelude::*;
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
-    pub type CallOf<T> = <T as Config>::RuntimeCall;
+    pub type CallOf<T, I = ()> = <T as Config<I>>::RuntimeCall;
 
     #[pallet::config]
-    pub trait Config:
-        frame_system::Config + pallet_parachain_staking::Config + pallet_farming::Config
+    pub trait Config<I: 'static = ()>:
+        frame_system::Config
+        + pallet_parachain_staking::Config
+        + pallet_farming::Config
+        + manta_collator_selection::Config
     {
         /// The aggregated `RuntimeCall` type.
         type RuntimeCall: Parameter
             + Dispatchable<RuntimeOrigin = Self::RuntimeOrigin>
-            + From<Call<Self>>;
-        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+            + From<Call<Self, I>>;
+        type RuntimeEvent: From<Event<Self, I>>
+            + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// The Scheduler.
         type Scheduler: ScheduleNamed<
             BlockNumberFor<Self>,
-            CallOf<Self>,
+            CallOf<Self, I>,
             Self::PalletsOrigin,
             Hash = Self::Hash,
         >;
@@ -149,12 +176,13 @@ pub mod pallet {
         type EstimateCallFee: frame_support::traits::EstimateCallFee<
                 pallet_parachain_staking::Call<Self>,
                 BalanceOf<Self>,
-            > + frame_support::traits::EstimateCallFee<Call<Self>, BalanceOf<Self>>;
+            > + frame_support::traits::EstimateCallFee<Call<Self, I>, BalanceOf<Self>>;
         /// Origin that can manage lottery parameters and start/stop drawings
         type ManageOrigin: EnsureOrigin<Self::RuntimeOrigin>;
         /// Overarching type of all pallets origins.
         type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
         /// Account Identifier from which the internal Pot is generated.
+        /// Must be unique per instance of this pallet, it also names the scheduled drawings
         #[pallet::constant]
         type LotteryPot: Get<PalletId>;
         /// Time in blocks between lottery drawings
@@ -168,6 +196,12 @@ pub mod pallet {
         /// Time in blocks until a collator is done unstaking
         #[pallet::constant]
         type UnstakeLockTime: Get<BlockNumberFor<Self>>; // XXX: could maybe alculate this from staking LeaveDelayRounds * DefaultBlocksPerRound
+        /// Number of past drawings kept in [`DrawingHistory`]
+        #[pallet::constant]
+        type MaxDrawingHistory: Get<u32>;
+        /// Maximum number of participant slots hashed into the commitment of the next drawing per block of its freezeout
+        #[pallet::constant]
+        type MaxCommittedSlotsPerBlock: Get<u32>;
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -175,7 +209,7 @@ pub mod pallet {
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
-    pub struct Pallet<T>(PhantomData<T>);
+    pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
     // Configurable (constant) storage items
 
@@ -183,63 +217,152 @@ pub mod pallet {
     /// This must be initialized at genesis, otherwise the pallet will run out of gas at the first drawing
     #[pallet::storage]
     #[pallet::getter(fn gas_reserve)]
-    pub(super) type GasReserve<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type GasReserve<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn min_deposit)]
-    pub(super) type MinDeposit<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type MinDeposit<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn min_withdraw)]
-    pub(super) type MinWithdraw<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type MinWithdraw<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Staking strategy used to distribute deposits to and withdrawals from collators
+    #[pallet::storage]
+    #[pallet::getter(fn staking_strategy)]
+    pub(super) type ActiveStakingStrategy<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, StakingStrategyKind, ValueQuery>;
+
+    /// Amount of user funds to keep unstaked in the pallet to serve [`Call::instant_withdraw`]
+    #[pallet::storage]
+    #[pallet::getter(fn liquidity_buffer_target)]
+    pub(super) type LiquidityBufferTarget<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Fee charged on [`Call::instant_withdraw`], it stays in the pallet and is paid out with the next drawing
+    #[pallet::storage]
+    #[pallet::getter(fn instant_withdraw_fee)]
+    pub(super) type InstantWithdrawFee<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, Perbill, ValueQuery>;
 
     // Dynamic Storage Items
 
     /// sum of all user's deposits, to ensure balance never drops below
-    /// Incremented on [`Call::deposit`]
-    /// Decremented on withdrawal to user wallet in [`Call::process_matured_withdrawals`]
+    /// Incremented on [`Call::deposit`] and [`Call::sponsor_deposit`]
+    /// Decremented on withdrawal to user wallet in [`Call::process_matured_withdrawals`] and [`Call::instant_withdraw`]
     #[pallet::storage]
     #[pallet::getter(fn sum_of_deposits)]
-    pub(super) type SumOfDeposits<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type SumOfDeposits<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     /// Total number of token eligible to win in the current drawing cycle
     /// Incremented on [`Call::deposit`]
     /// Decremented on [`Call::request_withdraw`]
     #[pallet::storage]
     #[pallet::getter(fn total_pot)]
-    pub(super) type TotalPot<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type TotalPot<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn total_users)]
-    pub(super) type TotalUsers<T: Config> = StorageValue<_, u32, ValueQuery>;
+    pub(super) type TotalUsers<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn is_rebalancing)]
-    pub(super) type RebalanceInProgress<T: Config> = StorageValue<_, bool, ValueQuery>;
+    pub(super) type RebalanceInProgress<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, bool, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn active_balance_per_user)]
-    pub(super) type ActiveBalancePerUser<T: Config> =
+    pub(super) type ActiveBalancePerUser<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
 
+    /// Slot of a participant in [`CumulativeBalanceIndex`]
+    /// Assigned on the first [`Call::deposit`] and released when all active funds are withdrawn
+    #[pallet::storage]
+    pub(super) type ParticipantSlot<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, OptionQuery>;
+
+    /// Participant currently owning a slot in [`CumulativeBalanceIndex`]
+    #[pallet::storage]
+    pub(super) type SlotOwner<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, T::AccountId, OptionQuery>;
+
+    /// Number of slots ( including released ones ) in [`CumulativeBalanceIndex`]
+    #[pallet::storage]
+    pub(super) type ParticipantSlots<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, u32, ValueQuery>;
+
+    /// Released slots that are reused before [`CumulativeBalanceIndex`] is grown
+    #[pallet::storage]
+    pub(super) type FreeSlots<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, Vec<u32>, ValueQuery>;
+
+    /// Fenwick tree over participant slots, node `i` holds the sum of [`ActiveBalancePerUser`] of the slots `(i - lowbit(i), i]`
+    /// Updated on [`Call::deposit`] and [`Call::request_withdraw`], used to find the winner of a drawing in `O(log n)`
+    #[pallet::storage]
+    pub(super) type CumulativeBalanceIndex<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, BalanceOf<T>, ValueQuery>;
+
+    /// Block at which the current drawing interval started, tickets are counted from here
+    #[pallet::storage]
+    pub(super) type TicketIntervalStart<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// Incremented whenever a drawing interval starts, ticket penalties tagged with an older epoch count as zero
+    #[pallet::storage]
+    pub(super) type TicketEpoch<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+    /// Per-user ticket accumulator as `(epoch, penalty)`
+    /// The tickets of a user are `balance * blocks_in_interval - penalty`, i.e. the penalty removes the blocks
+    /// of the current interval in which (part of) the balance was not deposited
+    #[pallet::storage]
+    pub(super) type TicketPenalty<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (u32, BalanceOf<T>), ValueQuery>;
+
+    /// Fenwick tree over participant slots mirroring [`CumulativeBalanceIndex`] for [`TicketPenalty`] as `(epoch, penalty)`
+    #[pallet::storage]
+    pub(super) type CumulativeTicketPenaltyIndex<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, (u32, BalanceOf<T>), ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn unclaimed_winnings_by_account)]
-    pub(super) type UnclaimedWinningsByAccount<T: Config> =
+    pub(super) type UnclaimedWinningsByAccount<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
 
+    /// Accounts whose winnings are added to their active balance and staked instead of kept in [`UnclaimedWinningsByAccount`]
+    /// Set with [`Call::set_auto_compound`]
+    #[pallet::storage]
+    #[pallet::getter(fn auto_compound_winnings)]
+    pub(super) type AutoCompoundWinnings<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
     /// Free balance in the pallet that belongs to a previous lottery winner
     /// Incremented on winner election in the course of a drawing
     /// Decremented on transfer of winnings to ower wallet in [`Call::claim_my_winnings`]
     #[pallet::storage]
     #[pallet::getter(fn total_unclaimed_winnings)]
-    pub(super) type TotalUnclaimedWinnings<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type TotalUnclaimedWinnings<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     /// Free balance in the pallet that was unstaked from a collator and is needed for future withdrawal requests
     /// Incremented on successful unstaking of a collator
     /// Decremented on transfer of funds to withdrawer and on restaking of funds a collator
     #[pallet::storage]
     #[pallet::getter(fn unlocked_unstaking_funds)]
-    pub(super) type UnlockedUnstakingFunds<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type UnlockedUnstakingFunds<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Free balance in the pallet that belongs to user deposits and is kept unstaked to serve [`Call::instant_withdraw`]
+    /// Incremented on [`Call::deposit`] and on restaking of matured unstakes while below [`LiquidityBufferTarget`]
+    /// Decremented on [`Call::instant_withdraw`] and on restaking once above [`LiquidityBufferTarget`]
+    #[pallet::storage]
+    #[pallet::getter(fn liquidity_buffer)]
+    pub(super) type LiquidityBuffer<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[derive(Clone, Encode, Decode, TypeInfo)]
     pub(super) struct UnstakingCollator<AccountId, BlockNumber> {
@@ -248,7 +371,7 @@ pub mod pallet {
     }
 
     #[pallet::storage]
-    pub(super) type UnstakingCollators<T: Config> =
+    pub(super) type UnstakingCollators<T: Config<I>, I: 'static = ()> =
         StorageValue<_, Vec<UnstakingCollator<T::AccountId, BlockNumberFor<T>>>, ValueQuery>;
 
     /// This is balance unstaked from a collator that is not needed to service user's withdrawal requests
@@ -256,7 +379,8 @@ pub mod pallet {
     /// Decremented on [`Call::request_withdraw`] (no collator unstake) and [`Call::rebalance_stake`] (restaking of surplus funds)
     #[pallet::storage]
     #[pallet::getter(fn surplus_unstaking_balance)]
-    pub(super) type SurplusUnstakingBalance<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub(super) type SurplusUnstakingBalance<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
     pub struct Request<AccountId, BlockNumber, Balance> {
@@ -267,16 +391,54 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn withdrawal_request_queue)]
-    pub(super) type WithdrawalRequestQueue<T: Config> =
+    pub(super) type WithdrawalRequestQueue<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, Vec<Request<T::AccountId, BlockNumberFor<T>, BalanceOf<T>>>, ValueQuery>;
+
+    /// Funds of a sponsor that are staked by the pallet to grow the prize pool without being eligible to win
+    /// Incremented on [`Call::sponsor_deposit`]
+    /// Decremented on [`Call::sponsor_withdraw`]
+    #[pallet::storage]
+    #[pallet::getter(fn sponsor_balance)]
+    pub(super) type SponsorBalance<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Sum of all [`SponsorBalance`], part of [`SumOfDeposits`] but not of [`TotalPot`]
+    #[pallet::storage]
+    #[pallet::getter(fn total_sponsor_balance)]
+    pub(super) type TotalSponsorBalance<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Withdrawals requested with [`Call::sponsor_withdraw`], paid out alongside [`WithdrawalRequestQueue`] once matured
+    #[pallet::storage]
+    #[pallet::getter(fn sponsor_withdrawal_request_queue)]
+    pub(super) type SponsorWithdrawalRequestQueue<T: Config<I>, I: 'static = ()> =
         StorageValue<_, Vec<Request<T::AccountId, BlockNumberFor<T>, BalanceOf<T>>>, ValueQuery>;
 
     /// Incremented whenever delegating tokens to a collator
     /// Collators are removed from here when their funds are unlocked in [`Call::finish_unstaking_collators`]
     #[pallet::storage]
     #[pallet::getter(fn staked_collators)]
-    pub(super) type StakedCollators<T: Config> =
+    pub(super) type StakedCollators<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
 
+    /// Reward points of the collators selected in a finished round, kept for [`crate::REWARD_HISTORY_ROUNDS`] rounds
+    /// as ParachainStaking removes them once the round is paid out. Recorded at the start of the next round
+    #[pallet::storage]
+    pub(super) type CollatorPointsHistory<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        RoundIndex,
+        Blake2_128Concat,
+        T::AccountId,
+        u32,
+        OptionQuery,
+    >;
+
+    /// Total reward points awarded in a finished round recorded in [`CollatorPointsHistory`]
+    #[pallet::storage]
+    pub(super) type RoundPointsHistory<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, RoundIndex, u32, OptionQuery>;
+
     #[derive(Clone, Copy, Encode, Decode, TypeInfo, Default, Serialize, Deserialize)]
     pub struct FarmingParams<T: Default + Copy> {
         pub mint_farming_token: bool,
@@ -287,20 +449,187 @@ pub mod pallet {
 
     pub type FarmingParamsOf<T> = FarmingParams<<T as pallet_farming::Config>::CurrencyId>;
 
+    /// Selects the [`crate::StakingStrategy`] the pallet stakes and unstakes with
+    #[derive(
+        Clone, Copy, Debug, Default, Eq, PartialEq, Encode, Decode, MaxEncodedLen, TypeInfo,
+    )]
+    pub enum StakingStrategyKind {
+        /// Balance the stake of all active collators, see [`crate::BalancedStrategy`]
+        #[default]
+        Balanced,
+        /// Prefer collators with the highest recent rewards per staked token, see [`crate::RewardHistoryStrategy`]
+        RewardHistory,
+    }
+
+    /// Outcome of a lottery drawing that selected a winner
+    #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct DrawingRecord<AccountId, BlockNumber, Balance, Hash> {
+        /// Block the drawing was executed in
+        pub block: BlockNumber,
+        /// Randomness the winning balance was derived from
+        pub randomness: Hash,
+        /// Funds eligible to win ( [`TotalPot`] ) at the time of the drawing
+        pub total_pot: Balance,
+        /// Number of accounts eligible to win
+        pub participants: u32,
+        /// Account that won the drawing
+        pub winner: AccountId,
+        /// Amount awarded to the winner
+        pub prize: Balance,
+        /// Ticket derived from `randomness`, the winner's cumulative ticket range covers it
+        pub winning_ticket: Balance,
+        /// Index of the 128 bit randomness sample the ticket was derived from:
+        /// The low half of the randomness for `[resamples / 2]` if even, the high half if odd
+        pub resamples: u8,
+        /// Blocks of the drawing interval tickets were counted for
+        pub ticket_blocks: Balance,
+        /// Root of the participants committed to at the start of the freezeout, see [`DrawingCommitment`]
+        /// `None` if the drawing was not scheduled, e.g. called manually
+        pub participants_root: Option<Hash>,
+    }
+
+    pub type DrawingRecordOf<T> = DrawingRecord<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        BalanceOf<T>,
+        <T as frame_system::Config>::Hash,
+    >;
+
+    /// Leaf of the participants tree committed to before a drawing
+    #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct ParticipantLeaf<AccountId, Balance> {
+        pub account: AccountId,
+        /// Funds of the account eligible to win
+        pub balance: Balance,
+        /// Tickets the balance did not earn in the drawing interval, see [`TicketPenalty`]
+        pub ticket_penalty: Balance,
+    }
+
+    pub type ParticipantLeafOf<T> =
+        ParticipantLeaf<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+    /// Commitment to the participants of the upcoming drawing
+    #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct SnapshotCommitment<BlockNumber, Hash> {
+        /// Block the commitment was made in, the first block of the freezeout
+        pub block: BlockNumber,
+        /// Block the committed drawing is scheduled at
+        pub drawing_at: BlockNumber,
+        /// Merkle root over all participants in slot order
+        pub participants_root: Hash,
+        /// Number of leaves of the participants tree
+        pub participants: u32,
+        /// Subject the drawing requests randomness with, resamples use `[n]` for the `n`th request
+        pub randomness_subject: Vec<u8>,
+    }
+
+    pub type SnapshotCommitmentOf<T> =
+        SnapshotCommitment<BlockNumberFor<T>, <T as frame_system::Config>::Hash>;
+
+    /// Progress of a [`SnapshotCommitment`] hashed over several blocks
+    #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct PendingCommitment<BlockNumber, Hash> {
+        /// Block the commitment was started in, the first block of the freezeout
+        pub block: BlockNumber,
+        /// Block the committed drawing is scheduled at
+        pub drawing_at: BlockNumber,
+        /// Next participant slot to hash into the tree
+        pub next_slot: u32,
+        /// Number of leaves hashed so far
+        pub participants: u32,
+        /// Roots of the perfect subtrees over the leaves hashed so far with their heights, largest first
+        pub peaks: Vec<(u8, Hash)>,
+    }
+
+    pub type PendingCommitmentOf<T> =
+        PendingCommitment<BlockNumberFor<T>, <T as frame_system::Config>::Hash>;
+
+    /// Merkle proof that a participant is part of a [`SnapshotCommitment`]
+    #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct ParticipantProof<AccountId, Balance, Hash> {
+        pub leaf: ParticipantLeaf<AccountId, Balance>,
+        /// Position of the leaf in the participants tree
+        pub leaf_index: u32,
+        pub number_of_leaves: u32,
+        /// Sibling hashes from the leaf up to the root
+        pub proof: Vec<Hash>,
+    }
+
+    pub type ParticipantProofOf<T> = ParticipantProof<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        <T as frame_system::Config>::Hash,
+    >;
+
+    /// A withdrawal of a user waiting in [`WithdrawalRequestQueue`]
+    #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct PendingWithdrawal<BlockNumber, Balance> {
+        pub balance: Balance,
+        pub requested_at: BlockNumber,
+        /// Block the staking timelock expires, payout happens in the first drawing after this
+        pub matures_at: BlockNumber,
+    }
+
+    /// Lottery state of a single account
+    #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct UserPosition<BlockNumber, Balance> {
+        /// Funds eligible to win in the next drawing
+        pub active_deposit: Balance,
+        /// Chance to win if the drawing happened in the current block
+        pub win_probability: Perbill,
+        pub pending_withdrawals: Vec<PendingWithdrawal<BlockNumber, Balance>>,
+        pub unclaimed_winnings: Balance,
+    }
+
+    pub type UserPositionOf<T> = UserPosition<BlockNumberFor<T>, BalanceOf<T>>;
+
+    /// Most recent drawings keyed by drawing number, at most [`Config::MaxDrawingHistory`] are kept
+    #[pallet::storage]
+    #[pallet::getter(fn drawing_record)]
+    pub(super) type DrawingHistory<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u32, DrawingRecordOf<T>, OptionQuery>;
+
+    /// Number of drawings that selected a winner, the next drawing is recorded under this number
+    #[pallet::storage]
+    #[pallet::getter(fn drawing_count)]
+    pub(super) type DrawingCount<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+    /// Commitment to the participants of the next drawing, completed during its freezeout and kept until the drawing
+    #[pallet::storage]
+    #[pallet::getter(fn drawing_commitment)]
+    pub(super) type DrawingCommitment<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, SnapshotCommitmentOf<T>, OptionQuery>;
+
+    /// Commitment to the participants of the next drawing while its slots are hashed over the blocks of the freezeout
+    #[pallet::storage]
+    #[pallet::getter(fn pending_commitment)]
+    pub(super) type PendingCommitment<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, PendingCommitmentOf<T>, OptionQuery>;
+
     /// Boolean for the minting of a farming token on `deposit` call
     #[pallet::storage]
-    pub(super) type FarmingParameters<T: Config> = StorageValue<_, FarmingParamsOf<T>, ValueQuery>;
+    pub(super) type FarmingParameters<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, FarmingParamsOf<T>, ValueQuery>;
 
     #[pallet::genesis_config]
-    pub struct GenesisConfig<T: Config> {
+    pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
         /// amount of token to keep in the pot for paying gas fees
         pub gas_reserve: BalanceOf<T>,
         pub min_deposit: BalanceOf<T>,
         pub min_withdraw: BalanceOf<T>,
         pub farming_pool_params: FarmingParamsOf<T>,
+        #[serde(skip)]
+        pub _config: PhantomData<I>,
     }
 
-    impl<T: Config> Default for GenesisConfig<T> {
+    impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
         fn default() -> Self {
             Self {
                 min_deposit: 1u32.into(),
@@ -312,24 +641,25 @@ pub mod pallet {
                     pool_id: 0,
                     currency_id: <T as pallet_farming::Config>::CurrencyId::default(),
                 },
+                _config: PhantomData,
             }
         }
     }
 
     #[pallet::genesis_build]
-    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+    impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
         #[inline]
         fn build(&self) {
-            GasReserve::<T>::set(self.gas_reserve);
-            MinDeposit::<T>::set(self.min_deposit);
-            MinWithdraw::<T>::set(self.min_withdraw);
-            FarmingParameters::<T>::set(self.farming_pool_params);
+            GasReserve::<T, I>::set(self.gas_reserve);
+            MinDeposit::<T, I>::set(self.min_deposit);
+            MinWithdraw::<T, I>::set(self.min_withdraw);
+            FarmingParameters::<T, I>::set(self.farming_pool_params);
         }
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(fn deposit_event)]
-    pub enum Event<T: Config> {
+    pub enum Event<T: Config<I>, I: 'static = ()> {
         LotteryStarted,
         LotteryStopped,
         LotteryWinner {
@@ -348,14 +678,46 @@ pub mod pallet {
             account: T::AccountId,
             amount: BalanceOf<T>,
         },
+        WithdrawCancelled {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+        },
         Claimed {
             account: T::AccountId,
             amount: BalanceOf<T>,
         },
+        AutoCompoundSet {
+            account: T::AccountId,
+            enabled: bool,
+        },
+        WinningsCompounded {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        StartedRebalance {
+            amount: BalanceOf<T>,
+        },
+        InstantWithdrawn {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+            fee: BalanceOf<T>,
+        },
+        SponsorDeposited {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        SponsorScheduledWithdraw {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        SponsorWithdrawn {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+        },
     }
 
     #[pallet::error]
-    pub enum Error<T> {
+    pub enum Error<T, I = ()> {
         /// Lottery has not been started
         LotteryNotStarted,
         /// Lottery has already been started
@@ -385,6 +747,12 @@ pub mod pallet {
         WithdrawAboveDeposit,
         /// No deposits found for this account
         NoDepositForAccount,
+        /// The account has not sponsored the lottery or tried to withdraw more than it sponsored
+        NoSponsorDepositForAccount,
+        /// No withdrawal request of this account at the given queue index
+        NoWithdrawRequestAtIndex,
+        /// Not enough funds in the liquidity buffer to serve this instant withdrawal
+        WithdrawAboveLiquidityBuffer,
         /// Fatal: No collators found to assign this deposit to
         NoCollatorForDeposit,
         /// Fatal: No collators found to assign this deposit to
@@ -403,31 +771,53 @@ pub mod pallet {
         NotImplemented,
     }
 
+    #[pallet::hooks]
+    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+        /// Starts committing to the participants of the next drawing in the first block of its freezeout and
+        /// hashes up to [`Config::MaxCommittedSlotsPerBlock`] of their slots in every block until it is complete
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let mut weight = T::DbWeight::get().reads(2);
+            if let Some(drawing_at) = Self::next_drawing_at() {
+                if drawing_at.saturating_sub(<T as Config<I>>::DrawingFreezeout::get()) == now {
+                    Self::start_participants_commitment(drawing_at);
+                    weight = weight.saturating_add(T::DbWeight::get().writes(2));
+                }
+            }
+            if Self::pending_commitment().is_some() {
+                let slots =
+                    Self::commit_participants(<T as Config<I>>::MaxCommittedSlotsPerBlock::get());
+                weight =
+                    weight.saturating_add(<T as Config<I>>::WeightInfo::commit_participants(slots));
+            }
+            weight
+        }
+    }
+
     #[pallet::call]
-    impl<T: Config> Pallet<T> {
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
         /// Allows any user to deposit tokens into the lottery
         ///
         /// # Arguments
         ///
         /// * `amount` - The amount of tokens to be deposited.
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as Config>::WeightInfo::deposit(Pallet::<T>::total_users(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::deposit(Pallet::<T, I>::total_users(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
         pub fn deposit(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
             let caller_account = ensure_signed(origin)?;
             ensure!(
                 amount >= Self::min_deposit(),
-                Error::<T>::DepositBelowMinAmount
+                Error::<T, I>::DepositBelowMinAmount
             );
             ensure!(
                 Self::not_in_drawing_freezeout(),
-                Error::<T>::TooCloseToDrawing
+                Error::<T, I>::TooCloseToDrawing
             );
             ensure! { // Sanity check: make sure we dont accept deposits that will fail in staking
                 Self::min_deposit() >= <T as pallet_parachain_staking::Config>::MinDelegation::get(),
-                Error::<T>::PalletMisconfigured
+                Error::<T, I>::PalletMisconfigured
             };
 
-            let farming_params = FarmingParameters::<T>::get();
+            let farming_params = FarmingParameters::<T, I>::get();
             if farming_params.mint_farming_token {
                 // mint JUMBO token and put it in farming pool
                 let convert_amount: T::BalanceConversion = amount.into();
@@ -452,22 +842,30 @@ pub mod pallet {
                 KeepAlive,
             )?;
 
-            // Attempt to stake them
-            let collator_balance_pairs = Self::calculate_deposit_distribution(amount);
-            ensure!(
-                !collator_balance_pairs.is_empty(),
-                Error::<T>::NoCollatorForDeposit
-            );
-            for (some_collator, balance) in collator_balance_pairs {
-                // TODO: What if the `balance` is below `MinDelegation`a on a new collator? this will fail
-                Self::do_stake_one_collator(some_collator, balance)?;
+            // Keep funds unstaked if the liquidity buffer needs refilling, attempt to stake the rest
+            let to_buffer = Self::liquidity_buffer_refill(amount);
+            let to_stake = amount.saturating_sub(to_buffer);
+            if !to_stake.is_zero() {
+                let collator_balance_pairs = Self::calculate_deposit_distribution(to_stake);
+                ensure!(
+                    !collator_balance_pairs.is_empty(),
+                    Error::<T, I>::NoCollatorForDeposit
+                );
+                for (some_collator, balance) in collator_balance_pairs {
+                    // TODO: What if the `balance` is below `MinDelegation`a on a new collator? this will fail
+                    Self::do_stake_one_collator(some_collator, balance)?;
+                }
             }
+            LiquidityBuffer::<T, I>::mutate(|buffer| *buffer += to_buffer);
 
             // Add to active funds
-            ActiveBalancePerUser::<T>::mutate(caller_account.clone(), |balance| *balance += amount);
-            TotalPot::<T>::mutate(|balance| *balance += amount);
-            TotalUsers::<T>::mutate(|users| *users += 1);
-            SumOfDeposits::<T>::mutate(|balance| *balance += amount);
+            ActiveBalancePerUser::<T, I>::mutate(caller_account.clone(), |balance| {
+                *balance += amount
+            });
+            Self::index_add_balance(&caller_account, amount)?;
+            TotalPot::<T, I>::mutate(|balance| *balance += amount);
+            TotalUsers::<T, I>::mutate(|users| *users += 1);
+            SumOfDeposits::<T, I>::mutate(|balance| *balance += amount);
             Self::deposit_event(Event::Deposited {
                 account: caller_account,
                 amount,
@@ -480,8 +878,7 @@ pub mod pallet {
         /// Withdrawal is not immediate as funds are subject to a timelock imposed by [`pallet_parachain_staking`]
         /// It will be executed with the first [`Call::draw_lottery`] call after timelock expires
         ///
-        /// Withdrawals can NOT be cancelled because they inflict ecomomic damage on the lottery through collator unstaking
-        /// and the user causing this damage must be subjected to economic consequences for doing so
+        /// A withdrawal that was not paid out yet can be reverted with [`Call::cancel_withdraw_request`]
         ///
         /// The withdrawal is paid from [`SurplusUnstakingBalance`]
         /// If this balance is too low to handle the request, another collator is unstaked
@@ -499,20 +896,20 @@ pub mod pallet {
         /// * The user has no or not enough active funds
         /// * There are any arithmetic underflows
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::request_withdraw(Pallet::<T>::total_users(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::request_withdraw(Pallet::<T, I>::total_users(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
         pub fn request_withdraw(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
             let caller = ensure_signed(origin)?;
 
             ensure!(
                 amount >= Self::min_withdraw(),
-                Error::<T>::WithdrawBelowMinAmount
+                Error::<T, I>::WithdrawBelowMinAmount
             );
             ensure!(
                 Self::not_in_drawing_freezeout(),
-                Error::<T>::TooCloseToDrawing
+                Error::<T, I>::TooCloseToDrawing
             );
 
-            let farming_params = FarmingParameters::<T>::get();
+            let farming_params = FarmingParameters::<T, I>::get();
             if farming_params.destroy_farming_token {
                 let convert_amount: T::BalanceConversion = amount.into();
                 pallet_farming::Pallet::<T>::withdraw_and_unstake(
@@ -530,153 +927,386 @@ pub mod pallet {
             let now = <frame_system::Pallet<T>>::block_number();
             log::debug!("Requesting withdraw of {:?} tokens", amount);
             // Ensure user has enough funds active and mark them as offboarding (remove from `ActiveBalancePerUser`)
-            ActiveBalancePerUser::<T>::try_mutate_exists(caller.clone(), |maybe_balance| {
-                match maybe_balance {
-                    None => Err(Error::<T>::NoDepositForAccount),
-                    Some(balance) => {
-                        // Withdraw only what's active
-                        ensure!(*balance >= amount, Error::<T>::WithdrawAboveDeposit);
-                        // Mark funds as offboarding
-                        WithdrawalRequestQueue::<T>::mutate(|withdraw_vec| {
-                            withdraw_vec.push(Request {
-                                user: caller.clone(),
-                                block: now,
-                                balance: amount,
-                            })
-                        });
-                        // store reduced balance
-                        *maybe_balance = match balance
-                            .checked_sub(&amount)
-                            .ok_or(Error::<T>::ArithmeticUnderflow)?
-                        {
-                            new_balance if new_balance.is_zero() => {
-                                // remove user if this was his last remaining funds
-                                TotalUsers::<T>::try_mutate(|users| {
-                                    *users = (*users)
-                                        .checked_sub(1u32)
-                                        .ok_or(Error::<T>::ArithmeticUnderflow)?;
-                                    Ok(())
-                                })?;
-                                None
-                            }
-                            new_balance => Some(new_balance),
-                        };
-                        TotalPot::<T>::try_mutate(|pot| {
-                            *pot = (*pot)
-                                .checked_sub(&amount)
-                                .ok_or(Error::<T>::ArithmeticUnderflow)?;
-                            Ok(())
-                        })?;
-                        Ok(())
-                    }
-                }
+            Self::do_deactivate_balance(&caller, amount)?;
+            WithdrawalRequestQueue::<T, I>::mutate(|withdraw_vec| {
+                withdraw_vec.push(Request {
+                    user: caller.clone(),
+                    block: now,
+                    balance: amount,
+                })
+            });
+
+            Self::do_unstake_for_withdrawal(now, amount)?;
+            Self::deposit_event(Event::ScheduledWithdraw {
+                account: caller,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Cancels the caller's pending withdrawal at position `index` of [`WithdrawalRequestQueue`] and moves its
+        /// funds back into the caller's active balance, making them eligible to win again.
+        ///
+        /// The funds reserved for the request are returned to [`SurplusUnstakingBalance`], where they serve future
+        /// withdrawals or get restaked once unlocked. If the request already matured, unlocked funds are restaked right away
+        ///
+        /// # Arguments
+        ///
+        /// * `index` - position of the request in [`WithdrawalRequestQueue`]
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if:
+        /// * There is no request at `index` or it belongs to another account
+        /// * It is too close to the drawing
+        /// * There are any arithmetic overflows
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::cancel_withdraw_request(Pallet::<T, I>::total_users(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        pub fn cancel_withdraw_request(origin: OriginFor<T>, index: u32) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(
+                Self::not_in_drawing_freezeout(),
+                Error::<T, I>::TooCloseToDrawing
+            );
+
+            let request = WithdrawalRequestQueue::<T, I>::try_mutate(|requests| {
+                let index = index as usize;
+                ensure!(
+                    requests
+                        .get(index)
+                        .map_or(false, |request| request.user == caller),
+                    Error::<T, I>::NoWithdrawRequestAtIndex
+                );
+                Ok::<_, Error<T, I>>(requests.remove(index))
             })?;
+            let amount = request.balance;
 
-            // Unstaking workflow
-            // 1. See if this withdrawal can be serviced with left-over balance from an already unstaking collator, if so deduct remaining balance and schedule the request
-            // 2. If it can't, find the collator with the smallest delegation that is able to handle this withdrawal request and fully unstake it
-            // 3. Add balance overshoot to "remaining balance" to handle further requests from
+            let farming_params = FarmingParameters::<T, I>::get();
+            if farming_params.destroy_farming_token {
+                // re-mint the farming tokens burned on `request_withdraw` and put them back in the farming pool
+                let convert_amount: T::BalanceConversion = amount.into();
+                <T as pallet_farming::Config>::MultiCurrency::deposit(
+                    farming_params.currency_id,
+                    &caller,
+                    convert_amount.into(),
+                )?;
+                pallet_farming::Pallet::<T>::deposit_farming(
+                    caller.clone(),
+                    farming_params.pool_id,
+                    convert_amount.into(),
+                    None,
+                )?;
+            }
 
-            // If the withdrawal fits in the currently unstaking funds, do nothing else
-            SurplusUnstakingBalance::<T>::try_mutate(|remaining_balance| {
-                match (*remaining_balance).checked_sub(&amount){
-                    Some(subtracted) => {
-                        *remaining_balance = subtracted;
-                        Ok(())
-                    }
-                    _ => {
-                        Err("not enough left to handle this request from current unstaking funds")
-                    }
-                }
-            })
-            .or_else(|_| {
-                // Withdrawal needs extra collators to unstake to have enough funds to serve withdrawals, do it
-                let reserve = SurplusUnstakingBalance::<T>::get();
-                let mut remaining_to_withdraw = amount - reserve;
+            // The funds backing this request are no longer owed to the caller, reuse them for other withdrawals
+            SurplusUnstakingBalance::<T, I>::try_mutate(|surplus| {
+                *surplus = (*surplus)
+                    .checked_add(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            // Re-enter the drawing, the funds earn tickets from now on like a fresh deposit
+            let was_active = !Self::active_balance_per_user(&caller).is_zero();
+            ActiveBalancePerUser::<T, I>::try_mutate(caller.clone(), |balance| {
+                *balance = (*balance)
+                    .checked_add(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            Self::index_add_balance(&caller, amount)?;
+            TotalPot::<T, I>::try_mutate(|pot| {
+                *pot = (*pot)
+                    .checked_add(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            if !was_active {
+                TotalUsers::<T, I>::mutate(|users| *users = users.saturating_add(1));
+            }
 
-                // unstake collators as necessary. This updates `SurplusUnstakingBalance`
-                for collator_to_unstake in Self::calculate_withdrawal_distribution(remaining_to_withdraw){
-                    let our_stake = StakedCollators::<T>::get(collator_to_unstake.clone());
-                    remaining_to_withdraw = remaining_to_withdraw.saturating_sub(our_stake);
-                    // The following call updates `SurplusUnstakingBalance` with newly unstaked funds
-                    Self::do_unstake_collator(now,collator_to_unstake)?;
-                }
-                if !remaining_to_withdraw.is_zero() {
-                    return Err("FATAL: Didn't unstake the full requested balance (or more)");
-                }
-                SurplusUnstakingBalance::<T>::try_mutate(|remaining_balance| {
-                    match (*remaining_balance).checked_sub(&amount){
-                        Some(subtracted) => {
-                            *remaining_balance = subtracted;
-                            Ok(())
-                        }
-                        _ => {
-                            Err("not enough unstaking balance to handle request after unstaking additional collators")
-                        }
-                    }
-                })
+            // Funds of a matured request may already be unlocked, restake them instead of leaving them idle until the next drawing
+            let now = <frame_system::Pallet<T>>::block_number();
+            if request.block + <T as Config<I>>::UnstakeLockTime::get() <= now {
+                Self::do_rebalance_remaining_funds()?;
+            }
+            Self::deposit_event(Event::WithdrawCancelled {
+                account: caller,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Withdraws `amount` of the caller's active funds immediately from the [`LiquidityBuffer`]
+        ///
+        /// Unlike [`Call::request_withdraw`] this does not wait for the staking timelock and the next drawing,
+        /// but [`InstantWithdrawFee`] is deducted from the payout and added to the prize pool of the next drawing
+        ///
+        /// # Arguments
+        ///
+        /// * `amount` - the amount of funds to withdraw, including the fee
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if:
+        /// * `amount` is below the minimum withdraw amount
+        /// * `amount` is larger than the funds in the liquidity buffer
+        /// * It is too close to the drawing
+        /// * The user has no or not enough active funds
+        /// * There are any arithmetic underflows
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::instant_withdraw(Pallet::<T, I>::total_users()))]
+        pub fn instant_withdraw(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            ensure!(
+                amount >= Self::min_withdraw(),
+                Error::<T, I>::WithdrawBelowMinAmount
+            );
+            ensure!(
+                Self::not_in_drawing_freezeout(),
+                Error::<T, I>::TooCloseToDrawing
+            );
+            ensure!(
+                amount <= Self::liquidity_buffer(),
+                Error::<T, I>::WithdrawAboveLiquidityBuffer
+            );
+
+            let farming_params = FarmingParameters::<T, I>::get();
+            if farming_params.destroy_farming_token {
+                let convert_amount: T::BalanceConversion = amount.into();
+                pallet_farming::Pallet::<T>::withdraw_and_unstake(
+                    caller.clone(),
+                    farming_params.pool_id,
+                    Some(convert_amount.into()),
+                )?;
+                <T as pallet_farming::Config>::MultiCurrency::withdraw(
+                    farming_params.currency_id,
+                    &caller,
+                    convert_amount.into(),
+                )?;
+            }
+
+            Self::do_deactivate_balance(&caller, amount)?;
+            LiquidityBuffer::<T, I>::try_mutate(|buffer| {
+                *buffer = (*buffer)
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                Ok::<(), Error<T, I>>(())
             })?;
-            // END UNSTAKING SECTION
-            Self::deposit_event(Event::ScheduledWithdraw {
+            SumOfDeposits::<T, I>::try_mutate(|sum| {
+                *sum = (*sum)
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            // The fee is no longer owed to anyone and becomes part of the prize pool
+            let fee = Self::instant_withdraw_fee() * amount;
+            <T as pallet_parachain_staking::Config>::Currency::transfer(
+                &Self::account_id(),
+                &caller,
+                amount.saturating_sub(fee),
+                KeepAlive,
+            )?;
+            Self::deposit_event(Event::InstantWithdrawn {
+                account: caller,
+                amount,
+                fee,
+            });
+            Ok(())
+        }
+
+        /// Allows the caller to transfer any of the account's previously unclaimed winnings to his their wallet
+        ///
+        /// # Errors
+        ///
+        /// CannotLookup: The caller has no unclaimed winnings.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::claim_my_winnings(pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        pub fn claim_my_winnings(origin: OriginFor<T>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            match UnclaimedWinningsByAccount::<T, I>::take(caller.clone()) {
+                Some(winnings) => {
+                    // Never pay out winnings if it would reduce pallet funds below total number of user's deposits
+                    let all_funds_in_pallet =
+                        <T as pallet_parachain_staking::Config>::Currency::total_balance(
+                            &Self::account_id(),
+                        );
+                    ensure!(
+                        // Sanity check: Never pay out funds that would draw on user deposits
+                        all_funds_in_pallet.saturating_sub(winnings) >= Self::sum_of_deposits(),
+                        Error::<T, I>::PotBalanceTooLow
+                    );
+
+                    TotalUnclaimedWinnings::<T, I>::try_mutate(|old| {
+                        *old = (*old)
+                            .checked_sub(&winnings)
+                            .ok_or(ArithmeticError::Underflow)?;
+                        Ok::<(), ArithmeticError>(())
+                    })?;
+                    <T as pallet_parachain_staking::Config>::Currency::transfer(
+                        &Self::account_id(),
+                        &caller,
+                        winnings,
+                        KeepAlive,
+                    )?; // NOTE: If the transfer fails, the TXN get rolled back and the winnings stay in the map for claiming later
+                    Self::deposit_event(Event::Claimed {
+                        account: caller,
+                        amount: winnings,
+                    });
+                    Ok(())
+                }
+                None => Err(DispatchError::CannotLookup),
+            }
+        }
+
+        /// Sets whether future winnings of the caller are compounded into the caller's active balance
+        ///
+        /// Compounded winnings are staked like a [`Call::deposit`] and immediately count for the next drawing.
+        /// Winnings that can not be staked ( e.g. below [`MinDeposit`] ) are still kept in [`UnclaimedWinningsByAccount`]
+        ///
+        /// # Arguments
+        ///
+        /// * `enabled` - `true` to compound, `false` to claim winnings manually with [`Call::claim_my_winnings`]
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_auto_compound())]
+        pub fn set_auto_compound(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            if enabled {
+                AutoCompoundWinnings::<T, I>::insert(&caller, true);
+            } else {
+                AutoCompoundWinnings::<T, I>::remove(&caller);
+            }
+            Self::deposit_event(Event::AutoCompoundSet {
+                account: caller,
+                enabled,
+            });
+            Ok(())
+        }
+
+        /// Allows any account to stake tokens through the lottery to grow the prize pool
+        ///
+        /// Sponsored funds are staked like a [`Call::deposit`] and their staking rewards are paid out to winners,
+        /// but they are not part of [`TotalPot`], so the sponsor never wins a drawing
+        ///
+        /// # Arguments
+        ///
+        /// * `amount` - The amount of tokens to be sponsored.
+        #[pallet::call_index(19)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::sponsor_deposit(pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        pub fn sponsor_deposit(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(
+                amount >= Self::min_deposit(),
+                Error::<T, I>::DepositBelowMinAmount
+            );
+            ensure!(
+                Self::not_in_drawing_freezeout(),
+                Error::<T, I>::TooCloseToDrawing
+            );
+
+            // Transfer funds to pot
+            <T as pallet_parachain_staking::Config>::Currency::transfer(
+                &caller,
+                &Self::account_id(),
+                amount,
+                KeepAlive,
+            )?;
+
+            // Attempt to stake them
+            let collator_balance_pairs = Self::calculate_deposit_distribution(amount);
+            ensure!(
+                !collator_balance_pairs.is_empty(),
+                Error::<T, I>::NoCollatorForDeposit
+            );
+            for (some_collator, balance) in collator_balance_pairs {
+                Self::do_stake_one_collator(some_collator, balance)?;
+            }
+
+            SponsorBalance::<T, I>::mutate(&caller, |balance| *balance += amount);
+            TotalSponsorBalance::<T, I>::mutate(|balance| *balance += amount);
+            SumOfDeposits::<T, I>::mutate(|balance| *balance += amount);
+            Self::deposit_event(Event::SponsorDeposited {
                 account: caller,
                 amount,
             });
             Ok(())
         }
 
-        /// Allows the caller to transfer any of the account's previously unclaimed winnings to his their wallet
+        /// Requests a withdrawal of `amount` from the caller's sponsored funds.
+        ///
+        /// Like [`Call::request_withdraw`], the funds are unstaked and paid out with the first [`Call::draw_lottery`]
+        /// call after the staking timelock expires. The request is kept in [`SponsorWithdrawalRequestQueue`]
+        ///
+        /// # Arguments
+        ///
+        /// * `amount` - the amount of funds to withdraw
         ///
         /// # Errors
         ///
-        /// CannotLookup: The caller has no unclaimed winnings.
-        #[pallet::call_index(2)]
-        #[pallet::weight(<T as Config>::WeightInfo::claim_my_winnings(pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
-        pub fn claim_my_winnings(origin: OriginFor<T>) -> DispatchResult {
+        /// Returns an error if:
+        /// * `amount` is below the minimum withdraw amount
+        /// * `amount` is larger than the caller's sponsored funds
+        /// * It is too close to the drawing
+        /// * There are any arithmetic underflows
+        #[pallet::call_index(20)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::sponsor_withdraw(pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        pub fn sponsor_withdraw(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            match UnclaimedWinningsByAccount::<T>::take(caller.clone()) {
-                Some(winnings) => {
-                    // Never pay out winnings if it would reduce pallet funds below total number of user's deposits
-                    let all_funds_in_pallet =
-                        <T as pallet_parachain_staking::Config>::Currency::total_balance(
-                            &Self::account_id(),
-                        );
-                    ensure!(
-                        // Sanity check: Never pay out funds that would draw on user deposits
-                        all_funds_in_pallet.saturating_sub(winnings) >= Self::sum_of_deposits(),
-                        Error::<T>::PotBalanceTooLow
-                    );
+            ensure!(
+                amount >= Self::min_withdraw(),
+                Error::<T, I>::WithdrawBelowMinAmount
+            );
+            ensure!(
+                Self::not_in_drawing_freezeout(),
+                Error::<T, I>::TooCloseToDrawing
+            );
 
-                    TotalUnclaimedWinnings::<T>::try_mutate(|old| {
-                        *old = (*old)
-                            .checked_sub(&winnings)
-                            .ok_or(ArithmeticError::Underflow)?;
-                        Ok::<(), ArithmeticError>(())
-                    })?;
-                    <T as pallet_parachain_staking::Config>::Currency::transfer(
-                        &Self::account_id(),
-                        &caller,
-                        winnings,
-                        KeepAlive,
-                    )?; // NOTE: If the transfer fails, the TXN get rolled back and the winnings stay in the map for claiming later
-                    Self::deposit_event(Event::Claimed {
-                        account: caller,
-                        amount: winnings,
-                    });
-                    Ok(())
-                }
-                None => Err(DispatchError::CannotLookup),
-            }
+            SponsorBalance::<T, I>::try_mutate_exists(&caller, |maybe_balance| {
+                let balance = (*maybe_balance)
+                    .unwrap_or_default()
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T, I>::NoSponsorDepositForAccount)?;
+                *maybe_balance = (!balance.is_zero()).then_some(balance);
+                Ok::<(), Error<T, I>>(())
+            })?;
+            TotalSponsorBalance::<T, I>::try_mutate(|total| {
+                *total = (*total)
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            SponsorWithdrawalRequestQueue::<T, I>::mutate(|withdraw_vec| {
+                withdraw_vec.push(Request {
+                    user: caller.clone(),
+                    block: now,
+                    balance: amount,
+                })
+            });
+
+            Self::do_unstake_for_withdrawal(now, amount)?;
+            Self::deposit_event(Event::SponsorScheduledWithdraw {
+                account: caller,
+                amount,
+            });
+            Ok(())
         }
 
-        /// Maximizes staking APY and thus accrued winnings by removing staked tokens from overallocated/inactive
-        /// collators and adding to underallocated ones.
+        /// Maximizes staking APY and thus accrued winnings by removing staked tokens from inactive
+        /// or underperforming collators and adding them to well performing ones.
         ///
         /// Can only be called by the account set as [`Config::ManageOrigin`]
         ///
-        /// This function should be called when the pallet's staked tokens are staked with overweight collators
-        /// or collators that became inactive or left the staking set.
-        /// This will withdraw the tokens from overallocated and inactive collators and wait until the funds are unlocked,
-        /// then re-allocate them to underallocated collators.
+        /// This function should be called when the pallet's tokens are staked with collators that left the staking set,
+        /// stopped producing blocks or are about to be evicted by [`manta_collator_selection`].
+        /// A collator is considered underperforming if it
+        /// * produced no blocks in the previous round
+        /// * produced fewer blocks this session than the eviction threshold of [`manta_collator_selection`]
+        /// * earned fewer reward points per round over the last [`crate::REWARD_HISTORY_ROUNDS`] rounds
+        ///   than the same threshold ( `EvictionBaseline`/`EvictionTolerance` ) applied to reward points
+        ///
+        /// This will unstake the tokens from these collators and wait until the funds are unlocked,
+        /// then re-allocate them to performing collators in the course of a drawing.
         ///
         /// Note that this operation can run in parallel with a drawing, but it will reduce the staking revenue
         /// generated in that drawing by the amount of funds being rebalanced.
@@ -684,20 +1314,22 @@ pub mod pallet {
         /// # Errors
         ///
         /// * BadOrigin: Caller is not ManageOrigin
-        /// * TODO: Amount of tokens to be rebalanced would be too low.
+        /// * errors defined by the do_unstake_collator function.
         #[pallet::call_index(3)]
-        #[pallet::weight(T::DbWeight::get().write)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::rebalance_stake(pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
         pub fn rebalance_stake(origin: OriginFor<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
-            Err(crate::pallet::DispatchError::Other(
-                Error::<T>::NotImplemented.into(),
-            ))
-
-            // withdraw from overallocated collators, wait until funds unlock, re-allocate to underallocated collators
-            // TODO: find some balancing algorithm that does this or just reuse the one we use for deposits
+            let now = <frame_system::Pallet<T>>::block_number();
 
-            // Self::deposit_event(Event::StartedRebalance(amount));
-            // Ok(())
+            let mut amount: BalanceOf<T> = 0u32.into();
+            for collator in Self::collators_to_rebalance() {
+                amount += StakedCollators::<T, I>::get(&collator);
+                Self::do_unstake_collator(now, collator)?;
+            }
+            if !amount.is_zero() {
+                Self::deposit_event(Event::StartedRebalance { amount });
+            }
+            Ok(())
         }
 
         /// Starts the lottery by scheduling a [`Call::draw_lottery`] call
@@ -720,26 +1352,26 @@ pub mod pallet {
         ///
         /// You can always learn what block the next drawing - if any - will happen by calling [`Self::next_drawing_at`]
         #[pallet::call_index(4)]
-        #[pallet::weight(<T as Config>::WeightInfo::start_lottery())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::start_lottery())]
         pub fn start_lottery(origin: OriginFor<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
             ensure!(
                 Self::next_drawing_at().is_none(),
-                Error::<T>::LotteryIsRunning
+                Error::<T, I>::LotteryIsRunning
             );
             // Pallet has enough funds to pay gas fees for at least the first drawing
             ensure!(
                 Self::surplus_funds() >= Self::gas_reserve(),
-                Error::<T>::PotBalanceBelowGasReserve
+                Error::<T, I>::PotBalanceBelowGasReserve
             );
             // NOTE: If more than gas_reserve is in the pallet, the full excess will be paid out to the winner of the next drawing! This is intended to dope the winning balance with extra rewards
 
-            let drawing_interval = <T as Config>::DrawingInterval::get();
+            let drawing_interval = <T as Config<I>>::DrawingInterval::get();
             ensure!(
                 drawing_interval > 0u32.into(),
-                Error::<T>::PalletMisconfigured
+                Error::<T, I>::PalletMisconfigured
             );
-            let lottery_drawing_call: CallOf<T> = Call::draw_lottery {}.into();
+            let lottery_drawing_call: CallOf<T, I> = Call::draw_lottery {}.into();
             T::Scheduler::schedule_named(
                 Self::lottery_schedule_id(),
                 DispatchTime::After(drawing_interval),
@@ -748,7 +1380,10 @@ pub mod pallet {
                 frame_support::dispatch::RawOrigin::Root.into(),
                 MaybeHashed::Value(lottery_drawing_call),
             )
-            .map_err(|_| Error::<T>::CouldNotSchedule)?;
+            .map_err(|_| Error::<T, I>::CouldNotSchedule)?;
+            // everybody who already deposited enters the first drawing with the same holding time
+            Self::start_ticket_interval();
+            Self::clear_participants_commitment();
 
             Self::deposit_event(Event::LotteryStarted);
             Ok(())
@@ -766,11 +1401,12 @@ pub mod pallet {
         /// * LotteryNotStarted: Nothing to stop
         ///
         #[pallet::call_index(5)]
-        #[pallet::weight(<T as Config>::WeightInfo::stop_lottery())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::stop_lottery())]
         pub fn stop_lottery(origin: OriginFor<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
             T::Scheduler::cancel_named(Self::lottery_schedule_id())
-                .map_err(|_| Error::<T>::LotteryNotStarted)?;
+                .map_err(|_| Error::<T, I>::LotteryNotStarted)?;
+            Self::clear_participants_commitment();
             Self::deposit_event(Event::LotteryStopped);
             Ok(())
         }
@@ -790,11 +1426,13 @@ pub mod pallet {
         /// * PotBalanceTooLow: The balance of the pot is too low.
         /// * NoWinnerFound: Nobody was selected as winner
         #[pallet::call_index(6)]
-        #[pallet::weight(<T as Config>::WeightInfo::draw_lottery(Pallet::<T>::total_users(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::draw_lottery(Pallet::<T, I>::total_users(), pallet_parachain_staking::Pallet::<T>::selected_candidates().len() as u32))]
         pub fn draw_lottery(origin: OriginFor<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
             let now = <frame_system::Pallet<T>>::block_number();
             log::trace!("Drawing lottery called at block {:?}", now.clone());
+            // commits the slots the freezeout did not get to, the drawing's weight scales with the participants anyway
+            Self::commit_participants(u32::MAX);
 
             let total_funds_in_pallet =
                 <T as pallet_parachain_staking::Config>::Currency::total_balance(
@@ -817,7 +1455,7 @@ pub mod pallet {
                         .saturating_add(Self::total_unclaimed_winnings())   // all prior winnings
                         .saturating_add(winning_claim)                      // and the current winner's new claim
                         <= total_funds_in_pallet, // don't exceed funds in the pallet
-                    Error::<T>::PotBalanceTooLow
+                    Error::<T, I>::PotBalanceTooLow
                 );
                 Self::select_winner(winning_claim)?;
             } else {
@@ -827,6 +1465,9 @@ pub mod pallet {
                     participating_funds,
                 );
             }
+            // tickets for the next drawing are only earned by funds held from now on
+            Self::start_ticket_interval();
+            Self::clear_participants_commitment();
             // unstake, pay out tokens due for withdrawals and restake excess funds
             // At this point, all excess funds except for `gas_reserve` have been reserved for the current winner
             Self::process_matured_withdrawals(origin)?;
@@ -842,7 +1483,7 @@ pub mod pallet {
         /// * BadOrigin: Caller is not ManageOrigin
         /// * errors defined by the do_process_matured_withdrawals function.
         #[pallet::call_index(7)]
-        #[pallet::weight(<T as Config>::WeightInfo::process_matured_withdrawals())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::process_matured_withdrawals())]
         pub fn process_matured_withdrawals(origin: OriginFor<T>) -> DispatchResult {
             log::trace!("process_matured_withdrawals");
             T::ManageOrigin::ensure_origin(origin.clone())?;
@@ -870,11 +1511,11 @@ pub mod pallet {
 
             ensure!(
                 Self::next_drawing_at().is_none(),
-                Error::<T>::LotteryIsRunning
+                Error::<T, I>::LotteryIsRunning
             );
 
             Err(crate::pallet::DispatchError::Other(
-                Error::<T>::NotImplemented.into(),
+                Error::<T, I>::NotImplemented.into(),
             ))
 
             // TODO: Unstake all collators, schedule return of all user deposits
@@ -888,35 +1529,35 @@ pub mod pallet {
             // Ok(())
         }
         #[pallet::call_index(9)]
-        #[pallet::weight(<T as Config>::WeightInfo::set_min_deposit())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_min_deposit())]
         pub fn set_min_deposit(origin: OriginFor<T>, min_deposit: BalanceOf<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
             ensure!(
                 min_deposit >= Self::min_withdraw(),
-                Error::<T>::PalletMisconfigured
+                Error::<T, I>::PalletMisconfigured
             );
-            MinDeposit::<T>::set(min_deposit);
+            MinDeposit::<T, I>::set(min_deposit);
             Ok(())
         }
         #[pallet::call_index(10)]
-        #[pallet::weight(<T as Config>::WeightInfo::set_min_withdraw())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_min_withdraw())]
         pub fn set_min_withdraw(
             origin: OriginFor<T>,
             min_withdraw: BalanceOf<T>,
         ) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
-            MinWithdraw::<T>::set(min_withdraw);
+            MinWithdraw::<T, I>::set(min_withdraw);
             Ok(())
         }
         #[pallet::call_index(11)]
-        #[pallet::weight(<T as Config>::WeightInfo::set_gas_reserve())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_gas_reserve())]
         pub fn set_gas_reserve(origin: OriginFor<T>, gas_reserve: BalanceOf<T>) -> DispatchResult {
             T::ManageOrigin::ensure_origin(origin.clone())?;
-            GasReserve::<T>::set(gas_reserve);
+            GasReserve::<T, I>::set(gas_reserve);
             Ok(())
         }
         #[pallet::call_index(12)]
-        #[pallet::weight(<T as Config>::WeightInfo::set_gas_reserve())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_gas_reserve())]
         pub fn set_farming_params(
             origin: OriginFor<T>,
             mint_farming_token: bool,
@@ -932,13 +1573,40 @@ pub mod pallet {
                 pool_id,
                 currency_id,
             };
-            FarmingParameters::<T>::set(farming_params);
+            FarmingParameters::<T, I>::set(farming_params);
 
             Ok(())
         }
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_gas_reserve())]
+        pub fn set_staking_strategy(
+            origin: OriginFor<T>,
+            strategy: StakingStrategyKind,
+        ) -> DispatchResult {
+            T::ManageOrigin::ensure_origin(origin)?;
+            ActiveStakingStrategy::<T, I>::set(strategy);
+            Ok(())
+        }
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_gas_reserve())]
+        pub fn set_liquidity_buffer_target(
+            origin: OriginFor<T>,
+            target: BalanceOf<T>,
+        ) -> DispatchResult {
+            T::ManageOrigin::ensure_origin(origin)?;
+            LiquidityBufferTarget::<T, I>::set(target);
+            Ok(())
+        }
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_gas_reserve())]
+        pub fn set_instant_withdraw_fee(origin: OriginFor<T>, fee: Perbill) -> DispatchResult {
+            T::ManageOrigin::ensure_origin(origin)?;
+            InstantWithdrawFee::<T, I>::set(fee);
+            Ok(())
+        }
     }
 
-    impl<T: Config> Pallet<T> {
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
         /// Get a unique, inaccessible account id from the `PotId`.
         pub(crate) fn account_id() -> T::AccountId {
             T::LotteryPot::get().into_account_truncating()
@@ -947,11 +1615,18 @@ pub mod pallet {
         fn lottery_schedule_id() -> Vec<u8> {
             T::LotteryPot::get().0.to_vec()
         }
+        /// Subject of the `n`th randomness request of a drawing
+        pub(crate) fn randomness_subject(n: u8) -> [u8; 1] {
+            [n; 1]
+        }
+        /// Returns the winning balance, the randomness it was derived from and the index of the 128 bit sample used
         fn select_winning_balance(
             max_winning_balance: BalanceOf<T>,
-        ) -> Result<BalanceOf<T>, Error<T>> {
+        ) -> Result<(BalanceOf<T>, T::Hash, u8), Error<T, I>> {
             const MAX_NUMBER_OF_RESAMPLES: u8 = 3;
             let mut winning_number = 0; // XXX: This shouldn't need initialization but the compiler doesn't get it
+            let mut randomness = T::Hash::default();
+            let mut resamples = 0u8;
             for n in 0u8..MAX_NUMBER_OF_RESAMPLES {
                 let random: (T::Hash, BlockNumberFor<T>);
                 #[cfg(feature = "runtime-benchmarks")]
@@ -972,20 +1647,22 @@ pub mod pallet {
                 }
                 #[cfg(not(feature = "runtime-benchmarks"))]
                 {
-                    random = T::RandomnessSource::random(&[n; 1]);
+                    random = T::RandomnessSource::random(&Self::randomness_subject(n));
                     log::debug!("select-winner using randomness {:?}", random);
                     // TODO: The following check needs a change to pallet randomness but is static,
                     //       so this can be done manually on deployment of the pallet
                     // ensure!(
                     //     random.1 = randomness_established_at_block
-                    //         .saturating_add(<T as Config>::DrawingFreezeout::get())
+                    //         .saturating_add(<T as Config<I>>::DrawingFreezeout::get())
                     //         < <frame_system::Pallet<T>>::block_number(),
-                    //     Error::<T>::PalletMisconfigured
+                    //     Error::<T, I>::PalletMisconfigured
                     // );
                 }
                 let random_hash = random.0;
+                randomness = random_hash;
                 let as_number = U256::from_big_endian(random_hash.as_ref());
                 winning_number = as_number.low_u128();
+                resamples = 2 * n;
                 // naive application of the modulo operation can bias the result, reject and resample if the number is larger than the maximum divisor of user array length in the u128 number range
                 debug_assert_eq!(
                     core::mem::size_of::<BalanceOf<T>>(),
@@ -1002,6 +1679,7 @@ pub mod pallet {
                 } else {
                     // sample must be rejected because it can't be safely modulo'd, retry with high u128
                     winning_number = (as_number >> 128).low_u128();
+                    resamples = 2 * n + 1;
                     let number_to_start_rejecting_at: u128 = u128::max_value().saturating_sub(
                         u128::max_value() % max_winning_balance.saturated_into::<u128>(),
                     );
@@ -1024,69 +1702,268 @@ pub mod pallet {
             }
             // no risk of modulo bias here unless we ran out of retries above
             let winning_balance: BalanceOf<T> = BalanceOf::<T>::try_from(winning_number)
-                .map_err(|_| Error::<T>::ArithmeticOverflow)?
+                .map_err(|_| Error::<T, I>::ArithmeticOverflow)?
                 % max_winning_balance;
             log::debug!(
                 "winning_number: {:?}, winning balance: {:?}",
                 winning_number,
                 winning_balance
             );
-            Ok(winning_balance)
+            Ok((winning_balance, randomness, resamples))
         }
-        fn select_winner(payout_for_winner: BalanceOf<T>) -> DispatchResult {
+        pub(crate) fn select_winner(payout_for_winner: BalanceOf<T>) -> DispatchResult {
             if payout_for_winner.is_zero() {
-                return Err(Error::<T>::NothingToWin.into());
+                return Err(Error::<T, I>::NothingToWin.into());
             }
             let participating_funds = Self::total_pot();
             if participating_funds.is_zero() {
-                return Err(Error::<T>::NobodyPlaying.into());
-            }
-            // Match random number to winner. We select a winning **balance** and then just add up accounts in the order they're stored until the sum of balance exceeds the winning amount
-            // IMPORTANT: This order and active balances must be locked to modification after the random seed is created (relay BABE randomness, 2 epochs ago)
-            let winning_balance = Self::select_winning_balance(participating_funds)?;
-            let mut maybe_winner: Option<T::AccountId> = None;
-            let mut count: BalanceOf<T> = 0u32.into();
-            for (account, balance) in ActiveBalancePerUser::<T>::iter() {
-                count += balance;
-                if count >= winning_balance {
-                    maybe_winner = Some(account);
-                    break;
-                }
+                return Err(Error::<T, I>::NobodyPlaying.into());
             }
+            // Match random number to winner. We select a winning **ticket** and then look up the participant slot whose
+            // cumulative ticket range contains it. A participant's tickets are its balance weighted by the blocks it was held
+            // during the current drawing interval, so depositing right before the freezeout buys proportionally fewer tickets
+            // IMPORTANT: Slots and active balances must be locked to modification after the random seed is created (relay BABE randomness, 2 epochs ago)
+            let total_tickets = Self::total_tickets();
+            let (winning_ticket, randomness, resamples) =
+                Self::select_winning_balance(total_tickets)?;
             // Should be impossible: If no winner was selected, return Error
-            ensure!(maybe_winner.is_some(), Error::<T>::NoWinnerFound);
-            let winner = maybe_winner.expect("we checked a winner exists before. qed");
-            // Allow winner to manually claim their winnings later
-            UnclaimedWinningsByAccount::<T>::mutate(winner.clone(), |maybe_balance| {
-                *maybe_balance = Some(
-                    maybe_balance
-                        .unwrap_or_else(|| 0u32.into())
-                        .saturating_add(payout_for_winner),
+            let winner =
+                Self::participant_at_ticket(winning_ticket).ok_or(Error::<T, I>::NoWinnerFound)?;
+            let compounded = Self::auto_compound_winnings(&winner)
+                && with_storage_layer(|| Self::do_compound_winnings(&winner, payout_for_winner))
+                    .map_err(|e| {
+                        log::warn!(
+                            "could not compound winning of {:?} for account {:?}: {:?}",
+                            payout_for_winner,
+                            winner,
+                            e
+                        );
+                    })
+                    .is_ok();
+            if !compounded {
+                // Allow winner to manually claim their winnings later
+                UnclaimedWinningsByAccount::<T, I>::mutate(winner.clone(), |maybe_balance| {
+                    *maybe_balance = Some(
+                        maybe_balance
+                            .unwrap_or_else(|| 0u32.into())
+                            .saturating_add(payout_for_winner),
+                    );
+                });
+                TotalUnclaimedWinnings::<T, I>::try_mutate(|old| {
+                    *old = (*old)
+                        .checked_add(&payout_for_winner)
+                        .ok_or(ArithmeticError::Overflow)?;
+                    Ok::<(), ArithmeticError>(())
+                })?;
+                log::debug!(
+                    "winning of {:?} added to claim for account {:?}",
+                    payout_for_winner,
+                    winner
                 );
+            }
+            let now = <frame_system::Pallet<T>>::block_number();
+            Self::record_drawing(DrawingRecord {
+                block: now,
+                randomness,
+                total_pot: participating_funds,
+                participants: Self::active_participants(),
+                winner: winner.clone(),
+                prize: payout_for_winner,
+                winning_ticket,
+                resamples,
+                ticket_blocks: Self::ticket_blocks(),
+                participants_root: Self::drawing_commitment()
+                    .map(|commitment| commitment.participants_root),
+            });
+            Self::deposit_event(Event::LotteryWinner {
+                account: winner,
+                amount: payout_for_winner,
             });
-            TotalUnclaimedWinnings::<T>::try_mutate(|old| {
-                *old = (*old)
-                    .checked_add(&payout_for_winner)
-                    .ok_or(ArithmeticError::Overflow)?;
-                Ok::<(), ArithmeticError>(())
+            Ok(())
+        }
+
+        /// Ensures `amount` of staked or unstaking funds is set aside for a withdrawal request made at `now`,
+        /// unstaking collators as needed
+        fn do_unstake_for_withdrawal(
+            now: BlockNumberFor<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            // Unstaking workflow
+            // 1. See if this withdrawal can be serviced with left-over balance from an already unstaking collator, if so deduct remaining balance and schedule the request
+            // 2. If it can't, find the collator with the smallest delegation that is able to handle this withdrawal request and fully unstake it
+            // 3. Add balance overshoot to "remaining balance" to handle further requests from
+
+            // If the withdrawal fits in the currently unstaking funds, do nothing else
+            SurplusUnstakingBalance::<T, I>::try_mutate(|remaining_balance| {
+                match (*remaining_balance).checked_sub(&amount){
+                    Some(subtracted) => {
+                        *remaining_balance = subtracted;
+                        Ok(())
+                    }
+                    _ => {
+                        Err("not enough left to handle this request from current unstaking funds")
+                    }
+                }
+            })
+            .or_else(|_| {
+                // Withdrawal needs extra collators to unstake to have enough funds to serve withdrawals, do it
+                let reserve = SurplusUnstakingBalance::<T, I>::get();
+                let mut remaining_to_withdraw = amount - reserve;
+
+                // Deposits kept in the liquidity buffer are not staked, they serve the part no collator can be unstaked for
+                let from_buffer = remaining_to_withdraw
+                    .saturating_sub(Self::unstakable_balance())
+                    .min(Self::liquidity_buffer());
+                if !from_buffer.is_zero() {
+                    LiquidityBuffer::<T, I>::mutate(|buffer| *buffer -= from_buffer);
+                    UnlockedUnstakingFunds::<T, I>::mutate(|unlocked| *unlocked += from_buffer);
+                    SurplusUnstakingBalance::<T, I>::mutate(|surplus| *surplus += from_buffer);
+                    remaining_to_withdraw -= from_buffer;
+                }
+
+                // unstake collators as necessary. This updates `SurplusUnstakingBalance`
+                for collator_to_unstake in Self::calculate_withdrawal_distribution(remaining_to_withdraw){
+                    let our_stake = StakedCollators::<T, I>::get(collator_to_unstake.clone());
+                    remaining_to_withdraw = remaining_to_withdraw.saturating_sub(our_stake);
+                    // The following call updates `SurplusUnstakingBalance` with newly unstaked funds
+                    Self::do_unstake_collator(now,collator_to_unstake)?;
+                }
+                if !remaining_to_withdraw.is_zero() {
+                    return Err("FATAL: Didn't unstake the full requested balance (or more)");
+                }
+                SurplusUnstakingBalance::<T, I>::try_mutate(|remaining_balance| {
+                    match (*remaining_balance).checked_sub(&amount){
+                        Some(subtracted) => {
+                            *remaining_balance = subtracted;
+                            Ok(())
+                        }
+                        _ => {
+                            Err("not enough unstaking balance to handle request after unstaking additional collators")
+                        }
+                    }
+                })
+            })?;
+            Ok(())
+        }
+
+        /// Removes `amount` from the active funds of `account`, they no longer participate in drawings
+        /// The account is removed from the lottery if this was its last remaining active balance
+        fn do_deactivate_balance(account: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            ActiveBalancePerUser::<T, I>::try_mutate_exists(account.clone(), |maybe_balance| {
+                match maybe_balance {
+                    None => Err(Error::<T, I>::NoDepositForAccount),
+                    Some(balance) => {
+                        // Withdraw only what's active
+                        ensure!(*balance >= amount, Error::<T, I>::WithdrawAboveDeposit);
+                        // store reduced balance
+                        let new_balance = balance
+                            .checked_sub(&amount)
+                            .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                        Self::index_sub_balance(account, amount, new_balance.is_zero())?;
+                        *maybe_balance = match new_balance {
+                            new_balance if new_balance.is_zero() => {
+                                // remove user if this was his last remaining funds
+                                TotalUsers::<T, I>::try_mutate(|users| {
+                                    *users = (*users)
+                                        .checked_sub(1u32)
+                                        .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                                    Ok(())
+                                })?;
+                                None
+                            }
+                            new_balance => Some(new_balance),
+                        };
+                        TotalPot::<T, I>::try_mutate(|pot| {
+                            *pot = (*pot)
+                                .checked_sub(&amount)
+                                .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                            Ok(())
+                        })?;
+                        Ok(())
+                    }
+                }
+            })?;
+            Ok(())
+        }
+
+        /// Adds `winnings` to the active balance of `winner` and stakes them like a [`Call::deposit`]
+        /// The winnings are free balance of the pallet already, so no transfer is needed
+        fn do_compound_winnings(winner: &T::AccountId, winnings: BalanceOf<T>) -> DispatchResult {
+            ensure!(
+                winnings >= Self::min_deposit(),
+                Error::<T, I>::DepositBelowMinAmount
+            );
+            let collator_balance_pairs = Self::calculate_deposit_distribution(winnings);
+            ensure!(
+                !collator_balance_pairs.is_empty(),
+                Error::<T, I>::NoCollatorForDeposit
+            );
+            for (collator, balance) in collator_balance_pairs {
+                Self::do_stake_one_collator(collator, balance)?;
+            }
+
+            let farming_params = FarmingParameters::<T, I>::get();
+            if farming_params.mint_farming_token {
+                let convert_amount: T::BalanceConversion = winnings.into();
+                <T as pallet_farming::Config>::MultiCurrency::deposit(
+                    farming_params.currency_id,
+                    winner,
+                    convert_amount.into(),
+                )?;
+                pallet_farming::Pallet::<T>::deposit_farming(
+                    winner.clone(),
+                    farming_params.pool_id,
+                    convert_amount.into(),
+                    None,
+                )?;
+            }
+
+            ActiveBalancePerUser::<T, I>::try_mutate(winner, |balance| {
+                *balance = (*balance)
+                    .checked_add(&winnings)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            Self::index_add_balance(winner, winnings)?;
+            TotalPot::<T, I>::try_mutate(|pot| {
+                *pot = (*pot)
+                    .checked_add(&winnings)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            SumOfDeposits::<T, I>::try_mutate(|sum| {
+                *sum = (*sum)
+                    .checked_add(&winnings)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok::<(), Error<T, I>>(())
             })?;
             log::debug!(
-                "winning of {:?} added to claim for account {:?}",
-                payout_for_winner,
+                "winning of {:?} compounded for account {:?}",
+                winnings,
                 winner
             );
-            Self::deposit_event(Event::LotteryWinner {
-                account: winner,
-                amount: payout_for_winner,
+            Self::deposit_event(Event::WinningsCompounded {
+                account: winner.clone(),
+                amount: winnings,
             });
             Ok(())
         }
 
+        /// Appends `record` to [`DrawingHistory`], dropping the oldest record beyond [`Config::MaxDrawingHistory`]
+        fn record_drawing(record: DrawingRecordOf<T>) {
+            let number = Self::drawing_count();
+            DrawingHistory::<T, I>::insert(number, record);
+            if let Some(expired) = number.checked_sub(T::MaxDrawingHistory::get()) {
+                DrawingHistory::<T, I>::remove(expired);
+            }
+            DrawingCount::<T, I>::put(number.saturating_add(1));
+        }
+
         /// Unstake any collators we can unstake
         /// This is infallible, if any step fails we just leave the collator in the request queue
         fn finish_unstaking_collators() {
             let now = <frame_system::Pallet<T>>::block_number();
-            let mut unstaking = UnstakingCollators::<T>::get();
+            let mut unstaking = UnstakingCollators::<T, I>::get();
             let original_len = unstaking.len();
             if unstaking.is_empty() {
                 return;
@@ -1094,7 +1971,7 @@ pub mod pallet {
             // Unstake what we can (false), leave the rest (true)
             unstaking.retain(|collator|{
                     // Leave collators that are not finished unstaking alone
-                    if collator.since + <T as Config>::UnstakeLockTime::get() > now {
+                    if collator.since + <T as Config<I>>::UnstakeLockTime::get() > now {
                         return true;
                     };
                     // Recover funds locked in the collator
@@ -1131,8 +2008,8 @@ pub mod pallet {
                         Ok(_) => {
                             // collator was unstaked, its funds are now "free balance", we track it so it won't be given to the next winner
                             log::debug!("Unstaked {:?} from collator {:?}",balance_to_unstake,collator.account.clone());
-                            <UnlockedUnstakingFunds<T>>::mutate(|unlocked| *unlocked = (*unlocked).saturating_add(balance_to_unstake));
-                            <StakedCollators<T>>::remove(collator.account.clone());
+                            <UnlockedUnstakingFunds<T, I>>::mutate(|unlocked| *unlocked = (*unlocked).saturating_add(balance_to_unstake));
+                            <StakedCollators<T, I>>::remove(collator.account.clone());
                             // don't retain this collator in the unstaking collators vec
                             false
                         },
@@ -1144,7 +2021,7 @@ pub mod pallet {
                     original_len - unstaking.len(),
                     original_len
                 );
-                UnstakingCollators::<T>::put(unstaking);
+                UnstakingCollators::<T, I>::put(unstaking);
             }
         }
 
@@ -1155,17 +2032,28 @@ pub mod pallet {
             // were previously called and all unlockable funds are claimed
 
             // Only restake what
-            // - isn't needed to service the still outstanding withdrawal requests
+            // - isn't needed to service the still outstanding withdrawal requests of users and sponsors
             // - is funds that were previously unstaked
             // - is surplus funds (we may have some from `finish_unstaking_collators`)
             // NOTE: Funds tracked in `surplus_unstaking_balance` might still be partially stake locked
-            let outstanding_balance_to_withdraw = <WithdrawalRequestQueue<T>>::get()
+            let outstanding_balance_to_withdraw = <WithdrawalRequestQueue<T, I>>::get()
                 .iter()
+                .chain(<SponsorWithdrawalRequestQueue<T, I>>::get().iter())
                 .map(|request| request.balance)
                 .reduce(|acc, balance| acc + balance)
                 .unwrap_or_else(|| 0u32.into());
-            let restakable_balance =
+            let unstaked_balance =
                 Self::unlocked_unstaking_funds().saturating_sub(outstanding_balance_to_withdraw);
+            // Refill the liquidity buffer first, funds it holds above its target are restaked as well
+            let to_buffer = Self::liquidity_buffer_refill(unstaked_balance);
+            if !to_buffer.is_zero() {
+                Self::release_unlocked_unstaking_funds(to_buffer)?;
+                LiquidityBuffer::<T, I>::mutate(|buffer| *buffer += to_buffer);
+            }
+            let unstaked_balance = unstaked_balance.saturating_sub(to_buffer);
+            let buffer_excess =
+                Self::liquidity_buffer().saturating_sub(Self::liquidity_buffer_target());
+            let restakable_balance = unstaked_balance.saturating_add(buffer_excess);
             if restakable_balance < Self::min_deposit() {
                 log::debug!(
                     "Restakable balance of {:?} is below staking minimum of {:?}. Not restaking",
@@ -1189,21 +2077,49 @@ pub mod pallet {
                     collator
                 );
             }
-            SurplusUnstakingBalance::<T>::try_mutate(|bal| -> DispatchResult {
+            Self::release_unlocked_unstaking_funds(unstaked_balance)?;
+            LiquidityBuffer::<T, I>::try_mutate(|buffer| -> DispatchResult {
+                *buffer = (*buffer)
+                    .checked_sub(&buffer_excess)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                Ok(())
+            })?;
+            Ok(())
+        }
+
+        /// Removes `amount` of unlocked funds that are not needed for withdrawal requests from the unstaking bookkeeping
+        /// once they are restaked or moved to the [`LiquidityBuffer`]
+        fn release_unlocked_unstaking_funds(amount: BalanceOf<T>) -> DispatchResult {
+            SurplusUnstakingBalance::<T, I>::try_mutate(|bal| -> DispatchResult {
                 *bal = (*bal)
-                    .checked_sub(&restakable_balance)
-                    .ok_or(Error::<T>::ArithmeticUnderflow)?;
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
                 Ok(())
             })?;
-            UnlockedUnstakingFunds::<T>::try_mutate(|unlocked| -> DispatchResult {
+            UnlockedUnstakingFunds::<T, I>::try_mutate(|unlocked| -> DispatchResult {
                 *unlocked = (*unlocked)
-                    .checked_sub(&restakable_balance)
-                    .ok_or(Error::<T>::ArithmeticUnderflow)?;
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
                 Ok(())
             })?;
             Ok(())
         }
 
+        /// Part of `available` user funds to keep unstaked to bring [`LiquidityBuffer`] up to [`LiquidityBufferTarget`]
+        /// If the rest would be too small to stake, all of `available` is kept, so the buffer may exceed its target by less than [`MinDeposit`]
+        fn liquidity_buffer_refill(available: BalanceOf<T>) -> BalanceOf<T> {
+            let shortfall =
+                Self::liquidity_buffer_target().saturating_sub(Self::liquidity_buffer());
+            if shortfall.is_zero() {
+                return Zero::zero();
+            }
+            if shortfall >= available || available - shortfall < Self::min_deposit() {
+                available
+            } else {
+                shortfall
+            }
+        }
+
         /// This fn schedules a single shot payout of all matured withdrawals
         /// Main usage: Automatic execution in the course of a drawing
         /// It can also be manually invoke by T::ManageOrigin to reprocess withdrawals that
@@ -1212,7 +2128,9 @@ pub mod pallet {
         #[named]
         fn do_process_matured_withdrawals() -> DispatchResult {
             log::trace!(function_name!());
-            if <WithdrawalRequestQueue<T>>::get().is_empty() {
+            if <WithdrawalRequestQueue<T, I>>::get().is_empty()
+                && <SponsorWithdrawalRequestQueue<T, I>>::get().is_empty()
+            {
                 return Ok(()); // nothing to do
             }
             let now = <frame_system::Pallet<T>>::block_number();
@@ -1221,69 +2139,85 @@ pub mod pallet {
                 Self::unlocked_unstaking_funds()
             );
             // Pay down the list from top (oldest) to bottom until we've paid out everyone or run out of available funds
-            <WithdrawalRequestQueue<T>>::mutate(|request_vec| -> Result<(), DispatchError> {
-                let mut left_overs: Vec<Request<_, _, _>> = Vec::new();
-                for request in request_vec.iter() {
-                    let funds_available_to_withdraw = Self::unlocked_unstaking_funds();
-                    // Don't pay anyone unless we have surplus funds
-                    if funds_available_to_withdraw.is_zero() {
-                        left_overs.push((*request).clone());
-                        continue;
-                    }
-                    // Don't pay anyone still timelocked
-                    if request.block + <T as Config>::UnstakeLockTime::get() > now {
-                        left_overs.push((*request).clone());
-                        continue;
-                    }
-                    // stop paying people if we've run out of free funds.
-                    // The assumption is the collators serving these requests will
-                    // finish unstaking next round ( next lottery drawing )
-                    if request.balance > funds_available_to_withdraw {
-                        left_overs.push((*request).clone());
-                        continue;
-                    }
-                    // we know we can pay this out, do it
-                    <SumOfDeposits<T>>::mutate(|sum| *sum = (*sum).saturating_sub(request.balance));
-                    log::debug!(
-                        "Transferring {:?} to {:?}",
-                        request.balance.clone(),
-                        request.user.clone()
-                    );
-                    <T as pallet_parachain_staking::Config>::Currency::transfer(
-                        &Self::account_id(),
-                        &request.user,
-                        request.balance,
-                        KeepAlive,
-                    )?;
-                    <UnlockedUnstakingFunds<T>>::try_mutate(|funds| -> DispatchResult {
-                        *funds = (*funds)
-                            .checked_sub(&request.balance)
-                            .ok_or(Error::<T>::ArithmeticUnderflow)?;
-                        Ok(())
-                    })?;
-                    Self::deposit_event(Event::Withdrawn {
-                        account: request.user.clone(),
-                        amount: request.balance,
-                    });
+            <WithdrawalRequestQueue<T, I>>::mutate(|request_vec| {
+                Self::pay_matured_requests(request_vec, now, |account, amount| Event::Withdrawn {
+                    account,
+                    amount,
+                })
+            })?;
+            // Sponsors are served from what is left after paying users
+            <SponsorWithdrawalRequestQueue<T, I>>::mutate(|request_vec| {
+                Self::pay_matured_requests(request_vec, now, |account, amount| {
+                    Event::SponsorWithdrawn { account, amount }
+                })
+            })?;
+            Ok(())
+        }
+
+        /// Transfers matured requests of `request_vec` from [`UnlockedUnstakingFunds`] to their owners and removes them,
+        /// requests that are still timelocked or can not be paid yet are kept
+        fn pay_matured_requests(
+            request_vec: &mut Vec<Request<T::AccountId, BlockNumberFor<T>, BalanceOf<T>>>,
+            now: BlockNumberFor<T>,
+            paid_event: fn(T::AccountId, BalanceOf<T>) -> Event<T, I>,
+        ) -> DispatchResult {
+            let mut left_overs: Vec<Request<_, _, _>> = Vec::new();
+            for request in request_vec.iter() {
+                let funds_available_to_withdraw = Self::unlocked_unstaking_funds();
+                // Don't pay anyone unless we have surplus funds
+                if funds_available_to_withdraw.is_zero() {
+                    left_overs.push((*request).clone());
+                    continue;
+                }
+                // Don't pay anyone still timelocked
+                if request.block + <T as Config<I>>::UnstakeLockTime::get() > now {
+                    left_overs.push((*request).clone());
+                    continue;
                 }
+                // stop paying people if we've run out of free funds.
+                // The assumption is the collators serving these requests will
+                // finish unstaking next round ( next lottery drawing )
+                if request.balance > funds_available_to_withdraw {
+                    left_overs.push((*request).clone());
+                    continue;
+                }
+                // we know we can pay this out, do it
+                <SumOfDeposits<T, I>>::mutate(|sum| *sum = (*sum).saturating_sub(request.balance));
                 log::debug!(
-                    "Have {:?} requests, {:?} free unstaking and {:?} surplus funds left over after transfers",
-                    left_overs.len(),
-                    Self::unlocked_unstaking_funds(),
-                    Self::surplus_funds()
+                    "Transferring {:?} to {:?}",
+                    request.balance.clone(),
+                    request.user.clone()
                 );
-                // Update T::WithdrawalRequestQueue by mutating `request_vec` if we paid at least one guy
-                if left_overs.len() != (*request_vec).len() {
-                    request_vec.clear();
-                    request_vec.append(&mut left_overs);
-                }
-                Ok(())
-            })?;
+                <T as pallet_parachain_staking::Config>::Currency::transfer(
+                    &Self::account_id(),
+                    &request.user,
+                    request.balance,
+                    KeepAlive,
+                )?;
+                <UnlockedUnstakingFunds<T, I>>::try_mutate(|funds| -> DispatchResult {
+                    *funds = (*funds)
+                        .checked_sub(&request.balance)
+                        .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                    Ok(())
+                })?;
+                Self::deposit_event(paid_event(request.user.clone(), request.balance));
+            }
+            log::debug!(
+                "Have {:?} requests, {:?} free unstaking and {:?} surplus funds left over after transfers",
+                left_overs.len(),
+                Self::unlocked_unstaking_funds(),
+                Self::surplus_funds()
+            );
+            // Update the queue by mutating `request_vec` if we paid at least one guy
+            if left_overs.len() != (*request_vec).len() {
+                request_vec.clear();
+                request_vec.append(&mut left_overs);
+            }
             Ok(())
         }
     }
 
-    impl<T: Config> Pallet<T> {
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
         // public getters for lottery state
         /// Returns the block the next drawing will execute, if any
         pub fn next_drawing_at() -> Option<BlockNumberFor<T>> {
@@ -1304,10 +2238,13 @@ pub mod pallet {
             // waiting to serve a pending withdrawal in the next cycle.
             // These free funds must not be touched until then, so we don't consider this balance a surplus
             let unlocked = Self::unlocked_unstaking_funds();
+            // user deposits kept unstaked for instant withdrawals
+            let buffer = Self::liquidity_buffer();
 
             non_staked_funds
                 .saturating_sub(unclaimed)
                 .saturating_sub(unlocked)
+                .saturating_sub(buffer)
         }
         /// funds in the lottery pallet that are not needed/reserved for anything and can be paid to the next winner
         pub fn current_prize_pool() -> BalanceOf<T> {
@@ -1322,9 +2259,49 @@ pub mod pallet {
                 }
                 Some(drawing) => {
                     let now = <frame_system::Pallet<T>>::block_number();
-                    now < drawing.saturating_sub(<T as Config>::DrawingFreezeout::get())
+                    now < drawing.saturating_sub(<T as Config<I>>::DrawingFreezeout::get())
                 }
             }
         }
+        /// Number of accounts with funds eligible to win
+        pub fn active_participants() -> u32 {
+            ParticipantSlots::<T, I>::get()
+                .saturating_sub(FreeSlots::<T, I>::decode_len().unwrap_or_default() as u32)
+        }
+        /// Returns up to `count` recorded drawings, oldest first, starting at drawing number `from`
+        pub fn drawing_history(from: u32, count: u32) -> Vec<DrawingRecordOf<T>> {
+            let count = count.min(T::MaxDrawingHistory::get());
+            (from..from.saturating_add(count))
+                .filter_map(DrawingHistory::<T, I>::get)
+                .collect()
+        }
+        /// Returns deposit, odds, pending withdrawals and unclaimed winnings of `account`
+        pub fn user_position(account: T::AccountId) -> UserPositionOf<T> {
+            let active_deposit = Self::active_balance_per_user(&account);
+            let total_tickets = Self::total_tickets();
+            let win_probability = if total_tickets.is_zero() {
+                Perbill::zero()
+            } else {
+                Perbill::from_rational(Self::tickets_of(&account), total_tickets)
+            };
+            let pending_withdrawals = Self::withdrawal_request_queue()
+                .into_iter()
+                .filter(|request| request.user == account)
+                .map(|request| PendingWithdrawal {
+                    balance: request.balance,
+                    requested_at: request.block,
+                    matures_at: request
+                        .block
+                        .saturating_add(<T as Config<I>>::UnstakeLockTime::get()),
+                })
+                .collect();
+            UserPosition {
+                active_deposit,
+                win_probability,
+                pending_withdrawals,
+                unclaimed_winnings: Self::unclaimed_winnings_by_account(&account)
+                    .unwrap_or_default(),
+            }
+        }
     }
 }
@@ -0,0 +1,118 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Migrations
+
+use crate::{ActiveBalancePerUser, Config, Pallet, ParticipantSlot};
+use core::marker::PhantomData;
+use frame_support::{
+    storage::with_storage_layer,
+    traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+};
+use sp_runtime::DispatchError;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Builds [`crate::CumulativeBalanceIndex`] from the existing [`ActiveBalancePerUser`] entries
+///
+/// Every entry must be indexed, otherwise its account could neither win nor withdraw. If any entry fails,
+/// all of the index is reverted and the storage version stays at 1.
+pub struct BuildCumulativeBalanceIndex<T, I = ()>(PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for BuildCumulativeBalanceIndex<T, I> {
+    fn on_runtime_upgrade() -> Weight {
+        let db_weight = T::DbWeight::get();
+        if Pallet::<T, I>::on_chain_storage_version() != 1 {
+            log::info!(target: "BuildCumulativeBalanceIndex", "skipping, storage version is not 1");
+            return db_weight.reads(1);
+        }
+        let mut participants = 0u64;
+        let indexed = with_storage_layer(|| {
+            // everybody who already deposited holds their tickets from the upgrade on
+            Pallet::<T, I>::start_ticket_interval();
+            for (account, balance) in ActiveBalancePerUser::<T, I>::iter() {
+                participants = participants.saturating_add(1);
+                Pallet::<T, I>::index_add_balance(&account, balance).map_err(|e| {
+                    log::error!(target: "BuildCumulativeBalanceIndex", "could not index {:?}: {:?}", account, e);
+                    DispatchError::from(e)
+                })?;
+            }
+            Ok::<(), DispatchError>(())
+        });
+        // every participant costs a slot assignment plus one read and write per tree level
+        let levels = u64::from(32 - crate::ParticipantSlots::<T, I>::get().leading_zeros());
+        let per_participant = levels.saturating_add(4);
+        let accesses = participants
+            .saturating_mul(per_participant)
+            .saturating_add(3);
+        if indexed.is_err() {
+            log::error!(
+                target: "BuildCumulativeBalanceIndex",
+                "reverted the index of {:?} participants, storage version stays at 1",
+                participants
+            );
+            return db_weight.reads_writes(accesses, accesses);
+        }
+        StorageVersion::new(2).put::<Pallet<T, I>>();
+        log::info!(
+            target: "BuildCumulativeBalanceIndex",
+            "indexed {:?} participants",
+            participants
+        );
+        db_weight.reads_writes(accesses, accesses)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+        // whether this upgrade builds the index
+        Ok(sp_std::vec![u8::from(
+            Pallet::<T, I>::on_chain_storage_version() == 1
+        )])
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+        use sp_runtime::traits::Zero;
+        frame_support::ensure!(
+            Pallet::<T, I>::on_chain_storage_version() == 2,
+            "CumulativeBalanceIndex was not built"
+        );
+        let mut sum: pallet_parachain_staking::BalanceOf<T> = Zero::zero();
+        for (account, balance) in ActiveBalancePerUser::<T, I>::iter() {
+            frame_support::ensure!(
+                crate::ParticipantSlot::<T, I>::contains_key(&account),
+                "ActiveBalancePerUser entry without a participant slot"
+            );
+            sum += balance;
+        }
+        frame_support::ensure!(
+            Pallet::<T, I>::index_prefix_sum(crate::ParticipantSlots::<T, I>::get()) == sum,
+            "CumulativeBalanceIndex does not match ActiveBalancePerUser"
+        );
+        frame_support::ensure!(
+            sum == Pallet::<T, I>::total_pot(),
+            "CumulativeBalanceIndex does not match TotalPot"
+        );
+        if state == [1] {
+            frame_support::ensure!(
+                crate::TicketIntervalStart::<T, I>::get()
+                    == <frame_system::Pallet<T>>::block_number(),
+                "ticket interval did not start at the upgrade"
+            );
+        }
+        Ok(())
+    }
+}
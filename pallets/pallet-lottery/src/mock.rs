@@ -21,12 +21,14 @@ use crate as pallet_lottery;
 use crate::{pallet, Config, FarmingParamsOf};
 use calamari_runtime::currency::KMA;
 use frame_support::{
-    assert_ok, construct_runtime, derive_impl, ord_parameter_types,
+    assert_ok, construct_runtime, derive_impl,
+    instances::Instance1,
+    ord_parameter_types,
     pallet_prelude::*,
     parameter_types,
     traits::{
-        fungible::HoldConsideration, AsEnsureOriginWithArg, ConstU128, ConstU32, EitherOfDiverse,
-        Everything, LinearStoragePrice,
+        fungible::HoldConsideration, fungibles::Destroy, AsEnsureOriginWithArg, ConstU128,
+        ConstU32, EitherOfDiverse, Everything, LinearStoragePrice, Nothing,
     },
     weights::Weight,
 };
@@ -92,6 +94,7 @@ construct_runtime!(
         BlockAuthor: block_author,
         CollatorSelection: manta_collator_selection,
         Lottery: pallet_lottery,
+        JackpotLottery: pallet_lottery::<Instance1>,
         Preimage: pallet_preimage,
         Assets: pallet_assets,
         AssetManager: pallet_asset_manager,
@@ -309,7 +312,7 @@ impl pallet_parachain_staking::Config for Test {
     /// Minimum stake required to be reserved to be a delegator
     type MinDelegatorStk = ConstU128<{ 5_000 * KMA }>;
     type OnCollatorPayout = ();
-    type OnNewRound = ();
+    type OnNewRound = (Lottery, JackpotLottery);
     type WeightInfo = calamari_runtime::weights::pallet_parachain_staking::SubstrateWeight<Test>; // XXX: Maybe use the actual calamari weights?
 }
 
@@ -406,6 +409,28 @@ impl AssetRegistry for MantaAssetRegistry {
             metadata.is_frozen,
         )
     }
+
+    fn start_destroy_asset(asset_id: &CalamariAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::start_destroy(*asset_id, None)
+    }
+
+    fn destroy_asset_accounts(
+        asset_id: &CalamariAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_accounts(*asset_id, max_items)
+    }
+
+    fn destroy_asset_approvals(
+        asset_id: &CalamariAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_approvals(*asset_id, max_items)
+    }
+
+    fn finish_destroy_asset(asset_id: &CalamariAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::finish_destroy(*asset_id)
+    }
 }
 
 parameter_types! {
@@ -460,7 +485,10 @@ impl pallet_asset_manager::Config for Test {
     type PermissionlessStartId = ConstU128<100>;
     type TokenNameMaxLen = ConstU32<100>;
     type TokenSymbolMaxLen = ConstU32<100>;
+    type TokenLogoMaxLen = ConstU32<100>;
     type PermissionlessAssetRegistryCost = ConstU128<1000>;
+    type AssetInUse = Nothing;
+    type PairReserves = ();
 }
 
 parameter_types! {
@@ -484,6 +512,13 @@ impl pallet_farming::Config for Test {
     type Keeper = FarmingKeeperPalletId;
     type RewardIssuer = FarmingRewardIssuerPalletId;
     type WeightInfo = ();
+    type Currency = Balances;
+    type PoolCreationDeposit = ConstU128<{ 1_000 * KMA }>;
+    type PermissionlessPoolTokens = Everything;
+    type VoteEpochLength = DrawingInterval;
+    type MaxVotablePools = ConstU32<10>;
+    type VoteToken = NativeAssetId;
+    type LiquidityProvider = ();
 }
 
 impl block_author::Config for Test {}
@@ -499,6 +534,9 @@ parameter_types! {
     pub DrawingFreezeout: BlockNumber = 5;
     /// Time in blocks until a collator is done unstaking
     pub UnstakeLockTime: BlockNumber = LeaveDelayRounds::get() * DefaultBlocksPerRound::get();
+    pub const JackpotLotteryPotId: PalletId = PalletId(*b"JackpotL");
+    /// A second lottery drawing every other round
+    pub JackpotDrawingInterval: BlockNumber = 2 * DefaultBlocksPerRound::get();
 }
 
 use frame_support::traits::Currency;
@@ -526,6 +564,16 @@ impl frame_support::traits::EstimateCallFee<pallet::Call<Test>, BalanceOf<Test>>
         3 * KMA
     }
 }
+impl frame_support::traits::EstimateCallFee<pallet::Call<Test, Instance1>, BalanceOf<Test>>
+    for MockEstimateFee
+{
+    fn estimate_call_fee(
+        _call: &pallet::Call<Test, Instance1>,
+        _post_info: frame_support::dispatch::PostDispatchInfo,
+    ) -> BalanceOf<Test> {
+        3 * KMA
+    }
+}
 impl Config for Test {
     type RuntimeCall = RuntimeCall;
     type RuntimeEvent = RuntimeEvent;
@@ -538,6 +586,25 @@ impl Config for Test {
     type DrawingInterval = DrawingInterval;
     type DrawingFreezeout = DrawingFreezeout;
     type UnstakeLockTime = UnstakeLockTime;
+    type MaxDrawingHistory = ConstU32<3>;
+    type MaxCommittedSlotsPerBlock = ConstU32<1_000>;
+    type BalanceConversion = Balance;
+    type WeightInfo = ();
+}
+impl Config<Instance1> for Test {
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type Scheduler = Scheduler;
+    type EstimateCallFee = MockEstimateFee;
+    type RandomnessSource = TestRandomness<Test>;
+    type ManageOrigin = frame_system::EnsureRoot<AccountId>;
+    type PalletsOrigin = OriginCaller;
+    type LotteryPot = JackpotLotteryPotId;
+    type DrawingInterval = JackpotDrawingInterval;
+    type DrawingFreezeout = DrawingFreezeout;
+    type UnstakeLockTime = UnstakeLockTime;
+    type MaxDrawingHistory = ConstU32<3>;
+    type MaxCommittedSlotsPerBlock = ConstU32<2>;
     type BalanceConversion = Balance;
     type WeightInfo = ();
 }
@@ -594,6 +661,12 @@ impl ExtBuilder {
         self
     }
 
+    pub(crate) fn with_funded_jackpot_account(mut self, balance: Balance) -> Self {
+        self.balances
+            .push((crate::Pallet::<Test, Instance1>::account_id(), balance));
+        self
+    }
+
     pub(crate) fn with_balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
         self.balances = balances;
         self
@@ -657,6 +730,16 @@ impl ExtBuilder {
             min_withdraw: 5_000 * KMA,
             gas_reserve: 10_000 * KMA,
             farming_pool_params: farming_params,
+            _config: PhantomData,
+        }
+        .assimilate_storage(&mut t)
+        .expect("pallet_lottery's storage can be assimilated");
+        pallet_lottery::GenesisConfig::<Test, Instance1> {
+            min_deposit: 50_000 * KMA,
+            min_withdraw: 50_000 * KMA,
+            gas_reserve: 10_000 * KMA,
+            farming_pool_params: FarmingParamsOf::<Test>::default(),
+            _config: PhantomData,
         }
         .assimilate_storage(&mut t)
         .expect("pallet_lottery's storage can be assimilated");
@@ -728,6 +811,7 @@ fn init_jumbo_farming() {
         RuntimeOrigin::signed(ALICE),
         tokens_proportion,
         basic_rewards,
+        pallet_farming::EmissionCurve::Constant,
         None,
         0, // min_deposit_to_start
         0, // after_block_to_start
@@ -812,6 +896,8 @@ pub(crate) fn roll_one_block() -> u64 {
     Balances::on_initialize(System::block_number());
     ParachainStaking::on_initialize(System::block_number());
     Scheduler::on_initialize(System::block_number());
+    Lottery::on_initialize(System::block_number());
+    JackpotLottery::on_initialize(System::block_number());
     System::block_number()
 }
 
@@ -0,0 +1,302 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Cumulative balance index over lottery participants
+//!
+//! Every participant is assigned a stable, 1-based slot. [`CumulativeBalanceIndex`] is a Fenwick tree
+//! over these slots where node `i` holds the sum of active balances of slots `(i - lowbit(i), i]`.
+//! This allows updating a balance and finding the owner of a given cumulative balance in `O(log n)`
+//! storage accesses, independently of the hashed storage order of [`ActiveBalancePerUser`].
+//!
+//! Drawings are weighted by tickets rather than balance. A participant holding balance `b` for every block
+//! of the current drawing interval has `b * blocks` tickets, funds deposited later in the interval earn
+//! tickets only for the blocks they were held. This is tracked with a per-user [`TicketPenalty`] so that
+//! `tickets = b * blocks - penalty`, and [`CumulativeTicketPenaltyIndex`] mirrors the balance tree for
+//! the penalties. Starting a new interval bumps [`TicketEpoch`], which lazily zeroes all penalties.
+
+use super::*;
+use pallet_parachain_staking::BalanceOf;
+use sp_arithmetic::{helpers_128bit::multiply_by_rational_with_rounding, Rounding};
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedSub, One, Saturating, Zero},
+    SaturatedConversion,
+};
+
+/// Lowest set bit of `i`, the range length covered by Fenwick node `i`
+fn lowbit(i: u32) -> u32 {
+    i & i.wrapping_neg()
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+    /// Adds `amount` to the indexed balance of `account`, assigning a slot to the account if it has none
+    pub(crate) fn index_add_balance(
+        account: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> Result<(), Error<T, I>> {
+        let slot = match ParticipantSlot::<T, I>::get(account) {
+            Some(slot) => slot,
+            None => Self::assign_participant_slot(account)?,
+        };
+        let old_balance = Self::index_balance_at(slot);
+        let slots = ParticipantSlots::<T, I>::get();
+        let mut i = slot;
+        while i <= slots {
+            CumulativeBalanceIndex::<T, I>::try_mutate(i, |node| {
+                *node = (*node)
+                    .checked_add(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            i += lowbit(i);
+        }
+        Self::update_tickets(
+            account,
+            slot,
+            old_balance,
+            old_balance.saturating_add(amount),
+        )
+    }
+
+    /// Subtracts `amount` from the indexed balance of `account`
+    /// If `release_slot` is set, the account's slot is freed for reuse by a future participant
+    pub(crate) fn index_sub_balance(
+        account: &T::AccountId,
+        amount: BalanceOf<T>,
+        release_slot: bool,
+    ) -> Result<(), Error<T, I>> {
+        let slot =
+            ParticipantSlot::<T, I>::get(account).ok_or(Error::<T, I>::NoDepositForAccount)?;
+        let old_balance = Self::index_balance_at(slot);
+        let slots = ParticipantSlots::<T, I>::get();
+        let mut i = slot;
+        while i <= slots {
+            CumulativeBalanceIndex::<T, I>::try_mutate(i, |node| {
+                *node = (*node)
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?;
+                Ok::<(), Error<T, I>>(())
+            })?;
+            i += lowbit(i);
+        }
+        Self::update_tickets(
+            account,
+            slot,
+            old_balance,
+            old_balance.saturating_sub(amount),
+        )?;
+        if release_slot {
+            ParticipantSlot::<T, I>::remove(account);
+            SlotOwner::<T, I>::remove(slot);
+            FreeSlots::<T, I>::append(slot);
+        }
+        Ok(())
+    }
+
+    /// Sum of indexed balances of slots `1..=slot`
+    pub(crate) fn index_prefix_sum(slot: u32) -> BalanceOf<T> {
+        let mut sum: BalanceOf<T> = Zero::zero();
+        let mut i = slot.min(ParticipantSlots::<T, I>::get());
+        while i > 0 {
+            sum = sum.saturating_add(CumulativeBalanceIndex::<T, I>::get(i));
+            i -= lowbit(i);
+        }
+        sum
+    }
+
+    /// Indexed balance of the single slot `slot`
+    fn index_balance_at(slot: u32) -> BalanceOf<T> {
+        // node `slot` covers `(slot - lowbit(slot), slot]`, subtract the nodes covering all but `slot` itself
+        let mut balance = CumulativeBalanceIndex::<T, I>::get(slot);
+        let covered_from = slot - lowbit(slot);
+        let mut i = slot - 1;
+        while i > covered_from {
+            balance = balance.saturating_sub(CumulativeBalanceIndex::<T, I>::get(i));
+            i -= lowbit(i);
+        }
+        balance
+    }
+
+    /// Starts a new drawing interval, from now on tickets are counted from the current block
+    pub(crate) fn start_ticket_interval() {
+        TicketEpoch::<T, I>::mutate(|epoch| *epoch = epoch.wrapping_add(1));
+        TicketIntervalStart::<T, I>::put(<frame_system::Pallet<T>>::block_number());
+    }
+
+    /// Blocks of the current drawing interval including the current one
+    pub(crate) fn ticket_blocks() -> BalanceOf<T> {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let blocks = now
+            .saturating_sub(TicketIntervalStart::<T, I>::get())
+            .saturating_add(One::one());
+        BalanceOf::<T>::saturated_from(blocks.saturated_into::<u128>())
+    }
+
+    /// Tickets `account` holds in the current drawing interval
+    pub fn tickets_of(account: &T::AccountId) -> BalanceOf<T> {
+        Self::active_balance_per_user(account)
+            .saturating_mul(Self::ticket_blocks())
+            .saturating_sub(Self::ticket_penalty_of(account))
+    }
+
+    /// Tickets of all participants in the current drawing interval
+    pub fn total_tickets() -> BalanceOf<T> {
+        let slots = ParticipantSlots::<T, I>::get();
+        Self::index_prefix_sum(slots)
+            .saturating_mul(Self::ticket_blocks())
+            .saturating_sub(Self::penalty_prefix_sum(slots))
+    }
+
+    /// Returns the participant whose slot covers `cumulative_tickets`, i.e. the owner of the
+    /// smallest slot `s` for which the sum of tickets in slots `1..=s` exceeds `cumulative_tickets`
+    pub(crate) fn participant_at_ticket(cumulative_tickets: BalanceOf<T>) -> Option<T::AccountId> {
+        let slots = ParticipantSlots::<T, I>::get();
+        if slots.is_zero() {
+            return None;
+        }
+        let blocks = Self::ticket_blocks();
+        let mut position = 0u32;
+        let mut remaining = cumulative_tickets;
+        let mut step = 1u32 << (31 - slots.leading_zeros());
+        while !step.is_zero() {
+            let next = position + step;
+            if next <= slots {
+                let node = CumulativeBalanceIndex::<T, I>::get(next)
+                    .saturating_mul(blocks)
+                    .saturating_sub(Self::penalty_node(next));
+                if node <= remaining {
+                    position = next;
+                    remaining -= node;
+                }
+            }
+            step >>= 1;
+        }
+        SlotOwner::<T, I>::get(position + 1)
+    }
+
+    /// Penalty of `account` in the current epoch
+    pub(crate) fn ticket_penalty_of(account: &T::AccountId) -> BalanceOf<T> {
+        let (epoch, penalty) = TicketPenalty::<T, I>::get(account);
+        if epoch == TicketEpoch::<T, I>::get() {
+            penalty
+        } else {
+            Zero::zero()
+        }
+    }
+
+    /// Penalty stored in node `i` of [`CumulativeTicketPenaltyIndex`] in the current epoch
+    fn penalty_node(i: u32) -> BalanceOf<T> {
+        let (epoch, penalty) = CumulativeTicketPenaltyIndex::<T, I>::get(i);
+        if epoch == TicketEpoch::<T, I>::get() {
+            penalty
+        } else {
+            Zero::zero()
+        }
+    }
+
+    /// Sum of ticket penalties of slots `1..=slot`
+    fn penalty_prefix_sum(slot: u32) -> BalanceOf<T> {
+        let mut sum: BalanceOf<T> = Zero::zero();
+        let mut i = slot.min(ParticipantSlots::<T, I>::get());
+        while i > 0 {
+            sum = sum.saturating_add(Self::penalty_node(i));
+            i -= lowbit(i);
+        }
+        sum
+    }
+
+    /// Updates the ticket penalty of `account` in `slot` after its balance changed from `old_balance` to `new_balance`
+    ///
+    /// A deposit earns no tickets for the blocks of the interval that already passed except the current one,
+    /// a withdrawal removes tickets proportionally to the withdrawn share of the balance
+    fn update_tickets(
+        account: &T::AccountId,
+        slot: u32,
+        old_balance: BalanceOf<T>,
+        new_balance: BalanceOf<T>,
+    ) -> Result<(), Error<T, I>> {
+        let epoch = TicketEpoch::<T, I>::get();
+        let blocks = Self::ticket_blocks();
+        let old_penalty = Self::ticket_penalty_of(account);
+        let old_tickets = old_balance
+            .saturating_mul(blocks)
+            .saturating_sub(old_penalty);
+        let new_tickets = if new_balance >= old_balance {
+            old_tickets.saturating_add(new_balance.saturating_sub(old_balance))
+        } else {
+            // `new_balance < old_balance` so the result is always smaller than `old_tickets`
+            multiply_by_rational_with_rounding(
+                old_tickets.saturated_into(),
+                new_balance.saturated_into(),
+                old_balance.saturated_into(),
+                Rounding::Down,
+            )
+            .map(|tickets| tickets.saturated_into())
+            .unwrap_or_else(Zero::zero)
+        };
+        let new_penalty = new_balance
+            .saturating_mul(blocks)
+            .saturating_sub(new_tickets);
+        if new_penalty.is_zero() {
+            TicketPenalty::<T, I>::remove(account);
+        } else {
+            TicketPenalty::<T, I>::insert(account, (epoch, new_penalty));
+        }
+
+        let slots = ParticipantSlots::<T, I>::get();
+        let mut i = slot;
+        while i <= slots {
+            let node = Self::penalty_node(i);
+            let node = if new_penalty >= old_penalty {
+                node.checked_add(&(new_penalty - old_penalty))
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?
+            } else {
+                node.checked_sub(&(old_penalty - new_penalty))
+                    .ok_or(Error::<T, I>::ArithmeticUnderflow)?
+            };
+            CumulativeTicketPenaltyIndex::<T, I>::insert(i, (epoch, node));
+            i += lowbit(i);
+        }
+        Ok(())
+    }
+
+    /// Reuses a released slot or grows the index by one slot
+    fn assign_participant_slot(account: &T::AccountId) -> Result<u32, Error<T, I>> {
+        let slot = match FreeSlots::<T, I>::mutate(|free| free.pop()) {
+            // released slots carry no balance, the tree needs no update
+            Some(slot) => slot,
+            None => {
+                let slot = ParticipantSlots::<T, I>::get()
+                    .checked_add(1)
+                    .ok_or(Error::<T, I>::ArithmeticOverflow)?;
+                // A new node covers `(slot - lowbit(slot), slot]`, all of which but `slot` itself already exist
+                let covered = Self::index_prefix_sum(slot - 1)
+                    .saturating_sub(Self::index_prefix_sum(slot - lowbit(slot)));
+                let covered_penalty = Self::penalty_prefix_sum(slot - 1)
+                    .saturating_sub(Self::penalty_prefix_sum(slot - lowbit(slot)));
+                CumulativeBalanceIndex::<T, I>::insert(slot, covered);
+                CumulativeTicketPenaltyIndex::<T, I>::insert(
+                    slot,
+                    (TicketEpoch::<T, I>::get(), covered_penalty),
+                );
+                ParticipantSlots::<T, I>::put(slot);
+                slot
+            }
+        };
+        ParticipantSlot::<T, I>::insert(account, slot);
+        SlotOwner::<T, I>::insert(slot, account.clone());
+        Ok(slot)
+    }
+}
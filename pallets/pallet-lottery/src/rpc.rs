@@ -16,30 +16,81 @@
 
 //! Lottery RPC Interfaces
 
-use crate::runtime::LotteryApi;
-use core::marker::PhantomData;
+use crate::{
+    runtime::{unknown_instance, LotteryApi, LotteryInstance, UnknownInstance},
+    DrawingRecord, ParticipantProof, UserPosition,
+};
+use codec::Codec;
+use core::{fmt::Debug, marker::PhantomData};
 use jsonrpsee::{
     core::{async_trait, RpcResult},
     proc_macros::rpc,
     types::error::{CallError, ErrorObject},
 };
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_runtime::traits::Block;
+use sp_runtime::traits::{Block, NumberFor, One, Saturating};
 use sp_std::sync::Arc;
 
 pub const LOTTERY_ERROR: i32 = 777;
 
+/// Every method takes the lottery `instance` to query as its last, optional parameter, the default instance `0` if omitted
+/// NOTE: Runtimes implementing a version of [`LotteryApi`] before 4 only serve the default instance
 #[rpc(server)]
-pub trait LotteryRpc {
+pub trait LotteryRpc<AccountId, BlockNumber, Hash>
+where
+    AccountId: Send + Sync + 'static,
+{
     #[method(name = "lottery_not_in_drawing_freezeout", blocking)]
-    fn not_in_drawing_freezeout(&self) -> RpcResult<bool>;
+    fn not_in_drawing_freezeout(&self, instance: Option<LotteryInstance>) -> RpcResult<bool>;
 
     #[method(name = "lottery_current_prize_pool", blocking)]
-    fn current_prize_pool(&self) -> RpcResult<u128>;
+    fn current_prize_pool(&self, instance: Option<LotteryInstance>) -> RpcResult<u128>;
 
     #[method(name = "lottery_next_drawing_at", blocking)]
-    fn next_drawing_at(&self) -> RpcResult<Option<u128>>;
+    fn next_drawing_at(&self, instance: Option<LotteryInstance>) -> RpcResult<Option<u128>>;
+
+    #[method(name = "lottery_drawing_history", blocking)]
+    fn drawing_history(
+        &self,
+        from: u32,
+        count: u32,
+        instance: Option<LotteryInstance>,
+    ) -> RpcResult<Vec<DrawingRecord<AccountId, BlockNumber, u128, Hash>>>;
+
+    #[method(name = "lottery_user_position", blocking)]
+    fn user_position(
+        &self,
+        account: AccountId,
+        instance: Option<LotteryInstance>,
+    ) -> RpcResult<UserPosition<BlockNumber, u128>>;
+
+    /// Merkle proof of the winner of drawing number `drawing` against its `participants_root`
+    /// NOTE: The proof is computed in the block before the drawing, so the node must still have its state ( e.g. an archive node )
+    #[method(name = "lottery_winner_proof", blocking)]
+    fn winner_proof(
+        &self,
+        drawing: u32,
+        instance: Option<LotteryInstance>,
+    ) -> RpcResult<Option<ParticipantProof<AccountId, u128, Hash>>>;
+}
+
+/// Lottery RPC error with `message`, carrying `err` as its data
+fn lottery_error(message: &str, err: impl Debug) -> CallError {
+    CallError::Custom(ErrorObject::owned(
+        LOTTERY_ERROR,
+        message,
+        Some(format!("{err:?}")),
+    ))
+}
+
+/// Returns `value` of a [`LotteryApi`] from before version 4 if `instance` is the default instance it served
+fn default_instance_only<T>(instance: LotteryInstance, value: T) -> Result<T, UnknownInstance> {
+    if instance == 0 {
+        Ok(value)
+    } else {
+        unknown_instance(instance)
+    }
 }
 
 /// Lottery RPC API Implementation
@@ -62,52 +113,180 @@ impl<B, C> Lottery<B, C> {
     }
 }
 
+impl<B, C> Lottery<B, C>
+where
+    B: Block,
+    C: 'static + ProvideRuntimeApi<B> + HeaderBackend<B>,
+{
+    /// Returns true if the runtime at `at` implements a version of [`LotteryApi`] from before
+    /// its queries took the instance
+    fn is_before_version_4<AccountId, BlockNumber, Hash>(api: &C::Api, at: B::Hash) -> bool
+    where
+        C::Api: LotteryApi<B, AccountId, BlockNumber, Hash>,
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Hash: Codec,
+    {
+        matches!(
+            api.api_version::<dyn LotteryApi<B, AccountId, BlockNumber, Hash>>(at),
+            Ok(Some(version)) if version < 4
+        )
+    }
+
+    /// Drawing history of `instance` in the state at `at`
+    fn drawing_history_at<AccountId, BlockNumber, Hash>(
+        &self,
+        at: B::Hash,
+        instance: LotteryInstance,
+        from: u32,
+        count: u32,
+    ) -> RpcResult<Vec<DrawingRecord<AccountId, BlockNumber, u128, Hash>>>
+    where
+        C::Api: LotteryApi<B, AccountId, BlockNumber, Hash>,
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Hash: Codec,
+    {
+        let api = self.client.runtime_api();
+        let result = if Self::is_before_version_4::<AccountId, BlockNumber, Hash>(&api, at) {
+            #[allow(deprecated)]
+            api.drawing_history_before_version_4(at, from, count)
+                .map(|history| default_instance_only(instance, history))
+        } else {
+            api.drawing_history(at, instance, from, count)
+        };
+        result
+            .map_err(|err| lottery_error("Unable to query drawing history", err))?
+            .map_err(|err| lottery_error("Unknown lottery instance", err).into())
+    }
+}
+
 #[async_trait]
-impl<B, C> LotteryRpcServer for Lottery<B, C>
+impl<B, C, AccountId, BlockNumber, Hash> LotteryRpcServer<AccountId, BlockNumber, Hash>
+    for Lottery<B, C>
 where
     B: Block,
     C: 'static + ProvideRuntimeApi<B> + HeaderBackend<B>,
-    C::Api: LotteryApi<B>,
+    C::Api: LotteryApi<B, AccountId, BlockNumber, Hash>,
+    AccountId: Codec + Send + Sync + 'static,
+    BlockNumber: Codec + Into<NumberFor<B>>,
+    Hash: Codec,
 {
     #[inline]
-    fn not_in_drawing_freezeout(&self) -> RpcResult<bool> {
+    fn not_in_drawing_freezeout(&self, instance: Option<LotteryInstance>) -> RpcResult<bool> {
         let api = self.client.runtime_api();
         let at = self.client.info().best_hash;
-        api.not_in_drawing_freezeout(at).map_err(|err| {
-            CallError::Custom(ErrorObject::owned(
-                LOTTERY_ERROR,
-                "Unable to compute drawing freezeout",
-                Some(format!("{err:?}")),
-            ))
-            .into()
-        })
+        let instance = instance.unwrap_or_default();
+        let result = if Self::is_before_version_4::<AccountId, BlockNumber, Hash>(&api, at) {
+            #[allow(deprecated)]
+            api.not_in_drawing_freezeout_before_version_4(at)
+                .map(|value| default_instance_only(instance, value))
+        } else {
+            api.not_in_drawing_freezeout(at, instance)
+        };
+        result
+            .map_err(|err| lottery_error("Unable to compute drawing freezeout", err))?
+            .map_err(|err| lottery_error("Unknown lottery instance", err).into())
     }
 
     #[inline]
-    fn current_prize_pool(&self) -> RpcResult<u128> {
+    fn current_prize_pool(&self, instance: Option<LotteryInstance>) -> RpcResult<u128> {
         let api = self.client.runtime_api();
         let at = self.client.info().best_hash;
-        api.current_prize_pool(at).map_err(|err| {
-            CallError::Custom(ErrorObject::owned(
-                LOTTERY_ERROR,
-                "Unable to compute current prize pool",
-                Some(format!("{err:?}")),
-            ))
-            .into()
-        })
+        let instance = instance.unwrap_or_default();
+        let result = if Self::is_before_version_4::<AccountId, BlockNumber, Hash>(&api, at) {
+            #[allow(deprecated)]
+            api.current_prize_pool_before_version_4(at)
+                .map(|value| default_instance_only(instance, value))
+        } else {
+            api.current_prize_pool(at, instance)
+        };
+        result
+            .map_err(|err| lottery_error("Unable to compute current prize pool", err))?
+            .map_err(|err| lottery_error("Unknown lottery instance", err).into())
     }
 
     #[inline]
-    fn next_drawing_at(&self) -> RpcResult<Option<u128>> {
+    fn next_drawing_at(&self, instance: Option<LotteryInstance>) -> RpcResult<Option<u128>> {
         let api = self.client.runtime_api();
         let at = self.client.info().best_hash;
-        api.next_drawing_at(at).map_err(|err| {
-            CallError::Custom(ErrorObject::owned(
-                LOTTERY_ERROR,
-                "Unable to compute next drawing",
-                Some(format!("{err:?}")),
-            ))
-            .into()
-        })
+        let instance = instance.unwrap_or_default();
+        let result = if Self::is_before_version_4::<AccountId, BlockNumber, Hash>(&api, at) {
+            #[allow(deprecated)]
+            api.next_drawing_at_before_version_4(at)
+                .map(|value| default_instance_only(instance, value))
+        } else {
+            api.next_drawing_at(at, instance)
+        };
+        result
+            .map_err(|err| lottery_error("Unable to compute next drawing", err))?
+            .map_err(|err| lottery_error("Unknown lottery instance", err).into())
+    }
+
+    #[inline]
+    fn drawing_history(
+        &self,
+        from: u32,
+        count: u32,
+        instance: Option<LotteryInstance>,
+    ) -> RpcResult<Vec<DrawingRecord<AccountId, BlockNumber, u128, Hash>>> {
+        let at = self.client.info().best_hash;
+        self.drawing_history_at(at, instance.unwrap_or_default(), from, count)
+    }
+
+    #[inline]
+    fn user_position(
+        &self,
+        account: AccountId,
+        instance: Option<LotteryInstance>,
+    ) -> RpcResult<UserPosition<BlockNumber, u128>> {
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+        let instance = instance.unwrap_or_default();
+        let result = if Self::is_before_version_4::<AccountId, BlockNumber, Hash>(&api, at) {
+            #[allow(deprecated)]
+            api.user_position_before_version_4(at, account)
+                .map(|position| default_instance_only(instance, position))
+        } else {
+            api.user_position(at, instance, account)
+        };
+        result
+            .map_err(|err| lottery_error("Unable to compute user position", err))?
+            .map_err(|err| lottery_error("Unknown lottery instance", err).into())
+    }
+
+    #[inline]
+    fn winner_proof(
+        &self,
+        drawing: u32,
+        instance: Option<LotteryInstance>,
+    ) -> RpcResult<Option<ParticipantProof<AccountId, u128, Hash>>> {
+        let instance = instance.unwrap_or_default();
+        let best = self.client.info().best_hash;
+        let record = match self.drawing_history_at(best, instance, drawing, 1)?.pop() {
+            Some(record) => record,
+            None => return Ok(None),
+        };
+        // the participants are frozen since the commitment, the state before the drawing still holds them
+        let before_drawing: NumberFor<B> = record.block.into().saturating_sub(One::one());
+        let at = match self
+            .client
+            .hash(before_drawing)
+            .map_err(|err| lottery_error("Unable to find block before the drawing", err))?
+        {
+            Some(at) => at,
+            None => return Ok(None),
+        };
+        let api = self.client.runtime_api();
+        let result = if Self::is_before_version_4::<AccountId, BlockNumber, Hash>(&api, at) {
+            #[allow(deprecated)]
+            api.participant_proof_before_version_4(at, record.winner)
+                .map(|proof| default_instance_only(instance, proof))
+        } else {
+            api.participant_proof(at, instance, record.winner)
+        };
+        result
+            .map_err(|err| lottery_error("Unable to compute winner proof", err))?
+            .map_err(|err| lottery_error("Unknown lottery instance", err).into())
     }
 }
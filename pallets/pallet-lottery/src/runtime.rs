@@ -14,10 +14,51 @@
 // You should have received a copy of the GNU General Public License
 // along with Manta.  If not, see <http://www.gnu.org/licenses/>.
 
+use crate::{DrawingRecord, ParticipantProof, UserPosition};
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+/// Index of a lottery instance in a runtime, `0` is the default instance
+pub type LotteryInstance = u8;
+
+/// A [`LotteryApi`] call named an instance the runtime does not run
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
+pub struct UnknownInstance(pub LotteryInstance);
+
+/// Fails a [`LotteryApi`] call for an instance the runtime does not run
+pub fn unknown_instance<T>(instance: LotteryInstance) -> Result<T, UnknownInstance> {
+    Err(UnknownInstance(instance))
+}
+
 sp_api::decl_runtime_apis! {
-    pub trait LotteryApi {
+    /// Queries of the lottery instance named by `instance`
+    ///
+    /// Version 2 adds the drawing history and user positions, version 3 the participant proofs,
+    /// version 4 takes the instance in every query and fails for instances the runtime does not run
+    #[api_version(4)]
+    pub trait LotteryApi<AccountId, BlockNumber, Hash> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Hash: Codec,
+    {
+        #[changed_in(4)]
         fn not_in_drawing_freezeout() -> bool;
+        fn not_in_drawing_freezeout(instance: LotteryInstance) -> Result<bool, UnknownInstance>;
+        #[changed_in(4)]
         fn current_prize_pool() -> u128;
+        fn current_prize_pool(instance: LotteryInstance) -> Result<u128, UnknownInstance>;
+        #[changed_in(4)]
         fn next_drawing_at() -> Option<u128>;
+        fn next_drawing_at(instance: LotteryInstance) -> Result<Option<u128>, UnknownInstance>;
+        #[changed_in(4)]
+        fn drawing_history(from: u32, count: u32) -> Vec<DrawingRecord<AccountId, BlockNumber, u128, Hash>>;
+        fn drawing_history(instance: LotteryInstance, from: u32, count: u32) -> Result<Vec<DrawingRecord<AccountId, BlockNumber, u128, Hash>>, UnknownInstance>;
+        #[changed_in(4)]
+        fn user_position(account: AccountId) -> UserPosition<BlockNumber, u128>;
+        fn user_position(instance: LotteryInstance, account: AccountId) -> Result<UserPosition<BlockNumber, u128>, UnknownInstance>;
+        #[changed_in(4)]
+        fn participant_proof(account: AccountId) -> Option<ParticipantProof<AccountId, u128, Hash>>;
+        fn participant_proof(instance: LotteryInstance, account: AccountId) -> Result<Option<ParticipantProof<AccountId, u128, Hash>>, UnknownInstance>;
     }
 }
@@ -20,12 +20,12 @@ use frame_support::traits::Randomness;
 use pallet_parachain_staking::BalanceOf;
 use sp_runtime::{
     traits::{Saturating, Zero},
-    Percent,
+    Percent, SaturatedConversion,
 };
 use sp_std::{vec, vec::Vec};
 
 #[named]
-pub(super) fn reactivate_bottom_collators<T: Config>(
+pub(super) fn reactivate_bottom_collators<T: Config<I>, I: 'static>(
     active_collators: &[T::AccountId],
     new_deposit: BalanceOf<T>,
 ) -> Vec<(T::AccountId, BalanceOf<T>)> {
@@ -35,17 +35,18 @@ pub(super) fn reactivate_bottom_collators<T: Config>(
     let mut remaining_deposit = new_deposit;
 
     // We only consider collators we're already staked to that are also currently active (and not being unstaked)
-    for collator in StakedCollators::<T>::iter_keys().filter(|coll| active_collators.contains(coll))
+    for collator in
+        StakedCollators::<T, I>::iter_keys().filter(|coll| active_collators.contains(coll))
     {
-        let staked = StakedCollators::<T>::get(collator.clone());
+        let staked = StakedCollators::<T, I>::get(collator.clone());
         let info = pallet_parachain_staking::Pallet::<T>::candidate_info(collator.clone())
             .expect("is active collator, therefore it has collator info. qed");
 
         // If collator not exist in delegatorState(PotAccount).delegations, ignore
         // delegations has other active collator to stake.
-        if let Some(state) =
-            pallet_parachain_staking::Pallet::<T>::delegator_state(crate::Pallet::<T>::account_id())
-        {
+        if let Some(state) = pallet_parachain_staking::Pallet::<T>::delegator_state(
+            crate::Pallet::<T, I>::account_id(),
+        ) {
             log::debug!("delegator PotAccount has state.");
             let mut is_kick = true;
             for x in &state.delegations.0 {
@@ -82,7 +83,8 @@ pub(super) fn reactivate_bottom_collators<T: Config>(
                 remaining_deposit - this_deposit
             );
             // Ensure we don't try to stake a smaller than allowed delegation to a collator
-            if remaining_deposit.saturating_sub(this_deposit) < crate::Pallet::<T>::min_deposit() {
+            if remaining_deposit.saturating_sub(this_deposit) < crate::Pallet::<T, I>::min_deposit()
+            {
                 deposits.push((collator, remaining_deposit)); // put the full remaining balance in this collator
                 break;
             } else {
@@ -95,7 +97,7 @@ pub(super) fn reactivate_bottom_collators<T: Config>(
 }
 
 /// second concern: We want to maximize staking APY earned, so we want to balance the staking pools with our deposits while conserving gas
-pub(super) fn split_to_underallocated_collators<T: Config>(
+pub(super) fn split_to_underallocated_collators<T: Config<I>, I: 'static>(
     active_collators: &[T::AccountId],
     new_deposit: BalanceOf<T>,
 ) -> Vec<(T::AccountId, BalanceOf<T>)> {
@@ -208,8 +210,8 @@ pub(super) fn split_to_underallocated_collators<T: Config>(
                 Percent::from_rational(tokens_to_reach_median, total_underallocation);
             let to_reach_mean = collator_proportion.mul_ceil(new_deposit);
             let to_deposit = to_reach_mean.min(remaining_deposit);
-            let our_stake = StakedCollators::<T>::get(account.clone());
-            if to_deposit > crate::Pallet::<T>::min_deposit()
+            let our_stake = StakedCollators::<T, I>::get(account.clone());
+            if to_deposit > crate::Pallet::<T, I>::min_deposit()
                 && to_deposit + our_stake > info.lowest_top_delegation_amount
             {
                 let this_deposit = core::cmp::min(to_deposit, remaining_deposit);
@@ -221,7 +223,7 @@ pub(super) fn split_to_underallocated_collators<T: Config>(
                     to_deposit
                 );
             };
-            if remaining_deposit < crate::Pallet::<T>::min_deposit() {
+            if remaining_deposit < crate::Pallet::<T, I>::min_deposit() {
                 break;
             }
         }
@@ -261,12 +263,86 @@ pub(super) fn split_to_underallocated_collators<T: Config>(
     deposits
 }
 
+/// Splits `new_deposit` into up to 4 chunks ( none below min deposit ) and assigns each chunk to the collator
+/// that pays the most rewards per staked token after accounting for the chunks already assigned to it
+/// Collators without rewards in the considered rounds and deposits that would not make it into the top delegations are skipped
+#[named]
+pub(super) fn split_by_reward_rate<T: Config<I>, I: 'static>(
+    active_collators: &[T::AccountId],
+    new_deposit: BalanceOf<T>,
+) -> Vec<(T::AccountId, BalanceOf<T>)> {
+    log::trace!(function_name!());
+    if active_collators.len().is_zero() || new_deposit.is_zero() {
+        return vec![];
+    }
+    // (collator, reward share, counted stake, lowest top delegation, our stake, assigned deposit)
+    let mut candidates: Vec<_> = active_collators
+        .iter()
+        .filter_map(|collator| {
+            let share = strategy::delegator_reward_share::<T, I>(collator);
+            if share.is_zero() {
+                return None;
+            }
+            let info = pallet_parachain_staking::Pallet::<T>::candidate_info(collator)?;
+            Some((
+                collator.clone(),
+                share,
+                info.total_counted,
+                info.lowest_top_delegation_amount,
+                StakedCollators::<T, I>::get(collator),
+                BalanceOf::<T>::zero(),
+            ))
+        })
+        .collect();
+    if candidates.is_empty() {
+        log::debug!("No collator earned rewards in the considered rounds");
+        return vec![];
+    }
+
+    let num_chunks = new_deposit
+        .saturated_into::<u128>()
+        .checked_div(crate::Pallet::<T, I>::min_deposit().saturated_into::<u128>())
+        .unwrap_or(1)
+        .clamp(1, 4) as u32;
+    let chunk = new_deposit / num_chunks.into();
+    let mut remaining_deposit = new_deposit;
+    for i in 0..num_chunks {
+        let this_deposit = if i + 1 == num_chunks {
+            remaining_deposit
+        } else {
+            chunk
+        };
+        let best = candidates
+            .iter_mut()
+            .filter(|(_, _, _, lowest_top, our_stake, assigned)| {
+                *our_stake + *assigned + this_deposit > *lowest_top
+            })
+            .max_by(|a, b| strategy::cmp_reward_rate::<T, I>((a.1, a.2 + a.5), (b.1, b.2 + b.5)));
+        match best {
+            Some(candidate) => {
+                log::debug!(
+                    "Selected collator {:?} for deposit of {:?} token",
+                    candidate.0,
+                    this_deposit
+                );
+                candidate.5 += this_deposit;
+                remaining_deposit -= this_deposit;
+            }
+            None => break,
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|candidate| !candidate.5.is_zero())
+        .map(|candidate| (candidate.0, candidate.5))
+        .collect()
+}
+
 /// fallback: just assign to a random active collator ( choose a different collator for each invocation )
-pub(crate) fn stake_to_random_collator<T: Config>(
+pub(crate) fn stake_to_random_collator<T: Config<I>, I: 'static>(
     active_collators: &[T::AccountId],
     new_deposit: BalanceOf<T>,
 ) -> Option<(T::AccountId, BalanceOf<T>)> {
-    use sp_runtime::traits::SaturatedConversion;
     if active_collators.len().is_zero() || new_deposit.is_zero() {
         return None;
     }
@@ -15,19 +15,25 @@
 // along with Manta.  If not, see <http://www.gnu.org/licenses/>.
 
 mod deposit_strategies;
+mod performance;
+mod strategy;
 mod withdraw_strategies;
 
+pub use strategy::{
+    BalancedStrategy, RewardHistoryStrategy, StakingStrategy, REWARD_HISTORY_ROUNDS,
+};
+
 use super::*;
 use frame_support::{dispatch::RawOrigin, ensure, traits::EstimateCallFee};
 use frame_system::pallet_prelude::*;
 use pallet_parachain_staking::BalanceOf;
 use sp_runtime::{
     traits::{Saturating, Zero},
-    DispatchResult, Percent,
+    DispatchResult,
 };
 use sp_std::{vec, vec::Vec};
 
-impl<T: Config> Pallet<T> {
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
     #[named]
     /// distributes a given amount of tokens to zero or more collators for staking
     /// if it can't distribute all tokens for some reason, it returns an empty vec
@@ -47,33 +53,25 @@ impl<T: Config> Pallet<T> {
             );
             return vec![];
         }
-        let mut deposits: Vec<(T::AccountId, BalanceOf<T>)> = vec![];
-        let mut remaining_deposit = new_deposit;
-
-        // Only deposit to active collators (according to ParachainStaking) that we are not currently undelegating from (re-delegating would fail) and that received some rewards in the last round
+        // Only deposit to active collators (according to ParachainStaking) that we are not currently undelegating from (re-delegating would fail) and that are expected to keep earning rewards
         let top_collator_accounts = pallet_parachain_staking::Pallet::<T>::selected_candidates();
         if top_collator_accounts.is_empty() {
             log::error!("FATAL: ParachainStaking returned no active collators"); // NOTE: guaranteed by ParachainStaking to not happen
             return vec![];
         }
-        let collators_we_are_unstaking_from = UnstakingCollators::<T>::get()
+        let collators_we_are_unstaking_from = UnstakingCollators::<T, I>::get()
             .iter()
             .cloned()
             .map(|uc| uc.account)
             .collect::<Vec<_>>();
+        let underperforming_collators = Self::underperforming_collators();
 
-        let round_info = pallet_parachain_staking::Pallet::<T>::round();
-        // NOTE: This is O(n^2) but both vecs are << 100 elements
+        // NOTE: This is O(n^2) but all vecs are << 100 elements
         let deposit_eligible_collators = top_collator_accounts
             .iter()
             .filter(|account| {
                 !collators_we_are_unstaking_from.contains(account)
-                    && (round_info.current <= 1
-                        || !pallet_parachain_staking::Pallet::<T>::awarded_pts(
-                            round_info.current - 1,
-                            account,
-                        )
-                        .is_zero())
+                    && !underperforming_collators.contains(account)
             })
             .cloned()
             .collect::<Vec<_>>();
@@ -82,104 +80,8 @@ impl<T: Config> Pallet<T> {
             "deposit_eligible_collators size: {:?}",
             deposit_eligible_collators.len()
         );
-        // first concern: If we fell out of the active set on one or more collators, we need to get back into it
-        deposits.append(&mut deposit_strategies::reactivate_bottom_collators::<T>(
-            deposit_eligible_collators.as_slice(),
-            new_deposit,
-        ));
-        // `reactivate_bottom_collators` has only distributed the funds needed for reactivation, we can have some left over
-        remaining_deposit -= deposits
-            .iter()
-            .map(|deposit| deposit.1)
-            .reduce(|sum, elem| sum + elem)
-            .unwrap_or_else(|| 0u32.into());
-
-        log::debug!(
-            "after reactivate_bottom_collators deposits: {:?}, remaining: ${:?}",
-            deposits.len(),
-            remaining_deposit
-        );
-        // If we have re-activated any collators and have leftover funds, we just distribute all surplus tokens to them evenly and call it a day
-        if !deposits.is_empty() {
-            if !remaining_deposit.is_zero() {
-                log::debug!(
-                    "deposits:{:?} not null and remaining:{:?} not zero",
-                    deposits,
-                    remaining_deposit
-                );
-                let deposit_per_collator =
-                    Percent::from_rational(1, deposits.len() as u32).mul_ceil(remaining_deposit); // this overshoots the amount if there's a remainder
-                for deposit in &mut deposits {
-                    let add = remaining_deposit.saturating_sub(deposit_per_collator); // we correct the overshoot here
-                    log::debug!(
-                        "deposit_per_collator:{:?}, add:{:?}",
-                        deposit_per_collator,
-                        add
-                    );
-                    deposit.1 += add;
-                    remaining_deposit -= add;
-                }
-            }
-            log::debug!(
-                "deposits:{:?} not null and remaining:{:?}",
-                deposits,
-                remaining_deposit
-            );
-            return deposits;
-        }
-
-        // second concern: We want to maximize staking APY earned, so we want to balance the staking pools with our deposits while conserving gas
-        log::debug!(
-            "deposit_eligible_collators size:{:?}, remaining_deposit:{:?}",
-            deposit_eligible_collators.len(),
-            remaining_deposit
-        );
-        deposits.append(
-            &mut deposit_strategies::split_to_underallocated_collators::<T>(
-                deposit_eligible_collators.as_slice(),
-                remaining_deposit,
-            ),
-        );
-        remaining_deposit -= deposits
-            .iter()
-            .map(|deposit| deposit.1)
-            .reduce(|sum, elem| sum + elem)
-            .unwrap_or_else(|| 0u32.into());
-        log::debug!(
-            "after split_to_underallocated_collators, remain: ${:?}",
-            remaining_deposit
-        );
-        // fallback: just assign to a random active collator ( choose a different collator for each invocation )
-        if !remaining_deposit.is_zero() {
-            log::warn!(
-                "Failed to distribute {:?} tokens by strategy",
-                remaining_deposit
-            );
-            if let Some(deposit) = deposit_strategies::stake_to_random_collator::<T>(
-                deposit_eligible_collators.as_slice(),
-                remaining_deposit,
-            ) {
-                deposits.push(deposit);
-                remaining_deposit = new_deposit
-                    - deposits
-                        .iter()
-                        .map(|deposit| deposit.1)
-                        .reduce(|sum, elem| sum + elem)
-                        .unwrap_or_else(|| 0u32.into());
-            }
-        }
-        if deposits.is_empty() {
-            log::error!("FATAL: Could not find any collator to stake to");
-        }
-        log::debug!("Deposits: {:?}", deposits);
-        if !remaining_deposit.is_zero() {
-            log::error!(
-                "FATAL: We have {:?} unstaked balance left over after depositing, returning empty vec",
-                remaining_deposit
-            );
-            deposits.clear();
-        }
-        deposits
+        Self::staking_strategy()
+            .deposit_distribution::<T, I>(deposit_eligible_collators.as_slice(), new_deposit)
     }
 
     #[named]
@@ -190,12 +92,9 @@ impl<T: Config> Pallet<T> {
         if withdrawal_amount.is_zero() {
             return vec![];
         }
-        let mut withdrawals = vec![];
-        let mut remaining_balance = withdrawal_amount;
-
         // Only unstake collators we're staked to **and not already unstaking from**
-        let staked_collators: Vec<_> = StakedCollators::<T>::iter_keys().collect();
-        let collators_we_are_unstaking_from: Vec<_> = UnstakingCollators::<T>::get()
+        let staked_collators: Vec<_> = StakedCollators::<T, I>::iter_keys().collect();
+        let collators_we_are_unstaking_from: Vec<_> = UnstakingCollators::<T, I>::get()
             .iter()
             .cloned()
             .map(|uc| uc.account)
@@ -210,9 +109,9 @@ impl<T: Config> Pallet<T> {
             return vec![];
         }
         let mut eligible_collators: Vec<_> = collators_we_are_unstaking_from;
-        if let Some(state) =
-            pallet_parachain_staking::Pallet::<T>::delegator_state(crate::Pallet::<T>::account_id())
-        {
+        if let Some(state) = pallet_parachain_staking::Pallet::<T>::delegator_state(
+            crate::Pallet::<T, I>::account_id(),
+        ) {
             let owners: Vec<_> = state
                 .delegations
                 .0
@@ -226,27 +125,9 @@ impl<T: Config> Pallet<T> {
                 .cloned()
                 .collect();
         }
-        // first concern: If there are inactive collators we are staked with, prefer these
-        let (mut collators, balance_unstaked) = withdraw_strategies::unstake_inactive_collators::<T>(
-            &eligible_collators,
-            remaining_balance,
-        );
-        withdrawals.append(&mut collators);
-        remaining_balance = remaining_balance.saturating_sub(balance_unstaked);
-        if remaining_balance.is_zero() && !withdrawals.is_empty() {
-            return withdrawals;
-        }
-        // If we have balance to withdraw left over, we have to unstake some healthy collator.
-        // Unstake starting from the highest overallocated collator ( since that yields the lowest APY ) going down until request is satisfied
-        let (mut collators, balance_unstaked) = withdraw_strategies::unstake_least_apy_collators::<T>(
-            &eligible_collators
-                .into_iter()
-                .filter(|collator| !withdrawals.contains(collator))
-                .collect(),
-            remaining_balance,
-        );
-        withdrawals.append(&mut collators);
-        remaining_balance = remaining_balance.saturating_sub(balance_unstaked);
+        let (withdrawals, balance_unstaked) = Self::staking_strategy()
+            .withdrawal_distribution::<T, I>(&eligible_collators, withdrawal_amount);
+        let remaining_balance = withdrawal_amount.saturating_sub(balance_unstaked);
 
         if !remaining_balance.is_zero() {
             log::error!(
@@ -263,6 +144,20 @@ impl<T: Config> Pallet<T> {
         withdrawals
     }
 
+    /// Funds staked with collators the pallet is not already unstaking from
+    pub(crate) fn unstakable_balance() -> BalanceOf<T> {
+        let collators_we_are_unstaking_from: Vec<_> = UnstakingCollators::<T, I>::get()
+            .iter()
+            .cloned()
+            .map(|uc| uc.account)
+            .collect();
+        StakedCollators::<T, I>::iter()
+            .filter(|(collator, _)| !collators_we_are_unstaking_from.contains(collator))
+            .fold(Zero::zero(), |sum: BalanceOf<T>, (_, stake)| {
+                sum.saturating_add(stake)
+            })
+    }
+
     #[named]
     pub(crate) fn do_stake_one_collator(
         collator: T::AccountId,
@@ -274,23 +169,23 @@ impl<T: Config> Pallet<T> {
         // - newly deposited funds > min_deposit
         // - unstaked-but-not-needed-for-withdrawals funds > min_deposit
         if amount < Self::min_deposit() {
-            return Err(Error::<T>::DepositBelowMinAmount.into());
+            return Err(Error::<T, I>::DepositBelowMinAmount.into());
         }
         if amount > Self::surplus_funds() && amount > Self::unlocked_unstaking_funds() {
             // we can't handle this withdrawal from new deposits or unstaked funds
-            return Err(Error::<T>::PotBalanceTooLowToStake.into());
+            return Err(Error::<T, I>::PotBalanceTooLowToStake.into());
         }
         // collator exists
         let candidate_delegation_count;
         if let Some(info) = pallet_parachain_staking::Pallet::<T>::candidate_info(&collator) {
             candidate_delegation_count = info.delegation_count;
         } else {
-            return Err(Error::<T>::NoCollatorForStake.into());
+            return Err(Error::<T, I>::NoCollatorForStake.into());
         };
-        let delegation_count = StakedCollators::<T>::iter_keys().count() as u32;
+        let delegation_count = StakedCollators::<T, I>::iter_keys().count() as u32;
 
         // If we're already delegated to this collator, we must call `delegator_bond_more`.
-        if StakedCollators::<T>::get(&collator).is_zero() {
+        if StakedCollators::<T, I>::get(&collator).is_zero() {
             log::debug!(
                 "delegator not staked on collator:{:?}, use delegate",
                 collator
@@ -307,7 +202,7 @@ impl<T: Config> Pallet<T> {
             );
             ensure!(
                 Self::surplus_funds() > fee_estimate,
-                Error::<T>::PotBalanceTooLowToPayTxFee
+                Error::<T, I>::PotBalanceTooLowToPayTxFee
             );
             pallet_parachain_staking::Pallet::<T>::delegate(
                 RawOrigin::Signed(Self::account_id()).into(),
@@ -340,7 +235,7 @@ impl<T: Config> Pallet<T> {
             );
             ensure!(
                 Self::surplus_funds() > fee_estimate,
-                Error::<T>::PotBalanceTooLowToPayTxFee
+                Error::<T, I>::PotBalanceTooLowToPayTxFee
             );
             pallet_parachain_staking::Pallet::<T>::delegator_bond_more(
                 RawOrigin::Signed(Self::account_id()).into(),
@@ -357,7 +252,7 @@ impl<T: Config> Pallet<T> {
                 e.error
             })?;
         }
-        StakedCollators::<T>::mutate(&collator, |balance| *balance += amount);
+        StakedCollators::<T, I>::mutate(&collator, |balance| *balance += amount);
 
         log::debug!("Delegated {:?} tokens to {:?}", amount, collator);
         Ok(())
@@ -369,10 +264,10 @@ impl<T: Config> Pallet<T> {
         some_collator: T::AccountId,
     ) -> DispatchResult {
         log::trace!(function_name!());
-        let delegated_amount_to_be_unstaked = StakedCollators::<T>::get(some_collator.clone());
+        let delegated_amount_to_be_unstaked = StakedCollators::<T, I>::get(some_collator.clone());
         if delegated_amount_to_be_unstaked.is_zero() {
             log::error!("requested to unstake a collator that isn't staked");
-            return Err(Error::<T>::NoCollatorForWithdrawal.into());
+            return Err(Error::<T, I>::NoCollatorForWithdrawal.into());
         };
         log::debug!(
             "Unstaking collator {:?} with balance {:?}",
@@ -388,7 +283,7 @@ impl<T: Config> Pallet<T> {
         );
         ensure!(
             Self::surplus_funds() > fee_estimate,
-            Error::<T>::PotBalanceTooLowToPayTxFee
+            Error::<T, I>::PotBalanceTooLowToPayTxFee
         );
         // unstake from parachain staking
         // NOTE: All funds that were delegated here will no longer produce staking rewards
@@ -399,10 +294,10 @@ impl<T: Config> Pallet<T> {
         .map_err(|e| e.error)?;
 
         // Update bookkeeping
-        SurplusUnstakingBalance::<T>::mutate(|bal| {
+        SurplusUnstakingBalance::<T, I>::mutate(|bal| {
             *bal = (*bal).saturating_add(delegated_amount_to_be_unstaked);
         });
-        UnstakingCollators::<T>::mutate(|collators| {
+        UnstakingCollators::<T, I>::mutate(|collators| {
             collators.push(UnstakingCollator {
                 account: some_collator.clone(),
                 since: now,
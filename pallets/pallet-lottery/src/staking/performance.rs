@@ -0,0 +1,168 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{pallet_prelude::Weight, traits::Get};
+use pallet_parachain_staking::RoundIndex;
+use sp_runtime::traits::Zero;
+use sp_std::{vec, vec::Vec};
+
+/// Reward points `collator` was awarded in the finished `round` and the total points awarded in it
+/// The previous round is read from ParachainStaking, older rounds from [`CollatorPointsHistory`]
+/// Returns `None` if there is no record of `round` or the collator was not selected for it
+pub(super) fn round_points<T: Config<I>, I: 'static>(
+    round: RoundIndex,
+    collator: &T::AccountId,
+) -> Option<(u32, u32)> {
+    let current_round = pallet_parachain_staking::Pallet::<T>::round().current;
+    if round.saturating_add(1) == current_round {
+        return Some((
+            pallet_parachain_staking::Pallet::<T>::awarded_pts(round, collator),
+            pallet_parachain_staking::Pallet::<T>::points(round),
+        ));
+    }
+    let total_points = RoundPointsHistory::<T, I>::get(round)?;
+    CollatorPointsHistory::<T, I>::get(round, collator).map(|points| (points, total_points))
+}
+
+/// Finished rounds considered when judging collator performance, oldest first
+pub(super) fn history_rounds<T: Config<I>, I: 'static>() -> core::ops::Range<RoundIndex> {
+    let current_round = pallet_parachain_staking::Pallet::<T>::round().current;
+    current_round.saturating_sub(REWARD_HISTORY_ROUNDS).max(1)..current_round
+}
+
+impl<T: Config<I>, I: 'static> pallet_parachain_staking::OnNewRound for Pallet<T, I> {
+    /// Records the reward points of the round that just finished, ParachainStaking removes them when paying out rewards
+    fn on_new_round(round_index: RoundIndex) -> Weight {
+        let finished_round = round_index.saturating_sub(1);
+        if finished_round.is_zero() {
+            return Weight::zero();
+        }
+        // NOTE: ParachainStaking selects the collators of the new round after this hook, so these are the ones of `finished_round`
+        let collators = pallet_parachain_staking::Pallet::<T>::selected_candidates();
+        for collator in collators.iter() {
+            CollatorPointsHistory::<T, I>::insert(
+                finished_round,
+                collator,
+                pallet_parachain_staking::Pallet::<T>::awarded_pts(finished_round, collator),
+            );
+        }
+        RoundPointsHistory::<T, I>::insert(
+            finished_round,
+            pallet_parachain_staking::Pallet::<T>::points(finished_round),
+        );
+
+        let mut removed = 0u32;
+        if finished_round > REWARD_HISTORY_ROUNDS {
+            let expired_round = finished_round - REWARD_HISTORY_ROUNDS;
+            removed =
+                CollatorPointsHistory::<T, I>::clear_prefix(expired_round, u32::MAX, None).unique;
+            RoundPointsHistory::<T, I>::remove(expired_round);
+        }
+        let collators = collators.len() as u64;
+        T::DbWeight::get().reads_writes(
+            collators.saturating_add(2),
+            collators.saturating_add(removed as u64).saturating_add(2),
+        )
+    }
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+    /// Selected collators that are not expected to keep earning staking rewards for the pot, because they
+    /// * produced no blocks in the previous round
+    /// * produced fewer blocks this session than [`manta_collator_selection`] requires to not evict them at the end of it
+    /// * earned fewer points per round in [`history_rounds`] than the same eviction threshold applied to reward points
+    pub(crate) fn underperforming_collators() -> Vec<T::AccountId> {
+        let selected = pallet_parachain_staking::Pallet::<T>::selected_candidates();
+        let current_round = pallet_parachain_staking::Pallet::<T>::round().current;
+        let mut underperforming: Vec<T::AccountId> = vec![];
+
+        // did not receive any points last round unless this is the first round
+        if current_round > 1 {
+            underperforming.extend(selected.iter().cloned().filter(|collator| {
+                pallet_parachain_staking::Pallet::<T>::awarded_pts(current_round - 1, collator)
+                    .is_zero()
+            }));
+        }
+
+        // on track to be evicted at the end of this session
+        if let Some(evict_below_blocks) =
+            manta_collator_selection::Pallet::<T>::session_eviction_threshold()
+        {
+            underperforming.extend(selected.iter().cloned().filter(|collator| {
+                manta_collator_selection::Pallet::<T>::blocks_this_session(collator)
+                    < evict_below_blocks
+            }));
+        }
+
+        // trending below the eviction threshold over multiple rounds
+        let points_per_round: Vec<_> = selected
+            .iter()
+            .filter_map(|collator| {
+                let (points, rounds) = history_rounds::<T, I>()
+                    .filter_map(|round| round_points::<T, I>(round, collator))
+                    .fold((0u32, 0u32), |(points, rounds), (awarded, _)| {
+                        (points.saturating_add(awarded), rounds + 1)
+                    });
+                (!rounds.is_zero()).then(|| (collator, points / rounds))
+            })
+            .collect();
+        if let Some(points_threshold) = manta_collator_selection::Pallet::<T>::eviction_threshold(
+            points_per_round.iter().map(|(_, points)| *points).collect(),
+        ) {
+            underperforming.extend(
+                points_per_round
+                    .iter()
+                    .filter(|(_, points)| *points < points_threshold)
+                    .map(|(collator, _)| (*collator).clone()),
+            );
+        }
+
+        underperforming.sort();
+        underperforming.dedup();
+        log::debug!("Underperforming collators: {:?}", underperforming);
+        underperforming
+    }
+
+    /// Collators the pot should move its stake away from in [`Call::rebalance_stake`]:
+    /// Staked collators that are no longer selected or underperforming, excluding the ones already unstaking
+    pub(crate) fn collators_to_rebalance() -> Vec<T::AccountId> {
+        let state = match pallet_parachain_staking::Pallet::<T>::delegator_state(Self::account_id())
+        {
+            Some(state) => state,
+            None => return vec![],
+        };
+        let selected = pallet_parachain_staking::Pallet::<T>::selected_candidates();
+        let underperforming = Self::underperforming_collators();
+        let collators_we_are_unstaking_from: Vec<_> = UnstakingCollators::<T, I>::get()
+            .iter()
+            .cloned()
+            .map(|uc| uc.account)
+            .collect();
+        // NOTE: We can only revoke delegations that are still listed for the pot ( i.e. not kicked from the bottom )
+        state
+            .delegations
+            .0
+            .iter()
+            .map(|delegation| delegation.owner.clone())
+            .filter(|collator| {
+                !StakedCollators::<T, I>::get(collator).is_zero()
+                    && !collators_we_are_unstaking_from.contains(collator)
+                    && (!selected.contains(collator) || underperforming.contains(collator))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,329 @@
+// Copyright 2020-2024 Manta Network.
+// This file is part of Manta.
+//
+// Manta is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Manta is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Manta.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use core::{cmp::Ordering, marker::PhantomData};
+use pallet_parachain_staking::BalanceOf;
+use sp_core::U256;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    Perbill, Percent, SaturatedConversion,
+};
+use sp_std::{vec, vec::Vec};
+
+/// Number of finished rounds of [`pallet_parachain_staking::AwardedPts`] considered when rating collators
+/// NOTE: ParachainStaking removes the points of a round once it is paid out, the pallet keeps its own copy in [`crate::CollatorPointsHistory`]
+pub const REWARD_HISTORY_ROUNDS: u32 = 4;
+
+/// Decides which collators the pallet delegates new funds to and which delegations it revokes
+///
+/// The pallet only passes collators it is able to stake to / unstake from, strategies only decide between them.
+pub trait StakingStrategy<T: Config<I>, I: 'static = ()> {
+    /// Distributes `new_deposit` to zero or more of `eligible_collators`
+    /// If it can't distribute all tokens for some reason, it returns an empty vec
+    fn deposit_distribution(
+        eligible_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)>;
+
+    /// Selects collators from `eligible_collators` to be unstaked until at least `withdrawal_amount` is freed
+    /// Returns the selected collators and the sum of the pallet's stake on them
+    fn withdrawal_distribution(
+        eligible_collators: &Vec<T::AccountId>,
+        withdrawal_amount: BalanceOf<T>,
+    ) -> (Vec<T::AccountId>, BalanceOf<T>);
+}
+
+impl StakingStrategyKind {
+    pub(crate) fn deposit_distribution<T: Config<I>, I: 'static>(
+        self,
+        eligible_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        match self {
+            Self::Balanced => {
+                BalancedStrategy::<T, I>::deposit_distribution(eligible_collators, new_deposit)
+            }
+            Self::RewardHistory => {
+                RewardHistoryStrategy::<T, I>::deposit_distribution(eligible_collators, new_deposit)
+            }
+        }
+    }
+
+    pub(crate) fn withdrawal_distribution<T: Config<I>, I: 'static>(
+        self,
+        eligible_collators: &Vec<T::AccountId>,
+        withdrawal_amount: BalanceOf<T>,
+    ) -> (Vec<T::AccountId>, BalanceOf<T>) {
+        match self {
+            Self::Balanced => BalancedStrategy::<T, I>::withdrawal_distribution(
+                eligible_collators,
+                withdrawal_amount,
+            ),
+            Self::RewardHistory => RewardHistoryStrategy::<T, I>::withdrawal_distribution(
+                eligible_collators,
+                withdrawal_amount,
+            ),
+        }
+    }
+}
+
+/// Balances the stake of all active collators by depositing to the ones with the least stake
+/// and unstaking the ones with the most stake ( since these yield the lowest APY )
+pub struct BalancedStrategy<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> StakingStrategy<T, I> for BalancedStrategy<T, I> {
+    #[named]
+    fn deposit_distribution(
+        eligible_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        log::trace!(function_name!());
+        let mut deposits: Vec<(T::AccountId, BalanceOf<T>)> = vec![];
+        let mut remaining_deposit = new_deposit;
+
+        // first concern: If we fell out of the active set on one or more collators, we need to get back into it
+        deposits.append(
+            &mut deposit_strategies::reactivate_bottom_collators::<T, I>(
+                eligible_collators,
+                new_deposit,
+            ),
+        );
+        // `reactivate_bottom_collators` has only distributed the funds needed for reactivation, we can have some left over
+        remaining_deposit -= sum_of_deposits::<T, I>(&deposits);
+
+        log::debug!(
+            "after reactivate_bottom_collators deposits: {:?}, remaining: ${:?}",
+            deposits.len(),
+            remaining_deposit
+        );
+        // If we have re-activated any collators and have leftover funds, we just distribute all surplus tokens to them evenly and call it a day
+        if !deposits.is_empty() {
+            if !remaining_deposit.is_zero() {
+                log::debug!(
+                    "deposits:{:?} not null and remaining:{:?} not zero",
+                    deposits,
+                    remaining_deposit
+                );
+                let deposit_per_collator =
+                    Percent::from_rational(1, deposits.len() as u32).mul_ceil(remaining_deposit); // this overshoots the amount if there's a remainder
+                for deposit in &mut deposits {
+                    let add = remaining_deposit.saturating_sub(deposit_per_collator); // we correct the overshoot here
+                    log::debug!(
+                        "deposit_per_collator:{:?}, add:{:?}",
+                        deposit_per_collator,
+                        add
+                    );
+                    deposit.1 += add;
+                    remaining_deposit -= add;
+                }
+            }
+            log::debug!(
+                "deposits:{:?} not null and remaining:{:?}",
+                deposits,
+                remaining_deposit
+            );
+            return deposits;
+        }
+
+        // second concern: We want to maximize staking APY earned, so we want to balance the staking pools with our deposits while conserving gas
+        log::debug!(
+            "deposit_eligible_collators size:{:?}, remaining_deposit:{:?}",
+            eligible_collators.len(),
+            remaining_deposit
+        );
+        deposits.append(
+            &mut deposit_strategies::split_to_underallocated_collators::<T, I>(
+                eligible_collators,
+                remaining_deposit,
+            ),
+        );
+        finalize_deposits::<T, I>(eligible_collators, new_deposit, deposits)
+    }
+
+    fn withdrawal_distribution(
+        eligible_collators: &Vec<T::AccountId>,
+        withdrawal_amount: BalanceOf<T>,
+    ) -> (Vec<T::AccountId>, BalanceOf<T>) {
+        // first concern: If there are inactive collators we are staked with, prefer these
+        let (mut withdrawals, mut unstaked) = withdraw_strategies::unstake_inactive_collators::<T, I>(
+            eligible_collators,
+            withdrawal_amount,
+        );
+        if unstaked >= withdrawal_amount && !withdrawals.is_empty() {
+            return (withdrawals, unstaked);
+        }
+        // If we have balance to withdraw left over, we have to unstake some healthy collator.
+        // Unstake starting from the highest overallocated collator ( since that yields the lowest APY ) going down until request is satisfied
+        let (mut collators, balance_unstaked) =
+            withdraw_strategies::unstake_least_apy_collators::<T, I>(
+                &eligible_collators
+                    .iter()
+                    .filter(|collator| !withdrawals.contains(collator))
+                    .cloned()
+                    .collect(),
+                withdrawal_amount.saturating_sub(unstaked),
+            );
+        withdrawals.append(&mut collators);
+        unstaked += balance_unstaked;
+        (withdrawals, unstaked)
+    }
+}
+
+/// Stakes to the collators that paid the most rewards per staked token in the last [`REWARD_HISTORY_ROUNDS`] rounds
+/// ( according to [`pallet_parachain_staking::AwardedPts`] and the collator commission )
+/// and unstakes the ones that paid the least
+pub struct RewardHistoryStrategy<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> StakingStrategy<T, I> for RewardHistoryStrategy<T, I> {
+    #[named]
+    fn deposit_distribution(
+        eligible_collators: &[T::AccountId],
+        new_deposit: BalanceOf<T>,
+    ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        log::trace!(function_name!());
+        // first concern: If we fell out of the active set on one or more collators, we need to get back into it
+        let mut deposits = deposit_strategies::reactivate_bottom_collators::<T, I>(
+            eligible_collators,
+            new_deposit,
+        );
+        let remaining_deposit = new_deposit.saturating_sub(sum_of_deposits::<T, I>(&deposits));
+        log::debug!(
+            "after reactivate_bottom_collators deposits: {:?}, remaining: ${:?}",
+            deposits.len(),
+            remaining_deposit
+        );
+
+        // second concern: Put the rest where it earns the most rewards
+        for (collator, amount) in
+            deposit_strategies::split_by_reward_rate::<T, I>(eligible_collators, remaining_deposit)
+        {
+            match deposits.iter_mut().find(|deposit| deposit.0 == collator) {
+                Some(deposit) => deposit.1 += amount,
+                None => deposits.push((collator, amount)),
+            }
+        }
+        finalize_deposits::<T, I>(eligible_collators, new_deposit, deposits)
+    }
+
+    fn withdrawal_distribution(
+        eligible_collators: &Vec<T::AccountId>,
+        withdrawal_amount: BalanceOf<T>,
+    ) -> (Vec<T::AccountId>, BalanceOf<T>) {
+        // first concern: If there are inactive collators we are staked with, prefer these
+        let (mut withdrawals, mut unstaked) = withdraw_strategies::unstake_inactive_collators::<T, I>(
+            eligible_collators,
+            withdrawal_amount,
+        );
+        if unstaked >= withdrawal_amount && !withdrawals.is_empty() {
+            return (withdrawals, unstaked);
+        }
+        // second concern: Give up the delegations that earn the least rewards
+        let (mut collators, balance_unstaked) =
+            withdraw_strategies::unstake_lowest_reward_rate_collators::<T, I>(
+                &eligible_collators
+                    .iter()
+                    .filter(|collator| !withdrawals.contains(collator))
+                    .cloned()
+                    .collect(),
+                withdrawal_amount.saturating_sub(unstaked),
+            );
+        withdrawals.append(&mut collators);
+        unstaked += balance_unstaked;
+        (withdrawals, unstaked)
+    }
+}
+
+/// Reward share a delegator of `collator` received over the last [`REWARD_HISTORY_ROUNDS`] finished rounds
+/// as the sum of per-round `Perbill` parts of the staking rewards, net of collator commission
+///
+/// `pallet_parachain_staking` charges one commission for all collators, so it scales every share by the same
+/// factor and keeps the share equal to what delegators actually receive
+pub(super) fn delegator_reward_share<T: Config<I>, I: 'static>(collator: &T::AccountId) -> u128 {
+    let mut share = 0u128;
+    for round in performance::history_rounds::<T, I>() {
+        match performance::round_points::<T, I>(round, collator) {
+            Some((points, total_points)) if !total_points.is_zero() => {
+                share = share.saturating_add(
+                    Perbill::from_rational(points, total_points).deconstruct() as u128,
+                );
+            }
+            _ => continue, // nobody produced blocks or the collator was not selected
+        }
+    }
+    let commission = pallet_parachain_staking::Pallet::<T>::collator_commission();
+    Perbill::one().saturating_sub(commission).mul_floor(share)
+}
+
+/// Compares the rewards per staked token of two collators given as `(reward share, total stake)`
+pub(super) fn cmp_reward_rate<T: Config<I>, I: 'static>(
+    a: (u128, BalanceOf<T>),
+    b: (u128, BalanceOf<T>),
+) -> Ordering {
+    // a.share / a.stake <=> b.share / b.stake, cross-multiplied to stay exact
+    let a_stake = U256::from(a.1.saturated_into::<u128>());
+    let b_stake = U256::from(b.1.saturated_into::<u128>());
+    (U256::from(a.0) * b_stake).cmp(&(U256::from(b.0) * a_stake))
+}
+
+fn sum_of_deposits<T: Config<I>, I: 'static>(
+    deposits: &[(T::AccountId, BalanceOf<T>)],
+) -> BalanceOf<T> {
+    deposits
+        .iter()
+        .map(|deposit| deposit.1)
+        .reduce(|sum, elem| sum + elem)
+        .unwrap_or_else(|| 0u32.into())
+}
+
+/// fallback: assign whatever the strategy could not distribute to a random eligible collator
+/// and discard the distribution if there are still funds left over
+#[named]
+fn finalize_deposits<T: Config<I>, I: 'static>(
+    eligible_collators: &[T::AccountId],
+    new_deposit: BalanceOf<T>,
+    mut deposits: Vec<(T::AccountId, BalanceOf<T>)>,
+) -> Vec<(T::AccountId, BalanceOf<T>)> {
+    log::trace!(function_name!());
+    let mut remaining_deposit = new_deposit.saturating_sub(sum_of_deposits::<T, I>(&deposits));
+    log::debug!("after strategy, remain: ${:?}", remaining_deposit);
+    // fallback: just assign to a random active collator ( choose a different collator for each invocation )
+    if !remaining_deposit.is_zero() {
+        log::warn!(
+            "Failed to distribute {:?} tokens by strategy",
+            remaining_deposit
+        );
+        if let Some(deposit) = deposit_strategies::stake_to_random_collator::<T, I>(
+            eligible_collators,
+            remaining_deposit,
+        ) {
+            deposits.push(deposit);
+            remaining_deposit = new_deposit.saturating_sub(sum_of_deposits::<T, I>(&deposits));
+        }
+    }
+    if deposits.is_empty() {
+        log::error!("FATAL: Could not find any collator to stake to");
+    }
+    log::debug!("Deposits: {:?}", deposits);
+    if !remaining_deposit.is_zero() {
+        log::error!(
+            "FATAL: We have {:?} unstaked balance left over after depositing, returning empty vec",
+            remaining_deposit
+        );
+        deposits.clear();
+    }
+    deposits
+}
@@ -19,7 +19,7 @@ use pallet_parachain_staking::BalanceOf;
 use sp_runtime::traits::{Saturating, Zero};
 use sp_std::{vec, vec::Vec};
 
-pub(super) fn unstake_inactive_collators<T: Config>(
+pub(super) fn unstake_inactive_collators<T: Config<I>, I: 'static>(
     eligible_collators: &Vec<T::AccountId>,
     withdrawal_amount: BalanceOf<T>,
 ) -> (Vec<T::AccountId>, BalanceOf<T>) {
@@ -30,18 +30,17 @@ pub(super) fn unstake_inactive_collators<T: Config>(
         return (withdrawals, 0u32.into());
     }
     // first concern: If there are inactive collators we are staked with, prefer these
-    let round_info = pallet_parachain_staking::Pallet::<T>::round();
     let selected = pallet_parachain_staking::Pallet::<T>::selected_candidates();
-    let inactive_eligible_collators = eligible_collators.iter().filter(
-            |collator|{
-                // no longer selected for block rewards
-                !selected.contains(collator) ||
-                // did not receive any points last round unless this is the first round
-                (round_info.current > 1 && pallet_parachain_staking::Pallet::<T>::awarded_pts(round_info.current-1,collator).is_zero())
-            });
+    let underperforming = crate::Pallet::<T, I>::underperforming_collators();
+    let inactive_eligible_collators = eligible_collators.iter().filter(|collator| {
+        // no longer selected for block rewards
+        !selected.contains(collator) ||
+        // not expected to keep earning rewards, e.g. did not receive any points last round
+        underperforming.contains(collator)
+    });
     // since these collators are inactive, we just unstake in any order until we have satisfied the withdrawal request
     for collator in inactive_eligible_collators {
-        let our_stake = StakedCollators::<T>::get(collator);
+        let our_stake = StakedCollators::<T, I>::get(collator);
         log::debug!("Unstaking {:?} from inactive {:?}", our_stake, collator);
         unstaked += our_stake;
         withdrawals.push(collator.clone());
@@ -56,7 +55,7 @@ pub(super) fn unstake_inactive_collators<T: Config>(
     (withdrawals, unstaked)
 }
 
-pub(super) fn unstake_least_apy_collators<T: Config>(
+pub(super) fn unstake_least_apy_collators<T: Config<I>, I: 'static>(
     eligible_collators: &Vec<T::AccountId>,
     withdrawal_amount: BalanceOf<T>,
 ) -> (Vec<T::AccountId>, BalanceOf<T>) {
@@ -87,7 +86,50 @@ pub(super) fn unstake_least_apy_collators<T: Config>(
         apy_ordered_active_collators_we_are_staked_with.len()
     );
     for c in apy_ordered_active_collators_we_are_staked_with {
-        let our_stake = StakedCollators::<T>::get(c.clone());
+        let our_stake = StakedCollators::<T, I>::get(c.clone());
+        log::debug!("Unstaking {:?} from active {:?}", our_stake, c);
+        withdrawals.push(c);
+        unstaked += our_stake;
+        if unstaked >= withdrawal_amount {
+            break;
+        }
+    }
+    (withdrawals, unstaked)
+}
+
+/// Unstakes active collators starting with the one that paid the least rewards per staked token
+/// ( see [`strategy::delegator_reward_share`] ) until the request is satisfied
+pub(super) fn unstake_lowest_reward_rate_collators<T: Config<I>, I: 'static>(
+    eligible_collators: &Vec<T::AccountId>,
+    withdrawal_amount: BalanceOf<T>,
+) -> (Vec<T::AccountId>, BalanceOf<T>) {
+    let mut withdrawals = vec![];
+    let mut unstaked = 0u32.into();
+
+    if eligible_collators.len().is_zero() || withdrawal_amount.is_zero() {
+        return (withdrawals, unstaked);
+    }
+
+    let selected = pallet_parachain_staking::Pallet::<T>::selected_candidates();
+    let mut rated_active_collators: Vec<_> = eligible_collators
+        .iter()
+        .filter(|collator| selected.contains(collator))
+        .map(|collator| {
+            let info = pallet_parachain_staking::Pallet::<T>::candidate_info(collator)
+                .expect("is a selected collator, therefore it has collator info. qed");
+            (
+                collator.clone(),
+                strategy::delegator_reward_share::<T, I>(collator),
+                info.total_counted,
+            )
+        })
+        .collect();
+    // sort ascending by rewards per staked token
+    rated_active_collators
+        .sort_by(|a, b| strategy::cmp_reward_rate::<T, I>((a.1, a.2), (b.1, b.2)));
+    log::debug!("Active collators size: {:?}", rated_active_collators.len());
+    for (c, _, _) in rated_active_collators {
+        let our_stake = StakedCollators::<T, I>::get(c.clone());
         log::debug!("Unstaking {:?} from active {:?}", our_stake, c);
         withdrawals.push(c);
         unstaked += our_stake;
@@ -15,18 +15,18 @@
 // along with Manta.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-    assert_last_event,
+    assert_last_event, audit,
     mock::{
         roll_one_block, roll_to, roll_to_round_begin, roll_to_round_end, AccountId, Assets,
-        Balance, Balances, ExtBuilder, Farming, Lottery, ParachainStaking, RuntimeOrigin as Origin,
-        System, Test, ALICE, BOB, CHARLIE, DAVE, DELEGATOR1, DELEGATOR2, DELEGATOR3, DELEGATOR4,
-        DELEGATOR5, DELEGATOR6, DELEGATOR7, DELEGATOR8, EVE, INIT_JUMBO_AMOUNT,
-        INIT_V_MANTA_AMOUNT, JUMBO_ID, POOL_ID, V_MANTA_ID,
+        Balance, Balances, CollatorSelection, ExtBuilder, Farming, JackpotLottery, Lottery,
+        ParachainStaking, RuntimeOrigin as Origin, System, Test, ALICE, BOB, CHARLIE, DAVE,
+        DELEGATOR1, DELEGATOR2, DELEGATOR3, DELEGATOR4, DELEGATOR5, DELEGATOR6, DELEGATOR7,
+        DELEGATOR8, EVE, INIT_JUMBO_AMOUNT, INIT_V_MANTA_AMOUNT, JUMBO_ID, POOL_ID, V_MANTA_ID,
     },
     Config, Error, FarmingParameters,
 };
 
-use frame_support::{assert_noop, assert_ok, traits::Currency};
+use frame_support::{assert_noop, assert_ok, instances::Instance1, traits::Currency};
 use frame_system::RawOrigin;
 use sp_runtime::TokenError;
 
@@ -60,6 +60,24 @@ fn call_manager_extrinsics_as_normal_user_should_not_work() {
             Lottery::rebalance_stake(Origin::signed(1)), // Somebody who is not T::ManageOrigin
             sp_runtime::DispatchError::BadOrigin
         );
+        assert_noop!(
+            Lottery::set_staking_strategy(
+                Origin::signed(1),
+                crate::StakingStrategyKind::RewardHistory
+            ), // Somebody who is not T::ManageOrigin
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Lottery::set_liquidity_buffer_target(Origin::signed(1), 1), // Somebody who is not T::ManageOrigin
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Lottery::set_instant_withdraw_fee(
+                Origin::signed(1),
+                sp_runtime::Perbill::from_percent(1)
+            ), // Somebody who is not T::ManageOrigin
+            sp_runtime::DispatchError::BadOrigin
+        );
     });
 }
 
@@ -1940,3 +1958,1409 @@ fn fails_withdrawing_more_than_vmanta() {
             assert_eq!(0, Assets::balance(V_MANTA_ID, CHARLIE));
         });
 }
+
+#[test]
+fn cumulative_balance_index_finds_owner_of_every_balance() {
+    ExtBuilder::default().build().execute_with(|| {
+        let deposits: Vec<(AccountId, Balance)> = (100..117).map(|a| (a, a as Balance)).collect();
+        for (account, amount) in deposits.iter() {
+            assert_ok!(Lottery::index_add_balance(account, *amount));
+        }
+        // slots are assigned in deposit order, so a naive scan in deposit order must agree with the index
+        let total: Balance = deposits.iter().map(|(_, amount)| amount).sum();
+        assert_eq!(Lottery::index_prefix_sum(deposits.len() as u32), total);
+        let mut cumulative = 0;
+        for (account, amount) in deposits.iter() {
+            assert_eq!(Lottery::participant_at_ticket(cumulative), Some(*account));
+            assert_eq!(
+                Lottery::participant_at_ticket(cumulative + amount - 1),
+                Some(*account)
+            );
+            cumulative += amount;
+        }
+        assert_eq!(Lottery::participant_at_ticket(total), None);
+
+        // fully withdrawing releases the slot, partially withdrawing shrinks the range
+        assert_ok!(Lottery::index_sub_balance(&100, 100, true));
+        assert_ok!(Lottery::index_sub_balance(&101, 1, false));
+        assert_eq!(Lottery::participant_at_ticket(0), Some(101));
+        assert_eq!(Lottery::participant_at_ticket(99), Some(101));
+        assert_eq!(Lottery::participant_at_ticket(100), Some(102));
+        assert_eq!(
+            Lottery::index_prefix_sum(deposits.len() as u32),
+            total - 101
+        );
+
+        // a new participant reuses the released slot instead of growing the index
+        assert_ok!(Lottery::index_add_balance(&EVE, 5));
+        assert_eq!(
+            crate::ParticipantSlots::<Test>::get(),
+            deposits.len() as u32
+        );
+        assert_eq!(crate::ParticipantSlot::<Test>::get(EVE), Some(1));
+        assert_eq!(Lottery::participant_at_ticket(4), Some(EVE));
+        assert_eq!(Lottery::participant_at_ticket(5), Some(101));
+    });
+}
+
+#[test]
+fn cumulative_balance_index_follows_deposits_and_withdrawals() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), 2 * balance));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_eq!(crate::ParticipantSlots::<Test>::get(), 2);
+            assert_eq!(Lottery::index_prefix_sum(2), Lottery::total_pot());
+            assert_eq!(Lottery::participant_at_ticket(balance), Some(ALICE));
+            assert_eq!(Lottery::participant_at_ticket(2 * balance), Some(CHARLIE));
+
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(ALICE),
+                2 * balance
+            ));
+            assert_eq!(crate::ParticipantSlot::<Test>::get(ALICE), None);
+            assert_eq!(Lottery::index_prefix_sum(2), Lottery::total_pot());
+            assert_eq!(Lottery::participant_at_ticket(0), Some(CHARLIE));
+
+            // CHARLIE is the only participant left and must win
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert!(Lottery::unclaimed_winnings_by_account(CHARLIE).is_some());
+            assert!(Lottery::unclaimed_winnings_by_account(ALICE).is_none());
+        });
+}
+
+#[test]
+fn drawing_history_keeps_most_recent_drawings() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(Lottery::drawing_count(), 1);
+            let first = Lottery::drawing_record(0).unwrap();
+            assert_eq!(first.winner, ALICE);
+            assert_eq!(first.total_pot, balance);
+            assert_eq!(first.participants, 1);
+            assert_eq!(
+                Lottery::unclaimed_winnings_by_account(ALICE),
+                Some(first.prize)
+            );
+
+            // the mock keeps 3 drawings, older ones are pruned
+            for prize in 1..=4 {
+                roll_one_block();
+                assert_ok!(Lottery::select_winner(prize * UNIT));
+            }
+            assert_eq!(Lottery::drawing_count(), 5);
+            assert!(Lottery::drawing_record(0).is_none());
+            assert!(Lottery::drawing_record(1).is_none());
+            let history = Lottery::drawing_history(0, 10);
+            assert_eq!(
+                history.iter().map(|r| r.prize).collect::<Vec<_>>(),
+                vec![2 * UNIT, 3 * UNIT, 4 * UNIT]
+            );
+            assert_eq!(Lottery::drawing_history(3, 1)[0].prize, 3 * UNIT);
+            assert!(Lottery::drawing_history(5, 3).is_empty());
+        });
+}
+
+#[test]
+fn participants_are_committed_when_drawing_freezeout_starts() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), 2 * balance));
+            assert_ok!(Lottery::start_lottery(RawOrigin::Root.into()));
+            let drawing = Lottery::next_drawing_at().unwrap();
+            let freezeout_start = drawing - <Test as Config>::DrawingFreezeout::get();
+
+            roll_to(freezeout_start - 1);
+            assert!(Lottery::drawing_commitment().is_none());
+            assert!(Lottery::participant_proof(ALICE).is_none());
+            roll_one_block();
+            let commitment = Lottery::drawing_commitment().unwrap();
+            assert_eq!(commitment.block, freezeout_start);
+            assert_eq!(commitment.drawing_at, drawing);
+            assert_eq!(commitment.participants, 2);
+
+            let proof = Lottery::participant_proof(CHARLIE).unwrap();
+            assert_eq!(proof.leaf.account, CHARLIE);
+            assert_eq!(proof.leaf.balance, 2 * balance);
+            assert_eq!(proof.number_of_leaves, 2);
+            assert!(Lottery::verify_participant_proof(
+                commitment.participants_root,
+                &proof
+            ));
+            // a proof does not verify for a modified leaf
+            let mut forged = proof;
+            forged.leaf.balance = 3 * balance;
+            assert!(!Lottery::verify_participant_proof(
+                commitment.participants_root,
+                &forged
+            ));
+            assert!(Lottery::participant_proof(DAVE).is_none());
+        });
+}
+
+#[test]
+fn inner_node_of_participants_tree_does_not_verify_as_leaf() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+            (DAVE, HIGH_BALANCE),
+            (EVE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), 2 * balance));
+            assert_ok!(Lottery::deposit(Origin::signed(DAVE), 3 * balance));
+            assert_ok!(Lottery::deposit(Origin::signed(EVE), 4 * balance));
+            assert_ok!(Lottery::start_lottery(RawOrigin::Root.into()));
+            let drawing = Lottery::next_drawing_at().unwrap();
+            roll_to(drawing - <Test as Config>::DrawingFreezeout::get());
+            let root = Lottery::drawing_commitment().unwrap().participants_root;
+
+            let leaves: Vec<_> = Lottery::participant_leaves()
+                .iter()
+                .map(audit::leaf_hash::<Test, ()>)
+                .collect();
+            assert_eq!(leaves.len(), 4);
+            let left = audit::node_hash::<Test, ()>(&leaves[0], &leaves[1]);
+            let right = audit::node_hash::<Test, ()>(&leaves[2], &leaves[3]);
+            assert_eq!(root, audit::node_hash::<Test, ()>(&left, &right));
+            // the inner node is the first of two leaves only if it were hashed as one
+            assert!(Lottery::verify_leaf_hash(root, left, 0, 2, &[right]));
+            // a leaf encoding the children of the inner node does not hash to it
+            let forged = audit::leaf_hash::<Test, ()>(&(leaves[0], leaves[1]));
+            assert_ne!(forged, left);
+            assert!(!Lottery::verify_leaf_hash(root, forged, 0, 2, &[right]));
+        });
+}
+
+#[test]
+fn drawing_winner_can_be_recomputed_from_audit_trail() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+            (DAVE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::start_lottery(RawOrigin::Root.into()));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            roll_one_block();
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), 2 * balance));
+            roll_one_block();
+            assert_ok!(Lottery::deposit(Origin::signed(DAVE), 3 * balance));
+            let drawing = Lottery::next_drawing_at().unwrap();
+
+            // the proofs served for the drawing are computed in the block before it
+            roll_to(drawing - 1);
+            let root = Lottery::drawing_commitment().unwrap().participants_root;
+            let mut proofs: Vec<_> = [ALICE, CHARLIE, DAVE]
+                .into_iter()
+                .map(|account| Lottery::participant_proof(account).unwrap())
+                .collect();
+            assert!(proofs
+                .iter()
+                .all(|proof| Lottery::verify_participant_proof(root, proof)));
+
+            roll_one_block();
+            assert_eq!(Lottery::drawing_count(), 1);
+            assert!(Lottery::drawing_commitment().is_none());
+            let record = Lottery::drawing_record(0).unwrap();
+            assert_eq!(record.block, drawing);
+            assert_eq!(record.participants_root, Some(root));
+            assert!(record.resamples < 6);
+
+            // recompute the winner from the committed leaves
+            proofs.sort_by_key(|proof| proof.leaf_index);
+            let mut cumulative_tickets = 0;
+            let winner = proofs
+                .iter()
+                .find(|proof| {
+                    cumulative_tickets +=
+                        proof.leaf.balance * record.ticket_blocks - proof.leaf.ticket_penalty;
+                    cumulative_tickets > record.winning_ticket
+                })
+                .map(|proof| proof.leaf.account);
+            assert_eq!(winner, Some(record.winner));
+        });
+}
+
+#[test]
+fn user_position_reports_odds_and_pending_withdrawals() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            let position = Lottery::user_position(ALICE);
+            assert_eq!(position.active_deposit, 0);
+            assert_eq!(position.win_probability, sp_runtime::Perbill::zero());
+
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), 3 * balance));
+            roll_one_block();
+            assert_ok!(Lottery::request_withdraw(Origin::signed(CHARLIE), balance));
+            let requested_at = System::block_number();
+
+            let position = Lottery::user_position(ALICE);
+            assert_eq!(position.active_deposit, balance);
+            assert_eq!(
+                position.win_probability,
+                sp_runtime::Perbill::from_rational(1u32, 3u32)
+            );
+            assert!(position.pending_withdrawals.is_empty());
+
+            let position = Lottery::user_position(CHARLIE);
+            assert_eq!(position.active_deposit, 2 * balance);
+            assert_eq!(
+                position.win_probability,
+                sp_runtime::Perbill::from_rational(2u32, 3u32)
+            );
+            assert_eq!(
+                position.pending_withdrawals,
+                vec![crate::PendingWithdrawal {
+                    balance,
+                    requested_at,
+                    matures_at: requested_at + <Test as Config>::UnstakeLockTime::get(),
+                }]
+            );
+            assert_eq!(position.unclaimed_winnings, 0);
+        });
+}
+
+#[test]
+fn late_deposit_gets_proportionally_fewer_tickets() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::start_lottery(RawOrigin::Root.into()));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            // CHARLIE deposits the same amount shortly before the freezeout
+            roll_to(9);
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), balance));
+            roll_to(10);
+            // ALICE held her deposit for blocks 1..=10, CHARLIE only for blocks 9..=10
+            assert_eq!(Lottery::tickets_of(&ALICE), 10 * balance);
+            assert_eq!(Lottery::tickets_of(&CHARLIE), 2 * balance);
+            assert_eq!(Lottery::total_tickets(), 12 * balance);
+            assert_eq!(
+                Lottery::user_position(CHARLIE).win_probability,
+                sp_runtime::Perbill::from_rational(1u32, 6u32)
+            );
+            // the first 10 units of tickets belong to ALICE, the rest to CHARLIE
+            assert_eq!(
+                Lottery::participant_at_ticket(10 * balance - 1),
+                Some(ALICE)
+            );
+            assert_eq!(Lottery::participant_at_ticket(10 * balance), Some(CHARLIE));
+            assert_eq!(Lottery::participant_at_ticket(12 * balance), None);
+
+            // topping up mid-interval only adds tickets for the remaining blocks
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), balance));
+            roll_to(11);
+            assert_eq!(Lottery::tickets_of(&CHARLIE), 3 * balance + 2 * balance);
+
+            // a drawing starts a new interval in which both balances count equally
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(Lottery::tickets_of(&ALICE), balance);
+            assert_eq!(Lottery::tickets_of(&CHARLIE), 2 * balance);
+            roll_to(12);
+            assert_eq!(Lottery::tickets_of(&ALICE), 2 * balance);
+            assert_eq!(Lottery::tickets_of(&CHARLIE), 4 * balance);
+        });
+}
+
+#[test]
+fn withdrawing_removes_tickets_proportionally() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+            (DAVE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::start_lottery(RawOrigin::Root.into()));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), 2 * balance));
+            roll_to(5);
+            assert_ok!(Lottery::deposit(Origin::signed(CHARLIE), balance));
+            assert_eq!(Lottery::tickets_of(&ALICE), 10 * balance);
+
+            // withdrawing half of the deposit forfeits half of the tickets earned so far
+            assert_ok!(Lottery::request_withdraw(Origin::signed(ALICE), balance));
+            assert_eq!(Lottery::tickets_of(&ALICE), 5 * balance);
+            roll_to(6);
+            assert_eq!(Lottery::tickets_of(&ALICE), 6 * balance);
+            assert_eq!(Lottery::tickets_of(&CHARLIE), 2 * balance);
+
+            // a full withdrawal forfeits all tickets and frees the slot without leaving a penalty behind
+            assert_ok!(Lottery::request_withdraw(Origin::signed(ALICE), balance));
+            assert_eq!(Lottery::tickets_of(&ALICE), 0);
+            assert_eq!(Lottery::total_tickets(), 2 * balance);
+            assert_eq!(Lottery::participant_at_ticket(0), Some(CHARLIE));
+            assert_ok!(Lottery::deposit(Origin::signed(DAVE), balance));
+            assert_eq!(crate::ParticipantSlot::<Test>::get(DAVE), Some(1));
+            assert_eq!(Lottery::tickets_of(&DAVE), balance);
+            assert_eq!(Lottery::total_tickets(), 3 * balance);
+        });
+}
+
+#[test]
+fn cancel_withdraw_request_reenters_the_drawing() {
+    let balance = 500_000_000 * UNIT;
+    let half_balance = 250_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(ALICE),
+                half_balance
+            ));
+            assert_eq!(half_balance, Lottery::surplus_unstaking_balance());
+            assert_eq!(half_balance, Lottery::total_pot());
+
+            assert_noop!(
+                Lottery::cancel_withdraw_request(Origin::signed(ALICE), 1),
+                Error::<Test>::NoWithdrawRequestAtIndex
+            );
+            assert_noop!(
+                Lottery::cancel_withdraw_request(Origin::signed(CHARLIE), 0),
+                Error::<Test>::NoWithdrawRequestAtIndex
+            );
+
+            assert_ok!(Lottery::cancel_withdraw_request(Origin::signed(ALICE), 0));
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::WithdrawCancelled {
+                    account: ALICE,
+                    amount: half_balance
+                }
+            ));
+            assert!(Lottery::withdrawal_request_queue().is_empty());
+            assert_eq!(balance, Lottery::active_balance_per_user(ALICE));
+            assert_eq!(balance, Lottery::total_pot());
+            assert_eq!(balance, Lottery::sum_of_deposits());
+            assert_eq!(1, Lottery::total_users());
+            assert_eq!(balance, Lottery::index_prefix_sum(1));
+            // the collator keeps unstaking, its funds are no longer reserved for a withdrawal
+            assert_eq!(balance, Lottery::surplus_unstaking_balance());
+            assert_eq!(1, crate::UnstakingCollators::<Test>::get().len());
+        });
+}
+
+/// Deposits with farming tokens minted, then requests and cancels a withdrawal of half of it under the given farming
+/// parameters. Returns the total supply of the farming token before and after.
+fn cancel_withdraw_request_with_farming_params(
+    mint_farming_token: bool,
+    destroy_farming_token: bool,
+) -> (Balance, Balance) {
+    let balance = 500_000_000 * UNIT;
+    let half_balance = 250_000_000 * UNIT;
+    let mut supply = (0, 0);
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .with_farming()
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            supply.0 = Assets::total_supply(V_MANTA_ID);
+            assert_ok!(Lottery::set_farming_params(
+                Origin::root(),
+                mint_farming_token,
+                destroy_farming_token,
+                0,
+                V_MANTA_ID
+            ));
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(ALICE),
+                half_balance
+            ));
+            assert_ok!(Lottery::cancel_withdraw_request(Origin::signed(ALICE), 0));
+            supply.1 = Assets::total_supply(V_MANTA_ID);
+        });
+    supply
+}
+
+#[test]
+fn cancel_withdraw_request_remints_burned_farming_tokens() {
+    let (before, after) = cancel_withdraw_request_with_farming_params(true, true);
+    assert_eq!(before, after);
+}
+
+#[test]
+fn cancel_withdraw_request_does_not_mint_unburned_farming_tokens() {
+    let (before, after) = cancel_withdraw_request_with_farming_params(true, false);
+    assert_eq!(before, after);
+}
+
+#[test]
+fn cancel_withdraw_request_remints_farming_tokens_burned_without_minting() {
+    let (before, after) = cancel_withdraw_request_with_farming_params(false, true);
+    assert_eq!(before, after);
+}
+
+#[test]
+fn cancel_withdraw_request_without_farming_tokens() {
+    let (before, after) = cancel_withdraw_request_with_farming_params(false, false);
+    assert_eq!(before, after);
+}
+
+#[test]
+fn cancel_withdraw_request_with_partially_matured_queue() {
+    let balance = 500_000_000 * UNIT;
+    let half_balance = 250_000_000 * UNIT;
+    let quarter_balance = 125_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            let alice_post_deposit_balance = Balances::free_balance(ALICE);
+            roll_one_block();
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(ALICE),
+                half_balance
+            ));
+            roll_to_round_begin(2);
+            roll_one_block();
+            pallet_parachain_staking::AwardedPts::<Test>::insert(1, BOB, 20);
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(ALICE),
+                quarter_balance
+            ));
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(2, Lottery::withdrawal_request_queue().len());
+            assert_eq!(quarter_balance, Lottery::surplus_unstaking_balance());
+
+            // the first request matured but the collator funds backing it are not unlocked yet
+            roll_to_round_begin(3);
+            pallet_parachain_staking::AwardedPts::<Test>::insert(2, BOB, 20);
+            assert_ok!(Lottery::cancel_withdraw_request(Origin::signed(ALICE), 0));
+            assert_eq!(
+                half_balance + quarter_balance,
+                Lottery::active_balance_per_user(ALICE)
+            );
+            assert_eq!(half_balance + quarter_balance, Lottery::total_pot());
+            assert_eq!(
+                half_balance + quarter_balance,
+                Lottery::surplus_unstaking_balance()
+            );
+            assert_eq!(0, Lottery::unlocked_unstaking_funds());
+            let remaining = Lottery::withdrawal_request_queue();
+            assert_eq!(1, remaining.len());
+            assert_eq!(quarter_balance, remaining[0].balance);
+
+            // unstaked funds are restaked except for what the immature request still needs
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(
+                half_balance + quarter_balance,
+                Lottery::staked_collators(BOB)
+            );
+            assert_eq!(quarter_balance, Lottery::unlocked_unstaking_funds());
+            assert_eq!(0, Lottery::surplus_unstaking_balance());
+            assert_eq!(1, Lottery::withdrawal_request_queue().len());
+            assert_eq!(alice_post_deposit_balance, Balances::free_balance(ALICE));
+
+            // once matured, the unlocked funds of a cancelled request are restaked right away
+            roll_to(remaining[0].block + <Test as Config>::UnstakeLockTime::get());
+            assert_ok!(Lottery::cancel_withdraw_request(Origin::signed(ALICE), 0));
+            assert!(Lottery::withdrawal_request_queue().is_empty());
+            assert_eq!(balance, Lottery::active_balance_per_user(ALICE));
+            assert_eq!(balance, Lottery::total_pot());
+            assert_eq!(balance, Lottery::staked_collators(BOB));
+            assert_eq!(0, Lottery::unlocked_unstaking_funds());
+            assert_eq!(0, Lottery::surplus_unstaking_balance());
+            assert_eq!(alice_post_deposit_balance, Balances::free_balance(ALICE));
+        });
+}
+
+#[test]
+fn cancel_withdraw_request_respects_drawing_freezeout() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::start_lottery(RawOrigin::Root.into()));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(Lottery::request_withdraw(Origin::signed(ALICE), balance));
+            let drawing = Lottery::next_drawing_at().unwrap();
+            roll_to(drawing - <Test as Config>::DrawingFreezeout::get());
+            assert_noop!(
+                Lottery::cancel_withdraw_request(Origin::signed(ALICE), 0),
+                Error::<Test>::TooCloseToDrawing
+            );
+        });
+}
+
+#[test]
+fn auto_compounded_winnings_are_staked_as_deposit() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .with_farming()
+        .build()
+        .execute_with(|| {
+            assert!(!Lottery::auto_compound_winnings(ALICE));
+            assert_ok!(Lottery::set_auto_compound(Origin::signed(ALICE), true));
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::AutoCompoundSet {
+                    account: ALICE,
+                    enabled: true
+                }
+            ));
+            assert!(Lottery::auto_compound_winnings(ALICE));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+
+            let prize = Lottery::current_prize_pool();
+            assert!(prize >= Lottery::min_deposit());
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert!(System::events().iter().any(|record| record.event
+                == crate::mock::RuntimeEvent::Lottery(crate::Event::WinningsCompounded {
+                    account: ALICE,
+                    amount: prize
+                })));
+
+            // winnings are part of the deposit now, not parked as unclaimed
+            assert!(Lottery::unclaimed_winnings_by_account(ALICE).is_none());
+            assert_eq!(0, Lottery::total_unclaimed_winnings());
+            assert_eq!(balance + prize, Lottery::active_balance_per_user(ALICE));
+            assert_eq!(balance + prize, Lottery::total_pot());
+            assert_eq!(balance + prize, Lottery::sum_of_deposits());
+            assert_eq!(balance + prize, Lottery::staked_collators(BOB));
+            assert_eq!(balance + prize, Lottery::index_prefix_sum(1));
+            assert_eq!(
+                balance + prize + INIT_V_MANTA_AMOUNT,
+                Assets::total_supply(V_MANTA_ID)
+            );
+        });
+}
+
+#[test]
+fn auto_compound_falls_back_to_unclaimed_winnings() {
+    let balance = 500_000_000 * UNIT;
+    let reserve = 10_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(reserve + 1_000 * UNIT)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::set_auto_compound(Origin::signed(ALICE), true));
+            assert_ok!(Lottery::set_auto_compound(Origin::signed(CHARLIE), true));
+            assert_ok!(Lottery::set_auto_compound(Origin::signed(CHARLIE), false));
+            assert!(!Lottery::auto_compound_winnings(CHARLIE));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+
+            // a prize below the minimum deposit can not be staked and is kept for claiming
+            let prize = Lottery::current_prize_pool();
+            assert!(prize > 0 && prize < Lottery::min_deposit());
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert_eq!(Some(prize), Lottery::unclaimed_winnings_by_account(ALICE));
+            assert_eq!(prize, Lottery::total_unclaimed_winnings());
+            assert_eq!(balance, Lottery::active_balance_per_user(ALICE));
+            assert_eq!(balance, Lottery::total_pot());
+            assert_eq!(balance, Lottery::sum_of_deposits());
+            assert_eq!(balance, Lottery::staked_collators(BOB));
+        });
+}
+
+/// Collators of the staking strategy simulation with their self-bond and the points they earn each round:
+/// ALICE, BOB and CHARLIE produce most blocks, DAVE and EVE have less stake but barely produce any
+const SIMULATED_COLLATORS: [(AccountId, Balance, u32); 5] = [
+    (ALICE, 10_000_000 * UNIT, 40),
+    (BOB, 10_000_000 * UNIT, 40),
+    (CHARLIE, 10_000_000 * UNIT, 40),
+    (DAVE, 5_000_000 * UNIT, 2),
+    (EVE, 5_000_000 * UNIT, 2),
+];
+
+fn award_simulated_points(round: u32) {
+    for (collator, _, points) in SIMULATED_COLLATORS {
+        pallet_parachain_staking::AwardedPts::<Test>::insert(round, collator, points);
+    }
+    pallet_parachain_staking::Points::<Test>::insert(
+        round,
+        SIMULATED_COLLATORS
+            .iter()
+            .map(|(_, _, points)| points)
+            .sum::<u32>(),
+    );
+}
+
+/// Staking rewards the pot earns if the round pays `round_reward` to the simulated collators and their delegators
+fn simulated_pot_reward(round_reward: Balance) -> Balance {
+    let total_points: u32 = SIMULATED_COLLATORS
+        .iter()
+        .map(|(_, _, points)| points)
+        .sum();
+    let delegator_share = sp_runtime::Perbill::one() - ParachainStaking::collator_commission();
+    SIMULATED_COLLATORS
+        .iter()
+        .map(|(collator, _, points)| {
+            let collator_reward =
+                sp_runtime::Perbill::from_rational(*points, total_points) * round_reward;
+            let total_counted = ParachainStaking::candidate_info(collator)
+                .unwrap()
+                .total_counted;
+            sp_runtime::Perbill::from_rational(Lottery::staked_collators(collator), total_counted)
+                * (delegator_share * collator_reward)
+        })
+        .sum()
+}
+
+/// Runs `rounds` rounds in which a new user deposits into the lottery using `strategy`
+/// and returns the staking rewards the pot earned over all rounds
+fn simulate_pot_yield(strategy: crate::StakingStrategyKind, rounds: u32) -> Balance {
+    let depositors = [DELEGATOR1, DELEGATOR2, DELEGATOR3, DELEGATOR4];
+    let deposit = 1_000_000 * UNIT;
+    let round_reward = 100_000 * UNIT;
+    assert!(rounds as usize <= depositors.len());
+    let mut balances: Vec<_> = SIMULATED_COLLATORS
+        .iter()
+        .map(|(collator, _, _)| (*collator, HIGH_BALANCE))
+        .collect();
+    balances.extend(
+        depositors
+            .iter()
+            .map(|depositor| (*depositor, HIGH_BALANCE)),
+    );
+    ExtBuilder::default()
+        .with_balances(balances)
+        .with_candidates(
+            SIMULATED_COLLATORS
+                .iter()
+                .map(|(collator, bond, _)| (*collator, *bond))
+                .collect(),
+        )
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::set_staking_strategy(
+                RawOrigin::Root.into(),
+                strategy
+            ));
+            award_simulated_points(1);
+            let mut pot_yield = 0;
+            for (round, depositor) in (2..2 + rounds).zip(depositors) {
+                roll_to_round_begin(round);
+                // points of the round are recorded to the lottery's history when it ends
+                award_simulated_points(round);
+                assert_ok!(Lottery::deposit(Origin::signed(depositor), deposit));
+                pot_yield += simulated_pot_reward(round_reward);
+            }
+            pot_yield
+        })
+}
+
+#[test]
+fn staking_strategy_is_selected_by_manager() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(
+            crate::StakingStrategyKind::Balanced,
+            Lottery::staking_strategy()
+        );
+        assert_ok!(Lottery::set_staking_strategy(
+            RawOrigin::Root.into(),
+            crate::StakingStrategyKind::RewardHistory
+        ));
+        assert_eq!(
+            crate::StakingStrategyKind::RewardHistory,
+            Lottery::staking_strategy()
+        );
+    });
+}
+
+#[test]
+fn reward_history_strategy_stakes_to_collators_with_highest_reward_rate() {
+    let mut balances: Vec<_> = SIMULATED_COLLATORS
+        .iter()
+        .map(|(collator, _, _)| (*collator, HIGH_BALANCE))
+        .collect();
+    balances.push((DELEGATOR1, HIGH_BALANCE));
+    ExtBuilder::default()
+        .with_balances(balances)
+        .with_candidates(
+            SIMULATED_COLLATORS
+                .iter()
+                .map(|(collator, bond, _)| (*collator, *bond))
+                .collect(),
+        )
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::set_staking_strategy(
+                RawOrigin::Root.into(),
+                crate::StakingStrategyKind::RewardHistory
+            ));
+            roll_to_round_begin(2);
+            award_simulated_points(1);
+            let balance = 1_000_000 * UNIT;
+            assert_ok!(Lottery::deposit(Origin::signed(DELEGATOR1), balance));
+            // DAVE and EVE have less stake but earn a fraction of the rewards per token
+            assert_eq!(0, Lottery::staked_collators(DAVE));
+            assert_eq!(0, Lottery::staked_collators(EVE));
+            assert_eq!(
+                balance,
+                Lottery::staked_collators(ALICE)
+                    + Lottery::staked_collators(BOB)
+                    + Lottery::staked_collators(CHARLIE)
+            );
+            // deposits are split to not dilute a single collator
+            assert!(Lottery::staked_collators(ALICE) > 0);
+            assert!(Lottery::staked_collators(BOB) > 0);
+            assert!(Lottery::staked_collators(CHARLIE) > 0);
+
+            // withdrawals give up the lowest yielding delegations first
+            pallet_parachain_staking::AwardedPts::<Test>::insert(1, BOB, 10);
+            pallet_parachain_staking::Points::<Test>::insert(1, 94);
+            let bob_stake = Lottery::staked_collators(BOB);
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(DELEGATOR1),
+                bob_stake
+            ));
+            assert_eq!(1, Lottery::withdrawal_request_queue().len());
+            let unstaking: Vec<_> = crate::UnstakingCollators::<Test>::get()
+                .into_iter()
+                .map(|collator| collator.account)
+                .collect();
+            assert_eq!(vec![BOB], unstaking);
+        });
+}
+
+#[test]
+fn reward_history_strategy_out_yields_balanced_strategy() {
+    let balanced = simulate_pot_yield(crate::StakingStrategyKind::Balanced, 4);
+    let reward_history = simulate_pot_yield(crate::StakingStrategyKind::RewardHistory, 4);
+    assert!(balanced > 0);
+    assert!(
+        reward_history > balanced,
+        "reward history yield {reward_history} not above balanced yield {balanced}"
+    );
+}
+
+#[frame_support::storage_alias]
+type BlocksPerCollatorThisSession =
+    StorageMap<CollatorSelection, frame_support::Blake2_128Concat, AccountId, u32>;
+
+fn enable_collator_eviction(baseline: u8, tolerance: u8) {
+    assert_ok!(CollatorSelection::set_eviction_baseline(
+        RawOrigin::Root.into(),
+        sp_runtime::Percent::from_percent(baseline)
+    ));
+    assert_ok!(CollatorSelection::set_eviction_tolerance(
+        RawOrigin::Root.into(),
+        sp_runtime::Percent::from_percent(tolerance)
+    ));
+}
+
+#[test]
+fn deposit_skips_collators_about_to_be_evicted() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance), (CHARLIE, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            enable_collator_eviction(100, 10);
+            BlocksPerCollatorThisSession::insert(BOB, 10);
+            BlocksPerCollatorThisSession::insert(CHARLIE, 1);
+            assert_eq!(vec![CHARLIE], Lottery::underperforming_collators());
+
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_eq!(balance, Lottery::staked_collators(BOB));
+            assert_eq!(0, Lottery::staked_collators(CHARLIE));
+        });
+}
+
+#[test]
+fn collators_trending_below_eviction_threshold_are_underperforming() {
+    ExtBuilder::default()
+        .with_balances(vec![(BOB, HIGH_BALANCE), (CHARLIE, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, HIGH_BALANCE), (CHARLIE, HIGH_BALANCE)])
+        .build()
+        .execute_with(|| {
+            for round in 1..4 {
+                pallet_parachain_staking::AwardedPts::<Test>::insert(round, BOB, 20);
+                pallet_parachain_staking::AwardedPts::<Test>::insert(round, CHARLIE, 2);
+                pallet_parachain_staking::Points::<Test>::insert(round, 22);
+                roll_to_round_begin(round + 1);
+                // history is recorded when the round ends
+                assert_eq!(
+                    Some(20),
+                    crate::CollatorPointsHistory::<Test>::get(round, BOB)
+                );
+                assert_eq!(
+                    Some(2),
+                    crate::CollatorPointsHistory::<Test>::get(round, CHARLIE)
+                );
+                assert_eq!(Some(22), crate::RoundPointsHistory::<Test>::get(round));
+            }
+            // CHARLIE produced blocks last round and eviction is disabled
+            assert!(Lottery::underperforming_collators().is_empty());
+
+            enable_collator_eviction(100, 50);
+            assert_eq!(vec![CHARLIE], Lottery::underperforming_collators());
+
+            // history of rounds older than REWARD_HISTORY_ROUNDS is removed
+            roll_to_round_begin(2 + crate::REWARD_HISTORY_ROUNDS);
+            assert_eq!(None, crate::CollatorPointsHistory::<Test>::get(1, BOB));
+            assert_eq!(None, crate::RoundPointsHistory::<Test>::get(1));
+            assert_eq!(Some(20), crate::CollatorPointsHistory::<Test>::get(2, BOB));
+        });
+}
+
+#[test]
+fn rebalance_stake_unstakes_from_underperforming_collators() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Lottery::rebalance_stake(Origin::signed(ALICE)),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_eq!(balance, Lottery::staked_collators(BOB));
+
+            // BOB performed well, nothing to rebalance
+            reward_collators_for_round(1, &[BOB]);
+            roll_to_round_begin(2);
+            assert!(Lottery::collators_to_rebalance().is_empty());
+            let events_before = System::events().len();
+            assert_ok!(Lottery::rebalance_stake(RawOrigin::Root.into()));
+            assert_eq!(events_before, System::events().len());
+            assert!(crate::UnstakingCollators::<Test>::get().is_empty());
+
+            // BOB stopped producing blocks
+            roll_to_round_begin(3);
+            assert_eq!(vec![BOB], Lottery::collators_to_rebalance());
+            assert_ok!(Lottery::rebalance_stake(RawOrigin::Root.into()));
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::StartedRebalance { amount: balance }
+            ));
+            let unstaking: Vec<_> = crate::UnstakingCollators::<Test>::get()
+                .into_iter()
+                .map(|collator| collator.account)
+                .collect();
+            assert_eq!(vec![BOB], unstaking);
+            assert_eq!(balance, Lottery::surplus_unstaking_balance());
+
+            // collators already unstaking are not rebalanced twice
+            assert!(Lottery::collators_to_rebalance().is_empty());
+        });
+}
+
+#[test]
+fn instant_withdraw_pays_from_liquidity_buffer_for_a_fee() {
+    let balance = 50_000_000 * UNIT;
+    let buffer = 10_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::set_liquidity_buffer_target(
+                RawOrigin::Root.into(),
+                buffer
+            ));
+            assert_ok!(Lottery::set_instant_withdraw_fee(
+                RawOrigin::Root.into(),
+                sp_runtime::Perbill::from_percent(10)
+            ));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_eq!(buffer, Lottery::liquidity_buffer());
+            assert_eq!(balance - buffer, Lottery::staked_collators(BOB));
+            assert_eq!(balance, Lottery::total_pot());
+
+            let prize_pool_before = Lottery::current_prize_pool();
+            let alice_balance_before = Balances::free_balance(ALICE);
+            assert_noop!(
+                Lottery::instant_withdraw(Origin::signed(ALICE), buffer + 1),
+                Error::<Test>::WithdrawAboveLiquidityBuffer
+            );
+            assert_ok!(Lottery::instant_withdraw(Origin::signed(ALICE), buffer));
+            let fee = buffer / 10;
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::InstantWithdrawn {
+                    account: ALICE,
+                    amount: buffer,
+                    fee
+                }
+            ));
+            assert_eq!(
+                alice_balance_before + buffer - fee,
+                Balances::free_balance(ALICE)
+            );
+            assert_eq!(0, Lottery::liquidity_buffer());
+            assert_eq!(balance - buffer, Lottery::active_balance_per_user(ALICE));
+            assert_eq!(balance - buffer, Lottery::total_pot());
+            assert_eq!(balance - buffer, Lottery::sum_of_deposits());
+            assert_eq!(prize_pool_before + fee, Lottery::current_prize_pool());
+            assert!(Lottery::withdrawal_request_queue().is_empty());
+
+            // an empty buffer only serves withdrawal requests
+            assert_noop!(
+                Lottery::instant_withdraw(Origin::signed(ALICE), buffer),
+                Error::<Test>::WithdrawAboveLiquidityBuffer
+            );
+        });
+}
+
+#[test]
+fn liquidity_buffer_is_refilled_from_matured_unstakes() {
+    let balance = 50_000_000 * UNIT;
+    let buffer = 10_000_000 * UNIT;
+    let withdrawal = 20_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_eq!(0, Lottery::liquidity_buffer());
+            assert_eq!(balance, Lottery::staked_collators(BOB));
+
+            assert_ok!(Lottery::set_liquidity_buffer_target(
+                RawOrigin::Root.into(),
+                buffer
+            ));
+            assert_ok!(Lottery::request_withdraw(Origin::signed(ALICE), withdrawal));
+            assert_eq!(balance - withdrawal, Lottery::surplus_unstaking_balance());
+
+            roll_to_round_begin(3);
+            reward_collators_for_round(2, &[BOB]);
+            let prize_pool_before = Lottery::current_prize_pool();
+            assert_ok!(Lottery::process_matured_withdrawals(RawOrigin::Root.into()));
+            assert!(Lottery::withdrawal_request_queue().is_empty());
+            assert_eq!(buffer, Lottery::liquidity_buffer());
+            assert_eq!(
+                balance - withdrawal - buffer,
+                Lottery::staked_collators(BOB)
+            );
+            assert_eq!(0, Lottery::unlocked_unstaking_funds());
+            assert_eq!(0, Lottery::surplus_unstaking_balance());
+            // buffered deposits are not paid to winners
+            assert!(Lottery::current_prize_pool() <= prize_pool_before);
+
+            // funds above a lowered target are restaked
+            assert_ok!(Lottery::set_liquidity_buffer_target(
+                RawOrigin::Root.into(),
+                0
+            ));
+            assert_ok!(Lottery::process_matured_withdrawals(RawOrigin::Root.into()));
+            assert_eq!(0, Lottery::liquidity_buffer());
+            assert_eq!(balance - withdrawal, Lottery::staked_collators(BOB));
+        });
+}
+
+#[test]
+fn request_withdraw_uses_liquidity_buffer_for_unstaked_deposits() {
+    let balance = 50_000_000 * UNIT;
+    let buffer = 10_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, HIGH_BALANCE), (BOB, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::set_liquidity_buffer_target(
+                RawOrigin::Root.into(),
+                buffer
+            ));
+            let alice_starting_balance = Balances::free_balance(ALICE);
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_eq!(balance - buffer, Lottery::staked_collators(BOB));
+
+            assert_ok!(Lottery::request_withdraw(Origin::signed(ALICE), balance));
+            assert_eq!(0, Lottery::liquidity_buffer());
+            assert_eq!(buffer, Lottery::unlocked_unstaking_funds());
+
+            roll_to_round_begin(3);
+            assert_ok!(Lottery::process_matured_withdrawals(RawOrigin::Root.into()));
+            assert!(Lottery::withdrawal_request_queue().is_empty());
+            assert_eq!(alice_starting_balance, Balances::free_balance(ALICE));
+            assert_eq!(0, Lottery::sum_of_deposits());
+            assert_eq!(0, Lottery::unlocked_unstaking_funds());
+        });
+}
+
+#[test]
+fn sponsor_deposit_grows_prize_pool_without_winning() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Lottery::sponsor_deposit(Origin::signed(CHARLIE), 1),
+                Error::<Test>::DepositBelowMinAmount
+            );
+            assert_ok!(Lottery::sponsor_deposit(Origin::signed(CHARLIE), balance));
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::SponsorDeposited {
+                    account: CHARLIE,
+                    amount: balance
+                }
+            ));
+            assert_eq!(balance, Lottery::sponsor_balance(CHARLIE));
+            assert_eq!(balance, Lottery::total_sponsor_balance());
+            assert_eq!(balance, Lottery::staked_collators(BOB));
+            assert_eq!(balance, Lottery::sum_of_deposits());
+            assert_eq!(0, Lottery::total_pot());
+            assert_eq!(0, Lottery::total_users());
+
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_eq!(2 * balance, Lottery::staked_collators(BOB));
+            assert_eq!(balance, Lottery::total_pot());
+
+            // the sponsored stake earns rewards for the winner, the sponsor can not win
+            let prize = 1_000 * UNIT;
+            assert_ok!(Balances::deposit_into_existing(
+                &crate::Pallet::<Test>::account_id(),
+                prize
+            ));
+            roll_to(10);
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert!(Lottery::unclaimed_winnings_by_account(CHARLIE).is_none());
+            assert!(Lottery::unclaimed_winnings_by_account(ALICE).is_some());
+            assert_eq!(balance, Lottery::sponsor_balance(CHARLIE));
+        });
+}
+
+#[test]
+fn sponsor_withdraw_is_paid_after_timelock() {
+    let balance = 50_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![(BOB, HIGH_BALANCE), (CHARLIE, HIGH_BALANCE)])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            let charlie_starting_balance = Balances::free_balance(CHARLIE);
+            assert_ok!(Lottery::sponsor_deposit(Origin::signed(CHARLIE), balance));
+            assert_noop!(
+                Lottery::sponsor_withdraw(Origin::signed(BOB), balance),
+                Error::<Test>::NoSponsorDepositForAccount
+            );
+            assert_noop!(
+                Lottery::sponsor_withdraw(Origin::signed(CHARLIE), balance + 1),
+                Error::<Test>::NoSponsorDepositForAccount
+            );
+            // sponsored funds are not an active deposit
+            assert_noop!(
+                Lottery::request_withdraw(Origin::signed(CHARLIE), balance),
+                Error::<Test>::NoDepositForAccount
+            );
+
+            assert_ok!(Lottery::sponsor_withdraw(Origin::signed(CHARLIE), balance));
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::SponsorScheduledWithdraw {
+                    account: CHARLIE,
+                    amount: balance
+                }
+            ));
+            assert_eq!(0, Lottery::sponsor_balance(CHARLIE));
+            assert_eq!(0, Lottery::total_sponsor_balance());
+            assert_eq!(1, Lottery::sponsor_withdrawal_request_queue().len());
+            assert!(Lottery::withdrawal_request_queue().is_empty());
+            assert_eq!(balance, Lottery::sum_of_deposits());
+
+            roll_to_round_begin(3);
+            assert_ok!(Lottery::process_matured_withdrawals(RawOrigin::Root.into()));
+            assert_last_event!(crate::mock::RuntimeEvent::Lottery(
+                crate::Event::SponsorWithdrawn {
+                    account: CHARLIE,
+                    amount: balance
+                }
+            ));
+            assert!(Lottery::sponsor_withdrawal_request_queue().is_empty());
+            assert_eq!(0, Lottery::sum_of_deposits());
+            assert_eq!(0, Lottery::unlocked_unstaking_funds());
+            assert_eq!(charlie_starting_balance, Balances::free_balance(CHARLIE));
+        });
+}
+
+#[test]
+fn rebalance_keeps_funds_for_pending_sponsor_withdrawals() {
+    let balance = 500_000_000 * UNIT;
+    let half_balance = 250_000_000 * UNIT;
+    let quarter_balance = 125_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(balance)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Lottery::sponsor_deposit(
+                Origin::signed(CHARLIE),
+                quarter_balance
+            ));
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_eq!(balance + quarter_balance, Lottery::staked_collators(BOB));
+            // unstakes all of BOB, what ALICE doesn't need is surplus
+            assert_ok!(Lottery::request_withdraw(
+                Origin::signed(ALICE),
+                half_balance
+            ));
+            assert_eq!(
+                half_balance + quarter_balance,
+                Lottery::surplus_unstaking_balance()
+            );
+
+            pallet_parachain_staking::AwardedPts::<Test>::insert(2, BOB, 20);
+            roll_to_round_begin(3);
+            // served from the surplus, but it matures only after this round's drawing
+            assert_ok!(Lottery::sponsor_withdraw(
+                Origin::signed(CHARLIE),
+                quarter_balance
+            ));
+            assert_eq!(half_balance, Lottery::surplus_unstaking_balance());
+
+            assert_ok!(Lottery::draw_lottery(RawOrigin::Root.into()));
+            assert!(Lottery::withdrawal_request_queue().is_empty());
+            assert_eq!(1, Lottery::sponsor_withdrawal_request_queue().len());
+            // only the surplus is restaked, the sponsor's funds stay unlocked
+            assert_eq!(0, Lottery::surplus_unstaking_balance());
+            assert_eq!(quarter_balance, Lottery::unlocked_unstaking_funds());
+            assert_eq!(half_balance, Lottery::staked_collators(BOB));
+
+            roll_to_round_begin(5);
+            assert_ok!(Lottery::process_matured_withdrawals(RawOrigin::Root.into()));
+            assert!(Lottery::sponsor_withdrawal_request_queue().is_empty());
+            assert_eq!(0, Lottery::unlocked_unstaking_funds());
+        });
+}
+
+#[test]
+fn lottery_instances_keep_separate_pots_and_stake() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_lottery_account(HIGH_BALANCE)
+        .with_funded_jackpot_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            assert_ne!(
+                crate::Pallet::<Test>::account_id(),
+                crate::Pallet::<Test, Instance1>::account_id()
+            );
+            // each instance has its own minimums
+            assert_noop!(
+                JackpotLottery::deposit(Origin::signed(ALICE), 10_000 * UNIT),
+                Error::<Test, Instance1>::DepositBelowMinAmount
+            );
+            assert_ok!(Lottery::deposit(Origin::signed(ALICE), balance));
+            assert_ok!(JackpotLottery::deposit(
+                Origin::signed(CHARLIE),
+                2 * balance
+            ));
+            assert_eq!(Lottery::total_pot(), balance);
+            assert_eq!(JackpotLottery::total_pot(), 2 * balance);
+            assert_eq!(Lottery::active_balance_per_user(CHARLIE), 0);
+            assert_eq!(JackpotLottery::active_balance_per_user(ALICE), 0);
+
+            // both pots delegate to the same collator from their own account
+            assert_eq!(Lottery::staked_collators(BOB), balance);
+            assert_eq!(JackpotLottery::staked_collators(BOB), 2 * balance);
+            let jackpot_delegator = crate::Pallet::<Test, Instance1>::account_id();
+            assert_eq!(
+                ParachainStaking::delegator_state(crate::Pallet::<Test>::account_id())
+                    .unwrap()
+                    .total,
+                balance
+            );
+            assert_eq!(
+                ParachainStaking::delegator_state(jackpot_delegator)
+                    .unwrap()
+                    .total,
+                2 * balance
+            );
+
+            // drawings are scheduled independently
+            assert_ok!(Lottery::start_lottery(RawOrigin::Root.into()));
+            assert_ok!(JackpotLottery::start_lottery(RawOrigin::Root.into()));
+            let drawing = Lottery::next_drawing_at().unwrap();
+            assert!(JackpotLottery::next_drawing_at().unwrap() > drawing);
+            roll_to(drawing);
+            assert_eq!(Lottery::drawing_count(), 1);
+            assert_eq!(JackpotLottery::drawing_count(), 0);
+
+            // unstaking from one pot leaves the other pot's delegation untouched
+            assert_ok!(Lottery::request_withdraw(Origin::signed(ALICE), balance));
+            assert_eq!(crate::UnstakingCollators::<Test>::get().len(), 1);
+            assert!(crate::UnstakingCollators::<Test, Instance1>::get().is_empty());
+            assert_eq!(JackpotLottery::staked_collators(BOB), 2 * balance);
+            let jackpot_state = ParachainStaking::delegator_state(jackpot_delegator).unwrap();
+            assert_eq!(jackpot_state.less_total, 0);
+        });
+}
+
+#[test]
+fn participants_are_committed_over_the_blocks_of_the_freezeout() {
+    let balance = 500_000_000 * UNIT;
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ALICE, HIGH_BALANCE),
+            (BOB, HIGH_BALANCE),
+            (CHARLIE, HIGH_BALANCE),
+            (DAVE, HIGH_BALANCE),
+        ])
+        .with_candidates(vec![(BOB, balance)])
+        .with_funded_jackpot_account(HIGH_BALANCE)
+        .build()
+        .execute_with(|| {
+            // the jackpot lottery of the mock commits two participant slots per block
+            for account in [ALICE, CHARLIE, DAVE] {
+                assert_ok!(JackpotLottery::deposit(Origin::signed(account), balance));
+            }
+            assert_ok!(JackpotLottery::start_lottery(RawOrigin::Root.into()));
+            let drawing = JackpotLottery::next_drawing_at().unwrap();
+            roll_to(drawing - <Test as Config<Instance1>>::DrawingFreezeout::get());
+            assert!(JackpotLottery::drawing_commitment().is_none());
+            let pending = JackpotLottery::pending_commitment().unwrap();
+            assert_eq!(pending.next_slot, 3);
+            assert_eq!(pending.participants, 2);
+
+            let leaves: Vec<_> = JackpotLottery::participant_leaves()
+                .iter()
+                .map(audit::leaf_hash::<Test, Instance1>)
+                .collect();
+            let root = audit::node_hash::<Test, Instance1>(
+                &audit::node_hash::<Test, Instance1>(&leaves[0], &leaves[1]),
+                &leaves[2],
+            );
+            // a drawing before the commitment is complete commits the remaining slots itself
+            assert_ok!(JackpotLottery::draw_lottery(RawOrigin::Root.into()));
+            assert!(JackpotLottery::pending_commitment().is_none());
+            let record = JackpotLottery::drawing_record(0).unwrap();
+            assert_ne!(record.block, drawing);
+            assert_eq!(record.participants_root, Some(root));
+        });
+}
+
+#[test]
+fn migration_indexes_every_deposit_and_restarts_the_ticket_interval() {
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+    ExtBuilder::default().build().execute_with(|| {
+        roll_to(10);
+        StorageVersion::new(1).put::<Lottery>();
+        for (account, balance) in [(ALICE, 100), (CHARLIE, 200), (DAVE, 300)] {
+            crate::ActiveBalancePerUser::<Test>::insert(account, balance);
+        }
+        crate::migrations::BuildCumulativeBalanceIndex::<Test>::on_runtime_upgrade();
+
+        assert_eq!(Lottery::on_chain_storage_version(), 2);
+        assert_eq!(crate::TicketIntervalStart::<Test>::get(), 10);
+        for account in [ALICE, CHARLIE, DAVE] {
+            assert!(crate::ParticipantSlot::<Test>::contains_key(account));
+            assert_eq!(
+                Lottery::tickets_of(&account),
+                Lottery::active_balance_per_user(account)
+            );
+        }
+        assert_eq!(
+            Lottery::index_prefix_sum(crate::ParticipantSlots::<Test>::get()),
+            600
+        );
+    });
+}
@@ -43,3 +43,20 @@ impl OnNewRound for () {
         Weight::zero()
     }
 }
+
+/// Notifies every element of a tuple, e.g. multiple instances of a pallet, in order
+macro_rules! impl_on_new_round_for_tuple {
+    ($($handler:ident),+) => {
+        impl<$($handler: OnNewRound),+> OnNewRound for ($($handler,)+) {
+            fn on_new_round(round_index: crate::RoundIndex) -> Weight {
+                let mut weight = Weight::zero();
+                $(weight = weight.saturating_add($handler::on_new_round(round_index));)+
+                weight
+            }
+        }
+    };
+}
+impl_on_new_round_for_tuple!(A);
+impl_on_new_round_for_tuple!(A, B);
+impl_on_new_round_for_tuple!(A, B, C);
+impl_on_new_round_for_tuple!(A, B, C, D);
@@ -24,7 +24,9 @@
 use super::*;
 use frame_support::{
     construct_runtime, derive_impl, ord_parameter_types, parameter_types,
-    traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, IsInVec},
+    traits::{
+        fungibles::Destroy, AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, IsInVec, Nothing,
+    },
     PalletId,
 };
 use frame_system::{EnsureNever, EnsureRoot};
@@ -203,6 +205,28 @@ impl AssetRegistry for MantaAssetRegistry {
             metadata.is_frozen,
         )
     }
+
+    fn start_destroy_asset(asset_id: &StandardAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::start_destroy(*asset_id, None)
+    }
+
+    fn destroy_asset_accounts(
+        asset_id: &StandardAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_accounts(*asset_id, max_items)
+    }
+
+    fn destroy_asset_approvals(
+        asset_id: &StandardAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_approvals(*asset_id, max_items)
+    }
+
+    fn finish_destroy_asset(asset_id: &StandardAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::finish_destroy(*asset_id)
+    }
 }
 
 parameter_types! {
@@ -257,7 +281,10 @@ impl pallet_asset_manager::Config for Runtime {
     type PermissionlessStartId = ConstU128<100>;
     type TokenNameMaxLen = ConstU32<100>;
     type TokenSymbolMaxLen = ConstU32<100>;
+    type TokenLogoMaxLen = ConstU32<100>;
     type PermissionlessAssetRegistryCost = ConstU128<1000>;
+    type AssetInUse = Nothing;
+    type PairReserves = ();
 }
 
 ord_parameter_types! {
@@ -20,13 +20,16 @@ use alloc::vec::Vec;
 use codec::{Decode, Encode};
 use core::{borrow::Borrow, marker::PhantomData};
 use frame_support::{
-    pallet_prelude::Get,
-    traits::tokens::{
-        currency::Currency,
-        fungible,
-        fungibles::{self, Mutate},
-        DepositConsequence, ExistenceRequirement, Fortitude, Precision, Preservation, Provenance,
-        WithdrawReasons,
+    pallet_prelude::{Get, Weight},
+    traits::{
+        tokens::{
+            currency::Currency,
+            fungible,
+            fungibles::{self, Mutate},
+            DepositConsequence, ExistenceRequirement, Fortitude, Precision, Preservation,
+            Provenance, WithdrawReasons,
+        },
+        IsInVec, Nothing,
     },
 };
 use frame_system::Config;
@@ -111,6 +114,25 @@ pub trait AssetRegistry: AssetIdType + BalanceType {
         asset_id: &Self::AssetId,
         metadata: Self::Metadata,
     ) -> Result<(), Self::Error>;
+
+    /// Freezes the asset with `asset_id` and starts its destruction.
+    fn start_destroy_asset(asset_id: &Self::AssetId) -> Result<(), Self::Error>;
+
+    /// Destroys up to `max_items` accounts of the asset with `asset_id` which is being destroyed,
+    /// returning the number of accounts destroyed.
+    fn destroy_asset_accounts(asset_id: &Self::AssetId, max_items: u32)
+        -> Result<u32, Self::Error>;
+
+    /// Destroys up to `max_items` approvals of the asset with `asset_id` which is being destroyed,
+    /// returning the number of approvals destroyed.
+    fn destroy_asset_approvals(
+        asset_id: &Self::AssetId,
+        max_items: u32,
+    ) -> Result<u32, Self::Error>;
+
+    /// Completes the destruction of the asset with `asset_id` once it has no accounts and
+    /// approvals left.
+    fn finish_destroy_asset(asset_id: &Self::AssetId) -> Result<(), Self::Error>;
 }
 
 /// Asset Configuration
@@ -291,6 +313,43 @@ pub trait AssetIdLpMap: AssetIdType {
     fn lp_asset_pool(pool_id: &Self::AssetId) -> Option<Self::AssetId>;
 }
 
+/// Assets which are in use outside of the asset manager and can not be deregistered.
+pub trait AssetInUse<AssetId> {
+    /// Returns `true` if `asset_id` is in use.
+    fn contains(asset_id: &AssetId) -> bool;
+
+    /// Returns the upper bound of the weight of [`contains`](Self::contains).
+    fn contains_weight() -> Weight;
+}
+
+impl<AssetId> AssetInUse<AssetId> for Nothing {
+    #[inline]
+    fn contains(_: &AssetId) -> bool {
+        false
+    }
+
+    #[inline]
+    fn contains_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+impl<AssetId, T> AssetInUse<AssetId> for IsInVec<T>
+where
+    AssetId: PartialEq,
+    T: Get<Vec<AssetId>>,
+{
+    #[inline]
+    fn contains(asset_id: &AssetId) -> bool {
+        T::get().contains(asset_id)
+    }
+
+    #[inline]
+    fn contains_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
 /// Defines the units per second charged given an `AssetId`.
 pub trait UnitsPerSecond: AssetIdType {
     /// Returns the units per second for `asset_id`.
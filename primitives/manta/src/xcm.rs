@@ -21,8 +21,11 @@ use super::{
     constants::WEIGHT_PER_SECOND,
 };
 
-use sp_runtime::traits::{CheckedConversion, Convert, Zero};
-use sp_std::marker::PhantomData;
+use sp_runtime::{
+    traits::{CheckedConversion, Convert, Zero},
+    DispatchError,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 use crate::assets::{AssetIdLocationMap, UnitsPerSecond};
 use frame_support::{
@@ -38,9 +41,9 @@ use frame_system::Config;
 use xcm::{
     latest::{
         prelude::{BuyExecution, Concrete, DescendOrigin, WithdrawAsset},
-        Error as XcmError,
+        Error as XcmError, ExecuteXcm, MultiAssets, Outcome, PreparedMessage,
         WeightLimit::{Limited, Unlimited},
-        XcmContext,
+        Xcm, XcmContext, XcmHash,
     },
     v3::{
         AssetId as XcmAssetId, Fungibility,
@@ -52,8 +55,8 @@ use xcm::{
 use xcm_builder::TakeRevenue;
 use xcm_executor::{
     traits::{
-        ConvertLocation, MatchesFungible, MatchesFungibles, Properties, ShouldExecute,
-        TransactAsset, WeightTrader,
+        ConvertLocation, Error as MatchError, MatchesFungible, MatchesFungibles, Properties,
+        ShouldExecute, TransactAsset, WeightTrader,
     },
     Assets,
 };
@@ -349,13 +352,35 @@ where
     }
 }
 
+/// Outflow Limiter
+pub trait OutflowLimiter<AssetId, Balance> {
+    /// Records `amount` of `asset_id` leaving this chain for the parachain `destination`, or for
+    /// any chain if `destination` is `None`, failing if it exceeds the cap on that outflow.
+    fn try_record_outflow(
+        asset_id: AssetId,
+        destination: Option<u32>,
+        amount: Balance,
+    ) -> Result<(), DispatchError>;
+}
+
+impl<AssetId, Balance> OutflowLimiter<AssetId, Balance> for () {
+    #[inline]
+    fn try_record_outflow(
+        _asset_id: AssetId,
+        _destination: Option<u32>,
+        _amount: Balance,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+}
+
 ///
-pub struct MultiAssetAdapter<T, A, AccountIdConverter, Native, NonNative>(
-    PhantomData<(T, A, AccountIdConverter, Native, NonNative)>,
+pub struct MultiAssetAdapter<T, A, AccountIdConverter, Native, NonNative, Limiter>(
+    PhantomData<(T, A, AccountIdConverter, Native, NonNative, Limiter)>,
 );
 
-impl<T, A, AccountIdConverter, Native, NonNative>
-    MultiAssetAdapter<T, A, AccountIdConverter, Native, NonNative>
+impl<T, A, AccountIdConverter, Native, NonNative, Limiter>
+    MultiAssetAdapter<T, A, AccountIdConverter, Native, NonNative, Limiter>
 where
     T: Config,
     A: AssetConfig<T>,
@@ -375,23 +400,35 @@ where
         let receiver = AccountIdConverter::convert_location(location).ok_or({
             XcmError::FailedToTransactAsset("Failed Location to AccountId Conversion")
         })?;
-        let (asset_id, amount) = match (
-            Native::matches_fungible(asset),
-            NonNative::matches_fungibles(asset),
-        ) {
+        let (asset_id, amount) = Self::matches_fungibles(asset)
+            .map_err(|_| XcmError::FailedToTransactAsset("Unknown Asset"))?;
+        Ok((asset_id, receiver, amount))
+    }
+}
+
+impl<T, A, AccountIdConverter, Native, NonNative, Limiter> MatchesFungibles<A::AssetId, A::Balance>
+    for MultiAssetAdapter<T, A, AccountIdConverter, Native, NonNative, Limiter>
+where
+    T: Config,
+    A: AssetConfig<T>,
+    Native: MatchesFungible<A::Balance>,
+    NonNative: MatchesFungibles<A::AssetId, A::Balance>,
+{
+    /// Matches `asset` to an `asset_id` and `amount` on this chain, with the matcher
+    /// implementation of both native and non-native assets.
+    #[inline]
+    fn matches_fungibles(asset: &MultiAsset) -> Result<(A::AssetId, A::Balance), MatchError> {
+        match Native::matches_fungible(asset) {
             // native asset
-            (Some(amount), _) => (A::NativeAssetId::get(), amount),
+            Some(amount) => Ok((A::NativeAssetId::get(), amount)),
             // assets asset
-            (_, Ok((asset_id, amount))) => (asset_id, amount),
-            // unknown asset
-            _ => return Err(XcmError::FailedToTransactAsset("Unknown Asset")),
-        };
-        Ok((asset_id, receiver, amount))
+            None => NonNative::matches_fungibles(asset),
+        }
     }
 }
 
-impl<T, A, AccountIdConverter, Native, NonNative> TransactAsset
-    for MultiAssetAdapter<T, A, AccountIdConverter, Native, NonNative>
+impl<T, A, AccountIdConverter, Native, NonNative, Limiter> TransactAsset
+    for MultiAssetAdapter<T, A, AccountIdConverter, Native, NonNative, Limiter>
 where
     T: Config,
     A: AssetConfig<T>,
@@ -400,6 +437,7 @@ where
     AccountIdConverter: ConvertLocation<T::AccountId>,
     Native: MatchesFungible<A::Balance>,
     NonNative: MatchesFungibles<A::AssetId, A::Balance>,
+    Limiter: OutflowLimiter<A::AssetId, A::Balance>,
 {
     #[inline]
     fn deposit_asset(
@@ -431,6 +469,18 @@ where
             asset, location,
         );
         let (asset_id, who, amount) = Self::match_asset_and_location(asset, location)?;
+        // NOTE: Withdrawals from the sovereign accounts of other chains return assets to this
+        //       chain, only withdrawals from local accounts count towards the outflow cap.
+        if location.parents == 0 {
+            Limiter::try_record_outflow(asset_id.clone(), None, amount.clone()).map_err(|e| {
+                log::debug!(
+                    target: "xcm::multi_asset_adapter",
+                    "withdraw_asset outflow of asset: {:?} rejected: {:?}",
+                    asset, e,
+                );
+                XcmError::FailedToTransactAsset("Outflow Cap Exceeded")
+            })?;
+        }
         A::FungibleLedger::withdraw_burning(
             asset_id,
             &who,
@@ -442,6 +492,159 @@ where
     }
 }
 
+/// XCM Executor Checking the Outflow Caps per Destination
+///
+/// Wraps the executor `E` of local messages, e.g. of `orml_xtokens`, and records the assets each
+/// message sends to a sibling parachain in `Limiter` before executing it. Assets are counted in
+/// full against the chain they end up on, following the instructions which send them on from the
+/// chains they pass through, e.g. from their reserve, and matched to asset ids with `Matcher`.
+pub struct OutflowCappedXcmExecutor<T, A, E, Matcher, Limiter>(
+    PhantomData<(T, A, E, Matcher, Limiter)>,
+);
+
+/// Prepared Message of the [`OutflowCappedXcmExecutor`]
+pub struct OutflowCappedPrepared<P, AssetId, Balance> {
+    /// Message prepared by the inner executor
+    prepared: P,
+
+    /// Assets sent to each sibling parachain by the message
+    outflows: Vec<(AssetId, u32, Balance)>,
+}
+
+impl<P, AssetId, Balance> PreparedMessage for OutflowCappedPrepared<P, AssetId, Balance>
+where
+    P: PreparedMessage,
+{
+    #[inline]
+    fn weight_of(&self) -> Weight {
+        self.prepared.weight_of()
+    }
+}
+
+impl<T, A, E, Matcher, Limiter> OutflowCappedXcmExecutor<T, A, E, Matcher, Limiter>
+where
+    T: Config,
+    A: AssetConfig<T>,
+    Matcher: MatchesFungibles<A::AssetId, A::Balance>,
+{
+    /// Returns the id of the sibling parachain at `location`.
+    #[inline]
+    fn sibling_para_id(location: &MultiLocation) -> Option<u32> {
+        match location {
+            MultiLocation {
+                parents: 1,
+                interior: X1(Parachain(para_id)),
+            } => Some(*para_id),
+            _ => None,
+        }
+    }
+
+    /// Returns the location, relative to this chain, of the chain the assets sent to `dest`
+    /// end up on once `dest` executes `xcm`.
+    fn final_destination(dest: &MultiLocation, xcm: &Xcm<()>) -> MultiLocation {
+        xcm.0
+            .iter()
+            .find_map(|instruction| match instruction {
+                TransferReserveAsset {
+                    dest: next, xcm, ..
+                }
+                | DepositReserveAsset {
+                    dest: next, xcm, ..
+                }
+                | InitiateReserveWithdraw {
+                    reserve: next, xcm, ..
+                }
+                | InitiateTeleport {
+                    dest: next, xcm, ..
+                } => {
+                    let mut location = Self::final_destination(next, xcm);
+                    location.prepend_with(*dest).ok()?;
+                    Some(location)
+                }
+                _ => None,
+            })
+            .unwrap_or(*dest)
+    }
+
+    /// Returns the assets `message` sends to each sibling parachain.
+    fn outflows<Call>(message: &Xcm<Call>) -> Vec<(A::AssetId, u32, A::Balance)> {
+        let mut withdrawn: Vec<MultiAsset> = Vec::new();
+        let mut outflows = Vec::new();
+        let mut send = |assets: &[MultiAsset], dest: &MultiLocation| {
+            if let Some(para_id) = Self::sibling_para_id(dest) {
+                outflows.extend(assets.iter().filter_map(|asset| {
+                    let (asset_id, amount) = Matcher::matches_fungibles(asset).ok()?;
+                    Some((asset_id, para_id, amount))
+                }));
+            }
+        };
+        for instruction in message.0.iter() {
+            match instruction {
+                WithdrawAsset(assets) => withdrawn.extend(assets.inner().iter().cloned()),
+                TransferReserveAsset { assets, dest, xcm } => {
+                    send(assets.inner(), &Self::final_destination(dest, xcm))
+                }
+                DepositReserveAsset { dest, xcm, .. }
+                | InitiateReserveWithdraw {
+                    reserve: dest, xcm, ..
+                }
+                | InitiateTeleport { dest, xcm, .. } => send(
+                    &sp_std::mem::take(&mut withdrawn),
+                    &Self::final_destination(dest, xcm),
+                ),
+                _ => {}
+            }
+        }
+        outflows
+    }
+}
+
+impl<T, A, E, Matcher, Limiter, Call> ExecuteXcm<Call>
+    for OutflowCappedXcmExecutor<T, A, E, Matcher, Limiter>
+where
+    T: Config,
+    A: AssetConfig<T>,
+    E: ExecuteXcm<Call>,
+    Matcher: MatchesFungibles<A::AssetId, A::Balance>,
+    Limiter: OutflowLimiter<A::AssetId, A::Balance>,
+{
+    type Prepared = OutflowCappedPrepared<E::Prepared, A::AssetId, A::Balance>;
+
+    #[inline]
+    fn prepare(message: Xcm<Call>) -> Result<Self::Prepared, Xcm<Call>> {
+        let outflows = Self::outflows(&message);
+        Ok(OutflowCappedPrepared {
+            prepared: E::prepare(message)?,
+            outflows,
+        })
+    }
+
+    #[inline]
+    fn execute(
+        origin: impl Into<MultiLocation>,
+        pre: Self::Prepared,
+        id: &mut XcmHash,
+        weight_credit: Weight,
+    ) -> Outcome {
+        for (asset_id, para_id, amount) in pre.outflows {
+            if let Err(e) = Limiter::try_record_outflow(asset_id, Some(para_id), amount) {
+                log::debug!(
+                    target: "xcm::outflow_capped_executor",
+                    "outflow to parachain: {:?} rejected: {:?}",
+                    para_id, e,
+                );
+                return Outcome::Error(XcmError::FailedToTransactAsset("Outflow Cap Exceeded"));
+            }
+        }
+        E::execute(origin, pre.prepared, id, weight_credit)
+    }
+
+    #[inline]
+    fn charge_fees(location: impl Into<MultiLocation>, fees: MultiAssets) -> Result {
+        E::charge_fees(location, fees)
+    }
+}
+
 use xcm::latest::{
     Instruction::{self, *},
     Weight,
@@ -447,6 +447,10 @@ parameter_types! {
     pub DrawingFreezeout: BlockNumber = prod_or_fast!(1 * DAYS, 1 * MINUTES);
     /// Time in blocks until a collator is done unstaking
     pub UnstakeLockTime: BlockNumber = LeaveDelayRounds::get() * DefaultBlocksPerRound::get();
+    /// Number of past drawings kept on chain, a year of weekly drawings
+    pub const MaxDrawingHistory: u32 = 52;
+    /// Number of participant slots committed per block of a drawing freezeout
+    pub const MaxCommittedSlotsPerBlock: u32 = 200;
 }
 impl pallet_lottery::Config for Runtime {
     type RuntimeCall = RuntimeCall;
@@ -460,6 +464,8 @@ impl pallet_lottery::Config for Runtime {
     type DrawingInterval = DrawingInterval;
     type DrawingFreezeout = DrawingFreezeout;
     type UnstakeLockTime = UnstakeLockTime;
+    type MaxDrawingHistory = MaxDrawingHistory;
+    type MaxCommittedSlotsPerBlock = MaxCommittedSlotsPerBlock;
     type BalanceConversion = Balance;
     type WeightInfo = weights::pallet_lottery::SubstrateWeight<Runtime>;
 }
@@ -778,7 +784,7 @@ impl pallet_parachain_staking::Config for Runtime {
     /// Minimum stake required to be reserved to be a delegator
     type MinDelegatorStk = ConstU128<{ 5_000 * KMA }>;
     type OnCollatorPayout = ();
-    type OnNewRound = ();
+    type OnNewRound = Lottery;
     type WeightInfo = weights::pallet_parachain_staking::SubstrateWeight<Runtime>;
 }
 
@@ -954,6 +960,7 @@ parameter_types! {
     pub const FarmingKeeperPalletId: PalletId = PalletId(*b"mt/fmkpr");
     pub const FarmingRewardIssuerPalletId: PalletId = PalletId(*b"mt/fmrir");
     pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
+    pub const FarmingVoteEpochLength: BlockNumber = 7 * DAYS;
 }
 
 /// Zenlink protocol Asset adaptor for orml_traits::MultiCurrency.
@@ -971,6 +978,33 @@ impl pallet_farming::Config for Runtime {
     type Keeper = FarmingKeeperPalletId;
     type RewardIssuer = FarmingRewardIssuerPalletId;
     type WeightInfo = weights::pallet_farming::SubstrateWeight<Runtime>;
+    type Currency = Balances;
+    type PoolCreationDeposit = ConstU128<{ 200_000 * KMA }>;
+    type PermissionlessPoolTokens = IsLpAsset;
+    type VoteEpochLength = FarmingVoteEpochLength;
+    type MaxVotablePools = ConstU32<100>;
+    type VoteToken = assets_config::NativeAssetId;
+    type LiquidityProvider = zenlink::ZenlinkLiquidityProvider;
+}
+
+/// Permissionless farming pools can only take shares in zenlink LP assets.
+pub struct IsLpAsset;
+impl Contains<CalamariAssetId> for IsLpAsset {
+    fn contains(asset_id: &CalamariAssetId) -> bool {
+        AssetManager::lp_to_asset_id_pair(asset_id).is_some()
+    }
+}
+
+/// Assets of farming pools can not be deregistered from the asset manager.
+pub struct IsFarmingAsset;
+impl manta_primitives::assets::AssetInUse<CalamariAssetId> for IsFarmingAsset {
+    fn contains(asset_id: &CalamariAssetId) -> bool {
+        Farming::is_farming_currency(asset_id)
+    }
+
+    fn contains_weight() -> Weight {
+        Farming::is_farming_currency_weight()
+    }
 }
 
 parameter_types! {
@@ -1113,7 +1147,11 @@ pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, RuntimeCall, Si
 
 /// Types for runtime upgrading.
 /// Each type should implement trait `OnRuntimeUpgrade`.
-pub type OnRuntimeUpgradeHooks = ();
+pub type OnRuntimeUpgradeHooks = (
+    pallet_lottery::migrations::BuildCumulativeBalanceIndex<Runtime>,
+    pallet_farming::migrations::MigrateToV1<Runtime>,
+    pallet_asset_manager::migrations::BuildAssetIdToLpCount<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
     Runtime,
@@ -1310,16 +1348,49 @@ impl_runtime_apis! {
         }
     }
 
-    impl pallet_lottery::runtime::LotteryApi<Block> for Runtime {
+    // `Lottery` is the only lottery instance of this runtime, it is served as instance `0`
+    impl pallet_lottery::runtime::LotteryApi<Block, AccountId, BlockNumber, Hash> for Runtime {
         fn not_in_drawing_freezeout(
-        ) -> bool {
-            Lottery::not_in_drawing_freezeout()
+            instance: pallet_lottery::runtime::LotteryInstance,
+        ) -> Result<bool, pallet_lottery::runtime::UnknownInstance> {
+            match instance {
+                0 => Ok(Lottery::not_in_drawing_freezeout()),
+                _ => pallet_lottery::runtime::unknown_instance(instance),
+            }
+        }
+        fn current_prize_pool(instance: pallet_lottery::runtime::LotteryInstance) -> Result<u128, pallet_lottery::runtime::UnknownInstance> {
+            match instance {
+                0 => Ok(Lottery::current_prize_pool()),
+                _ => pallet_lottery::runtime::unknown_instance(instance),
+            }
         }
-        fn current_prize_pool() -> u128 {
-            Lottery::current_prize_pool()
+        fn next_drawing_at(instance: pallet_lottery::runtime::LotteryInstance) -> Result<Option<u128>, pallet_lottery::runtime::UnknownInstance> {
+            match instance {
+                0 => Ok(Lottery::next_drawing_at().map(|x| x as u128)),
+                _ => pallet_lottery::runtime::unknown_instance(instance),
+            }
         }
-        fn next_drawing_at() -> Option<u128> {
-            Lottery::next_drawing_at().map(|x| x as u128)
+        fn drawing_history(
+            instance: pallet_lottery::runtime::LotteryInstance,
+            from: u32,
+            count: u32,
+        ) -> Result<Vec<pallet_lottery::DrawingRecord<AccountId, BlockNumber, Balance, Hash>>, pallet_lottery::runtime::UnknownInstance> {
+            match instance {
+                0 => Ok(Lottery::drawing_history(from, count)),
+                _ => pallet_lottery::runtime::unknown_instance(instance),
+            }
+        }
+        fn user_position(instance: pallet_lottery::runtime::LotteryInstance, account: AccountId) -> Result<pallet_lottery::UserPosition<BlockNumber, Balance>, pallet_lottery::runtime::UnknownInstance> {
+            match instance {
+                0 => Ok(Lottery::user_position(account)),
+                _ => pallet_lottery::runtime::unknown_instance(instance),
+            }
+        }
+        fn participant_proof(instance: pallet_lottery::runtime::LotteryInstance, account: AccountId) -> Result<Option<pallet_lottery::ParticipantProof<AccountId, Balance, Hash>>, pallet_lottery::runtime::UnknownInstance> {
+            match instance {
+                0 => Ok(Lottery::participant_proof(account)),
+                _ => pallet_lottery::runtime::unknown_instance(instance),
+            }
         }
     }
 
@@ -1489,6 +1560,67 @@ impl_runtime_apis! {
         fn get_gauge_rewards(who: AccountId, pid: PoolId) -> Vec<(CalamariAssetId, Balance)> {
             Farming::get_gauge_rewards(&who, pid).unwrap_or_default()
         }
+
+        fn get_gauge_weights() -> Vec<(PoolId, u128)> {
+            Farming::get_gauge_weights()
+        }
+
+        fn user_positions(
+            who: AccountId,
+        ) -> Vec<pallet_farming_rpc_runtime_api::UserPosition<CalamariAssetId, BlockNumber, Balance>> {
+            Farming::user_positions(&who)
+        }
+
+        fn pool_overview(
+            pid: PoolId,
+        ) -> Option<pallet_farming_rpc_runtime_api::PoolOverview<CalamariAssetId, BlockNumber, Balance>> {
+            Farming::pool_overview(pid)
+        }
+
+        fn pool_overviews() -> Vec<pallet_farming_rpc_runtime_api::PoolOverview<CalamariAssetId, BlockNumber, Balance>> {
+            Farming::pool_overviews()
+        }
+
+        fn user_vestings(
+            who: AccountId,
+        ) -> Vec<pallet_farming_rpc_runtime_api::VestingPosition<CalamariAssetId, BlockNumber, Balance>> {
+            Farming::user_vestings(&who)
+        }
+    }
+
+    impl pallet_asset_manager_rpc_runtime_api::AssetManagerApi<Block, AccountId, CalamariAssetId> for Runtime {
+        fn list_assets(
+            start_after: Option<CalamariAssetId>,
+            limit: u32,
+        ) -> Vec<pallet_asset_manager_rpc_runtime_api::AssetInfo<AccountId, CalamariAssetId, Balance>> {
+            AssetManager::list_assets(start_after, limit)
+        }
+
+        fn asset_by_id(
+            asset_id: CalamariAssetId,
+        ) -> Option<pallet_asset_manager_rpc_runtime_api::AssetInfo<AccountId, CalamariAssetId, Balance>> {
+            AssetManager::asset_info(asset_id)
+        }
+
+        fn asset_by_location(
+            location: MultiLocation,
+        ) -> Option<pallet_asset_manager_rpc_runtime_api::AssetInfo<AccountId, CalamariAssetId, Balance>> {
+            AssetManager::asset_by_location(location)
+        }
+
+        fn lp_pair_of(asset_id: CalamariAssetId) -> Option<(CalamariAssetId, CalamariAssetId)> {
+            AssetManager::lp_pair_of(asset_id)
+        }
+
+        fn xcm_fee_info(
+            asset_id: CalamariAssetId,
+        ) -> Option<pallet_asset_manager_rpc_runtime_api::XcmFeeInfo> {
+            AssetManager::xcm_fee_info(asset_id)
+        }
+
+        fn remaining_outflow(asset_id: CalamariAssetId, destination: Option<u32>) -> Option<Balance> {
+            AssetManager::remaining_outflow(asset_id, destination)
+        }
     }
 
     #[cfg(feature = "try-runtime")]
@@ -54,6 +54,15 @@ pub trait WeightInfo {
 	fn update_outgoing_filtered_assets() -> Weight;
 	fn register_lp_asset() -> Weight;
 	fn permissionless_register_asset() -> Weight;
+	fn deregister_asset(n: u32, ) -> Weight;
+	fn set_units_per_second_oracle() -> Weight;
+	fn update_permissionless_asset_metadata() -> Weight;
+	fn transfer_asset_ownership() -> Weight;
+	fn renounce_asset_ownership() -> Weight;
+	fn burn_asset() -> Weight;
+	fn register_location_alias() -> Weight;
+	fn remove_location_alias() -> Weight;
+	fn set_outflow_cap() -> Weight;
 }
 
 /// Weights for pallet_asset_manager using the Substrate node and recommended hardware.
@@ -202,6 +211,161 @@ impl<T: frame_system::Config> pallet_asset_manager::WeightInfo for SubstrateWeig
 			.saturating_add(T::DbWeight::get().reads(5_u64))
 			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
+	/// Storage: `AssetManager::DestroyingAssets` (r:1 w:1)
+	/// Proof: `AssetManager::DestroyingAssets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::AssetIdMetadata` (r:1 w:1)
+	/// Proof: `AssetManager::AssetIdMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::LpToAssetIdPair` (r:1 w:0)
+	/// Proof: `AssetManager::LpToAssetIdPair` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::AssetIdLocation` (r:1 w:1)
+	/// Proof: `AssetManager::AssetIdLocation` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::AllowedDestParaIds` (r:1 w:1)
+	/// Proof: `AssetManager::AllowedDestParaIds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::FilteredOutgoingAssetLocations` (r:0 w:1)
+	/// Proof: `AssetManager::FilteredOutgoingAssetLocations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::UnitsPerSecond` (r:0 w:1)
+	/// Proof: `AssetManager::UnitsPerSecond` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::LocationAssetId` (r:0 w:1)
+	/// Proof: `AssetManager::LocationAssetId` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:1000 w:999)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:999 w:999)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Approvals` (r:1 w:0)
+	/// Proof: `Assets::Approvals` (`max_values`: None, `max_size`: Some(148), added: 2623, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Metadata` (r:1 w:1)
+	/// Proof: `Assets::Metadata` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 1000]`.
+	fn deregister_asset(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1102 + n * (137 ±0)`
+		//  Estimated: `5048 + n * (2621 ±0)`
+		// Minimum execution time: 64_217_000 picoseconds.
+		Weight::from_parts(65_893_000, 5048)
+			// Standard Error: 6_104
+			.saturating_add(Weight::from_parts(14_388_252, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2621).saturating_mul(n.into()))
+	}
+	/// Storage: `AssetManager::AssetIdLocation` (r:1 w:0)
+	/// Proof: `AssetManager::AssetIdLocation` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::UnitsPerSecondOracles` (r:0 w:1)
+	/// Proof: `AssetManager::UnitsPerSecondOracles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::AveragedUnitsPerSecond` (r:0 w:1)
+	/// Proof: `AssetManager::AveragedUnitsPerSecond` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_units_per_second_oracle() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1443`
+		//  Estimated: `4908`
+		// Minimum execution time: 19_871_000 picoseconds.
+		Weight::from_parts(23_904_000, 4908)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetManager::PermissionlessAssetOwner` (r:1 w:0)
+	/// Proof: `AssetManager::PermissionlessAssetOwner` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::AssetIdMetadata` (r:1 w:1)
+	/// Proof: `AssetManager::AssetIdMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:0)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Metadata` (r:1 w:1)
+	/// Proof: `Assets::Metadata` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `AssetManager::AssetLogo` (r:0 w:1)
+	/// Proof: `AssetManager::AssetLogo` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_permissionless_asset_metadata() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `4077`
+		// Minimum execution time: 31_218_000 picoseconds.
+		Weight::from_parts(32_044_000, 4077)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `AssetManager::PermissionlessAssetOwner` (r:1 w:1)
+	/// Proof: `AssetManager::PermissionlessAssetOwner` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn transfer_asset_ownership() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `277`
+		//  Estimated: `3742`
+		// Minimum execution time: 14_503_000 picoseconds.
+		Weight::from_parts(15_027_000, 3742)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AssetManager::PermissionlessAssetOwner` (r:1 w:1)
+	/// Proof: `AssetManager::PermissionlessAssetOwner` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn renounce_asset_ownership() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `277`
+		//  Estimated: `3742`
+		// Minimum execution time: 14_126_000 picoseconds.
+		Weight::from_parts(14_688_000, 3742)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AssetManager::PermissionlessAssetOwner` (r:1 w:0)
+	/// Proof: `AssetManager::PermissionlessAssetOwner` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:1 w:1)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	fn burn_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `598`
+		//  Estimated: `3687`
+		// Minimum execution time: 36_911_000 picoseconds.
+		Weight::from_parts(37_830_000, 3687)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetManager::AssetIdLocation` (r:1 w:0)
+	/// Proof: `AssetManager::AssetIdLocation` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::LocationAssetId` (r:1 w:1)
+	/// Proof: `AssetManager::LocationAssetId` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::LocationAliases` (r:0 w:1)
+	/// Proof: `AssetManager::LocationAliases` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn register_location_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1466`
+		//  Estimated: `4931`
+		// Minimum execution time: 21_604_000 picoseconds.
+		Weight::from_parts(22_391_000, 4931)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetManager::LocationAliases` (r:1 w:1)
+	/// Proof: `AssetManager::LocationAliases` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::LocationAssetId` (r:0 w:1)
+	/// Proof: `AssetManager::LocationAssetId` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn remove_location_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1322`
+		//  Estimated: `4787`
+		// Minimum execution time: 18_992_000 picoseconds.
+		Weight::from_parts(19_570_000, 4787)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetManager::AssetIdMetadata` (r:1 w:0)
+	/// Proof: `AssetManager::AssetIdMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::OutflowUsage` (r:0 w:1)
+	/// Proof: `AssetManager::OutflowUsage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::OutflowCaps` (r:0 w:1)
+	/// Proof: `AssetManager::OutflowCaps` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_outflow_cap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1231`
+		//  Estimated: `4696`
+		// Minimum execution time: 17_484_000 picoseconds.
+		Weight::from_parts(18_031_000, 4696)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -349,4 +513,159 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5_u64))
 			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
+	/// Storage: `AssetManager::DestroyingAssets` (r:1 w:1)
+	/// Proof: `AssetManager::DestroyingAssets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::AssetIdMetadata` (r:1 w:1)
+	/// Proof: `AssetManager::AssetIdMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::LpToAssetIdPair` (r:1 w:0)
+	/// Proof: `AssetManager::LpToAssetIdPair` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::AssetIdLocation` (r:1 w:1)
+	/// Proof: `AssetManager::AssetIdLocation` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::AllowedDestParaIds` (r:1 w:1)
+	/// Proof: `AssetManager::AllowedDestParaIds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::FilteredOutgoingAssetLocations` (r:0 w:1)
+	/// Proof: `AssetManager::FilteredOutgoingAssetLocations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::UnitsPerSecond` (r:0 w:1)
+	/// Proof: `AssetManager::UnitsPerSecond` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::LocationAssetId` (r:0 w:1)
+	/// Proof: `AssetManager::LocationAssetId` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:1000 w:999)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:999 w:999)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Approvals` (r:1 w:0)
+	/// Proof: `Assets::Approvals` (`max_values`: None, `max_size`: Some(148), added: 2623, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Metadata` (r:1 w:1)
+	/// Proof: `Assets::Metadata` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 1000]`.
+	fn deregister_asset(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1102 + n * (137 ±0)`
+		//  Estimated: `5048 + n * (2621 ±0)`
+		// Minimum execution time: 64_217_000 picoseconds.
+		Weight::from_parts(65_893_000, 5048)
+			// Standard Error: 6_104
+			.saturating_add(Weight::from_parts(14_388_252, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2621).saturating_mul(n.into()))
+	}
+	/// Storage: `AssetManager::AssetIdLocation` (r:1 w:0)
+	/// Proof: `AssetManager::AssetIdLocation` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::UnitsPerSecondOracles` (r:0 w:1)
+	/// Proof: `AssetManager::UnitsPerSecondOracles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::AveragedUnitsPerSecond` (r:0 w:1)
+	/// Proof: `AssetManager::AveragedUnitsPerSecond` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_units_per_second_oracle() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1443`
+		//  Estimated: `4908`
+		// Minimum execution time: 19_871_000 picoseconds.
+		Weight::from_parts(23_904_000, 4908)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetManager::PermissionlessAssetOwner` (r:1 w:0)
+	/// Proof: `AssetManager::PermissionlessAssetOwner` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::AssetIdMetadata` (r:1 w:1)
+	/// Proof: `AssetManager::AssetIdMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:0)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Metadata` (r:1 w:1)
+	/// Proof: `Assets::Metadata` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `AssetManager::AssetLogo` (r:0 w:1)
+	/// Proof: `AssetManager::AssetLogo` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_permissionless_asset_metadata() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `4077`
+		// Minimum execution time: 31_218_000 picoseconds.
+		Weight::from_parts(32_044_000, 4077)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `AssetManager::PermissionlessAssetOwner` (r:1 w:1)
+	/// Proof: `AssetManager::PermissionlessAssetOwner` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn transfer_asset_ownership() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `277`
+		//  Estimated: `3742`
+		// Minimum execution time: 14_503_000 picoseconds.
+		Weight::from_parts(15_027_000, 3742)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AssetManager::PermissionlessAssetOwner` (r:1 w:1)
+	/// Proof: `AssetManager::PermissionlessAssetOwner` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn renounce_asset_ownership() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `277`
+		//  Estimated: `3742`
+		// Minimum execution time: 14_126_000 picoseconds.
+		Weight::from_parts(14_688_000, 3742)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AssetManager::PermissionlessAssetOwner` (r:1 w:0)
+	/// Proof: `AssetManager::PermissionlessAssetOwner` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:1 w:1)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	fn burn_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `598`
+		//  Estimated: `3687`
+		// Minimum execution time: 36_911_000 picoseconds.
+		Weight::from_parts(37_830_000, 3687)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetManager::AssetIdLocation` (r:1 w:0)
+	/// Proof: `AssetManager::AssetIdLocation` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::LocationAssetId` (r:1 w:1)
+	/// Proof: `AssetManager::LocationAssetId` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::LocationAliases` (r:0 w:1)
+	/// Proof: `AssetManager::LocationAliases` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn register_location_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1466`
+		//  Estimated: `4931`
+		// Minimum execution time: 21_604_000 picoseconds.
+		Weight::from_parts(22_391_000, 4931)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetManager::LocationAliases` (r:1 w:1)
+	/// Proof: `AssetManager::LocationAliases` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::LocationAssetId` (r:0 w:1)
+	/// Proof: `AssetManager::LocationAssetId` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn remove_location_alias() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1322`
+		//  Estimated: `4787`
+		// Minimum execution time: 18_992_000 picoseconds.
+		Weight::from_parts(19_570_000, 4787)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetManager::AssetIdMetadata` (r:1 w:0)
+	/// Proof: `AssetManager::AssetIdMetadata` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::OutflowUsage` (r:0 w:1)
+	/// Proof: `AssetManager::OutflowUsage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetManager::OutflowCaps` (r:0 w:1)
+	/// Proof: `AssetManager::OutflowCaps` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_outflow_cap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1231`
+		//  Estimated: `4696`
+		// Minimum execution time: 17_484_000 picoseconds.
+		Weight::from_parts(18_031_000, 4696)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
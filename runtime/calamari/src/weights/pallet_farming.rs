@@ -45,30 +45,26 @@ use core::marker::PhantomData;
 
 /// Weight functions needed for pallet_farming.
 pub trait WeightInfo {
-	fn on_initialize() -> Weight;
 	fn create_farming_pool() -> Weight;
+	fn create_permissionless_pool() -> Weight;
 	fn charge() -> Weight;
 	fn deposit() -> Weight;
 	fn withdraw() -> Weight;
 	fn claim() -> Weight;
 	fn gauge_withdraw() -> Weight;
+	fn vote_gauge(v: u32, ) -> Weight;
+	fn settle_gauge_votes(p: u32, ) -> Weight;
+	fn zap_deposit() -> Weight;
+	fn withdraw_and_remove_liquidity() -> Weight;
+	fn set_reward_vesting() -> Weight;
+	fn claim_vested() -> Weight;
+	fn exit_vesting() -> Weight;
+	fn transfer_shares() -> Weight;
 }
 
 /// Weights for pallet_farming using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_farming::WeightInfo for SubstrateWeight<T> {
-	/// Storage: `Farming::PoolInfos` (r:1 w:0)
-	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `Farming::GaugePoolInfos` (r:1 w:0)
-	/// Proof: `Farming::GaugePoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	fn on_initialize() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `2105`
-		//  Estimated: `5570`
-		// Minimum execution time: 11_151_000 picoseconds.
-		Weight::from_parts(11_436_000, 5570)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-	}
 	/// Storage: `Farming::PoolNextId` (r:1 w:1)
 	/// Proof: `Farming::PoolNextId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Farming::GaugePoolNextId` (r:1 w:1)
@@ -86,6 +82,23 @@ impl<T: frame_system::Config> pallet_farming::WeightInfo for SubstrateWeight<T>
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Farming PoolNextId (r:1 w:1)
+	/// Proof Skipped: Farming PoolNextId (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Farming PoolOwners (r:0 w:1)
+	/// Proof Skipped: Farming PoolOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolInfos (r:0 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	fn create_permissionless_pool() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `144`
+		//  Estimated: `3593`
+		// Minimum execution time: 24_310_000 picoseconds.
+		Weight::from_parts(25_102_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
 	/// Storage: `Farming::PoolInfos` (r:1 w:1)
 	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Assets::Asset` (r:1 w:1)
@@ -96,10 +109,10 @@ impl<T: frame_system::Config> pallet_farming::WeightInfo for SubstrateWeight<T>
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	fn charge() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `1330`
+		//  Measured:  `1358`
 		//  Estimated: `6232`
-		// Minimum execution time: 61_405_000 picoseconds.
-		Weight::from_parts(62_693_000, 6232)
+		// Minimum execution time: 67_116_000 picoseconds.
+		Weight::from_parts(68_523_000, 6232)
 			.saturating_add(T::DbWeight::get().reads(5_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
@@ -115,10 +128,10 @@ impl<T: frame_system::Config> pallet_farming::WeightInfo for SubstrateWeight<T>
 	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn deposit() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `1504`
+		//  Measured:  `1532`
 		//  Estimated: `6232`
-		// Minimum execution time: 68_494_000 picoseconds.
-		Weight::from_parts(69_730_000, 6232)
+		// Minimum execution time: 74_384_000 picoseconds.
+		Weight::from_parts(75_727_000, 6232)
 			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
@@ -126,29 +139,41 @@ impl<T: frame_system::Config> pallet_farming::WeightInfo for SubstrateWeight<T>
 	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
 	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::RewardVestings` (r:1 w:0)
+	/// Proof: `Farming::RewardVestings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
 	fn withdraw() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `506`
-		//  Estimated: `3971`
-		// Minimum execution time: 28_536_000 picoseconds.
-		Weight::from_parts(29_094_000, 3971)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+		//  Measured:  `1066`
+		//  Estimated: `6232`
+		// Minimum execution time: 83_040_000 picoseconds.
+		Weight::from_parts(84_664_000, 6232)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Farming::PoolInfos` (r:1 w:1)
 	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
 	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::RewardVestings` (r:1 w:0)
+	/// Proof: `Farming::RewardVestings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
 	/// Storage: `Farming::GaugeInfos` (r:1 w:0)
 	/// Proof: `Farming::GaugeInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn claim() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `510`
-		//  Estimated: `3975`
-		// Minimum execution time: 29_139_000 picoseconds.
-		Weight::from_parts(29_700_000, 3975)
-			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+		//  Measured:  `1070`
+		//  Estimated: `6232`
+		// Minimum execution time: 85_669_000 picoseconds.
+		Weight::from_parts(87_318_000, 6232)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Farming::GaugePoolInfos` (r:1 w:1)
 	/// Proof: `Farming::GaugePoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -167,22 +192,176 @@ impl<T: frame_system::Config> pallet_farming::WeightInfo for SubstrateWeight<T>
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
-}
-
-// For backwards compatibility and tests
-impl WeightInfo for () {
-	/// Storage: `Farming::PoolInfos` (r:1 w:0)
+	/// Storage: Farming VotablePools (r:1 w:0)
+	/// Proof Skipped: Farming VotablePools (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Farming GaugePoolInfos (r:2 w:0)
+	/// Proof Skipped: Farming GaugePoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugeInfos (r:1 w:0)
+	/// Proof Skipped: Farming GaugeInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugeVotes (r:1 w:1)
+	/// Proof Skipped: Farming GaugeVotes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugeWeights (r:100 w:100)
+	/// Proof Skipped: Farming GaugeWeights (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `v` is `[1, 100]`.
+	fn vote_gauge(v: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `601 + v * (40 ±0)`
+		//  Estimated: `6541 + v * (2515 ±0)`
+		// Minimum execution time: 31_620_000 picoseconds.
+		Weight::from_parts(32_415_000, 6541)
+			// Standard Error: 4_212
+			.saturating_add(Weight::from_parts(3_126_470, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(v.into())))
+			.saturating_add(Weight::from_parts(0, 2515).saturating_mul(v.into()))
+	}
+	/// Storage: Farming GaugeWeights (r:101 w:100)
+	/// Proof Skipped: Farming GaugeWeights (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugeVoteEmission (r:1 w:0)
+	/// Proof Skipped: Farming GaugeVoteEmission (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Farming PoolInfos (r:100 w:100)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:101 w:101)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `p` is `[1, 100]`.
+	fn settle_gauge_votes(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `842 + p * (321 ±0)`
+		//  Estimated: `6232 + p * (7824 ±0)`
+		// Minimum execution time: 38_902_000 picoseconds.
+		Weight::from_parts(39_517_000, 6232)
+			// Standard Error: 21_036
+			.saturating_add(Weight::from_parts(29_480_312, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 7824).saturating_mul(p.into()))
+	}
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
 	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ZenlinkProtocol::PairStatuses` (r:1 w:1)
+	/// Proof: `ZenlinkProtocol::PairStatuses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:3 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:6 w:6)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
+	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn zap_deposit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1789`
+		//  Estimated: `16137`
+		// Minimum execution time: 112_350_000 picoseconds.
+		Weight::from_parts(115_046_000, 16137)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+	}
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ZenlinkProtocol::PairStatuses` (r:1 w:1)
+	/// Proof: `ZenlinkProtocol::PairStatuses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:3 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:6 w:6)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
+	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Farming::GaugePoolInfos` (r:1 w:0)
 	/// Proof: `Farming::GaugePoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	fn on_initialize() -> Weight {
+	fn withdraw_and_remove_liquidity() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `2105`
-		//  Estimated: `5570`
-		// Minimum execution time: 11_151_000 picoseconds.
-		Weight::from_parts(11_436_000, 5570)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
+		//  Measured:  `1932`
+		//  Estimated: `16137`
+		// Minimum execution time: 121_704_000 picoseconds.
+		Weight::from_parts(124_512_000, 16137)
+			.saturating_add(T::DbWeight::get().reads(15_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	/// Storage: `Farming::PoolOwners` (r:1 w:0)
+	/// Proof: `Farming::PoolOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::PoolInfos` (r:1 w:0)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::RewardVestings` (r:1 w:1)
+	/// Proof: `Farming::RewardVestings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_reward_vesting() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `391`
+		//  Estimated: `3856`
+		// Minimum execution time: 11_482_000 picoseconds.
+		Weight::from_parts(11_910_000, 3856)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Farming::VestingLedgers` (r:1 w:1)
+	/// Proof: `Farming::VestingLedgers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	fn claim_vested() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `906`
+		//  Estimated: `6232`
+		// Minimum execution time: 36_140_000 picoseconds.
+		Weight::from_parts(37_028_000, 6232)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Farming::VestingLedgers` (r:1 w:1)
+	/// Proof: `Farming::VestingLedgers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::RewardVestings` (r:1 w:0)
+	/// Proof: `Farming::RewardVestings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:3 w:3)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	fn exit_vesting() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1522`
+		//  Estimated: `8853`
+		// Minimum execution time: 54_716_000 picoseconds.
+		Weight::from_parts(56_201_000, 8853)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::GaugePoolInfos` (r:1 w:1)
+	/// Proof: `Farming::GaugePoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::GaugeInfos` (r:2 w:1)
+	/// Proof: `Farming::GaugeInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:2 w:2)
+	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::RewardVestings` (r:1 w:0)
+	/// Proof: `Farming::RewardVestings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	fn transfer_shares() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2214`
+		//  Estimated: `7854`
+		// Minimum execution time: 79_305_000 picoseconds.
+		Weight::from_parts(81_176_000, 7854)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
 	/// Storage: `Farming::PoolNextId` (r:1 w:1)
 	/// Proof: `Farming::PoolNextId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Farming::GaugePoolNextId` (r:1 w:1)
@@ -200,6 +379,23 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Farming PoolNextId (r:1 w:1)
+	/// Proof Skipped: Farming PoolNextId (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Farming PoolOwners (r:0 w:1)
+	/// Proof Skipped: Farming PoolOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming PoolInfos (r:0 w:1)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	fn create_permissionless_pool() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `144`
+		//  Estimated: `3593`
+		// Minimum execution time: 24_310_000 picoseconds.
+		Weight::from_parts(25_102_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
 	/// Storage: `Farming::PoolInfos` (r:1 w:1)
 	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Assets::Asset` (r:1 w:1)
@@ -210,10 +406,10 @@ impl WeightInfo for () {
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	fn charge() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `1330`
+		//  Measured:  `1358`
 		//  Estimated: `6232`
-		// Minimum execution time: 61_405_000 picoseconds.
-		Weight::from_parts(62_693_000, 6232)
+		// Minimum execution time: 67_116_000 picoseconds.
+		Weight::from_parts(68_523_000, 6232)
 			.saturating_add(RocksDbWeight::get().reads(5_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
@@ -229,10 +425,10 @@ impl WeightInfo for () {
 	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn deposit() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `1504`
+		//  Measured:  `1532`
 		//  Estimated: `6232`
-		// Minimum execution time: 68_494_000 picoseconds.
-		Weight::from_parts(69_730_000, 6232)
+		// Minimum execution time: 74_384_000 picoseconds.
+		Weight::from_parts(75_727_000, 6232)
 			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
@@ -240,29 +436,41 @@ impl WeightInfo for () {
 	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
 	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::RewardVestings` (r:1 w:0)
+	/// Proof: `Farming::RewardVestings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
 	fn withdraw() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `506`
-		//  Estimated: `3971`
-		// Minimum execution time: 28_536_000 picoseconds.
-		Weight::from_parts(29_094_000, 3971)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+		//  Measured:  `1066`
+		//  Estimated: `6232`
+		// Minimum execution time: 83_040_000 picoseconds.
+		Weight::from_parts(84_664_000, 6232)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Farming::PoolInfos` (r:1 w:1)
 	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
 	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::RewardVestings` (r:1 w:0)
+	/// Proof: `Farming::RewardVestings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
 	/// Storage: `Farming::GaugeInfos` (r:1 w:0)
 	/// Proof: `Farming::GaugeInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn claim() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `510`
-		//  Estimated: `3975`
-		// Minimum execution time: 29_139_000 picoseconds.
-		Weight::from_parts(29_700_000, 3975)
-			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+		//  Measured:  `1070`
+		//  Estimated: `6232`
+		// Minimum execution time: 85_669_000 picoseconds.
+		Weight::from_parts(87_318_000, 6232)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Farming::GaugePoolInfos` (r:1 w:1)
 	/// Proof: `Farming::GaugePoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -281,4 +489,170 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: Farming VotablePools (r:1 w:0)
+	/// Proof Skipped: Farming VotablePools (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Farming GaugePoolInfos (r:2 w:0)
+	/// Proof Skipped: Farming GaugePoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugeInfos (r:1 w:0)
+	/// Proof Skipped: Farming GaugeInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugeVotes (r:1 w:1)
+	/// Proof Skipped: Farming GaugeVotes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugeWeights (r:100 w:100)
+	/// Proof Skipped: Farming GaugeWeights (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `v` is `[1, 100]`.
+	fn vote_gauge(v: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `601 + v * (40 ±0)`
+		//  Estimated: `6541 + v * (2515 ±0)`
+		// Minimum execution time: 31_620_000 picoseconds.
+		Weight::from_parts(32_415_000, 6541)
+			// Standard Error: 4_212
+			.saturating_add(Weight::from_parts(3_126_470, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(v.into())))
+			.saturating_add(Weight::from_parts(0, 2515).saturating_mul(v.into()))
+	}
+	/// Storage: Farming GaugeWeights (r:101 w:100)
+	/// Proof Skipped: Farming GaugeWeights (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Farming GaugeVoteEmission (r:1 w:0)
+	/// Proof Skipped: Farming GaugeVoteEmission (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Farming PoolInfos (r:100 w:100)
+	/// Proof Skipped: Farming PoolInfos (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(222), added: 2697, mode: MaxEncodedLen)
+	/// Storage: Assets Account (r:101 w:101)
+	/// Proof: Assets Account (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: System Account (r:100 w:100)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `p` is `[1, 100]`.
+	fn settle_gauge_votes(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `842 + p * (321 ±0)`
+		//  Estimated: `6232 + p * (7824 ±0)`
+		// Minimum execution time: 38_902_000 picoseconds.
+		Weight::from_parts(39_517_000, 6232)
+			// Standard Error: 21_036
+			.saturating_add(Weight::from_parts(29_480_312, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 7824).saturating_mul(p.into()))
+	}
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ZenlinkProtocol::PairStatuses` (r:1 w:1)
+	/// Proof: `ZenlinkProtocol::PairStatuses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:3 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:6 w:6)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
+	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn zap_deposit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1789`
+		//  Estimated: `16137`
+		// Minimum execution time: 112_350_000 picoseconds.
+		Weight::from_parts(115_046_000, 16137)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+	}
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ZenlinkProtocol::PairStatuses` (r:1 w:1)
+	/// Proof: `ZenlinkProtocol::PairStatuses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:3 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:6 w:6)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:1 w:1)
+	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::GaugePoolInfos` (r:1 w:0)
+	/// Proof: `Farming::GaugePoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn withdraw_and_remove_liquidity() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1932`
+		//  Estimated: `16137`
+		// Minimum execution time: 121_704_000 picoseconds.
+		Weight::from_parts(124_512_000, 16137)
+			.saturating_add(RocksDbWeight::get().reads(15_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	/// Storage: `Farming::PoolOwners` (r:1 w:0)
+	/// Proof: `Farming::PoolOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::PoolInfos` (r:1 w:0)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::RewardVestings` (r:1 w:1)
+	/// Proof: `Farming::RewardVestings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_reward_vesting() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `391`
+		//  Estimated: `3856`
+		// Minimum execution time: 11_482_000 picoseconds.
+		Weight::from_parts(11_910_000, 3856)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Farming::VestingLedgers` (r:1 w:1)
+	/// Proof: `Farming::VestingLedgers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	fn claim_vested() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `906`
+		//  Estimated: `6232`
+		// Minimum execution time: 36_140_000 picoseconds.
+		Weight::from_parts(37_028_000, 6232)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Farming::VestingLedgers` (r:1 w:1)
+	/// Proof: `Farming::VestingLedgers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::RewardVestings` (r:1 w:0)
+	/// Proof: `Farming::RewardVestings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(222), added: 2697, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:3 w:3)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	fn exit_vesting() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1522`
+		//  Estimated: `8853`
+		// Minimum execution time: 54_716_000 picoseconds.
+		Weight::from_parts(56_201_000, 8853)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::GaugePoolInfos` (r:1 w:1)
+	/// Proof: `Farming::GaugePoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::GaugeInfos` (r:2 w:1)
+	/// Proof: `Farming::GaugeInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::SharesAndWithdrawnRewards` (r:2 w:2)
+	/// Proof: `Farming::SharesAndWithdrawnRewards` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::RewardVestings` (r:1 w:0)
+	/// Proof: `Farming::RewardVestings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(146), added: 2621, mode: `MaxEncodedLen`)
+	fn transfer_shares() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2214`
+		//  Estimated: `7854`
+		// Minimum execution time: 79_305_000 picoseconds.
+		Weight::from_parts(81_176_000, 7854)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
 }
@@ -47,10 +47,17 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn deposit(x: u32, y: u32, ) -> Weight;
 	fn request_withdraw(x: u32, y: u32, ) -> Weight;
+	fn cancel_withdraw_request(x: u32, y: u32, ) -> Weight;
+	fn instant_withdraw(x: u32, ) -> Weight;
+	fn sponsor_deposit(y: u32, ) -> Weight;
+	fn sponsor_withdraw(y: u32, ) -> Weight;
 	fn claim_my_winnings(y: u32, ) -> Weight;
+	fn set_auto_compound() -> Weight;
+	fn rebalance_stake(y: u32, ) -> Weight;
 	fn start_lottery() -> Weight;
 	fn stop_lottery() -> Weight;
 	fn draw_lottery(x: u32, y: u32, ) -> Weight;
+	fn commit_participants(x: u32, ) -> Weight;
 	fn process_matured_withdrawals() -> Weight;
 	fn set_min_deposit() -> Weight;
 	fn set_min_withdraw() -> Weight;
@@ -170,6 +177,62 @@ impl<T: frame_system::Config> pallet_lottery::WeightInfo for SubstrateWeight<T>
 			.saturating_add(Weight::from_parts(0, 1).saturating_mul(x.into()))
 			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
 	}
+	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `y` is `[0, 63]`.
+	fn cancel_withdraw_request(x: u32, y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3459 + x * (1 ±0) + y * (72 ±0)`
+		//  Estimated: `8716 + x * (2 ±0) + y * (79 ±0)`
+		// Minimum execution time: 197_635_000 picoseconds.
+		Weight::from_parts(221_869_681, 8716)
+			// Standard Error: 1_801
+			.saturating_add(Weight::from_parts(169_554, 0).saturating_mul(x.into()))
+			// Standard Error: 28_381
+			.saturating_add(Weight::from_parts(210_063, 0).saturating_mul(y.into()))
+			.saturating_add(T::DbWeight::get().reads(24_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+			.saturating_add(Weight::from_parts(0, 2).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(0, 79).saturating_mul(y.into()))
+	}
+	/// The range of component `x` is `[0, 1000]`.
+	fn instant_withdraw(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2341 + x * (1 ±0)`
+		//  Estimated: `7844 + x * (1 ±0)`
+		// Minimum execution time: 71_406_000 picoseconds.
+		Weight::from_parts(78_251_113, 7844)
+			// Standard Error: 291
+			.saturating_add(Weight::from_parts(33_540, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+			.saturating_add(Weight::from_parts(0, 1).saturating_mul(x.into()))
+	}
+	/// The range of component `y` is `[0, 63]`.
+	fn sponsor_deposit(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2583 + y * (48 ±0)`
+		//  Estimated: `8103 + y * (49 ±0)`
+		// Minimum execution time: 146_317_000 picoseconds.
+		Weight::from_parts(160_842_605, 8103)
+			// Standard Error: 12_406
+			.saturating_add(Weight::from_parts(263_119, 0).saturating_mul(y.into()))
+			.saturating_add(T::DbWeight::get().reads(21_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+			.saturating_add(Weight::from_parts(0, 49).saturating_mul(y.into()))
+	}
+	/// The range of component `y` is `[0, 63]`.
+	fn sponsor_withdraw(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2204`
+		//  Estimated: `7707 + y * (5 ±0)`
+		// Minimum execution time: 49_817_000 picoseconds.
+		Weight::from_parts(55_372_640, 7707)
+			// Standard Error: 3_713
+			.saturating_add(Weight::from_parts(4_108, 0).saturating_mul(y.into()))
+			.saturating_add(T::DbWeight::get().reads(16_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
+	}
 	/// Storage: `Lottery::UnclaimedWinningsByAccount` (r:1 w:1)
 	/// Proof: `Lottery::UnclaimedWinningsByAccount` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `System::Account` (r:2 w:2)
@@ -191,6 +254,49 @@ impl<T: frame_system::Config> pallet_lottery::WeightInfo for SubstrateWeight<T>
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 			.saturating_add(Weight::from_parts(0, 6).saturating_mul(y.into()))
 	}
+	/// Storage: `Lottery::AutoCompoundWinnings` (r:0 w:1)
+	/// Proof: `Lottery::AutoCompoundWinnings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_auto_compound() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(9_500_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ParachainStaking::DelegatorState` (r:1 w:1)
+	/// Proof: `ParachainStaking::DelegatorState` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainStaking::SelectedCandidates` (r:1 w:0)
+	/// Proof: `ParachainStaking::SelectedCandidates` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainStaking::AwardedPts` (r:63 w:0)
+	/// Proof: `ParachainStaking::AwardedPts` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CollatorSelection::BlocksPerCollatorThisSession` (r:64 w:0)
+	/// Proof: `CollatorSelection::BlocksPerCollatorThisSession` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::CollatorPointsHistory` (r:252 w:0)
+	/// Proof: `Lottery::CollatorPointsHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::StakedCollators` (r:63 w:0)
+	/// Proof: `Lottery::StakedCollators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::UnstakingCollators` (r:1 w:1)
+	/// Proof: `Lottery::UnstakingCollators` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainStaking::DelegationScheduledRequests` (r:63 w:63)
+	/// Proof: `ParachainStaking::DelegationScheduledRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::SurplusUnstakingBalance` (r:1 w:1)
+	/// Proof: `Lottery::SurplusUnstakingBalance` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `y` is `[1, 63]`.
+	fn rebalance_stake(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1874 + y * (412 ±0)`
+		//  Estimated: `6340 + y * (2887 ±0)`
+		// Minimum execution time: 48_231_000 picoseconds.
+		Weight::from_parts(41_092_361, 6340)
+			// Standard Error: 21_305
+			.saturating_add(Weight::from_parts(31_870_418, 0).saturating_mul(y.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(y.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(y.into())))
+			.saturating_add(Weight::from_parts(0, 2887).saturating_mul(y.into()))
+	}
 	/// Storage: `Scheduler::Lookup` (r:1 w:1)
 	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:0)
@@ -265,6 +371,32 @@ impl<T: frame_system::Config> pallet_lottery::WeightInfo for SubstrateWeight<T>
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 			.saturating_add(Weight::from_parts(0, 1112).saturating_mul(x.into()))
 	}
+	/// Storage: `Lottery::ParticipantSlots` (r:1 w:0)
+	/// Proof: `Lottery::ParticipantSlots` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::SlotOwner` (r:1000 w:0)
+	/// Proof: `Lottery::SlotOwner` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::ActiveBalancePerUser` (r:1000 w:0)
+	/// Proof: `Lottery::ActiveBalancePerUser` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::TicketPenalty` (r:1000 w:0)
+	/// Proof: `Lottery::TicketPenalty` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::TicketEpoch` (r:1 w:0)
+	/// Proof: `Lottery::TicketEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::DrawingCommitment` (r:0 w:1)
+	/// Proof: `Lottery::DrawingCommitment` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `x` is `[0, 1000]`.
+	fn commit_participants(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212 + x * (142 ±0)`
+		//  Estimated: `3677 + x * (7617 ±0)`
+		// Minimum execution time: 9_342_000 picoseconds.
+		Weight::from_parts(10_114_257, 3677)
+			// Standard Error: 4_518
+			.saturating_add(Weight::from_parts(12_903_455, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 7617).saturating_mul(x.into()))
+	}
 	/// Storage: `Lottery::UnstakingCollators` (r:1 w:0)
 	/// Proof: `Lottery::UnstakingCollators` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Lottery::WithdrawalRequestQueue` (r:1 w:0)
@@ -428,6 +560,62 @@ impl WeightInfo for () {
 			.saturating_add(Weight::from_parts(0, 1).saturating_mul(x.into()))
 			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
 	}
+	/// The range of component `x` is `[0, 1000]`.
+	/// The range of component `y` is `[0, 63]`.
+	fn cancel_withdraw_request(x: u32, y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3459 + x * (1 ±0) + y * (72 ±0)`
+		//  Estimated: `8716 + x * (2 ±0) + y * (79 ±0)`
+		// Minimum execution time: 197_635_000 picoseconds.
+		Weight::from_parts(221_869_681, 8716)
+			// Standard Error: 1_801
+			.saturating_add(Weight::from_parts(169_554, 0).saturating_mul(x.into()))
+			// Standard Error: 28_381
+			.saturating_add(Weight::from_parts(210_063, 0).saturating_mul(y.into()))
+			.saturating_add(RocksDbWeight::get().reads(24_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+			.saturating_add(Weight::from_parts(0, 2).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(0, 79).saturating_mul(y.into()))
+	}
+	/// The range of component `x` is `[0, 1000]`.
+	fn instant_withdraw(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2341 + x * (1 ±0)`
+		//  Estimated: `7844 + x * (1 ±0)`
+		// Minimum execution time: 71_406_000 picoseconds.
+		Weight::from_parts(78_251_113, 7844)
+			// Standard Error: 291
+			.saturating_add(Weight::from_parts(33_540, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+			.saturating_add(Weight::from_parts(0, 1).saturating_mul(x.into()))
+	}
+	/// The range of component `y` is `[0, 63]`.
+	fn sponsor_deposit(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2583 + y * (48 ±0)`
+		//  Estimated: `8103 + y * (49 ±0)`
+		// Minimum execution time: 146_317_000 picoseconds.
+		Weight::from_parts(160_842_605, 8103)
+			// Standard Error: 12_406
+			.saturating_add(Weight::from_parts(263_119, 0).saturating_mul(y.into()))
+			.saturating_add(RocksDbWeight::get().reads(21_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+			.saturating_add(Weight::from_parts(0, 49).saturating_mul(y.into()))
+	}
+	/// The range of component `y` is `[0, 63]`.
+	fn sponsor_withdraw(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2204`
+		//  Estimated: `7707 + y * (5 ±0)`
+		// Minimum execution time: 49_817_000 picoseconds.
+		Weight::from_parts(55_372_640, 7707)
+			// Standard Error: 3_713
+			.saturating_add(Weight::from_parts(4_108, 0).saturating_mul(y.into()))
+			.saturating_add(RocksDbWeight::get().reads(16_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(Weight::from_parts(0, 5).saturating_mul(y.into()))
+	}
 	/// Storage: `Lottery::UnclaimedWinningsByAccount` (r:1 w:1)
 	/// Proof: `Lottery::UnclaimedWinningsByAccount` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `System::Account` (r:2 w:2)
@@ -449,6 +637,49 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 			.saturating_add(Weight::from_parts(0, 6).saturating_mul(y.into()))
 	}
+	/// Storage: `Lottery::AutoCompoundWinnings` (r:0 w:1)
+	/// Proof: `Lottery::AutoCompoundWinnings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_auto_compound() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(9_500_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ParachainStaking::DelegatorState` (r:1 w:1)
+	/// Proof: `ParachainStaking::DelegatorState` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainStaking::SelectedCandidates` (r:1 w:0)
+	/// Proof: `ParachainStaking::SelectedCandidates` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainStaking::AwardedPts` (r:63 w:0)
+	/// Proof: `ParachainStaking::AwardedPts` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CollatorSelection::BlocksPerCollatorThisSession` (r:64 w:0)
+	/// Proof: `CollatorSelection::BlocksPerCollatorThisSession` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::CollatorPointsHistory` (r:252 w:0)
+	/// Proof: `Lottery::CollatorPointsHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::StakedCollators` (r:63 w:0)
+	/// Proof: `Lottery::StakedCollators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::UnstakingCollators` (r:1 w:1)
+	/// Proof: `Lottery::UnstakingCollators` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParachainStaking::DelegationScheduledRequests` (r:63 w:63)
+	/// Proof: `ParachainStaking::DelegationScheduledRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::SurplusUnstakingBalance` (r:1 w:1)
+	/// Proof: `Lottery::SurplusUnstakingBalance` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `y` is `[1, 63]`.
+	fn rebalance_stake(y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1874 + y * (412 ±0)`
+		//  Estimated: `6340 + y * (2887 ±0)`
+		// Minimum execution time: 48_231_000 picoseconds.
+		Weight::from_parts(41_092_361, 6340)
+			// Standard Error: 21_305
+			.saturating_add(Weight::from_parts(31_870_418, 0).saturating_mul(y.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(y.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(y.into())))
+			.saturating_add(Weight::from_parts(0, 2887).saturating_mul(y.into()))
+	}
 	/// Storage: `Scheduler::Lookup` (r:1 w:1)
 	/// Proof: `Scheduler::Lookup` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:0)
@@ -523,6 +754,32 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 			.saturating_add(Weight::from_parts(0, 1112).saturating_mul(x.into()))
 	}
+	/// Storage: `Lottery::ParticipantSlots` (r:1 w:0)
+	/// Proof: `Lottery::ParticipantSlots` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::SlotOwner` (r:1000 w:0)
+	/// Proof: `Lottery::SlotOwner` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::ActiveBalancePerUser` (r:1000 w:0)
+	/// Proof: `Lottery::ActiveBalancePerUser` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::TicketPenalty` (r:1000 w:0)
+	/// Proof: `Lottery::TicketPenalty` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::TicketEpoch` (r:1 w:0)
+	/// Proof: `Lottery::TicketEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Lottery::DrawingCommitment` (r:0 w:1)
+	/// Proof: `Lottery::DrawingCommitment` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `x` is `[0, 1000]`.
+	fn commit_participants(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `212 + x * (142 ±0)`
+		//  Estimated: `3677 + x * (7617 ±0)`
+		// Minimum execution time: 9_342_000 picoseconds.
+		Weight::from_parts(10_114_257, 3677)
+			// Standard Error: 4_518
+			.saturating_add(Weight::from_parts(12_903_455, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(x.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 7617).saturating_mul(x.into()))
+	}
 	/// Storage: `Lottery::UnstakingCollators` (r:1 w:0)
 	/// Proof: `Lottery::UnstakingCollators` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Lottery::WithdrawalRequestQueue` (r:1 w:0)
@@ -26,8 +26,8 @@ use frame_support::{
     pallet_prelude::DispatchResult,
     parameter_types,
     traits::{
-        AsEnsureOriginWithArg, ConstU128, ConstU32, Contains, Currency, EnqueueWithOrigin,
-        Everything, Nothing,
+        fungibles::Destroy, AsEnsureOriginWithArg, ConstU128, ConstU32, Contains, Currency,
+        EnqueueWithOrigin, Everything, Nothing,
     },
     weights::Weight,
     PalletId,
@@ -52,7 +52,7 @@ use manta_primitives::{
     xcm::{
         AccountIdToMultiLocation, AllowTopLevelPaidExecutionDescendOriginFirst,
         AllowTopLevelPaidExecutionFrom, FirstAssetTrader, IsNativeConcrete, MultiAssetAdapter,
-        MultiNativeAsset, XcmFeesToAccount,
+        MultiNativeAsset, OutflowCappedXcmExecutor, XcmFeesToAccount,
     },
 };
 use pallet_xcm::XcmPassthrough;
@@ -243,6 +243,8 @@ pub type MultiAssetTransactor = MultiAssetAdapter<
     IsNativeConcrete<SelfReserve>,
     // Used to match incoming assets which are not the native asset.
     ConvertedConcreteId<CalamariAssetId, Balance, AssetIdLocationConvert<AssetManager>, JustTry>,
+    // Used to cap the outflow of assets from local accounts.
+    AssetManager,
 >;
 
 pub type XcmRouter = super::ParachainXcmRouter<MsgQueue>;
@@ -618,6 +620,28 @@ impl AssetRegistry for CalamariAssetRegistry {
             metadata.is_frozen,
         )
     }
+
+    fn start_destroy_asset(asset_id: &CalamariAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::start_destroy(*asset_id, None)
+    }
+
+    fn destroy_asset_accounts(
+        asset_id: &CalamariAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_accounts(*asset_id, max_items)
+    }
+
+    fn destroy_asset_approvals(
+        asset_id: &CalamariAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_approvals(*asset_id, max_items)
+    }
+
+    fn finish_destroy_asset(asset_id: &CalamariAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::finish_destroy(*asset_id)
+    }
 }
 
 parameter_types! {
@@ -671,7 +695,10 @@ impl pallet_asset_manager::Config for Runtime {
     type PermissionlessStartId = ConstU128<1_000_000_000>;
     type TokenNameMaxLen = ConstU32<100>;
     type TokenSymbolMaxLen = ConstU32<100>;
+    type TokenLogoMaxLen = ConstU32<100>;
     type PermissionlessAssetRegistryCost = ConstU128<1000>;
+    type AssetInUse = Nothing;
+    type PairReserves = ();
 }
 
 impl cumulus_pallet_xcm::Config for Runtime {
@@ -719,7 +746,14 @@ impl orml_xtokens::Config for Runtime {
     type CurrencyId = CurrencyId;
     type AccountIdToMultiLocation = AccountIdToMultiLocation;
     type CurrencyIdConvert = CurrencyIdtoMultiLocation<AssetIdLocationConvert<AssetManager>>;
-    type XcmExecutor = XcmExecutor<XcmExecutorConfig>;
+    // Caps the outflow of assets to each sibling parachain.
+    type XcmExecutor = OutflowCappedXcmExecutor<
+        Runtime,
+        ParachainAssetConfig,
+        XcmExecutor<XcmExecutorConfig>,
+        MultiAssetTransactor,
+        AssetManager,
+    >;
     type SelfLocation = SelfReserve;
     type Weigher = WeightInfoBounds<RuntimeXcmWeight, RuntimeCall, MaxInstructions>;
     type BaseXcmWeight = BaseXcmWeight;
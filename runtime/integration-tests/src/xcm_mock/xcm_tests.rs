@@ -513,6 +513,282 @@ fn send_para_a_native_asset_to_para_b() {
     });
 }
 
+#[test]
+fn outflow_capped_transfer_should_not_work() {
+    MockNet::reset();
+
+    let para_a_source_location = create_asset_location(1, PARA_A_ID);
+    let para_b_source_location = create_asset_location(1, PARA_B_ID);
+
+    let para_a_asset_metadata = create_asset_metadata("ParaAToken", "ParaA", 18, 1, false, false);
+    let para_b_asset_metadata = create_asset_metadata("ParaBToken", "ParaB", 18, 1, false, false);
+
+    let a_asset_id_on_a = register_assets_on_parachain::<ParaA>(
+        &para_a_source_location,
+        &para_a_asset_metadata,
+        Some(0u128),
+        None,
+    );
+    let _ = register_assets_on_parachain::<ParaA>(
+        &para_b_source_location,
+        &para_b_asset_metadata,
+        Some(0u128),
+        None,
+    );
+
+    let _ = register_assets_on_parachain::<ParaB>(
+        &para_b_source_location,
+        &para_b_asset_metadata,
+        Some(0u128),
+        None,
+    );
+    let a_asset_id_on_b = register_assets_on_parachain::<ParaB>(
+        &para_a_source_location,
+        &para_a_asset_metadata,
+        Some(0u128),
+        None,
+    );
+
+    let dest = MultiLocation {
+        parents: 1,
+        interior: X2(
+            Parachain(PARA_B_ID),
+            AccountId32 {
+                network: None,
+                id: ALICE.into(),
+            },
+        ),
+    };
+    let cap = pallet_asset_manager::OutflowCap {
+        amount: 100,
+        period: 10,
+    };
+
+    ParaA::execute_with(|| {
+        assert_ok!(AssetManager::set_outflow_cap(
+            parachain::RuntimeOrigin::root(),
+            a_asset_id_on_a,
+            Some(PARA_B_ID),
+            Some(cap)
+        ));
+        assert_ok!(parachain::XTokens::transfer(
+            parachain::RuntimeOrigin::signed(ALICE),
+            parachain::CurrencyId::MantaCurrency(a_asset_id_on_a),
+            100,
+            Box::new(VersionedMultiLocation::V3(dest)),
+            WeightLimit::Limited(ADVERTISED_DEST_WEIGHT)
+        ));
+        assert_eq!(
+            AssetManager::remaining_outflow(a_asset_id_on_a, Some(PARA_B_ID)),
+            Some(0)
+        );
+        let expected_event: RuntimeEvent =
+            pallet_asset_manager::Event::<parachain::Runtime>::OutflowCapReached {
+                asset_id: a_asset_id_on_a,
+                destination: Some(PARA_B_ID),
+                cap,
+            }
+            .into();
+        assert!(parachain::para_events().contains(&expected_event));
+
+        // The cap is reached until the window ends
+        assert_noop!(
+            parachain::XTokens::transfer(
+                parachain::RuntimeOrigin::signed(ALICE),
+                parachain::CurrencyId::MantaCurrency(a_asset_id_on_a),
+                1,
+                Box::new(VersionedMultiLocation::V3(dest)),
+                WeightLimit::Limited(ADVERTISED_DEST_WEIGHT)
+            ),
+            orml_xtokens::Error::<parachain::Runtime>::XcmExecutionFailed,
+        );
+
+        // The global cap applies to the outflow to any chain
+        assert_ok!(AssetManager::set_outflow_cap(
+            parachain::RuntimeOrigin::root(),
+            a_asset_id_on_a,
+            Some(PARA_B_ID),
+            None
+        ));
+        assert_ok!(AssetManager::set_outflow_cap(
+            parachain::RuntimeOrigin::root(),
+            a_asset_id_on_a,
+            None,
+            Some(cap)
+        ));
+        assert_noop!(
+            parachain::XTokens::transfer(
+                parachain::RuntimeOrigin::signed(ALICE),
+                parachain::CurrencyId::MantaCurrency(a_asset_id_on_a),
+                101,
+                Box::new(VersionedMultiLocation::V3(dest)),
+                WeightLimit::Limited(ADVERTISED_DEST_WEIGHT)
+            ),
+            orml_xtokens::Error::<parachain::Runtime>::XcmExecutionFailed,
+        );
+        assert_ok!(parachain::XTokens::transfer(
+            parachain::RuntimeOrigin::signed(ALICE),
+            parachain::CurrencyId::MantaCurrency(a_asset_id_on_a),
+            100,
+            Box::new(VersionedMultiLocation::V3(dest)),
+            WeightLimit::Limited(ADVERTISED_DEST_WEIGHT)
+        ));
+        assert_eq!(
+            AssetManager::remaining_outflow(a_asset_id_on_a, None),
+            Some(0)
+        );
+    });
+
+    // Make sure B received the tokens within the caps
+    ParaB::execute_with(|| {
+        assert_eq!(parachain::Assets::balance(a_asset_id_on_b, &ALICE), 200);
+    });
+}
+
+#[test]
+fn outflow_cap_should_apply_to_final_destination() {
+    MockNet::reset();
+
+    let para_a_source_location = create_asset_location(1, PARA_A_ID);
+    let para_b_source_location = create_asset_location(1, PARA_B_ID);
+    let para_c_source_location = create_asset_location(1, PARA_C_ID);
+
+    let amount = 8888888u128;
+    let para_a_asset_metadata = create_asset_metadata("ParaAToken", "ParaA", 18, 1, false, false);
+    let para_b_asset_metadata = create_asset_metadata("ParaBToken", "ParaB", 18, 1, false, false);
+    let para_c_asset_metadata = create_asset_metadata("ParaCToken", "ParaC", 18, 1, false, false);
+
+    let a_asset_id_on_a = register_assets_on_parachain::<ParaA>(
+        &para_a_source_location,
+        &para_a_asset_metadata,
+        Some(0u128),
+        None,
+    );
+    let _ = register_assets_on_parachain::<ParaA>(
+        &para_b_source_location,
+        &para_b_asset_metadata,
+        Some(0u128),
+        None,
+    );
+
+    let _ = register_assets_on_parachain::<ParaB>(
+        &para_b_source_location,
+        &para_b_asset_metadata,
+        Some(0u128),
+        None,
+    );
+    let a_asset_id_on_b = register_assets_on_parachain::<ParaB>(
+        &para_a_source_location,
+        &para_a_asset_metadata,
+        Some(0u128),
+        None,
+    );
+    let _ = register_assets_on_parachain::<ParaB>(
+        &para_c_source_location,
+        &para_c_asset_metadata,
+        Some(0u128),
+        None,
+    );
+
+    let _ = register_assets_on_parachain::<ParaC>(
+        &para_c_source_location,
+        &para_c_asset_metadata,
+        Some(0u128),
+        None,
+    );
+    let _ = register_assets_on_parachain::<ParaC>(
+        &para_a_source_location,
+        &para_a_asset_metadata,
+        Some(0u128),
+        None,
+    );
+
+    let alice_on_b = MultiLocation {
+        parents: 1,
+        interior: X2(
+            Parachain(PARA_B_ID),
+            AccountId32 {
+                network: None,
+                id: ALICE.into(),
+            },
+        ),
+    };
+    ParaA::execute_with(|| {
+        assert_ok!(parachain::XTokens::transfer(
+            parachain::RuntimeOrigin::signed(ALICE),
+            parachain::CurrencyId::MantaCurrency(a_asset_id_on_a),
+            amount,
+            Box::new(VersionedMultiLocation::V3(alice_on_b)),
+            WeightLimit::Limited(ADVERTISED_DEST_WEIGHT)
+        ));
+    });
+
+    // B sends the asset of A on to C through its reserve A
+    let alice_on_c = MultiLocation {
+        parents: 1,
+        interior: X2(
+            Parachain(PARA_C_ID),
+            AccountId32 {
+                network: None,
+                id: ALICE.into(),
+            },
+        ),
+    };
+    ParaB::execute_with(|| {
+        assert_ok!(AssetManager::set_outflow_cap(
+            parachain::RuntimeOrigin::root(),
+            a_asset_id_on_b,
+            Some(PARA_C_ID),
+            Some(pallet_asset_manager::OutflowCap {
+                amount: amount - 1,
+                period: 10,
+            })
+        ));
+        assert_noop!(
+            parachain::XTokens::transfer(
+                parachain::RuntimeOrigin::signed(ALICE),
+                parachain::CurrencyId::MantaCurrency(a_asset_id_on_b),
+                amount,
+                Box::new(VersionedMultiLocation::V3(alice_on_c)),
+                WeightLimit::Limited(ADVERTISED_DEST_WEIGHT),
+            ),
+            orml_xtokens::Error::<parachain::Runtime>::XcmExecutionFailed,
+        );
+
+        // The reserve only passes the asset on
+        assert_ok!(AssetManager::set_outflow_cap(
+            parachain::RuntimeOrigin::root(),
+            a_asset_id_on_b,
+            Some(PARA_A_ID),
+            Some(pallet_asset_manager::OutflowCap {
+                amount: 1,
+                period: 10,
+            })
+        ));
+        assert_ok!(AssetManager::set_outflow_cap(
+            parachain::RuntimeOrigin::root(),
+            a_asset_id_on_b,
+            Some(PARA_C_ID),
+            Some(pallet_asset_manager::OutflowCap { amount, period: 10 })
+        ));
+        assert_ok!(parachain::XTokens::transfer(
+            parachain::RuntimeOrigin::signed(ALICE),
+            parachain::CurrencyId::MantaCurrency(a_asset_id_on_b),
+            amount,
+            Box::new(VersionedMultiLocation::V3(alice_on_c)),
+            WeightLimit::Limited(ADVERTISED_DEST_WEIGHT),
+        ));
+        assert_eq!(
+            AssetManager::remaining_outflow(a_asset_id_on_b, Some(PARA_C_ID)),
+            Some(0)
+        );
+        assert_eq!(
+            AssetManager::remaining_outflow(a_asset_id_on_b, Some(PARA_A_ID)),
+            Some(1)
+        );
+    });
+}
+
 #[test]
 fn send_para_b_asset_to_para_b() {
     MockNet::reset();
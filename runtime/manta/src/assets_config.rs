@@ -15,9 +15,9 @@
 // along with Manta.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
-    weights, xcm_config::SelfReserve, AssetManager, Assets, Balances, CouncilCollective,
-    NativeTokenExistentialDeposit, Runtime, RuntimeEvent, RuntimeOrigin, TechnicalCollective,
-    Timestamp, MANTA,
+    weights, xcm_config::SelfReserve, zenlink::ZenlinkPairReserves, AssetManager, Assets, Balances,
+    CouncilCollective, IsFarmingAsset, NativeTokenExistentialDeposit, Runtime, RuntimeEvent,
+    RuntimeOrigin, TechnicalCollective, Timestamp, MANTA,
 };
 
 use manta_primitives::{
@@ -32,7 +32,9 @@ use manta_primitives::{
 use frame_support::{
     pallet_prelude::DispatchResult,
     parameter_types,
-    traits::{AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU32, EitherOfDiverse},
+    traits::{
+        fungibles::Destroy, AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU32, EitherOfDiverse,
+    },
     PalletId,
 };
 use frame_system::EnsureRoot;
@@ -124,6 +126,28 @@ impl AssetRegistry for MantaAssetRegistry {
             metadata.is_frozen,
         )
     }
+
+    fn start_destroy_asset(asset_id: &MantaAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::start_destroy(*asset_id, None)
+    }
+
+    fn destroy_asset_accounts(
+        asset_id: &MantaAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_accounts(*asset_id, max_items)
+    }
+
+    fn destroy_asset_approvals(
+        asset_id: &MantaAssetId,
+        max_items: u32,
+    ) -> Result<u32, sp_runtime::DispatchError> {
+        <Assets as Destroy<AccountId>>::destroy_approvals(*asset_id, max_items)
+    }
+
+    fn finish_destroy_asset(asset_id: &MantaAssetId) -> DispatchResult {
+        <Assets as Destroy<AccountId>>::finish_destroy(*asset_id)
+    }
 }
 
 parameter_types! {
@@ -172,7 +196,10 @@ impl pallet_asset_manager::Config for Runtime {
     type PermissionlessStartId = ConstU128<1_000_000_000>;
     type TokenNameMaxLen = StringLimit;
     type TokenSymbolMaxLen = ConstU32<10>;
+    type TokenLogoMaxLen = ConstU32<256>;
     type PermissionlessAssetRegistryCost = ConstU128<{ 50 * MANTA }>;
+    type AssetInUse = IsFarmingAsset;
+    type PairReserves = ZenlinkPairReserves;
     type RuntimeEvent = RuntimeEvent;
     type AssetId = MantaAssetId;
     type Location = AssetLocation;
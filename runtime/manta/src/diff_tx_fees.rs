@@ -2534,6 +2534,7 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
                 (2, Perbill::from_percent(40)),
             ],
             basic_rewards: vec![(1, 64), (2, 128)],
+            emission_curve: pallet_farming::EmissionCurve::Constant,
             gauge_init: None,
             min_deposit_to_start: 10,
             after_block_to_start: 100,
@@ -2628,6 +2629,7 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
         let call = crate::RuntimeCall::Farming(pallet_farming::Call::edit_pool {
             pool_id: 1,
             basic_rewards: Some(vec![(1, 64), (2, 128)]),
+            emission_curve: None,
             withdraw_limit_time: Some(200),
             claim_limit_time: Some(300),
             gauge_basic_rewards: Some(vec![(1, 64), (2, 128)]),
@@ -2656,7 +2658,7 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
     {
         assert_eq!(
             crate::RuntimeCall::get_call_names("Lottery").len(),
-            13,
+            21,
             "Please update new extrinsic here."
         );
         t.execute_with(|| {
@@ -2674,6 +2676,17 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
                 dispatch_info,
                 call_len,
             ));
+            // cancel_withdraw_request
+            let call = crate::RuntimeCall::Lottery(pallet_lottery::Call::cancel_withdraw_request {
+                index: 0,
+            });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "cancel_withdraw_request",
+                dispatch_info,
+                call_len,
+            ));
 
             // claim_my_winnings
             let call = crate::RuntimeCall::Lottery(pallet_lottery::Call::claim_my_winnings {});
@@ -2684,6 +2697,17 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
                 dispatch_info,
                 call_len,
             ));
+            // set_auto_compound
+            let call = crate::RuntimeCall::Lottery(pallet_lottery::Call::set_auto_compound {
+                enabled: true,
+            });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "set_auto_compound",
+                dispatch_info,
+                call_len,
+            ));
             // rebalance_stake
             let call = crate::RuntimeCall::Lottery(pallet_lottery::Call::rebalance_stake {});
             let (dispatch_info, call_len) = get_call_details(&call);
@@ -2794,6 +2818,71 @@ fn calculate_all_current_extrinsic_tx_fee() -> (
                 dispatch_info,
                 call_len,
             ));
+
+            let call = crate::RuntimeCall::Lottery(pallet_lottery::Call::set_staking_strategy {
+                strategy: pallet_lottery::StakingStrategyKind::RewardHistory,
+            });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "set_staking_strategy",
+                dispatch_info,
+                call_len,
+            ));
+
+            let call =
+                crate::RuntimeCall::Lottery(pallet_lottery::Call::instant_withdraw { amount: 10 });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "instant_withdraw",
+                dispatch_info,
+                call_len,
+            ));
+
+            let call =
+                crate::RuntimeCall::Lottery(pallet_lottery::Call::set_liquidity_buffer_target {
+                    target: 10,
+                });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "set_liquidity_buffer_target",
+                dispatch_info,
+                call_len,
+            ));
+
+            let call =
+                crate::RuntimeCall::Lottery(pallet_lottery::Call::set_instant_withdraw_fee {
+                    fee: Perbill::from_percent(1),
+                });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "set_instant_withdraw_fee",
+                dispatch_info,
+                call_len,
+            ));
+
+            let call =
+                crate::RuntimeCall::Lottery(pallet_lottery::Call::sponsor_deposit { amount: 10 });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "sponsor_deposit",
+                dispatch_info,
+                call_len,
+            ));
+
+            let call =
+                crate::RuntimeCall::Lottery(pallet_lottery::Call::sponsor_withdraw { amount: 10 });
+            let (dispatch_info, call_len) = get_call_details(&call);
+            calamari_runtime_calls.push((
+                "pallet_lottery",
+                "sponsor_withdraw",
+                dispatch_info,
+                call_len,
+            ));
         });
     }
 
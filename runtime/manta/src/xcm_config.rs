@@ -37,7 +37,7 @@ use manta_primitives::{
     xcm::{
         AccountIdToMultiLocation, AllowTopLevelPaidExecutionDescendOriginFirst,
         AllowTopLevelPaidExecutionFrom, FirstAssetTrader, IsNativeConcrete, MultiAssetAdapter,
-        MultiNativeAsset, XcmFeesToAccount,
+        MultiNativeAsset, OutflowCappedXcmExecutor, XcmFeesToAccount,
     },
 };
 use orml_traits::location::AbsoluteReserveProvider;
@@ -145,6 +145,8 @@ pub type MultiAssetTransactor = MultiAssetAdapter<
     IsNativeConcrete<SelfReserve>,
     // Used to match incoming assets which are not the native asset.
     ConvertedConcreteId<MantaAssetId, Balance, AssetIdLocationConvert<AssetManager>, JustTry>,
+    // Used to cap the outflow of assets from local accounts.
+    AssetManager,
 >;
 
 match_types! {
@@ -385,7 +387,14 @@ impl orml_xtokens::Config for Runtime {
     type CurrencyId = CurrencyId;
     type AccountIdToMultiLocation = AccountIdToMultiLocation;
     type CurrencyIdConvert = CurrencyIdtoMultiLocation<AssetIdLocationConvert<AssetManager>>;
-    type XcmExecutor = XcmExecutor<XcmExecutorConfig>;
+    // Caps the outflow of assets to each sibling parachain.
+    type XcmExecutor = OutflowCappedXcmExecutor<
+        Runtime,
+        MantaAssetConfig,
+        XcmExecutor<XcmExecutorConfig>,
+        MultiAssetTransactor,
+        AssetManager,
+    >;
     type SelfLocation = SelfReserve;
     /// Weigher Configuration
     ///
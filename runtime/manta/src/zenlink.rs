@@ -14,17 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with Manta.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::{AssetManager, Balances, ParachainInfo, Runtime, RuntimeEvent, ZenlinkProtocol};
+use super::{
+    AssetManager, Balances, ParachainInfo, Runtime, RuntimeEvent, RuntimeOrigin, System,
+    ZenlinkProtocol,
+};
 use crate::assets_config::MantaConcreteFungibleLedger;
 use frame_support::{parameter_types, traits::ExistenceRequirement, PalletId};
 use manta_primitives::{
     assets::{AssetIdLpMap, FungibleLedger},
-    types::MantaAssetId,
+    types::{AccountId, Balance, BlockNumber, MantaAssetId},
 };
-use sp_runtime::{traits::Zero, DispatchError};
+use pallet_asset_manager::PairReserves;
+use pallet_farming::LiquidityProvider;
+use sp_runtime::{traits::Zero, DispatchError, DispatchResult, MultiAddress};
 use zenlink_protocol::{
     AssetBalance, AssetId as ZenlinkAssetId, GenerateLpAssetId, LocalAssetHandler,
-    ZenlinkMultiAssets, LOCAL,
+    ZenlinkMultiAssets, LOCAL, NATIVE,
 };
 
 // Normal Coin AMM
@@ -165,6 +170,93 @@ impl LocalAssetHandler<sp_runtime::AccountId32> for LocalAssetAdaptor {
     }
 }
 
+/// Adds and removes zenlink liquidity for the farming pools taking shares in LP assets.
+pub struct ZenlinkLiquidityProvider;
+
+impl ZenlinkLiquidityProvider {
+    fn zenlink_asset_id(asset_id: MantaAssetId) -> ZenlinkAssetId {
+        // Notice: Manta native asset id is 1, but Zenlink native asset id is 0.
+        if asset_id == MantaNativeAssetId::get() {
+            ZenlinkAssetId {
+                chain_id: SelfParaId::get(),
+                asset_type: NATIVE,
+                asset_index: ZenlinkNativeAssetId::get(),
+            }
+        } else {
+            ZenlinkAssetId {
+                chain_id: SelfParaId::get(),
+                asset_type: LOCAL,
+                asset_index: asset_id as u64,
+            }
+        }
+    }
+
+    fn deadline() -> BlockNumber {
+        System::block_number() + 1
+    }
+}
+
+impl LiquidityProvider<AccountId, MantaAssetId, Balance> for ZenlinkLiquidityProvider {
+    fn lp_currency(currency_0: MantaAssetId, currency_1: MantaAssetId) -> Option<MantaAssetId> {
+        // The LP asset is registered for the pair in zenlink order.
+        <AssetManager as AssetIdLpMap>::lp_asset_id(&currency_0, &currency_1)
+            .or_else(|| <AssetManager as AssetIdLpMap>::lp_asset_id(&currency_1, &currency_0))
+    }
+
+    fn add_liquidity(
+        who: &AccountId,
+        currencies: (MantaAssetId, MantaAssetId),
+        amounts_desired: (Balance, Balance),
+        amounts_min: (Balance, Balance),
+    ) -> DispatchResult {
+        ZenlinkProtocol::add_liquidity(
+            RuntimeOrigin::signed(who.clone()),
+            Self::zenlink_asset_id(currencies.0),
+            Self::zenlink_asset_id(currencies.1),
+            amounts_desired.0,
+            amounts_desired.1,
+            amounts_min.0,
+            amounts_min.1,
+            Self::deadline(),
+        )
+    }
+
+    fn remove_liquidity(
+        who: &AccountId,
+        currencies: (MantaAssetId, MantaAssetId),
+        liquidity: Balance,
+        amounts_min: (Balance, Balance),
+    ) -> DispatchResult {
+        ZenlinkProtocol::remove_liquidity(
+            RuntimeOrigin::signed(who.clone()),
+            Self::zenlink_asset_id(currencies.0),
+            Self::zenlink_asset_id(currencies.1),
+            liquidity,
+            amounts_min.0,
+            amounts_min.1,
+            MultiAddress::Id(who.clone()),
+            Self::deadline(),
+        )
+    }
+}
+
+/// Reads the reserves of the zenlink pairs the asset manager prices units-per-second through.
+pub struct ZenlinkPairReserves;
+
+impl PairReserves<MantaAssetId> for ZenlinkPairReserves {
+    fn reserves(asset_0: MantaAssetId, asset_1: MantaAssetId) -> Option<(Balance, Balance)> {
+        let zenlink_asset_0 = ZenlinkLiquidityProvider::zenlink_asset_id(asset_0);
+        let zenlink_asset_1 = ZenlinkLiquidityProvider::zenlink_asset_id(asset_1);
+        let pair = ZenlinkProtocol::get_pair_by_asset_id(zenlink_asset_0, zenlink_asset_1)?;
+        // Zenlink sorts the assets of its pairs
+        if pair.asset_0 == zenlink_asset_0 {
+            Some((pair.reserve_0, pair.reserve_1))
+        } else {
+            Some((pair.reserve_1, pair.reserve_0))
+        }
+    }
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 mod mock_benchmark {
     use super::super::*;